@@ -0,0 +1,187 @@
+//! A read-only HTTP JSON API over the brontes database.
+//!
+//! All routes are `GET` requests that are answered straight from the
+//! [`LibmdbxReader`], so a frontend can query a local brontes database while
+//! brontes is running:
+//!
+//! - `/blocks/latest`: the most recent block in the database
+//! - `/blocks/{block_number}`: the mev block & its bundles
+//! - `/bundles`: bundles filtered by `tx_hash`, `searcher`, `mev_type`,
+//!   `start_block` & `end_block`
+//! - `/searchers/{address}`: searcher eoa or contract info
+//! - `/builders/{address}`: builder info
+//! - `/tokens/{address}`: token info
+//! - `/dex-price/{block_number}/{tx_index}?token0=..&token1=..`: the dex price
+//!   of a pair at a tx index
+//!
+//! Blocks processed at the tip can also be streamed live over WebSocket, see
+//! [`stream`].
+use std::{convert::Infallible, net::SocketAddr};
+
+use brontes_types::db::traits::LibmdbxReader;
+use eyre::WrapErr;
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value};
+use tracing::info;
+
+mod routes;
+pub mod stream;
+pub use routes::MAX_BUNDLE_BLOCK_RANGE;
+pub use stream::{BundleFilter, BundleStream};
+
+pub struct BrontesApi<DB: LibmdbxReader> {
+    db: &'static DB,
+}
+
+impl<DB: LibmdbxReader> BrontesApi<DB> {
+    pub fn new(db: &'static DB) -> Self {
+        Self { db }
+    }
+
+    /// Serves the api until the server errors
+    pub async fn serve(self, addr: SocketAddr) -> eyre::Result<()> {
+        let db = self.db;
+        let make_svc = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| handle_request(db, req)))
+        });
+
+        let server = Server::try_bind(&addr)
+            .wrap_err("Could not bind to address")?
+            .serve(make_svc);
+        info!(%addr, "serving brontes api");
+
+        server.await.wrap_err("Brontes api crashed")
+    }
+}
+
+async fn handle_request<DB: LibmdbxReader>(
+    db: &'static DB,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET {
+        return Ok(ApiError::MethodNotAllowed.into_response())
+    }
+
+    let path = req.uri().path().to_string();
+    let query = Query::parse(req.uri().query());
+
+    // libmdbx reads are blocking
+    let res = tokio::task::spawn_blocking(move || routes::route(db, &path, &query))
+        .await
+        .unwrap_or_else(|e| Err(ApiError::Internal(e.into())));
+
+    Ok(match res {
+        Ok(body) => json_response(StatusCode::OK, body),
+        Err(e) => e.into_response(),
+    })
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("not found")]
+    NotFound,
+    #[error("only GET requests are supported")]
+    MethodNotAllowed,
+    #[error("{0}")]
+    BadRequest(String),
+    #[error(transparent)]
+    Internal(#[from] eyre::Report),
+}
+
+impl ApiError {
+    fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn into_response(self) -> Response<Body> {
+        if let Self::Internal(e) = &self {
+            tracing::error!(error = %e, "brontes api request failed");
+        }
+
+        json_response(self.status(), json!({ "error": self.to_string() }))
+    }
+}
+
+/// The percent decoded query parameters of a request
+#[derive(Debug, Default)]
+pub struct Query(Vec<(String, String)>);
+
+impl Query {
+    fn parse(query: Option<&str>) -> Self {
+        Self(
+            query
+                .unwrap_or_default()
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(key, value)| (percent_decode(key), percent_decode(value)))
+                .collect(),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Decodes the `%XX` escapes of a query component & `+` as a space. Malformed
+/// escapes are kept as is.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if bytes
+                .get(i + 1..i + 3)
+                .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                i += 2;
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_parse() {
+        let query = Query::parse(Some("mev_type=Sandwich%2CAtomicArb&name=a+b&bad=100%&x%5Fy=1"));
+
+        assert_eq!(query.get("mev_type"), Some("Sandwich,AtomicArb"));
+        assert_eq!(query.get("name"), Some("a b"));
+        assert_eq!(query.get("bad"), Some("100%"));
+        assert_eq!(query.get("x_y"), Some("1"));
+        assert_eq!(query.get("missing"), None);
+        assert!(Query::parse(None).0.is_empty());
+    }
+}
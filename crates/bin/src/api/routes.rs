@@ -0,0 +1,280 @@
+use std::{fmt::Display, str::FromStr};
+
+use alloy_primitives::{Address, B256};
+use brontes_types::{
+    db::traits::LibmdbxReader,
+    mev::{Bundle, Mev, MevType},
+    pair::Pair,
+    ToFloatNearest,
+};
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use super::{ApiError, Query};
+
+/// The most blocks a single bundle query can scan
+pub const MAX_BUNDLE_BLOCK_RANGE: u64 = 10_000;
+
+pub(super) fn route<DB: LibmdbxReader>(
+    db: &DB,
+    path: &str,
+    query: &Query,
+) -> Result<Value, ApiError> {
+    let segments = path.trim_matches('/').split('/').collect_vec();
+
+    match segments.as_slice() {
+        ["blocks", "latest"] => Ok(json!({ "block_number": db.get_most_recent_block()? })),
+        ["blocks", block_number] => mev_block(db, parse("block_number", block_number)?),
+        ["bundles"] => bundles(db, query),
+        ["searchers", address] => searcher(db, parse("address", address)?),
+        ["builders", address] => builder(db, parse("address", address)?),
+        ["tokens", address] => token(db, parse("address", address)?),
+        ["dex-price", block_number, tx_index] => {
+            dex_price(db, parse("block_number", block_number)?, parse("tx_index", tx_index)?, query)
+        }
+        _ => Err(ApiError::NotFound),
+    }
+}
+
+fn mev_block<DB: LibmdbxReader>(db: &DB, block_number: u64) -> Result<Value, ApiError> {
+    let mev_block = db
+        .try_fetch_mev_block(block_number)?
+        .ok_or(ApiError::NotFound)?;
+
+    to_json(&mev_block)
+}
+
+/// Scans the mev blocks of the range for the bundles that match all given
+/// filters. Without a range the most recent blocks are scanned.
+fn bundles<DB: LibmdbxReader>(db: &DB, query: &Query) -> Result<Value, ApiError> {
+    let tx_hash: Option<B256> = parse_param(query, "tx_hash")?;
+    let searcher: Option<Address> = parse_param(query, "searcher")?;
+    let mev_type = query
+        .get("mev_type")
+        .map(|mev_type| {
+            <MevType as ValueEnum>::from_str(mev_type, true).map_err(ApiError::BadRequest)
+        })
+        .transpose()?;
+
+    let end_block = match parse_param(query, "end_block")? {
+        Some(end_block) => end_block,
+        None => db.get_most_recent_block()?,
+    };
+    let start_block = parse_param(query, "start_block")?
+        .unwrap_or_else(|| end_block.saturating_sub(MAX_BUNDLE_BLOCK_RANGE - 1));
+
+    if start_block > end_block {
+        return Err(ApiError::BadRequest("start_block is after end_block".to_string()))
+    }
+    if end_block - start_block >= MAX_BUNDLE_BLOCK_RANGE {
+        return Err(ApiError::BadRequest(format!(
+            "block range can't be larger than {MAX_BUNDLE_BLOCK_RANGE} blocks"
+        )))
+    }
+
+    let bundles = db
+        .try_fetch_mev_blocks(Some(start_block), end_block)?
+        .into_iter()
+        .flat_map(|mev_block| mev_block.mev)
+        .filter(|bundle| {
+            let header = &bundle.header;
+
+            mev_type.map_or(true, |mev_type| header.mev_type == mev_type)
+                && searcher.map_or(true, |searcher| {
+                    header.eoa == searcher || header.mev_contract == Some(searcher)
+                })
+                && tx_hash.map_or(true, |tx_hash| {
+                    header.tx_hash == tx_hash
+                        || bundle.data.mev_transaction_hashes().contains(&tx_hash)
+                })
+        })
+        .collect::<Vec<Bundle>>();
+
+    to_json(&bundles)
+}
+
+fn searcher<DB: LibmdbxReader>(db: &DB, address: Address) -> Result<Value, ApiError> {
+    let eoa_info = db.try_fetch_searcher_eoa_info(address)?;
+    let contract_info = db.try_fetch_searcher_contract_info(address)?;
+
+    if eoa_info.is_none() && contract_info.is_none() {
+        return Err(ApiError::NotFound)
+    }
+
+    Ok(json!({
+        "address": address,
+        "eoa_info": eoa_info,
+        "contract_info": contract_info,
+    }))
+}
+
+fn builder<DB: LibmdbxReader>(db: &DB, address: Address) -> Result<Value, ApiError> {
+    let builder_info = db
+        .try_fetch_builder_info(address)?
+        .ok_or(ApiError::NotFound)?;
+
+    to_json(&builder_info)
+}
+
+fn token<DB: LibmdbxReader>(db: &DB, address: Address) -> Result<Value, ApiError> {
+    // missing tokens are returned as an error by the reader
+    let token_info = db
+        .try_fetch_token_info(address)
+        .map_err(|_| ApiError::NotFound)?;
+
+    to_json(&token_info)
+}
+
+fn dex_price<DB: LibmdbxReader>(
+    db: &DB,
+    block_number: u64,
+    tx_index: usize,
+    query: &Query,
+) -> Result<Value, ApiError> {
+    let token0: Address = parse_param(query, "token0")?
+        .ok_or_else(|| ApiError::BadRequest("missing token0".to_string()))?;
+    let token1: Address = parse_param(query, "token1")?
+        .ok_or_else(|| ApiError::BadRequest("missing token1".to_string()))?;
+
+    // blocks without dex pricing are returned as an error by the reader
+    let quotes = db
+        .get_dex_quotes(block_number)
+        .map_err(|_| ApiError::NotFound)?;
+    let price = quotes
+        .price_at(Pair(token0, token1), tx_index)
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(json!({
+        "block_number": block_number,
+        "tx_index": tx_index,
+        "token0": token0,
+        "token1": token1,
+        "pre_state": price.pre_state.to_float(),
+        "post_state": price.post_state.to_float(),
+        "goes_through": [price.goes_through.0, price.goes_through.1],
+        "is_transfer": price.is_transfer,
+    }))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::Internal(e.into()))
+}
+
+fn parse<T>(name: &str, value: &str) -> Result<T, ApiError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| ApiError::BadRequest(format!("invalid {name} `{value}`: {e}")))
+}
+
+fn parse_param<T>(query: &Query, name: &str) -> Result<Option<T>, ApiError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    query.get(name).map(|value| parse(name, value)).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::{libmdbx::LibmdbxReadWriter, MevBlocks, MevBlocksData};
+    use brontes_types::{
+        db::mev_block::MevBlockWithClassified,
+        mev::{BundleData, BundleHeader, MevBlock},
+    };
+
+    use super::*;
+
+    fn bundle(block_number: u64, eoa: u8, mev_type: MevType) -> Bundle {
+        Bundle {
+            header: BundleHeader {
+                block_number,
+                tx_hash: B256::with_last_byte(block_number as u8),
+                eoa: Address::with_last_byte(eoa),
+                mev_type,
+                ..Default::default()
+            },
+            data:   BundleData::default(),
+        }
+    }
+
+    fn query(query: &str) -> Query {
+        Query::parse(Some(query))
+    }
+
+    fn bundle_count(db: &LibmdbxReadWriter, q: &str) -> Result<usize, ApiError> {
+        let bundles = route(db, "/bundles", &query(q))?;
+        Ok(bundles.as_array().unwrap().len())
+    }
+
+    #[test]
+    fn test_routes() {
+        let path = std::env::temp_dir().join("brontes-api-test-db");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let db = LibmdbxReadWriter::init_db_tests(&path).unwrap();
+        let mev_blocks = (1..=3)
+            .map(|block_number| {
+                MevBlocksData::new(
+                    block_number,
+                    MevBlockWithClassified {
+                        block: MevBlock { block_number, ..Default::default() },
+                        mev:   vec![
+                            bundle(block_number, 1, MevType::Sandwich),
+                            bundle(block_number, 2, MevType::AtomicArb),
+                        ],
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<MevBlocks, MevBlocksData>(&mev_blocks)
+            .unwrap();
+
+        let block = route(&db, "/blocks/2", &Query::default()).unwrap();
+        assert_eq!(block["block"]["block_number"], 2);
+        assert!(matches!(route(&db, "/blocks/9", &Query::default()), Err(ApiError::NotFound)));
+        assert!(matches!(
+            route(&db, "/blocks/abc", &Query::default()),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(route(&db, "/unknown", &Query::default()), Err(ApiError::NotFound)));
+
+        // the range is inclusive on both ends
+        assert_eq!(bundle_count(&db, "start_block=1&end_block=3").unwrap(), 6);
+        assert_eq!(bundle_count(&db, "start_block=2&end_block=2").unwrap(), 2);
+        assert_eq!(bundle_count(&db, "start_block=1&end_block=3&mev_type=sandwich").unwrap(), 3);
+        assert_eq!(
+            bundle_count(
+                &db,
+                "start_block=1&end_block=3&searcher=0x0000000000000000000000000000000000000002"
+            )
+            .unwrap(),
+            3
+        );
+        let tx_hash = B256::with_last_byte(3);
+        assert_eq!(
+            bundle_count(&db, &format!("start_block=1&end_block=3&tx_hash={tx_hash}")).unwrap(),
+            2
+        );
+
+        assert!(matches!(
+            bundle_count(&db, "start_block=3&end_block=1"),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            bundle_count(&db, &format!("start_block=0&end_block={MAX_BUNDLE_BLOCK_RANGE}")),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            bundle_count(&db, "start_block=1&end_block=3&mev_type=nope"),
+            Err(ApiError::BadRequest(_))
+        ));
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
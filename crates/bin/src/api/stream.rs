@@ -0,0 +1,313 @@
+//! A live WebSocket stream of the mev blocks finalized at the tip.
+//!
+//! The [`TipInspector`](crate::TipInspector) publishes every block once its
+//! results are written. Subscribers connect to
+//! `ws://{addr}/?mev_type=Sandwich,AtomicArb&searcher=0x..&min_profit=100`,
+//! all filters being optional. Each message is a JSON object with the `block`
+//! and the `bundles` that pass the filters. With any filter set, blocks
+//! without a matching bundle aren't sent.
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use alloy_primitives::Address;
+use brontes_types::mev::{Bundle, MevBlock, MevType};
+use clap::ValueEnum;
+use eyre::WrapErr;
+use futures::{SinkExt, StreamExt};
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::json;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        http::StatusCode,
+        Message,
+    },
+};
+use tracing::{debug, info, warn};
+
+use super::Query;
+
+/// Number of blocks buffered for a subscriber before it starts missing blocks
+pub const BUNDLE_STREAM_CAPACITY: usize = 256;
+/// How long to wait before accepting subscribers again after a failed accept
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize)]
+pub struct StreamedBlock {
+    pub block:   MevBlock,
+    pub bundles: Vec<Bundle>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BundleStream {
+    tx: broadcast::Sender<Arc<StreamedBlock>>,
+}
+
+impl Default for BundleStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BundleStream {
+    pub fn new() -> Self {
+        Self { tx: broadcast::channel(BUNDLE_STREAM_CAPACITY).0 }
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        self.tx.receiver_count() > 0
+    }
+
+    /// Pushes a finalized block to all subscribers
+    pub fn publish(&self, block: &MevBlock, bundles: &[Bundle]) {
+        if !self.has_subscribers() {
+            return
+        }
+
+        let block = StreamedBlock { block: block.clone(), bundles: bundles.to_vec() };
+        // all subscribers could have disconnected in the meantime
+        let _ = self.tx.send(Arc::new(block));
+    }
+
+    /// Accepts subscribers until the task is shut down. Failed accepts are
+    /// logged & skipped.
+    pub async fn serve(self, addr: SocketAddr) -> eyre::Result<()> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .wrap_err("Could not bind to address")?;
+        info!(%addr, "serving bundle stream");
+
+        loop {
+            let (socket, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!(err=%e, "failed to accept bundle stream subscriber");
+                    // errors like running out of file descriptors persist for a while
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue
+                }
+            };
+            let rx = self.tx.subscribe();
+
+            tokio::spawn(async move {
+                if let Err(e) = handle_subscriber(socket, rx).await {
+                    debug!(%peer, err=%e, "bundle stream subscriber disconnected");
+                }
+            });
+        }
+    }
+}
+
+async fn handle_subscriber(
+    socket: TcpStream,
+    mut rx: broadcast::Receiver<Arc<StreamedBlock>>,
+) -> eyre::Result<()> {
+    let mut filter = BundleFilter::default();
+    let ws =
+        accept_hdr_async(socket, |req: &Request, res: Response| {
+            match BundleFilter::from_query(req.uri().query()) {
+                Ok(parsed) => {
+                    filter = parsed;
+                    Ok(res)
+                }
+                Err(e) => Err(bad_request(e.to_string())),
+            }
+        })
+        .await?;
+    let (mut sink, mut incoming) = ws.split();
+
+    loop {
+        tokio::select! {
+            block = rx.recv() => match block {
+                Ok(block) => {
+                    if let Some(msg) = filter.apply(&block) {
+                        sink.send(Message::Text(msg)).await?;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(%skipped, "bundle stream subscriber is lagging, skipped blocks");
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            msg = incoming.next() => match msg {
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Err(e)) => return Err(e.into()),
+                _ => {}
+            },
+        }
+    }
+}
+
+fn bad_request(reason: String) -> ErrorResponse {
+    let mut res = ErrorResponse::new(Some(reason));
+    *res.status_mut() = StatusCode::BAD_REQUEST;
+    res
+}
+
+/// The bundles a subscriber is interested in. Empty filters match every
+/// bundle.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BundleFilter {
+    pub mev_types:      Vec<MevType>,
+    pub searchers:      Vec<Address>,
+    pub min_profit_usd: Option<f64>,
+}
+
+impl BundleFilter {
+    /// Parses the comma separated `mev_type` & `searcher` lists and the
+    /// `min_profit` in usd
+    pub fn from_query(query: Option<&str>) -> eyre::Result<Self> {
+        let query = Query::parse(query);
+
+        let mev_types = query
+            .get("mev_type")
+            .into_iter()
+            .flat_map(|types| types.split(','))
+            .map(|mev_type| <MevType as ValueEnum>::from_str(mev_type, true))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| eyre::eyre!("invalid mev_type: {e}"))?;
+        let searchers = query
+            .get("searcher")
+            .into_iter()
+            .flat_map(|searchers| searchers.split(','))
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("invalid searcher")?;
+        let min_profit_usd = query
+            .get("min_profit")
+            .map(str::parse)
+            .transpose()
+            .wrap_err("invalid min_profit")?;
+
+        Ok(Self { mev_types, searchers, min_profit_usd })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mev_types.is_empty() && self.searchers.is_empty() && self.min_profit_usd.is_none()
+    }
+
+    pub fn matches(&self, bundle: &Bundle) -> bool {
+        let header = &bundle.header;
+
+        (self.mev_types.is_empty() || self.mev_types.contains(&header.mev_type))
+            && (self.searchers.is_empty()
+                || self.searchers.contains(&header.eoa)
+                || header
+                    .mev_contract
+                    .is_some_and(|contract| self.searchers.contains(&contract)))
+            && self
+                .min_profit_usd
+                .map_or(true, |min_profit| header.profit_usd >= min_profit)
+    }
+
+    /// The message for a block, or `None` if the subscriber filters out all of
+    /// its bundles
+    fn apply(&self, block: &StreamedBlock) -> Option<String> {
+        if self.is_empty() {
+            return serde_json::to_string(block).ok()
+        }
+
+        let bundles = block
+            .bundles
+            .iter()
+            .filter(|bundle| self.matches(bundle))
+            .collect_vec();
+        if bundles.is_empty() {
+            return None
+        }
+
+        Some(json!({ "block": block.block, "bundles": bundles }).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::mev::BundleHeader;
+
+    use super::*;
+
+    fn bundle(eoa: u8, mev_contract: Option<u8>, mev_type: MevType, profit_usd: f64) -> Bundle {
+        Bundle {
+            header: BundleHeader {
+                eoa: Address::with_last_byte(eoa),
+                mev_contract: mev_contract.map(Address::with_last_byte),
+                mev_type,
+                profit_usd,
+                ..Default::default()
+            },
+            data:   Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_from_query() {
+        let filter = BundleFilter::from_query(Some(
+            "mev_type=sandwich%2Catomic-arb&searcher=0x0000000000000000000000000000000000000001&\
+             min_profit=100.5",
+        ))
+        .unwrap();
+        assert_eq!(
+            filter,
+            BundleFilter {
+                mev_types:      vec![MevType::Sandwich, MevType::AtomicArb],
+                searchers:      vec![Address::with_last_byte(1)],
+                min_profit_usd: Some(100.5),
+            }
+        );
+
+        assert!(BundleFilter::from_query(None).unwrap().is_empty());
+        assert!(BundleFilter::from_query(Some("mev_type=nope")).is_err());
+        assert!(BundleFilter::from_query(Some("searcher=0x01")).is_err());
+        assert!(BundleFilter::from_query(Some("min_profit=lots")).is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = BundleFilter {
+            mev_types:      vec![MevType::Sandwich],
+            searchers:      vec![Address::with_last_byte(1)],
+            min_profit_usd: Some(10.0),
+        };
+
+        assert!(filter.matches(&bundle(1, None, MevType::Sandwich, 10.0)));
+        // the searcher can be the mev contract
+        assert!(filter.matches(&bundle(2, Some(1), MevType::Sandwich, 10.0)));
+        assert!(!filter.matches(&bundle(2, Some(3), MevType::Sandwich, 10.0)));
+        assert!(!filter.matches(&bundle(1, None, MevType::AtomicArb, 10.0)));
+        assert!(!filter.matches(&bundle(1, None, MevType::Sandwich, 9.9)));
+
+        assert!(BundleFilter::default().matches(&bundle(2, None, MevType::AtomicArb, -1.0)));
+    }
+
+    #[test]
+    fn test_apply() {
+        let block = StreamedBlock {
+            block:   MevBlock { block_number: 1, ..Default::default() },
+            bundles: vec![
+                bundle(1, None, MevType::Sandwich, 10.0),
+                bundle(2, None, MevType::AtomicArb, 10.0),
+            ],
+        };
+        let bundle_count = |msg: String| {
+            let msg: serde_json::Value = serde_json::from_str(&msg).unwrap();
+            assert_eq!(msg["block"]["block_number"], 1);
+            msg["bundles"].as_array().unwrap().len()
+        };
+
+        // without filters every block is sent
+        assert_eq!(bundle_count(BundleFilter::default().apply(&block).unwrap()), 2);
+
+        let sandwiches = BundleFilter { mev_types: vec![MevType::Sandwich], ..Default::default() };
+        assert_eq!(bundle_count(sandwiches.apply(&block).unwrap()), 1);
+
+        // blocks without a matching bundle are skipped
+        let liquidations =
+            BundleFilter { mev_types: vec![MevType::Liquidation], ..Default::default() };
+        assert_eq!(liquidations.apply(&block), None);
+    }
+}
@@ -0,0 +1,61 @@
+use brontes_database::{
+    libmdbx::{DBWriter, LibmdbxReader, LibmdbxTableReader},
+    BlockInfo,
+};
+use brontes_types::block_metadata::Relays;
+use clap::Parser;
+use futures::{stream, StreamExt};
+
+use crate::{
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct BuilderAuction {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Number of blocks to fetch the relay bids of at once
+    #[arg(long, default_value_t = 4)]
+    pub concurrency: usize,
+}
+
+impl BuilderAuction {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+
+        let blocks = libmdbx.read_table_range::<BlockInfo>(self.start_block..=self.end_block)?;
+        tracing::info!(blocks = blocks.len(), "fetching builder auctions");
+
+        let mut auctions = stream::iter(blocks)
+            .map(|(block_number, meta)| async move {
+                let auction =
+                    Relays::get_builder_auction(block_number, meta.block_hash.into()).await;
+                (block_number, auction)
+            })
+            .buffer_unordered(self.concurrency.max(1));
+
+        while let Some((block_number, auction)) = auctions.next().await {
+            let Some(auction) = auction else {
+                tracing::debug!(block_number, "no relay received the winning bid");
+                continue
+            };
+
+            // keep the summary of already inspected blocks in sync
+            if let Some(mut mev_block) = libmdbx.try_fetch_mev_block(block_number)? {
+                mev_block.block.builder_auction = auction.summary(mev_block.block.block_hash);
+                libmdbx
+                    .save_mev_blocks(block_number, mev_block.block, mev_block.mev)
+                    .await?;
+            }
+
+            libmdbx.write_builder_auction(block_number, auction).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,14 @@
 use ahash::HashSetExt;
 use alloy_primitives::Address;
-use brontes_database::{clickhouse::cex_config::CexDownloadConfig, libmdbx::LibmdbxReader};
+use brontes_database::{
+    cex_files::CexFileSource, clickhouse::cex_config::CexDownloadConfig, libmdbx::LibmdbxReader,
+};
 use brontes_types::{
     constants::USDT_ADDRESS,
-    db::cex::{trades::CexTrades, CexExchange},
+    db::cex::{
+        trades::{CexTrades, RawCexTrades},
+        CexExchange,
+    },
     init_thread_pools,
     pair::Pair,
     FastHashMap, FastHashSet,
@@ -11,14 +16,12 @@ use brontes_types::{
 use clap::Parser;
 use clickhouse::Row;
 use db_interfaces::{
-    clickhouse::{
-        client::ClickhouseClient,
-        dbms::{ClickhouseDBMS, NullDBMS},
-    },
+    clickhouse::{client::ClickhouseClient, dbms::NullDBMS},
     errors::DatabaseError,
     Database,
 };
 use eyre::Result;
+use itertools::Itertools;
 use prettytable::{Cell, Row, Table};
 use serde::{Deserialize, Serialize};
 
@@ -43,6 +46,16 @@ pub struct CexDB {
     /// Time window multiplier (expands it)
     #[arg(long, short, default_value_t = 1.0)]
     pub w_multiplier: f64,
+    /// Read the trades from a directory of csv or parquet files instead of
+    /// the Sorella clickhouse
+    #[arg(long)]
+    pub cex_data_dir: Option<String>,
+}
+
+/// Where the trades of a pair are queried from
+enum CexDataSource {
+    Clickhouse(ClickhouseClient<NullDBMS>),
+    Files(CexFileSource),
 }
 
 impl CexDB {
@@ -57,7 +70,10 @@ impl CexDB {
 
         let metadata = libmdbx.get_metadata(self.block_number, USDT_ADDRESS)?;
 
-        let clickhouse: ClickhouseClient<NullDBMS> = get_clickhouse_env();
+        let clickhouse = match self.cex_data_dir {
+            Some(dir) => CexDataSource::Files(CexFileSource::new(dir, cex_config.clone())?),
+            None => CexDataSource::Clickhouse(get_clickhouse_env()),
+        };
 
         let token0: Address = self.token_0.parse()?;
         let token1: Address = self.token_1.parse()?;
@@ -100,8 +116,8 @@ impl CexDB {
     }
 }
 
-async fn process_intermediaries<D: ClickhouseDBMS>(
-    clickhouse: &ClickhouseClient<D>,
+async fn process_intermediaries(
+    clickhouse: &CexDataSource,
     pair: Pair,
     intermediaries: FastHashSet<Address>,
     block_timestamp: u64,
@@ -127,8 +143,8 @@ async fn process_intermediaries<D: ClickhouseDBMS>(
     Ok(())
 }
 
-async fn process_pair<D: ClickhouseDBMS>(
-    clickhouse: &ClickhouseClient<D>,
+async fn process_pair(
+    clickhouse: &CexDataSource,
     pair: Pair,
     block_timestamp: u64,
     tw_size: u64,
@@ -140,8 +156,8 @@ async fn process_pair<D: ClickhouseDBMS>(
     Ok(())
 }
 
-async fn query_trade_stats<D: ClickhouseDBMS>(
-    clickhouse: &ClickhouseClient<D>,
+async fn query_trade_stats(
+    clickhouse: &CexDataSource,
     trading_pair: &str,
     block_timestamp: u64,
     tw_size: u64,
@@ -151,9 +167,15 @@ async fn query_trade_stats<D: ClickhouseDBMS>(
     let start_time = block_timestamp - tw_size * SECONDS_TO_US;
     let end_time = block_timestamp + tw_size * SECONDS_TO_US;
 
-    let result: Result<Vec<TradeStats>, DatabaseError> = clickhouse
-        .query_many(TRADE_STATS_QUERY, &(block_timestamp, start_time, end_time, trading_pair))
-        .await;
+    let result: Result<Vec<TradeStats>, eyre::Report> = match clickhouse {
+        CexDataSource::Clickhouse(clickhouse) => clickhouse
+            .query_many(TRADE_STATS_QUERY, &(block_timestamp, start_time, end_time, trading_pair))
+            .await
+            .map_err(|e: DatabaseError| e.into()),
+        CexDataSource::Files(files) => files
+            .read_trades(start_time, end_time + 1)
+            .map(|trades| file_trade_stats(&trades, trading_pair, block_timestamp)),
+    };
 
     match result {
         Ok(stats) => print_trade_stats(&stats),
@@ -198,18 +220,73 @@ pub fn calculate_intermediary_addresses(
         .collect()
 }
 
-async fn query_trading_pair_info<D: ClickhouseDBMS>(
-    clickhouse: &ClickhouseClient<D>,
+async fn query_trading_pair_info(
+    clickhouse: &CexDataSource,
     pair: Pair,
 ) -> Result<TradingPairInfo, eyre::Report> {
-    let result: TradingPairInfo = clickhouse
-        .query_one(
-            TRADING_PAIR_INFO_QUERY,
-            &(pair.0.to_string().to_lowercase(), pair.1.to_string().to_lowercase()),
-        )
-        .await?;
+    let files = match clickhouse {
+        CexDataSource::Clickhouse(clickhouse) => {
+            let result: TradingPairInfo = clickhouse
+                .query_one(
+                    TRADING_PAIR_INFO_QUERY,
+                    &(pair.0.to_string().to_lowercase(), pair.1.to_string().to_lowercase()),
+                )
+                .await?;
 
-    Ok(result)
+            return Ok(result)
+        }
+        CexDataSource::Files(files) => files,
+    };
+
+    let symbol = files
+        .symbols()
+        .iter()
+        .find(|symbol| symbol.address_pair == pair || symbol.address_pair == pair.flip())
+        .ok_or_else(|| eyre::eyre!("no symbol found for {:?} in the cex data files", pair))?;
+
+    Ok(TradingPairInfo {
+        exchange:     symbol.exchange.to_string(),
+        trading_pair: symbol.symbol_pair.clone(),
+        base_asset:   (String::new(), symbol.address_pair.0.to_string()),
+        quote_asset:  (String::new(), symbol.address_pair.1.to_string()),
+    })
+}
+
+/// Buckets the trades of `symbol` by the second they happened before or after
+/// the block, mirroring [`TRADE_STATS_QUERY`]
+fn file_trade_stats(
+    trades: &[RawCexTrades],
+    symbol: &str,
+    block_timestamp: u64,
+) -> Vec<TradeStats> {
+    trades
+        .iter()
+        .filter(|trade| trade.symbol == symbol)
+        .into_group_map_by(|trade| {
+            let (period, seconds) = if trade.timestamp < block_timestamp {
+                ("before", (block_timestamp - trade.timestamp) / SECONDS_TO_US)
+            } else {
+                ("after", (trade.timestamp - block_timestamp) / SECONDS_TO_US)
+            };
+            (period, seconds as i64, trade.exchange)
+        })
+        .into_iter()
+        .map(|((period, seconds_from_block, exchange), trades)| {
+            let total_volume: f64 = trades.iter().map(|trade| trade.amount).sum();
+            let notional: f64 = trades.iter().map(|trade| trade.price * trade.amount).sum();
+
+            TradeStats {
+                symbol: symbol.to_string(),
+                exchange: exchange.to_string(),
+                period: period.to_string(),
+                seconds_from_block,
+                trade_count: trades.len() as u64,
+                total_volume,
+                average_price: if total_volume > 0.0 { notional / total_volume } else { 0.0 },
+            }
+        })
+        .sorted_by(|a, b| (&a.period, a.seconds_from_block).cmp(&(&b.period, b.seconds_from_block)))
+        .collect()
 }
 
 #[derive(Debug, Clone, Row, Deserialize, Serialize)]
@@ -35,7 +35,8 @@ pub struct Clear {
 
 impl Clear {
     pub async fn execute(self, brontes_db_path: String) -> eyre::Result<()> {
-        let db = Libmdbx::init_db(brontes_db_path, None)?;
+        // outdated tables can't be decoded, but can still be cleared
+        let db = Libmdbx::init_db_unchecked(brontes_db_path, None)?;
 
         macro_rules! clear_table {
     ($table:expr, $($tables:ident),+) => {
@@ -66,6 +67,10 @@ impl Clear {
                 AddressMeta,
                 SearcherEOAs,
                 SearcherContracts,
+                SearcherInventories,
+                BuilderAuctions,
+                UniswapV4PoolKeys,
+                TableVersions,
                 TxTraces
             )
         });
@@ -52,7 +52,11 @@ impl Insert {
             AddressMeta,
             SearcherEOAs,
             SearcherContracts,
+            SearcherInventories,
+            BuilderAuctions,
+            UniswapV4PoolKeys,
             InitializedState,
+            TableVersions,
             PoolCreationBlocks = &self.key,
             &self.value
         );
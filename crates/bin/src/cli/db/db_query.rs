@@ -77,6 +77,10 @@ impl DatabaseQuery {
                     AddressMeta,
                     SearcherEOAs,
                     SearcherContracts,
+                    SearcherInventories,
+                    BuilderAuctions,
+                    UniswapV4PoolKeys,
+                    TableVersions,
                     TxTraces
                 );
             } else {
@@ -96,6 +100,10 @@ impl DatabaseQuery {
                     AddressMeta,
                     SearcherEOAs,
                     SearcherContracts,
+                    SearcherInventories,
+                    BuilderAuctions,
+                    UniswapV4PoolKeys,
+                    TableVersions,
                     TxTraces,
                     PoolCreationBlocks = &self.key
                 );
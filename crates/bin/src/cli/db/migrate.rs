@@ -0,0 +1,14 @@
+use brontes_database::libmdbx::Libmdbx;
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+pub struct Migrate {}
+
+impl Migrate {
+    pub fn execute(self, brontes_db_path: String) -> eyre::Result<()> {
+        Libmdbx::migrate_db(brontes_db_path, None)?;
+        tracing::info!("migration complete");
+
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 use clap::{Parser, Subcommand};
-mod r2_uploader;
 mod snapshot;
+mod snapshot_uploader;
 use crate::runner::CliContext;
+mod builder_auction;
 mod cex_data;
 #[cfg(feature = "local-clickhouse")]
 mod clickhouse_download;
@@ -14,11 +15,15 @@ mod discovery;
 mod ensure_test_traces;
 mod export;
 mod init;
+mod migrate;
+mod searcher_inventory;
+mod sql;
 mod table_stats;
 #[cfg(feature = "local-clickhouse")]
 mod tip_tracer;
 mod trace_range;
 pub mod utils;
+mod verify;
 
 #[derive(Debug, Parser)]
 pub struct Database {
@@ -34,6 +39,9 @@ pub enum DatabaseCommands {
     /// Query data from any libmdbx table and pretty print it in stdout
     #[command(name = "query")]
     DbQuery(db_query::DatabaseQuery),
+    /// Run a sql query over the libmdbx tables
+    #[command(name = "sql")]
+    Sql(sql::Sql),
     /// Clear a libmdbx table
     #[command(name = "clear")]
     DbClear(db_clear::Clear),
@@ -51,22 +59,37 @@ pub enum DatabaseCommands {
     /// Libmbdx Table Stats
     #[command(name = "table-stats")]
     TableStats(table_stats::Stats),
+    /// Rewrites libmdbx tables that were written with an older layout
+    #[command(name = "migrate")]
+    Migrate(migrate::Migrate),
     /// Export libmbdx data to parquet
     #[command(name = "export")]
     Export(export::Export),
+    /// Tracks the cex inventory of cex-dex searchers across a block range
+    /// and flags dex flow that the cex trade tape can't explain
+    #[command(name = "searcher-inventory")]
+    SearcherInventory(searcher_inventory::SearcherInventory),
+    /// Fetches the bids the relays received for a block range and stores the
+    /// builder auction of each block
+    #[command(name = "builder-auction")]
+    BuilderAuction(builder_auction::BuilderAuction),
     /// Downloads a database snapshot. Without specified blocks, it fetches
     /// the full range. With start/end blocks, it downloads that range and
     /// merges it into the current database.
     #[command(name = "download-snapshot")]
     DownloadSnapshot(snapshot::Snapshot),
+    /// Recomputes the checksums of downloaded partitions and compares them
+    /// to the published ones
+    #[command(name = "verify")]
+    Verify(verify::Verify),
     #[cfg(feature = "local-clickhouse")]
     /// Downloads the db data from clickhouse
     #[command(name = "download-clickhouse")]
     DownloadClickhouse(clickhouse_download::ClickhouseDownload),
-    /// For internal use only. Uploads snapshots
-    /// of db every 100k blocks to r2
-    #[command(name = "r2-upload")]
-    UploadSnapshot(r2_uploader::R2Uploader),
+    /// Partitions the db and publishes the snapshots to a S3 compatible
+    /// bucket
+    #[command(name = "upload-snapshot", alias = "r2-upload")]
+    UploadSnapshot(snapshot_uploader::SnapshotUploader),
     #[cfg(feature = "local-clickhouse")]
     /// Traces all blocks required to run the tests and inserts them into
     /// clickhouse
@@ -89,11 +112,16 @@ impl Database {
             DatabaseCommands::DbQuery(cmd) => cmd.execute(brontes_db_path).await,
             DatabaseCommands::TraceRange(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::Init(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::Sql(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::DbClear(cmd) => cmd.execute(brontes_db_path).await,
             DatabaseCommands::UploadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::Export(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::SearcherInventory(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::BuilderAuction(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::TableStats(cmd) => cmd.execute(brontes_db_path),
+            DatabaseCommands::Migrate(cmd) => cmd.execute(brontes_db_path),
             DatabaseCommands::DownloadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::Verify(cmd) => cmd.execute().await,
             DatabaseCommands::CexData(cmd) => cmd.execute(brontes_db_path, ctx).await,
             #[cfg(feature = "local-clickhouse")]
             DatabaseCommands::DownloadClickhouse(cmd) => cmd.execute(brontes_db_path, ctx).await,
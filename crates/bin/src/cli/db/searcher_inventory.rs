@@ -0,0 +1,76 @@
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_types::{
+    db::searcher_inventory::{InventoryTracker, DEFAULT_UNEXPLAINED_TOLERANCE},
+    mev::MevType,
+};
+use clap::Parser;
+use itertools::Itertools;
+
+use crate::{
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct SearcherInventory {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End Block
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// The share of a token's dex flow that may be left unhedged on the cex
+    /// tape before the searcher is flagged
+    #[arg(long, default_value_t = DEFAULT_UNEXPLAINED_TOLERANCE)]
+    pub tolerance:   f64,
+    /// Rebuild the inventories from the start block instead of continuing
+    /// from the stored ones
+    #[arg(long, default_value_t = false)]
+    pub reset:       bool,
+}
+
+impl SearcherInventory {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+
+        let mut tracker = if self.reset {
+            InventoryTracker::default()
+        } else {
+            InventoryTracker::new(libmdbx.fetch_all_searcher_inventories()?)
+        };
+
+        let mev_blocks = libmdbx.try_fetch_mev_blocks(Some(self.start_block), self.end_block)?;
+        tracing::info!(blocks = mev_blocks.len(), "building searcher inventories");
+
+        for bundle in mev_blocks
+            .iter()
+            .sorted_by_key(|mev_block| mev_block.block.block_number)
+            .flat_map(|mev_block| &mev_block.mev)
+            .filter(|bundle| bundle.header.mev_type == MevType::CexDexTrades)
+        {
+            let (eoa_info, contract_info) =
+                libmdbx.try_fetch_searcher_info(bundle.header.eoa, bundle.header.mev_contract)?;
+            tracker.on_bundle(bundle, eoa_info.as_ref(), contract_info.as_ref());
+        }
+
+        for (searcher, mut inventory) in tracker.into_inventories() {
+            inventory.flag_unexplained(self.tolerance);
+            for position in inventory.unexplained_positions(self.tolerance) {
+                tracing::warn!(
+                    %searcher,
+                    fund = %inventory.fund,
+                    token = %position.token,
+                    net_position = position.net_position(),
+                    unexplained_ratio = position.unexplained_ratio(),
+                    "dex flow not explained by the cex trade tape"
+                );
+            }
+
+            libmdbx
+                .write_searcher_inventory(searcher, inventory)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,17 @@
-use std::{env::temp_dir, path::PathBuf, str::FromStr};
+use std::{
+    env::temp_dir,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use brontes_database::libmdbx::{
-    merge_libmdbx_dbs, rclone_wrapper::BlockRangeList, LibmdbxReadWriter, FULL_RANGE_NAME,
-};
-use brontes_types::{
-    buf_writer::DownloadBufWriterWithProgress, unordered_buffer_map::BrontesStreamExt,
+    merge_libmdbx_dbs, snapshot_publisher::BlockRangeList, verify_partition, LibmdbxReadWriter,
+    CHECKSUM_PATH, FULL_RANGE_NAME,
 };
+use brontes_types::{buf_writer::download_with_resume, unordered_buffer_map::BrontesStreamExt};
 use clap::Parser;
 use directories::UserDirs;
+use eyre::WrapErr;
 use flate2::read::GzDecoder;
 use fs_extra::dir::{move_dir, CopyOptions};
 use futures::{stream::StreamExt, Stream};
@@ -18,7 +22,7 @@ use tar::Archive;
 
 use crate::runner::CliContext;
 
-const NAME: &str = "brontes-db-partition";
+pub(super) const NAME: &str = "brontes-db-partition";
 const FIXED_DB: &str = "full-range-tables";
 const SIZE_PATH: &str = "byte-count.txt";
 const RANGES_AVAILABLE: &str = "brontes-available-ranges.json";
@@ -40,7 +44,7 @@ pub struct Snapshot {
 impl Snapshot {
     pub async fn execute(self, brontes_db_endpoint: String, ctx: CliContext) -> eyre::Result<()> {
         let client = reqwest::Client::new();
-        let ranges_avail = self.get_available_ranges(&client).await?;
+        let ranges_avail = get_available_ranges(&client, &self.endpoint).await?;
         let ranges_to_download = self.ranges_to_download(ranges_avail)?;
         fs_extra::dir::create_all(&brontes_db_endpoint, false)?;
 
@@ -52,35 +56,45 @@ impl Snapshot {
         let multi_bar = MultiProgress::new();
 
         // ensure dir exists
-        let mut download_dir = temp_dir();
-        download_dir.push(format!("{}s", NAME));
+        let download_dir = partition_download_dir();
         let mut cloned_download_dir = download_dir.clone();
         fs_extra::dir::create_all(&download_dir, false)?;
 
+        // a failed download or checksum is returned instead of panicking the task
+        let (result_tx, result_rx) = tokio::sync::oneshot::channel();
         ctx.task_executor
             .spawn_critical("download_streams", async move {
-                futures::stream::iter(curl_queries)
-                    .map(|DbRequestWithBytes { url, size_bytes, file_name }| {
+                let res = futures::stream::iter(curl_queries)
+                    .map(|request| {
                         let client = client.clone();
                         let mb = multi_bar.clone();
+                        let partition = download_dir.join(request.partition_name());
+                        let DbRequestWithBytes { url, size_bytes, file_name, checksum } = request;
                         tracing::info!(?url, ?size_bytes, ?file_name);
                         let mut download_dir = download_dir.clone();
                         async move {
-                            download_dir.push(file_name);
-
-                            tracing::info!("creating file");
-                            let file = tokio::fs::File::create(&download_dir).await?;
+                            // already downloaded by a previous, interrupted run
+                            if let Some(checksum) = checksum.as_deref() {
+                                if partition.exists()
+                                    && verify_partition(&partition, checksum).is_ok()
+                                {
+                                    tracing::info!(?partition, "partition already downloaded");
+                                    return eyre::Ok(())
+                                }
+                            }
 
-                            let stream = client.get(url).send().await?.bytes_stream();
-                            DownloadBufWriterWithProgress::new(
+                            download_dir.push(file_name);
+                            download_with_resume(
+                                &client,
+                                &url,
+                                &download_dir,
                                 Some(size_bytes),
-                                stream,
-                                file,
                                 40 * 1024 * 1024,
                                 &mb,
                             )
                             .await?;
                             Self::handle_downloaded_file(&download_dir)?;
+                            Self::verify_downloaded_partition(&partition, checksum.as_deref())?;
 
                             eyre::Ok(())
                         }
@@ -89,10 +103,13 @@ impl Snapshot {
                     .map(|s| s.map_err(eyre::Error::from))
                     .collect_vec_transpose_double()
                     .await
-                    .unwrap()
-                    .unwrap();
+                    .and_then(|res| res.map(|_| ()));
+                let _ = result_tx.send(res);
             })
             .await?;
+        result_rx
+            .await
+            .wrap_err("download task exited without a result")??;
 
         if self.should_merge() {
             tracing::info!(
@@ -180,7 +197,7 @@ impl Snapshot {
             (Some(start), Some(end)) => {
                 let ranges = ranges_avail
                     .into_iter()
-                    .filter(|BlockRangeList { start_block, end_block }| {
+                    .filter(|BlockRangeList { start_block, end_block, .. }| {
                         end_block >= &start && start_block <= &end
                     })
                     .collect_vec();
@@ -199,18 +216,6 @@ impl Snapshot {
         }
     }
 
-    async fn get_available_ranges(
-        &self,
-        client: &reqwest::Client,
-    ) -> eyre::Result<Vec<BlockRangeList>> {
-        Ok(client
-            .get(format!("{}{}", self.endpoint, RANGES_AVAILABLE))
-            .send()
-            .await?
-            .json()
-            .await?)
-    }
-
     /// returns a error if there is not enough space remaining. If the overwrite
     /// db flag is enabled. Will delete the current db if that frees enough
     /// space
@@ -231,6 +236,7 @@ impl Snapshot {
                     url:        format!("{}{}.tar.gz", self.endpoint, FULL_RANGE_NAME),
                     file_name:  format!("{}.tar.gz", FULL_RANGE_NAME),
                     size_bytes: size,
+                    checksum:   get_checksum(client, &self.endpoint, FULL_RANGE_NAME).await?,
                 });
 
                 new_db_size += size;
@@ -253,6 +259,7 @@ impl Snapshot {
                             NAME, range.start_block, range.end_block
                         ),
                         size_bytes: size,
+                        checksum:   range.checksum,
                     });
 
                     new_db_size += size;
@@ -267,6 +274,12 @@ impl Snapshot {
                     url:        format!("{}{}-{}.tar.gz", self.endpoint, NAME, FIXED_DB),
                     file_name:  format!("{}-{}.tar.gz", NAME, FIXED_DB),
                     size_bytes: size,
+                    checksum:   get_checksum(
+                        client,
+                        &self.endpoint,
+                        &format!("{}-{}", NAME, FIXED_DB),
+                    )
+                    .await?,
                 });
                 new_db_size += size;
             }
@@ -299,11 +312,71 @@ impl Snapshot {
         Ok(())
     }
 
+    /// Errors if the unpacked partition doesn't match its published checksum.
+    /// A corrupt partition is removed so that it's downloaded again on the
+    /// next run.
+    fn verify_downloaded_partition(partition: &Path, checksum: Option<&str>) -> eyre::Result<()> {
+        let Some(checksum) = checksum else {
+            tracing::warn!(
+                ?partition,
+                "no checksum published for partition, skipping verification"
+            );
+            return Ok(())
+        };
+
+        if let Err(e) = verify_partition(partition, checksum) {
+            fs_extra::dir::remove(partition)?;
+            return Err(e)
+        }
+        tracing::info!(?partition, "verified partition checksum");
+
+        Ok(())
+    }
+
     fn should_merge(&self) -> bool {
         self.start_block.is_some() || self.end_block.is_some()
     }
 }
 
+/// Where partitions are downloaded to before they are merged
+pub(super) fn partition_download_dir() -> PathBuf {
+    let mut download_dir = temp_dir();
+    download_dir.push(format!("{}s", NAME));
+    download_dir
+}
+
+pub(super) async fn get_available_ranges(
+    client: &reqwest::Client,
+    endpoint: &Url,
+) -> eyre::Result<Vec<BlockRangeList>> {
+    Ok(client
+        .get(format!("{}{}", endpoint, RANGES_AVAILABLE))
+        .send()
+        .await?
+        .json()
+        .await?)
+}
+
+/// The checksum published next to the tarball of a partition that isn't part
+/// of the available ranges. Snapshots published before checksums don't have
+/// one.
+pub(super) async fn get_checksum(
+    client: &reqwest::Client,
+    endpoint: &Url,
+    partition_name: &str,
+) -> eyre::Result<Option<String>> {
+    let response = client
+        .get(format!("{}{}-{}", endpoint, partition_name, CHECKSUM_PATH))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Ok(None)
+    }
+
+    Ok(Some(response.text().await?.trim().to_string()))
+}
+
 pub enum RangeOrFull {
     Full,
     Range(Vec<BlockRangeList>),
@@ -313,6 +386,14 @@ pub struct DbRequestWithBytes {
     pub url:        String,
     pub file_name:  String,
     pub size_bytes: u64,
+    pub checksum:   Option<String>,
+}
+
+impl DbRequestWithBytes {
+    /// The directory the tarball unpacks to
+    pub fn partition_name(&self) -> &str {
+        self.file_name.trim_end_matches(".tar.gz")
+    }
 }
 
 impl<S> AsyncFlatten for S where S: Stream + Sized {}
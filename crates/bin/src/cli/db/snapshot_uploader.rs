@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use brontes_database::libmdbx::{
-    rclone_wrapper::RCloneWrapper, LibmdbxInit, LibmdbxPartitioner, LibmdbxReadWriter,
+    snapshot_publisher::SnapshotPublisher, LibmdbxInit, LibmdbxPartitioner, LibmdbxReadWriter,
     FULL_RANGE_NAME,
 };
 use clap::Parser;
@@ -9,10 +9,18 @@ use clap::Parser;
 use crate::runner::CliContext;
 
 #[derive(Debug, Parser)]
-pub struct R2Uploader {
-    /// R2 Config Name
-    #[clap(short, long)]
-    r2_config_name:      String,
+pub struct SnapshotUploader {
+    /// Bucket the snapshots are published to. Credentials are read from
+    /// `AWS_ACCESS_KEY_ID` & `AWS_SECRET_ACCESS_KEY`
+    #[clap(short, long, default_value = "brontes-db")]
+    bucket:              String,
+    /// Endpoint of the S3 compatible store, e.g. the R2 account url or a
+    /// local MinIO. Defaults to AWS
+    #[clap(long)]
+    endpoint:            Option<String>,
+    /// Region of the bucket
+    #[clap(long, default_value = "auto")]
+    region:              String,
     /// Start Block
     #[clap(short, long)]
     start_block:         Option<u64>,
@@ -21,59 +29,48 @@ pub struct R2Uploader {
     partition_db_folder: PathBuf,
 }
 
-impl R2Uploader {
+impl SnapshotUploader {
     pub async fn execute(self, database_path: String, ctx: CliContext) -> eyre::Result<()> {
-        let r2wrapper = RCloneWrapper::new(self.r2_config_name.clone()).await?;
+        let publisher =
+            SnapshotPublisher::s3(&self.bucket, self.endpoint.as_deref(), &self.region)?;
 
         let db = LibmdbxReadWriter::init_db(&database_path, None, &ctx.task_executor, true)?;
 
         let start_block = if let Some(b) = self.start_block {
             b
         } else {
-            tracing::info!("Grabbing most recent r2 snapshot");
-            r2wrapper
-                .get_most_recent_partition_block()
-                .await
-                .unwrap_or_else(|e| {
+            tracing::info!("Grabbing most recent published snapshot");
+            match publisher.get_most_recent_partition_block().await {
+                Ok(block) => block,
+                Err(e) => {
                     tracing::warn!(err=%e,"using databases first block");
-                    db.get_db_range().expect("empty libmdbx").0
-                })
+                    db.get_db_range()?.0
+                }
+            }
         };
 
         tracing::info!("Partitioning new data into respective files");
 
-        if let Err(e) = LibmdbxPartitioner::new(
+        LibmdbxPartitioner::new(
             db,
             self.partition_db_folder.clone(),
             start_block,
             ctx.task_executor.clone(),
         )
-        .execute()
-        {
-            tracing::error!(error=%e);
-            return Ok(())
-        }
+        .execute()?;
 
         tracing::info!(
             "Partitioning complete, uploading files, this will take a while. ~10 min per partition"
         );
 
-        if let Err(e) = r2wrapper
+        publisher
             .tar_ball_and_upload_files(self.partition_db_folder, start_block)
-            .await
-        {
-            tracing::error!(error=%e);
-            return Ok(())
-        }
+            .await?;
 
         tracing::info!("uploading full database");
-        if let Err(e) = r2wrapper
+        publisher
             .tar_ball_dir(&PathBuf::from(database_path), Some(FULL_RANGE_NAME))
-            .await
-        {
-            tracing::error!(error=%e);
-            return Ok(())
-        }
+            .await?;
 
         tracing::info!("uploading files completed");
 
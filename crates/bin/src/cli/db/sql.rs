@@ -0,0 +1,37 @@
+use brontes_database::sql::LibmdbxSql;
+use clap::Parser;
+
+use crate::{
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Sql {
+    /// The query to run. The tables it references are loaded from libmdbx:
+    /// mev_blocks, bundle_header, atomic_arb, jit, jit_sandwich, sandwich,
+    /// searcher_tx, liquidation, generalized_frontrun, dex_price, cex_price,
+    /// address_meta, searcher_eoas, searcher_contracts, searcher_inventories,
+    /// builder, builder_auctions & token_decimals
+    pub query:       String,
+    /// Start block of the block keyed tables
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End block of the block keyed tables (inclusive)
+    #[arg(long, short)]
+    pub end_block:   u64,
+}
+
+impl Sql {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+
+        LibmdbxSql::new(libmdbx, self.start_block, self.end_block)?
+            .sql(&self.query)
+            .await?
+            .show()
+            .await?;
+
+        Ok(())
+    }
+}
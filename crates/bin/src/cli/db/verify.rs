@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use brontes_database::libmdbx::partition_checksum;
+use clap::Parser;
+use reqwest::Url;
+
+use super::snapshot::{get_available_ranges, get_checksum, partition_download_dir, NAME};
+
+#[derive(Debug, Parser)]
+pub struct Verify {
+    /// Snapshot endpoint the checksums are published at
+    #[arg(long, default_value = "https://data.brontes.xyz/")]
+    pub endpoint:         Url,
+    /// Folder with the partitions to verify. Defaults to the folder snapshots
+    /// are downloaded to
+    #[arg(long, short)]
+    pub partition_folder: Option<PathBuf>,
+}
+
+impl Verify {
+    pub async fn execute(self) -> eyre::Result<()> {
+        let partition_folder = self
+            .partition_folder
+            .clone()
+            .unwrap_or_else(partition_download_dir);
+
+        let client = reqwest::Client::new();
+        let ranges = get_available_ranges(&client, &self.endpoint).await?;
+
+        let mut partitions = std::fs::read_dir(&partition_folder)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        partitions.sort();
+
+        if partitions.is_empty() {
+            eyre::bail!("no partitions found in {}", partition_folder.display());
+        }
+
+        let mut mismatched = 0;
+        for partition in partitions {
+            let Some(name) = partition.file_name().and_then(|name| name.to_str()) else { continue };
+
+            let published = match ranges
+                .iter()
+                .find(|range| name == format!("{}-{}-{}", NAME, range.start_block, range.end_block))
+            {
+                Some(range) => range.checksum.clone(),
+                None => get_checksum(&client, &self.endpoint, name).await?,
+            };
+
+            let Some(published) = published else {
+                tracing::warn!(partition = name, "no checksum published");
+                continue
+            };
+
+            let checksum = partition_checksum(&partition)?;
+            if checksum == published {
+                tracing::info!(partition = name, %checksum, "ok");
+            } else {
+                tracing::error!(partition = name, %checksum, %published, "checksum mismatch");
+                mismatched += 1;
+            }
+        }
+
+        if mismatched != 0 {
+            eyre::bail!("{mismatched} partitions don't match their published checksum");
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
-use std::{path::Path, time::Duration};
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
 
+use brontes_classifier::runtime_classifier::RuntimeClassifierRegistry;
 use brontes_core::decoding::Parser as DParser;
-use brontes_database::clickhouse::cex_config::CexDownloadConfig;
+use brontes_database::{
+    cex_files::{CexFileHandle, CexFileSource},
+    clickhouse::cex_config::CexDownloadConfig,
+    libmdbx::DBWriter,
+};
 use brontes_inspect::Inspectors;
 use brontes_metrics::ParserMetricsListener;
+use brontes_pricing::types::PoolSyncMode;
 use brontes_types::{
     constants::USDT_ADDRESS_STRING,
-    db::cex::{trades::CexDexTradeConfig, CexExchange},
+    db::cex::{trades::CexDexTradeConfig, CexExchange, CexFeeSchedule, FeeTier},
     db_write_trigger::{backup_server_heartbeat, start_hr_monitor, HeartRateMonitor},
     init_thread_pools, UnboundedYapperReceiver,
 };
@@ -15,6 +21,7 @@ use tokio::sync::mpsc::unbounded_channel;
 
 use super::{determine_max_tasks, get_env_vars, load_clickhouse, load_database, static_object};
 use crate::{
+    api::BundleStream,
     banner::rain,
     cli::{get_tracing_provider, init_inspectors, load_tip_database},
     runner::CliContext,
@@ -64,6 +71,19 @@ pub struct RunArgs {
         value_delimiter = ','
     )]
     pub cex_exchanges:        Vec<CexExchange>,
+    /// Load cex quotes and trades from a directory of csv or parquet files
+    /// instead of the Sorella clickhouse. See `brontes_database::cex_files`
+    /// for the expected layout.
+    #[arg(long)]
+    pub cex_data_dir:         Option<String>,
+    /// Optional toml or json cex fee schedule, with fees per exchange, pair
+    /// class and searcher fund. See `brontes_types::db::cex::fees`.
+    #[arg(long)]
+    pub cex_fee_schedule:     Option<String>,
+    /// Overrides the default fees of an exchange, format:
+    /// "exchange=maker:taker", e.g. "binance=0.0002:0.0004"
+    #[arg(long, value_parser = parse_cex_fees)]
+    pub cex_fees:             Vec<(CexExchange, FeeTier)>,
     /// Force DEX price calculation for every block, ignoring existing database
     /// values.
     #[arg(long, short, default_value = "false")]
@@ -73,7 +93,21 @@ pub struct RunArgs {
     /// database.
     #[arg(long, default_value = "false")]
     pub force_no_dex_pricing: bool,
+    /// Re-execute sandwich victims against the pre-frontrun state to get their
+    /// exact loss. Requires a local reth node.
+    #[arg(long, default_value = "false")]
+    pub simulate_victim_loss: bool,
+    /// Sync uniswap pool state in the pricer from the classified actions
+    /// instead of the logs. With metrics enabled the two are compared.
+    #[arg(long, default_value = "false")]
+    pub sync_from_actions:    bool,
+    /// Optional toml or json config of classifiers to load at runtime. These
+    /// are tried for calls that no built in classifier matches.
+    #[arg(long)]
+    pub runtime_classifiers:  Option<String>,
     /// Number of blocks to lag behind the chain tip when processing.
+    /// Processed blocks that are reorged out are rolled back and processed
+    /// again, so this can be as low as 1.
     #[arg(long, default_value = "10")]
     pub behind_tip:           u64,
     /// Legacy, run in CLI only mode (no TUI) - will output progress bars to
@@ -91,6 +125,10 @@ pub struct RunArgs {
     /// loss.
     #[arg(long)]
     pub fallback_server:      Option<String>,
+    /// Stream the mev blocks processed at the tip to WebSocket subscribers on
+    /// this address, e.g. "127.0.0.1:3001"
+    #[arg(long)]
+    pub bundle_stream:        Option<SocketAddr>,
     /// Set a custom run ID used when inserting data into the Clickhouse
     ///
     /// If omitted, the ID will be automatically incremented from the last run
@@ -151,7 +189,23 @@ impl RunArgs {
         );
 
         let range_type = self.get_range_type()?;
-        let clickhouse = static_object(load_clickhouse(cex_download_config, self.run_id).await?);
+        let cex_files = self
+            .cex_data_dir
+            .as_ref()
+            .map(|dir| CexFileSource::new(dir, cex_download_config.clone()))
+            .transpose()?;
+        let shared_cex_files = cex_files.clone().map(Arc::new);
+        // with cex data files all other data is read from libmdbx
+        let inner_clickhouse = if cex_files.is_some() {
+            None
+        } else {
+            Some(load_clickhouse(cex_download_config, self.run_id).await?)
+        };
+        let clickhouse = static_object(CexFileHandle::new(
+            inner_clickhouse,
+            cex_files,
+            DBWriter::inner(libmdbx),
+        ));
         tracing::info!(target: "brontes", "Databases initialized");
 
         let only_cex_dex = self
@@ -167,7 +221,17 @@ impl RunArgs {
             self.force_no_dex_pricing = true;
         }
 
-        let trade_config = self.time_window_args.trade_config();
+        let mut trade_config = self.time_window_args.trade_config();
+
+        let fee_schedule = self.cex_fees.iter().cloned().fold(
+            self.cex_fee_schedule
+                .as_ref()
+                .map(CexFeeSchedule::from_file)
+                .transpose()?
+                .unwrap_or_default(),
+            |schedule, (exchange, tier)| schedule.with_exchange_fees(exchange, tier),
+        );
+        trade_config.cex_fee_schedule = Some(static_object(fee_schedule));
 
         let inspectors = init_inspectors(
             quote_asset,
@@ -182,6 +246,26 @@ impl RunArgs {
             get_tracing_provider(Path::new(&reth_db_path), max_tasks, task_executor.clone());
         let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
 
+        let runtime_classifiers = self
+            .runtime_classifiers
+            .as_ref()
+            .map(RuntimeClassifierRegistry::load)
+            .transpose()?
+            .map(Arc::new);
+
+        let bundle_stream = self.bundle_stream.take().map(|addr| {
+            let stream = BundleStream::new();
+            task_executor.spawn_critical("bundle stream", {
+                let stream = stream.clone();
+                async move {
+                    if let Err(e) = stream.serve(addr).await {
+                        tracing::error!(err=%e, "bundle stream failed");
+                    }
+                }
+            });
+            stream
+        });
+
         let executor = task_executor.clone();
         let result = executor
             .clone()
@@ -193,6 +277,10 @@ impl RunArgs {
                     quote_asset,
                     self.force_dex_pricing,
                     self.force_no_dex_pricing,
+                    self.simulate_victim_loss,
+                    if self.sync_from_actions { PoolSyncMode::Actions } else { PoolSyncMode::Logs },
+                    runtime_classifiers,
+                    shared_cex_files,
                     inspectors,
                     clickhouse,
                     parser,
@@ -202,6 +290,7 @@ impl RunArgs {
                     self.with_metrics,
                     snapshot_mode,
                     load_window,
+                    bundle_stream,
                 )
                 .build(task_executor, shutdown)
                 .await
@@ -267,6 +356,11 @@ impl RunArgs {
                 return Err(eyre::eyre!("start block must be less than end block"))
             }
         }
+        if self.cex_data_dir.is_some() && self.ranges.is_none() && self.end_block.is_none() {
+            return Err(eyre::eyre!(
+                "cex data files can't be used at tip, the block metadata is only in the clickhouse"
+            ))
+        }
         Ok(())
     }
 }
@@ -295,6 +389,23 @@ fn parse_ranges(ranges: &[String]) -> Result<Vec<(u64, u64)>, String> {
         .collect()
 }
 
+fn parse_cex_fees(fees: &str) -> Result<(CexExchange, FeeTier), String> {
+    let (exchange, tier) = fees
+        .split_once('=')
+        .ok_or_else(|| format!("invalid cex fees: {}", fees))?;
+    let (maker, taker) = tier
+        .split_once(':')
+        .ok_or_else(|| format!("invalid cex fee tier: {}", tier))?;
+    let maker: f64 = maker
+        .parse()
+        .map_err(|_| format!("invalid maker fee: {}", maker))?;
+    let taker: f64 = taker
+        .parse()
+        .map_err(|_| format!("invalid taker fee: {}", taker))?;
+
+    Ok((CexExchange::from(exchange), FeeTier::new(maker, taker).map_err(|e| e.to_string())?))
+}
+
 #[derive(Debug, Parser)]
 pub struct TimeWindowArgs {
     /// The initial sliding time window (BEFORE) for cex prices or trades
@@ -386,6 +497,16 @@ pub struct TimeWindowArgs {
     /// Cex Dex Quotes price time offset from block timestamp
     #[arg(long = "quote-offset", default_value = "0.0")]
     pub quote_offset: f64,
+
+    /// Price the hedge on the perp exchanges in `--cex-exchanges` when spot
+    /// doesn't have the volume to fill the swap
+    #[arg(long = "perp-hedge", default_value = "false")]
+    pub perp_hedge: bool,
+
+    /// How long (in seconds) a perp hedge is assumed to be held, used to
+    /// charge its funding
+    #[arg(long = "perp-hedge-horizon", default_value = "3600.0")]
+    pub perp_hedge_horizon: f64,
 }
 
 impl TimeWindowArgs {
@@ -417,6 +538,10 @@ impl TimeWindowArgs {
             pre_decay_weight_op:               self.pre_decay_weight_optimistic,
             post_decay_weight_op:              self.post_decay_weight_optimistic,
             quote_offset_from_block_us:        (self.quote_offset * SECONDS_TO_US_FLOAT) as u64,
+            cex_fee_schedule:                  None,
+            perp_hedge_on_thin_spot:           self.perp_hedge,
+            perp_hedge_horizon_us:             (self.perp_hedge_horizon * SECONDS_TO_US_FLOAT)
+                as u64,
         }
     }
 }
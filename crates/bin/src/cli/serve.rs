@@ -0,0 +1,29 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use clap::Parser;
+
+use crate::{
+    api::BrontesApi,
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+#[derive(Debug, Parser)]
+pub struct Serve {
+    /// Address the api listens on
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub host: IpAddr,
+    /// Port the api listens on
+    #[arg(long, short, default_value_t = 3000)]
+    pub port: u16,
+}
+
+impl Serve {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+
+        BrontesApi::new(libmdbx)
+            .serve(SocketAddr::new(self.host, self.port))
+            .await
+    }
+}
@@ -7,12 +7,13 @@ use brontes_database::libmdbx::StateToInitialize;
 use brontes_metrics::{
     pricing::DexPricingMetrics,
     range::{FinishedRange, GlobalRangeMetrics},
+    tip::TipMetrics,
 };
 use futures::{future::join_all, Stream};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
 pub use processors::*;
 mod shared;
-use brontes_database::{clickhouse::ClickhouseHandle, Tables};
+use brontes_database::{cex_files::CexFileSource, clickhouse::ClickhouseHandle, Tables};
 use futures::pin_mut;
 use shared::multi_block_window::MultiBlockWindow;
 mod tip;
@@ -24,11 +25,14 @@ use std::{
 };
 
 use alloy_primitives::Address;
-use brontes_classifier::Classifier;
+use brontes_classifier::{runtime_classifier::RuntimeClassifierRegistry, Classifier};
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::libmdbx::LibmdbxInit;
 use brontes_inspect::Inspector;
-use brontes_pricing::{BrontesBatchPricer, GraphManager, LoadState};
+use brontes_pricing::{
+    types::PoolSyncMode, uniswap_v4::db_pool_key_lookup, BrontesBatchPricer, GraphManager,
+    LoadState,
+};
 use brontes_types::{
     db::traits::LibmdbxReader, BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver,
 };
@@ -44,7 +48,7 @@ use self::shared::{
     dex_pricing::WaitingForPricerFuture, metadata_loader::MetadataLoader,
     state_collector::StateCollector,
 };
-use crate::cli::static_object;
+use crate::{api::BundleStream, cli::static_object};
 
 pub const PROMETHEUS_ENDPOINT_IP: [u8; 4] = [0u8, 0u8, 0u8, 0u8];
 
@@ -56,6 +60,10 @@ pub struct BrontesRunConfig<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseH
     pub quote_asset: Address,
     pub force_dex_pricing: bool,
     pub force_no_dex_pricing: bool,
+    pub simulate_victim_loss: bool,
+    pub pool_sync_mode: PoolSyncMode,
+    pub runtime_classifiers: Option<Arc<RuntimeClassifierRegistry>>,
+    pub cex_files: Option<Arc<CexFileSource>>,
     pub inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
     pub clickhouse: &'static CH,
     pub parser: &'static Parser<T, DB>,
@@ -65,6 +73,7 @@ pub struct BrontesRunConfig<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseH
     pub metrics: bool,
     pub is_snapshot: bool,
     pub cex_window: usize,
+    pub bundle_stream: Option<BundleStream>,
     _p: PhantomData<P>,
 }
 
@@ -79,6 +88,10 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         quote_asset: Address,
         force_dex_pricing: bool,
         force_no_dex_pricing: bool,
+        simulate_victim_loss: bool,
+        pool_sync_mode: PoolSyncMode,
+        runtime_classifiers: Option<Arc<RuntimeClassifierRegistry>>,
+        cex_files: Option<Arc<CexFileSource>>,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
         clickhouse: &'static CH,
         parser: &'static Parser<T, DB>,
@@ -88,6 +101,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         metrics: bool,
         is_snapshot: bool,
         cex_window: usize,
+        bundle_stream: Option<BundleStream>,
     ) -> Self {
         Self {
             clickhouse,
@@ -100,11 +114,16 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             inspectors,
             quote_asset,
             force_no_dex_pricing,
+            simulate_victim_loss,
+            pool_sync_mode,
+            runtime_classifiers,
+            cex_files,
             cli_only,
             metrics,
             tip_db,
             is_snapshot,
             cex_window,
+            bundle_stream,
             _p: PhantomData,
         }
     }
@@ -330,6 +349,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                         ),
                         self.libmdbx,
                         self.inspectors,
+                        self.simulation_tracer(),
                         prgrs_bar,
                         metrics,
                     )
@@ -362,9 +382,28 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             self.parser,
             self.tip_db,
             self.inspectors,
+            self.simulation_tracer(),
+            self.bundle_stream.clone(),
+            self.metrics.then(TipMetrics::default),
         )
     }
 
+    /// The tracer used to simulate sandwich victims, if enabled and
+    /// supported by the tracer.
+    fn simulation_tracer(&self) -> Option<Arc<T>> {
+        if !self.simulate_victim_loss {
+            return None
+        }
+
+        let tracer = self.parser.get_tracer();
+        if !tracer.supports_simulation() {
+            tracing::warn!("the tracer doesn't support simulation, skipping victim simulation");
+            return None
+        }
+
+        Some(tracer)
+    }
+
     /// Initializes a StateCollector for a specific range of blocks.
     ///
     /// This function sets up the necessary components for collecting state data
@@ -395,7 +434,10 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     ) -> StateCollector<T, DB, CH> {
         let shutdown = Arc::new(AtomicBool::new(false));
         let (tx, rx) = unbounded_channel();
-        let classifier = static_object(Classifier::new(self.libmdbx, tx, self.parser.get_tracer()));
+        let classifier = static_object(
+            Classifier::new(self.libmdbx, tx, self.parser.get_tracer())
+                .with_runtime_classifiers(self.runtime_classifiers.clone()),
+        );
 
         let pairs = self.libmdbx.protocols_created_before(start_block).unwrap();
 
@@ -408,12 +450,13 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                 pools
                     .into_iter()
                     .filter(|(_, p, _)| p.has_state_updater())
-                    .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                    .map(|(addr, protocol, pairs)| (addr, (protocol, pairs)))
                     .collect::<Vec<_>>()
             })
             .collect::<FastHashMap<_, _>>();
 
-        let pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone());
+        let pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone())
+            .with_pool_sync_mode(self.pool_sync_mode);
 
         let data_req = Arc::new(AtomicBool::new(true));
 
@@ -428,6 +471,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             rest_pairs,
             data_req.clone(),
             pricing_metrics.clone(),
+            db_pool_key_lookup(self.libmdbx),
             executor.clone(),
         );
 
@@ -439,7 +483,8 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             self.force_no_dex_pricing,
             data_req,
             self.cex_window,
-        );
+        )
+        .with_cex_files(self.cex_files.clone());
 
         let block_window_size = self
             .inspectors
@@ -1,9 +1,10 @@
-#[cfg(feature = "local-clickhouse")]
 use std::sync::Arc;
 
+use brontes_core::decoding::TracingProvider;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_inspect::{
     composer::{run_block_inspection, ComposerResults},
+    sandwich::simulate_victim_loss,
     Inspector,
 };
 #[cfg(feature = "local-clickhouse")]
@@ -22,7 +23,7 @@ use brontes_types::{
 };
 use tracing::debug;
 
-use crate::Processor;
+use crate::{api::BundleStream, Processor};
 
 #[derive(Debug, Clone, Copy)]
 pub struct MevProcessor;
@@ -30,10 +31,12 @@ pub struct MevProcessor;
 impl Processor for MevProcessor {
     type InspectType = Vec<Bundle>;
 
-    async fn process_results<DB: DBWriter + LibmdbxReader>(
+    async fn process_results<T: TracingProvider, DB: DBWriter + LibmdbxReader>(
         db: &'static DB,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
         data: MultiBlockData,
+        simulation_tracer: Option<Arc<T>>,
+        bundle_stream: Option<BundleStream>,
     ) {
         let last = data.get_most_recent_block().clone();
         let BlockData { metadata, tree } = last;
@@ -54,10 +57,19 @@ impl Processor for MevProcessor {
             return
         }
 
-        let ComposerResults { block_details, mev_details, block_analysis, .. } =
+        let simulation_data = simulation_tracer.as_ref().map(|_| data.clone());
+
+        let ComposerResults { block_details, mut mev_details, block_analysis, .. } =
             execute_on!(async_inspect, { run_block_inspection(inspectors, data, db) }).await;
 
-        insert_mev_results(db, block_details, mev_details, block_analysis).await;
+        if let (Some(tracer), Some(data), Some(inspector)) =
+            (simulation_tracer, simulation_data, inspectors.first())
+        {
+            simulate_victim_loss(&*tracer, inspector.get_quote_token(), &mut mev_details, &data)
+                .await;
+        }
+
+        insert_mev_results(db, block_details, mev_details, block_analysis, bundle_stream).await;
     }
 }
 
@@ -82,6 +94,7 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     block_details: MevBlock,
     mev_details: Vec<Bundle>,
     analysis: BlockAnalysis,
+    bundle_stream: Option<BundleStream>,
 ) {
     debug!(
         target: "brontes::results",
@@ -92,11 +105,16 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
     let block_number = block_details.block_number;
     output_mev_and_update_searcher_info(database, &mev_details).await;
 
+    // only copied when someone is subscribed, as the block is moved into the db
+    let streamed = bundle_stream
+        .filter(BundleStream::has_subscribers)
+        .map(|stream| (stream, block_details.clone(), mev_details.clone()));
+
     // Attempt to save the MEV block details
-    if let Err(e) = database
+    let saved = database
         .save_mev_blocks(block_details.block_number, block_details, mev_details)
-        .await
-    {
+        .await;
+    if let Err(e) = &saved {
         tracing::error!(
             "Failed to insert classified data into libmdbx: {:?} at block: {}",
             e,
@@ -110,6 +128,11 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
             block_number
         );
     }
+
+    // subscribers only get the blocks that are in the db
+    if let (Ok(_), Some((stream, block_details, mev_details))) = (saved, streamed) {
+        stream.publish(&block_details, &mev_details);
+    }
 }
 async fn output_mev_and_update_searcher_info<DB: DBWriter + LibmdbxReader>(
     database: &DB,
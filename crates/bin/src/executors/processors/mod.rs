@@ -1,17 +1,27 @@
 pub mod mev;
 
+use std::sync::Arc;
+
+use brontes_core::decoding::TracingProvider;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
 use brontes_inspect::Inspector;
 use brontes_types::MultiBlockData;
 use futures::Future;
 pub use mev::*;
 
+use crate::api::BundleStream;
+
 pub trait Processor: Send + Sync + 'static + Unpin + Copy + Clone {
     type InspectType: Send + Sync + Unpin;
 
-    fn process_results<DB: DBWriter + LibmdbxReader>(
+    /// `simulation_tracer` is only set if the results should be enriched
+    /// with transaction simulations. `bundle_stream` is only set at the tip,
+    /// where the finalized results are also pushed to the stream subscribers.
+    fn process_results<T: TracingProvider, DB: DBWriter + LibmdbxReader>(
         db: &'static DB,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
         data: MultiBlockData,
+        simulation_tracer: Option<Arc<T>>,
+        bundle_stream: Option<BundleStream>,
     ) -> impl Future<Output = ()> + Send;
 }
@@ -1,6 +1,7 @@
 use std::{
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -27,16 +28,17 @@ pub struct RangeExecutorWithPricing<
     CH: ClickhouseHandle,
     P: Processor,
 > {
-    id:             usize,
-    collector:      StateCollector<T, DB, CH>,
-    insert_futures: FuturesUnordered<InsertFutures>,
-    current_block:  u64,
-    end_block:      u64,
-    libmdbx:        &'static DB,
-    inspectors:     &'static [&'static dyn Inspector<Result = P::InspectType>],
-    progress_bar:   Option<ProgressBar>,
-    global_metrics: Option<GlobalRangeMetrics>,
-    _p:             PhantomData<P>,
+    id:                usize,
+    collector:         StateCollector<T, DB, CH>,
+    insert_futures:    FuturesUnordered<InsertFutures>,
+    current_block:     u64,
+    end_block:         u64,
+    libmdbx:           &'static DB,
+    inspectors:        &'static [&'static dyn Inspector<Result = P::InspectType>],
+    simulation_tracer: Option<Arc<T>>,
+    progress_bar:      Option<ProgressBar>,
+    global_metrics:    Option<GlobalRangeMetrics>,
+    _p:                PhantomData<P>,
 }
 
 impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P: Processor>
@@ -49,6 +51,7 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
         state_collector: StateCollector<T, DB, CH>,
         libmdbx: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
+        simulation_tracer: Option<Arc<T>>,
         progress_bar: Option<ProgressBar>,
         global_metrics: Option<GlobalRangeMetrics>,
     ) -> Self {
@@ -60,6 +63,7 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             end_block,
             libmdbx,
             inspectors,
+            simulation_tracer,
             progress_bar,
             global_metrics,
             _p: PhantomData,
@@ -98,13 +102,16 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
         let metrics = self.global_metrics.clone();
         let inspectors = self.inspectors;
         let libmdbx = self.libmdbx;
+        let tracer = self.simulation_tracer.clone();
         self.insert_futures.push(Box::pin(async move {
             if let Some(metrics) = metrics {
                 metrics
-                    .meter_processing(|| Box::pin(P::process_results(libmdbx, inspectors, data)))
+                    .meter_processing(|| {
+                        Box::pin(P::process_results(libmdbx, inspectors, data, tracer, None))
+                    })
                     .await
             } else {
-                P::process_results(libmdbx, inspectors, data).await
+                P::process_results(libmdbx, inspectors, data, tracer, None).await
             }
         }));
     }
@@ -10,10 +10,15 @@ use std::{
 };
 
 use alloy_primitives::Address;
-use brontes_database::clickhouse::ClickhouseHandle;
+use brontes_database::{cex_files::CexFileSource, clickhouse::ClickhouseHandle};
 use brontes_types::{
+    block_metadata::Relays,
     db::{
-        cex::trades::{window_loader::CexWindow, CexTradeMap},
+        cex::{
+            quotes::CexOrderBookMap,
+            trades::{window_loader::CexWindow, CexTradeMap},
+            PerpTickerMap,
+        },
         dex::DexQuotes,
         metadata::Metadata,
         traits::{DBWriter, LibmdbxReader},
@@ -47,6 +52,7 @@ pub struct MetadataLoader<T: TracingProvider, CH: ClickhouseHandle> {
     cex_window_data:       CexWindow,
     always_generate_price: bool,
     force_no_dex_pricing:  bool,
+    cex_files:             Option<Arc<CexFileSource>>,
 }
 
 impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
@@ -67,9 +73,17 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
             result_buf: VecDeque::new(),
             always_generate_price,
             force_no_dex_pricing,
+            cex_files: None,
         }
     }
 
+    /// Loads cex order book snapshots & perp tickers into the metadata of
+    /// each block
+    pub fn with_cex_files(mut self, cex_files: Option<Arc<CexFileSource>>) -> Self {
+        self.cex_files = cex_files;
+        self
+    }
+
     pub fn should_process_next_block(&self) -> bool {
         self.needs_more_data.load(Ordering::SeqCst)
             && self.dex_pricer_stream.pending_trees() < MAX_PENDING_TREES
@@ -155,6 +169,22 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
         Some(self.cex_window_data.cex_trade_map())
     }
 
+    fn load_cex_books(&self, block: u64, block_timestamp: u64) -> Option<CexOrderBookMap> {
+        self.cex_files
+            .as_ref()?
+            .cex_order_books(block_timestamp * 1_000_000)
+            .inspect_err(|err| tracing::error!(?block, %err, "failed to load cex order books"))
+            .ok()
+    }
+
+    fn load_perp_tickers(&self, block: u64, block_timestamp: u64) -> Option<PerpTickerMap> {
+        self.cex_files
+            .as_ref()?
+            .perp_tickers(block_timestamp * 1_000_000)
+            .inspect_err(|err| tracing::error!(?block, %err, "failed to load perp tickers"))
+            .ok()
+    }
+
     fn load_metadata_no_dex_pricing<DB: LibmdbxReader>(
         &mut self,
         tree: BlockTree<Action>,
@@ -179,6 +209,8 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
             .expect("failed to fetch builder info table in libmdbx");
 
         meta.cex_trades = self.load_cex_trades(libmdbx, block);
+        meta.cex_books = self.load_cex_books(block, tree.header.timestamp);
+        meta.perp_tickers = self.load_perp_tickers(block, tree.header.timestamp);
 
         tracing::debug!(?block, "waiting for dex price");
 
@@ -211,6 +243,8 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
 
         let mut meta = meta.into_full_metadata(DexQuotes(vec![]));
         meta.cex_trades = self.load_cex_trades(libmdbx, block);
+        meta.cex_books = self.load_cex_books(block, tree.header.timestamp);
+        meta.perp_tickers = self.load_perp_tickers(block, tree.header.timestamp);
 
         self.result_buf
             .push_back(BlockData { metadata: meta.into(), tree: tree.into() });
@@ -237,6 +271,8 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
             .expect("failed to fetch builder info table in libmdbx");
 
         meta.cex_trades = self.load_cex_trades(libmdbx, block);
+        meta.cex_books = self.load_cex_books(block, tree.header.timestamp);
+        meta.perp_tickers = self.load_perp_tickers(block, tree.header.timestamp);
 
         tracing::debug!(?block, "caching result buf");
         self.result_buf
@@ -257,11 +293,17 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
         // given every download is -6 + 6 around the block
         // we calculate the offset from the current block that we need
         let offsets = (window / 12) as u64;
+        let cex_books = self.load_cex_books(block, tree.header.timestamp);
+        let perp_tickers = self.load_perp_tickers(block, tree.header.timestamp);
         let future = Box::pin(async move {
             let builder_info = libmdbx
                 .try_fetch_builder_info(tree.header.beneficiary)
                 .expect("failed to fetch builder info table in libmdbx");
 
+            // the bids are fetched from the same relays as the relay payload of the
+            // metadata
+            let builder_auction = tokio::spawn(Relays::get_builder_auction(block, block_hash));
+
             //fetch metadata till it works
             let mut meta = loop {
                 if let Ok(res) = clickhouse
@@ -318,8 +360,18 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
                 }
             };
 
+            let builder_auction = builder_auction.await.ok().flatten();
+            if let Some(auction) = builder_auction.clone() {
+                if let Err(e) = libmdbx.write_builder_auction(block, auction).await {
+                    tracing::warn!(?block, err=?e, "failed to store builder auction");
+                }
+            }
+
             meta.cex_trades = Some(trades);
+            meta.cex_books = cex_books;
+            meta.perp_tickers = perp_tickers;
             meta.builder_info = builder_info;
+            meta.builder_auction = builder_auction;
             (block, tree, meta)
         });
 
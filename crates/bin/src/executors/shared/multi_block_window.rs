@@ -27,4 +27,10 @@ impl MultiBlockWindow {
 
         MultiBlockData { blocks: block_count, per_block_data: block_data }
     }
+
+    /// Drops the data of `block` & every block after it
+    pub fn rollback_to(&mut self, block: u64) {
+        self.block_window_queue
+            .retain(|data| data.metadata.block_num < block);
+    }
 }
@@ -79,6 +79,17 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
         self.metadata_fetcher.should_process_next_block()
     }
 
+    /// No block is being traced, priced or waiting to be inspected
+    pub fn is_idle(&self) -> bool {
+        self.collection_future.is_none() && self.metadata_fetcher.is_finished()
+    }
+
+    /// Forgets the data of `block` & every block after it, so that it isn't
+    /// part of the window of the blocks that are processed again
+    pub fn rollback_to(&mut self, block: u64) {
+        self.multi_block.rollback_to(block);
+    }
+
     async fn state_future(
         generate_pricing: bool,
         block: u64,
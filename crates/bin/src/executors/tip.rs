@@ -1,16 +1,19 @@
 use std::{
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
+use alloy_primitives::B256;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::{
     clickhouse::ClickhouseHandle,
     libmdbx::{DBWriter, LibmdbxReader},
 };
 use brontes_inspect::Inspector;
+use brontes_metrics::tip::TipMetrics;
 use brontes_types::MultiBlockData;
 use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
@@ -18,7 +21,11 @@ use tokio::time::{interval, Interval};
 use tracing::debug;
 
 use super::shared::state_collector::StateCollector;
-use crate::Processor;
+use crate::{api::BundleStream, Processor};
+
+/// How many of the last processed blocks are checked against the canonical
+/// chain for reorgs
+const MAX_REORG_DEPTH: u64 = 64;
 
 pub struct TipInspector<
     T: TracingProvider,
@@ -32,8 +39,11 @@ pub struct TipInspector<
     state_collector:    StateCollector<T, DB, CH>,
     database:           &'static DB,
     inspectors:         &'static [&'static dyn Inspector<Result = P::InspectType>],
+    simulation_tracer:  Option<Arc<T>>,
+    bundle_stream:      Option<BundleStream>,
     processing_futures: FuturesUnordered<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
     poll_interval:      Interval,
+    metrics:            Option<TipMetrics>,
     _p:                 PhantomData<P>,
 }
 
@@ -47,16 +57,22 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         parser: &'static Parser<T, DB>,
         database: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
+        simulation_tracer: Option<Arc<T>>,
+        bundle_stream: Option<BundleStream>,
+        metrics: Option<TipMetrics>,
     ) -> Self {
         Self {
             back_from_tip,
             state_collector,
             inspectors,
+            simulation_tracer,
+            bundle_stream,
             current_block,
             parser,
             processing_futures: FuturesUnordered::new(),
             database,
             poll_interval: interval(Duration::from_secs(3)),
+            metrics,
             _p: PhantomData,
         }
     }
@@ -114,16 +130,110 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         }
     }
 
+    /// Rolls back the results of every processed block that is no longer
+    /// canonical & rewinds so that they are processed again
+    fn handle_reorgs(&mut self) {
+        let tracer = self.parser.get_tracer();
+        let reorged = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(find_reorg(
+                self.database,
+                self.current_block,
+                |block| {
+                    let tracer = tracer.clone();
+                    async move { tracer.block_hash_for_id(block).await }
+                },
+            ))
+        });
+
+        let first_reorged = match reorged {
+            Ok(Some(block)) => block,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Error: {:?}", e);
+                return
+            }
+        };
+        let depth = self.current_block - first_reorged;
+        tracing::warn!(
+            target:"brontes::tip_inspector",
+            %first_reorged,
+            %depth,
+            "reorg detected, re-running blocks"
+        );
+
+        for block in first_reorged..self.current_block {
+            let res = tokio::task::block_in_place(|| {
+                tokio::runtime::Handle::current().block_on(self.database.rollback_block(block))
+            });
+            if let Err(e) = res {
+                tracing::error!(%block, "failed to roll back block: {:?}", e);
+                return
+            }
+        }
+
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.reorgs.increment(1);
+            metrics.reorg_depth.record(depth as f64);
+            metrics.rolled_back_blocks.increment(depth);
+        }
+
+        self.state_collector.rollback_to(first_reorged);
+        self.current_block = first_reorged;
+    }
+
     fn on_price_finish(&mut self, data: MultiBlockData) {
         debug!(target:"brontes::tip_inspector","Completed DEX pricing");
+        // the hash the block is processed with is what reorgs are detected
+        // against, it is overwritten when the block is processed again
+        if let Some(block) = data.per_block_data.last() {
+            let block_number = block.metadata.block_num;
+            let mut block_info = block.metadata.block_metadata.to_block_info();
+            block_info.block_hash = block.tree.header.hash_slow().into();
+
+            let database = self.database;
+            self.processing_futures.push(Box::pin(async move {
+                if let Err(e) = database.write_block_info(block_number, block_info).await {
+                    tracing::error!(%block_number, "failed to store block info: {:?}", e);
+                }
+            }));
+        }
         self.processing_futures.push(Box::pin(P::process_results(
             self.database,
             self.inspectors,
             data,
+            self.simulation_tracer.clone(),
+            self.bundle_stream.clone(),
         )));
     }
 }
 
+/// Walks back from `current_block`, comparing the block hash each block was
+/// processed with to the canonical one. Returns the first block that was
+/// reorged out.
+async fn find_reorg<DB, F, Fut>(
+    db: &DB,
+    current_block: u64,
+    canonical_hash: F,
+) -> eyre::Result<Option<u64>>
+where
+    DB: LibmdbxReader,
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = eyre::Result<Option<B256>>>,
+{
+    let mut reorged = None;
+
+    for block in (current_block.saturating_sub(MAX_REORG_DEPTH)..current_block).rev() {
+        let Some(processed) = db.try_fetch_block_info(block)? else { break };
+
+        match canonical_hash(block).await? {
+            Some(canonical) if canonical == B256::from(processed.block_hash) => break,
+            _ => reorged = Some(block),
+        }
+    }
+
+    Ok(reorged)
+}
+
 impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P: Processor> Future
     for TipInspector<T, DB, CH, P>
 {
@@ -132,7 +242,16 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         // given we pull the next block sync, we use this to trigger looking
         // for the next block.
-        while self.poll_interval.poll_tick(cx).is_ready() {}
+        let mut ticked = false;
+        while self.poll_interval.poll_tick(cx).is_ready() {
+            ticked = true;
+        }
+
+        // only check for reorgs in between blocks, so that no results of a
+        // reorged block are written after it was rolled back
+        if ticked && self.processing_futures.is_empty() && self.state_collector.is_idle() {
+            self.handle_reorgs();
+        }
 
         if self.start_block_inspector() && self.state_collector.should_process_next_block() {
             let block = self.current_block;
@@ -153,3 +272,48 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_database::{libmdbx::LibmdbxReadWriter, BlockInfo, BlockInfoData};
+    use brontes_types::db::metadata::BlockMetadataInner;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_find_reorg() {
+        let path = std::env::temp_dir().join("brontes-reorg-test-db");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let db = LibmdbxReadWriter::init_db_tests(&path).unwrap();
+        let block_info = (3..=10)
+            .map(|block| {
+                BlockInfoData::new(
+                    block,
+                    BlockMetadataInner {
+                        block_hash: B256::with_last_byte(block as u8).into(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<BlockInfo, BlockInfoData>(&block_info)
+            .unwrap();
+
+        // blocks 8 & 9 were processed with hashes that are no longer canonical
+        let canonical = |block: u64| async move {
+            let byte = if block >= 8 { 0xff } else { block as u8 };
+            eyre::Ok(Some(B256::with_last_byte(byte)))
+        };
+        assert_eq!(find_reorg(&db, 10, canonical).await.unwrap(), Some(8));
+        // block 10 isn't processed yet
+        assert_eq!(find_reorg(&db, 8, canonical).await.unwrap(), None);
+
+        // the walk stops at the first block without a processed hash
+        let all_reorged = |_: u64| async move { eyre::Ok(None) };
+        assert_eq!(find_reorg(&db, 10, all_reorged).await.unwrap(), Some(3));
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
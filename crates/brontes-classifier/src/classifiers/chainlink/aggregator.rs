@@ -0,0 +1,47 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo, Protocol,
+};
+use reth_primitives::U256;
+
+action_impl!(
+    Protocol::ChainlinkOCR,
+    crate::ChainlinkOCR::transmitCall,
+    OracleUpdate,
+    [..NewTransmission],
+    logs: true,
+    |info: CallInfo,
+    log_data: ChainlinkOCRTransmitCallLogs,
+    _db_tx: &DB| {
+        let logs = log_data.new_transmission_field?;
+
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::ChainlinkOCR,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            oracle: info.target_address,
+            answer: (!logs.answer.is_negative()).then(|| U256::from(logs.answer.into_raw())),
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ChainlinkOCR2,
+    crate::ChainlinkOCR2::transmitCall,
+    OracleUpdate,
+    [..NewTransmission],
+    logs: true,
+    |info: CallInfo,
+    log_data: ChainlinkOCR2TransmitCallLogs,
+    _db_tx: &DB| {
+        let logs = log_data.new_transmission_field?;
+
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::ChainlinkOCR2,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            oracle: info.target_address,
+            answer: (!logs.answer.is_negative()).then(|| U256::from(logs.answer.into_raw())),
+        })
+    }
+);
@@ -0,0 +1,2 @@
+mod aggregator;
+pub use aggregator::*;
@@ -0,0 +1,247 @@
+use alloy_sol_types::{SolCall, SolEvent};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    normalized_actions::{Action, NormalizedLiquidation},
+    structured_trace::CallFrameInfo,
+    utils::ToScaledRational,
+};
+
+use crate::CompoundV3Comet::{absorbCall, AbsorbCollateral, AbsorbDebt};
+
+/// An absorb liquidates every account passed to it in a single call, which
+/// can't be expressed by the single action the classifier dispatch returns.
+/// These are classified ahead of the dispatch into one liquidation per
+/// absorbed account, using the largest collateral absorbed from it.
+///
+/// Liquidators don't receive the absorbed collateral, they buy it from the
+/// protocol reserves at a discount afterwards. Those purchases aren't
+/// liquidations and are left to the transfer classification.
+///
+/// Only called for calls to a Compound V3 comet.
+pub fn classify_absorb<DB: LibmdbxReader>(
+    call_info: &CallFrameInfo<'_>,
+    db_tx: &DB,
+) -> Option<Vec<Action>> {
+    if call_info.call_data.get(..4)? != absorbCall::SELECTOR.as_slice() {
+        return None
+    }
+
+    absorb_liquidations(call_info, db_tx)
+        .map_err(|e| {
+            tracing::error!(
+                error=%e,
+                pool=?call_info.target_address,
+                "classifier: failed to classify compound v3 absorb"
+            )
+        })
+        .ok()
+}
+
+fn absorb_liquidations<DB: LibmdbxReader>(
+    call_info: &CallFrameInfo<'_>,
+    db_tx: &DB,
+) -> eyre::Result<Vec<Action>> {
+    let (mut debts, mut collaterals) = (Vec::new(), Vec::new());
+    // the comet is a proxy, so the events are emitted by the delegate call
+    for log in call_info
+        .logs
+        .iter()
+        .chain(call_info.delegate_logs.iter().copied())
+    {
+        if let Ok(debt) = AbsorbDebt::decode_log_data(&log.data, false) {
+            debts.push(debt);
+        } else if let Ok(collateral) = AbsorbCollateral::decode_log_data(&log.data, false) {
+            collaterals.push(collateral);
+        }
+    }
+
+    let details = db_tx.get_protocol_details(call_info.target_address)?;
+    let debt_info = db_tx.try_fetch_token_info(details.token0)?;
+
+    debts
+        .into_iter()
+        .filter_map(|debt| {
+            // accounts without collateral only leave bad debt behind
+            let collateral = collaterals
+                .iter()
+                .filter(|collateral| collateral.borrower == debt.borrower)
+                .max_by_key(|collateral| collateral.usdValue)?;
+
+            Some(
+                db_tx
+                    .try_fetch_token_info(collateral.asset)
+                    .map(|collateral_info| {
+                        Action::Liquidation(NormalizedLiquidation {
+                            protocol:              Protocol::CompoundV3,
+                            trace_index:           call_info.trace_idx,
+                            pool:                  call_info.target_address,
+                            liquidator:            debt.absorber,
+                            debtor:                debt.borrower,
+                            covered_debt:          debt
+                                .basePaidOut
+                                .to_scaled_rational(debt_info.decimals),
+                            liquidated_collateral: collateral
+                                .collateralAbsorbed
+                                .to_scaled_rational(collateral_info.decimals),
+                            collateral_asset:      collateral_info,
+                            debt_asset:            debt_info.clone(),
+                            msg_value:             call_info.msg_value,
+                        })
+                    }),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{structured_trace::CallFrameInfo, Protocol, ToScaledRational};
+
+    use super::classify_absorb;
+    use crate::{
+        test_utils::ClassifierTestUtils,
+        CompoundV3Comet::{absorbCall, AbsorbCollateral, AbsorbDebt},
+    };
+
+    const COMET: Address = Address::new(hex!("c3d688B66703497DAA19211EEdff47f25384cdc3"));
+    const USDC: Address = Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+    const WBTC: Address = Address::new(hex!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599"));
+
+    #[brontes_macros::test]
+    async fn test_compound_v3_absorb_liquidates_each_account() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV3,
+            COMET,
+            USDC,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let [absorber, first, second, bad_debt] = [1u8, 2, 3, 4].map(Address::with_last_byte);
+        let log = |data| Log { address: COMET, data };
+        let logs = vec![
+            log(AbsorbCollateral {
+                absorber,
+                borrower: first,
+                asset: WETH,
+                collateralAbsorbed: U256::from(10u128.pow(18)),
+                usdValue: U256::from(3000),
+            }
+            .encode_log_data()),
+            log(AbsorbCollateral {
+                absorber,
+                borrower: first,
+                asset: WBTC,
+                collateralAbsorbed: U256::from(10u128.pow(8)),
+                usdValue: U256::from(60000),
+            }
+            .encode_log_data()),
+            log(AbsorbDebt {
+                absorber,
+                borrower: first,
+                basePaidOut: U256::from(50_000 * 10u128.pow(6)),
+                usdValue: U256::from(50000),
+            }
+            .encode_log_data()),
+            log(AbsorbCollateral {
+                absorber,
+                borrower: second,
+                asset: WETH,
+                collateralAbsorbed: U256::from(2 * 10u128.pow(18)),
+                usdValue: U256::from(6000),
+            }
+            .encode_log_data()),
+            log(AbsorbDebt {
+                absorber,
+                borrower: second,
+                basePaidOut: U256::from(5_000 * 10u128.pow(6)),
+                usdValue: U256::from(5000),
+            }
+            .encode_log_data()),
+            log(AbsorbDebt {
+                absorber,
+                borrower: bad_debt,
+                basePaidOut: U256::from(10u128.pow(6)),
+                usdValue: U256::from(1),
+            }
+            .encode_log_data()),
+        ];
+
+        let call_info = CallFrameInfo {
+            trace_idx:      0,
+            call_data:      Bytes::from(
+                absorbCall { absorber, accounts: vec![first, second, bad_debt] }.abi_encode(),
+            ),
+            return_data:    Bytes::new(),
+            target_address: COMET,
+            from_address:   absorber,
+            logs:           &[],
+            delegate_logs:  logs.iter().collect(),
+            msg_sender:     absorber,
+            msg_value:      U256::ZERO,
+        };
+
+        let liquidations = classify_absorb(&call_info, classifier_utils.libmdbx)
+            .unwrap()
+            .into_iter()
+            .map(|action| action.force_liquidation())
+            .collect::<Vec<_>>();
+
+        assert_eq!(liquidations.len(), 2);
+        assert!(liquidations
+            .iter()
+            .all(|liq| liq.liquidator == absorber && liq.debt_asset.address == USDC));
+
+        assert_eq!(liquidations[0].debtor, first);
+        assert_eq!(liquidations[0].collateral_asset.address, WBTC);
+        assert_eq!(
+            liquidations[0].liquidated_collateral,
+            U256::from(10u128.pow(8)).to_scaled_rational(8)
+        );
+        assert_eq!(
+            liquidations[0].covered_debt,
+            U256::from(50_000 * 10u128.pow(6)).to_scaled_rational(6)
+        );
+
+        assert_eq!(liquidations[1].debtor, second);
+        assert_eq!(liquidations[1].collateral_asset.address, WETH);
+    }
+
+    #[brontes_macros::test]
+    async fn test_compound_v3_buy_collateral_isnt_a_liquidation() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV3,
+            COMET,
+            USDC,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let call_info = CallFrameInfo {
+            trace_idx:      0,
+            // buyCollateral(address,uint256,uint256,address)
+            call_data:      Bytes::from(hex!("e4e6e779")),
+            return_data:    Bytes::new(),
+            target_address: COMET,
+            from_address:   Address::ZERO,
+            logs:           &[],
+            delegate_logs:  vec![],
+            msg_sender:     Address::ZERO,
+            msg_value:      U256::ZERO,
+        };
+
+        assert!(classify_absorb(&call_info, classifier_utils.libmdbx).is_none());
+    }
+}
@@ -35,6 +35,30 @@ action_impl!(
     }
 );
 
+// Comet markets are deployed behind proxies that governance configures
+// through the configurator, the configuration holds the base token and all
+// collateral assets of the market.
+action_impl!(
+    Protocol::CompoundV3,
+    crate::CompoundV3Configurator::setConfigurationCall,
+    NewPool,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: setConfigurationCall, _| {
+        let config = call_data.newConfiguration;
+        let tokens = std::iter::once(config.baseToken)
+            .chain(config.assetConfigs.into_iter().map(|asset| asset.asset))
+            .collect();
+
+        Ok(NormalizedNewPool {
+            trace_index: info.trace_idx,
+            protocol: Protocol::CompoundV3,
+            pool_address: call_data.cometProxy,
+            tokens,
+        })
+    }
+);
+
 #[cfg(test)]
 mod tests {
     use alloy_primitives::{hex, B256};
@@ -1,5 +1,7 @@
 mod compound_v2;
+mod compound_v3;
 mod discovery;
 
 pub use compound_v2::*;
+pub use compound_v3::*;
 pub use discovery::*;
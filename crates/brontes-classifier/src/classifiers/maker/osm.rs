@@ -0,0 +1,28 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo, Protocol,
+};
+use reth_primitives::U256;
+
+// A poke moves the queued price into the current slot of the OSM, which is
+// when the new price becomes usable for the vaults of that collateral.
+action_impl!(
+    Protocol::MakerOSM,
+    crate::MakerOSM::pokeCall,
+    OracleUpdate,
+    [..LogValue],
+    logs: true,
+    |info: CallInfo,
+    log_data: MakerOSMPokeCallLogs,
+    _db_tx: &DB| {
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::MakerOSM,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            oracle: info.target_address,
+            answer: log_data
+                .log_value_field
+                .map(|log| U256::from_be_bytes(log.val.0)),
+        })
+    }
+);
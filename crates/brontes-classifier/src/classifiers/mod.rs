@@ -41,6 +41,15 @@ pub use clipper::*;
 pub mod dodo;
 pub use dodo::*;
 
+pub mod chainlink;
+pub use chainlink::*;
+
+pub mod spark;
+pub use spark::*;
+
+pub mod morpho;
+pub use morpho::*;
+
 discovery_dispatch!(
     DiscoveryClassifier,
     SushiSwapV2Discovery,
@@ -81,6 +90,10 @@ action_dispatch!(
     UniswapV3MintCall,
     UniswapV3BurnCall,
     UniswapV3CollectCall,
+    UniswapV4SwapCall,
+    UniswapV4ModifyLiquidityCall,
+    UniswapV4DonateCall,
+    UniswapV4InitializeCall,
     SushiSwapV3SwapCall,
     SushiSwapV3MintCall,
     SushiSwapV3BurnCall,
@@ -145,6 +158,7 @@ action_dispatch!(
     MakerPSMBuyGemCall,
     MakerPSMSellGemCall,
     MakerDssFlashFlashLoanCall,
+    MakerOSMPokeCall,
     AaveV2LiquidationCallCall,
     AaveV3LiquidationCallCall,
     AaveV2FlashLoanCall,
@@ -162,6 +176,7 @@ action_dispatch!(
     CompoundV2LiquidateBorrowCall,
     CompoundV2Initialize_0Call,
     CompoundV2Initialize_1Call,
+    CompoundV3SetConfigurationCall,
     OneInchV5SwapCall,
     OneInchV5ClipperSwapCall,
     OneInchV5ClipperSwapToCall,
@@ -208,5 +223,9 @@ action_dispatch!(
     DodoSellSharesCall,
     DodoSellBaseCall,
     DodoSellQuoteCall,
-    DodoFlashLoanCall
+    DodoFlashLoanCall,
+    ChainlinkOCRTransmitCall,
+    ChainlinkOCR2TransmitCall,
+    SparkLiquidationCallCall,
+    MorphoBlueLiquidateCall
 );
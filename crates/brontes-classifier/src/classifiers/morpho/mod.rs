@@ -0,0 +1,3 @@
+mod morpho_blue;
+
+pub use morpho_blue::*;
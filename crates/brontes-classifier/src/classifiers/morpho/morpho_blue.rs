@@ -0,0 +1,136 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedLiquidation, structured_trace::CallInfo, utils::ToScaledRational,
+    Protocol,
+};
+
+// Morpho Blue markets all live in the same singleton and are identified by
+// their params, which are passed in with every call. This means we don't need
+// to discover them to classify liquidations.
+action_impl!(
+    Protocol::MorphoBlue,
+    crate::MorphoBlue::liquidateCall,
+    Liquidation,
+    [..Liquidate],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: liquidateCall,
+    log_data: MorphoBlueLiquidateCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.liquidate_field?;
+        let market = call_data.marketParams;
+
+        let debt_info = db_tx.try_fetch_token_info(market.loanToken)?;
+        let collateral_info = db_tx.try_fetch_token_info(market.collateralToken)?;
+
+        let covered_debt = logs.repaidAssets.to_scaled_rational(debt_info.decimals);
+        let liquidated_collateral = logs.seizedAssets.to_scaled_rational(collateral_info.decimals);
+
+        return Ok(NormalizedLiquidation {
+            protocol: Protocol::MorphoBlue,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: logs.caller,
+            debtor: logs.borrower,
+            collateral_asset: collateral_info,
+            debt_asset: debt_info,
+            covered_debt,
+            liquidated_collateral,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, Log, B256, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{structured_trace::CallFrameInfo, Protocol, ToScaledRational};
+
+    use crate::{
+        test_utils::ClassifierTestUtils,
+        ActionCollection,
+        MorphoBlue::{liquidateCall, Liquidate, MarketParams},
+        ProtocolClassifier,
+    };
+
+    const MORPHO: Address = Address::new(hex!("BBBBBbbBBb9cC5e90e3b3Af64bdAF62C37EEFFCb"));
+    const USDC: Address = Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_morpho_blue_liquidation() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::MorphoBlue,
+            MORPHO,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let [liquidator, borrower] = [1u8, 2].map(Address::with_last_byte);
+        let repaid = U256::from(2_000u128 * 10u128.pow(6));
+        let seized = U256::from(10u128.pow(18));
+
+        let log = Log {
+            address: MORPHO,
+            data:    Liquidate {
+                id: B256::with_last_byte(1),
+                caller: liquidator,
+                borrower,
+                repaidAssets: repaid,
+                repaidShares: U256::from(1),
+                seizedAssets: seized,
+                badDebtAssets: U256::ZERO,
+                badDebtShares: U256::ZERO,
+            }
+            .encode_log_data(),
+        };
+
+        let call_info = CallFrameInfo {
+            trace_idx:      1,
+            call_data:      Bytes::from(
+                liquidateCall {
+                    marketParams: MarketParams {
+                        loanToken:       USDC,
+                        collateralToken: WETH,
+                        oracle:          Address::with_last_byte(3),
+                        irm:             Address::with_last_byte(4),
+                        lltv:            U256::from(86 * 10u128.pow(16)),
+                    },
+                    borrower,
+                    seizedAssets: seized,
+                    repaidShares: U256::ZERO,
+                    data: Bytes::new(),
+                }
+                .abi_encode(),
+            ),
+            return_data:    Bytes::new(),
+            target_address: MORPHO,
+            from_address:   liquidator,
+            logs:           std::slice::from_ref(&log),
+            delegate_logs:  vec![],
+            msg_sender:     liquidator,
+            msg_value:      U256::ZERO,
+        };
+
+        let (_, action) = ProtocolClassifier::default()
+            .dispatch(call_info, classifier_utils.libmdbx, 0, 0)
+            .unwrap();
+        let liquidation = action.force_liquidation();
+
+        assert_eq!(liquidation.protocol, Protocol::MorphoBlue);
+        assert_eq!(liquidation.liquidator, liquidator);
+        assert_eq!(liquidation.debtor, borrower);
+        assert_eq!(liquidation.debt_asset.address, USDC);
+        assert_eq!(liquidation.collateral_asset.address, WETH);
+        assert_eq!(liquidation.covered_debt, repaid.to_scaled_rational(6));
+        assert_eq!(liquidation.liquidated_collateral, seized.to_scaled_rational(18));
+    }
+}
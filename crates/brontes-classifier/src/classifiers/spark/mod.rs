@@ -0,0 +1,3 @@
+mod spark_lend;
+
+pub use spark_lend::*;
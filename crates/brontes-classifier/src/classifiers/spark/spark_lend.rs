@@ -0,0 +1,104 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedLiquidation, structured_trace::CallInfo, utils::ToScaledRational,
+    Protocol,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+// SparkLend is a fork of the Aave V3 pool so we share its abi. Unlike Aave we
+// track the pool proxy rather than the implementation so the pool is the
+// target of the call.
+action_impl!(
+    Protocol::Spark,
+    crate::AaveV3::liquidationCallCall,
+    Liquidation,
+    [LiquidationEvent],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: liquidationCallCall,
+    db_tx: &DB | {
+
+        let debt_info = db_tx.try_fetch_token_info(call_data.debtAsset)?;
+        let collateral_info = db_tx.try_fetch_token_info(call_data.collateralAsset)?;
+
+        let covered_debt = call_data.debtToCover.to_scaled_rational(debt_info.decimals);
+
+        return Ok(NormalizedLiquidation {
+            protocol: Protocol::Spark,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: info.msg_sender,
+            debtor: call_data.user,
+            collateral_asset: collateral_info,
+            debt_asset: debt_info,
+            covered_debt,
+            // filled in later
+            liquidated_collateral: Rational::ZERO,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, U256};
+    use alloy_sol_types::SolCall;
+    use brontes_types::{structured_trace::CallFrameInfo, Protocol, ToScaledRational};
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use crate::{
+        test_utils::ClassifierTestUtils, AaveV3::liquidationCallCall, ActionCollection,
+        ProtocolClassifier,
+    };
+
+    const POOL: Address = Address::new(hex!("C13e21B648A5Ee794902342038FF3aDAB66BE987"));
+    const DAI: Address = Address::new(hex!("6B175474E89094C44Da98b954EedeAC495271d0F"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_spark_liquidation() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(Protocol::Spark, POOL, DAI, None, None, None, None, None);
+
+        let [liquidator, user] = [1u8, 2].map(Address::with_last_byte);
+        let debt_to_cover = U256::from(1_000u128 * 10u128.pow(18));
+
+        let call_info = CallFrameInfo {
+            trace_idx:      3,
+            call_data:      Bytes::from(
+                liquidationCallCall {
+                    collateralAsset: WETH,
+                    debtAsset: DAI,
+                    user,
+                    debtToCover: debt_to_cover,
+                    receiveAToken: false,
+                }
+                .abi_encode(),
+            ),
+            return_data:    Bytes::new(),
+            target_address: POOL,
+            from_address:   liquidator,
+            logs:           &[],
+            delegate_logs:  vec![],
+            msg_sender:     liquidator,
+            msg_value:      U256::ZERO,
+        };
+
+        let (_, action) = ProtocolClassifier::default()
+            .dispatch(call_info, classifier_utils.libmdbx, 0, 0)
+            .unwrap();
+        let liquidation = action.force_liquidation();
+
+        assert_eq!(liquidation.protocol, Protocol::Spark);
+        assert_eq!(liquidation.trace_index, 3);
+        assert_eq!(liquidation.pool, POOL);
+        assert_eq!(liquidation.liquidator, liquidator);
+        assert_eq!(liquidation.debtor, user);
+        assert_eq!(liquidation.debt_asset.address, DAI);
+        assert_eq!(liquidation.collateral_asset.address, WETH);
+        assert_eq!(liquidation.covered_debt, debt_to_cover.to_scaled_rational(18));
+        // filled in by the multi frame classification
+        assert_eq!(liquidation.liquidated_collateral, Rational::ZERO);
+    }
+}
@@ -0,0 +1,383 @@
+use alloy_primitives::{keccak256, I256, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    db::uniswap_v4::{native_to_weth, UniswapV4PoolKey},
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedMint, NormalizedPoolConfigUpdate, NormalizedSwap,
+    },
+    structured_trace::{CallFrameInfo, CallInfo},
+    ToScaledRational,
+};
+use tracing::error;
+
+use crate::UniswapV4::{donateCall, initializeCall, modifyLiquidityCall, swapCall, PoolKey};
+
+// All V4 pools live inside of the PoolManager. The calls below are made by the
+// locker from within its `unlockCallback`, so the pool is identified by the
+// PoolKey in the call data instead of the target address. Calls on pools with
+// hooks are filtered out by `record_v4_pool` before they get here.
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::swapCall,
+    Swap,
+    [Swap],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    return_data: swapReturn,
+    db_tx: &DB| {
+        let pool = pool_key(&call_data.key).pool_address();
+        let (token_0_delta, token_1_delta) = split_balance_delta(return_data.swapDelta);
+
+        let t0_info = db_tx.try_fetch_token_info(native_to_weth(call_data.key.currency0))?;
+        let t1_info = db_tx.try_fetch_token_info(native_to_weth(call_data.key.currency1))?;
+
+        // deltas are from the view of the caller, negative means it was paid in
+        let (amount_in, amount_out, token_in, token_out) = if token_0_delta.is_negative() {
+            (
+                token_0_delta.unsigned_abs().to_scaled_rational(t0_info.decimals),
+                token_1_delta.unsigned_abs().to_scaled_rational(t1_info.decimals),
+                t0_info,
+                t1_info,
+            )
+        } else {
+            (
+                token_1_delta.unsigned_abs().to_scaled_rational(t1_info.decimals),
+                token_0_delta.unsigned_abs().to_scaled_rational(t0_info.decimals),
+                t1_info,
+                t0_info,
+            )
+        };
+
+        Ok(NormalizedSwap {
+            protocol: Protocol::UniswapV4,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            pool,
+            recipient: info.from_address,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::modifyLiquidityCall,
+    Action,
+    [ModifyLiquidity],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: modifyLiquidityCall,
+    return_data: modifyLiquidityReturn,
+    db_tx: &DB| {
+        let pool = pool_key(&call_data.key).pool_address();
+
+        let t0_info = db_tx.try_fetch_token_info(native_to_weth(call_data.key.currency0))?;
+        let t1_info = db_tx.try_fetch_token_info(native_to_weth(call_data.key.currency1))?;
+
+        // the caller delta includes the fees accrued by the position, these are
+        // a collect and not part of the liquidity change
+        let (caller_0, caller_1) = split_balance_delta(return_data.callerDelta);
+        let (fees_0, fees_1) = split_balance_delta(return_data.feesAccrued);
+        let am0 = (caller_0 - fees_0).unsigned_abs().to_scaled_rational(t0_info.decimals);
+        let am1 = (caller_1 - fees_1).unsigned_abs().to_scaled_rational(t1_info.decimals);
+
+        if call_data.params.liquidityDelta.is_negative() {
+            Ok(Action::Burn(NormalizedBurn {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                token: vec![t0_info, t1_info],
+                amount: vec![am0, am1],
+            }))
+        } else {
+            Ok(Action::Mint(NormalizedMint {
+                protocol: Protocol::UniswapV4,
+                trace_index: info.trace_idx,
+                from: info.from_address,
+                recipient: info.from_address,
+                pool,
+                token: vec![t0_info, t1_info],
+                amount: vec![am0, am1],
+            }))
+        }
+    }
+);
+
+// Donations are paid to the in range liquidity providers without minting a
+// position, which from the view of the pool is the same as a mint.
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::donateCall,
+    Mint,
+    [Donate],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: donateCall,
+    db_tx: &DB| {
+        let pool = pool_key(&call_data.key).pool_address();
+
+        let t0_info = db_tx.try_fetch_token_info(native_to_weth(call_data.key.currency0))?;
+        let t1_info = db_tx.try_fetch_token_info(native_to_weth(call_data.key.currency1))?;
+
+        let am0 = call_data.amount0.to_scaled_rational(t0_info.decimals);
+        let am1 = call_data.amount1.to_scaled_rational(t1_info.decimals);
+
+        Ok(NormalizedMint {
+            protocol: Protocol::UniswapV4,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            recipient: info.from_address,
+            pool,
+            token: vec![t0_info, t1_info],
+            amount: vec![am0, am1],
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::initializeCall,
+    PoolConfigUpdate,
+    [..Initialize],
+    call_data: true,
+    |info: CallInfo, call_data: initializeCall, _| {
+        let key = pool_key(&call_data.key);
+
+        Ok(NormalizedPoolConfigUpdate {
+            trace_index: info.trace_idx,
+            protocol: Protocol::UniswapV4,
+            pool_address: key.pool_address(),
+            tokens: key.tokens().to_vec(),
+        })
+    }
+);
+
+/// Stores the key of the V4 pool a PoolManager call acts on, as the pool can't
+/// be loaded from its address alone. Pools that were initialized before they
+/// were tracked are added to the protocol info on their first call, the
+/// config update for them is returned so the pricing engine can add them.
+///
+/// Hooks can change the amounts & the pool state in ways the PoolManager
+/// calls don't show, so calls on pools with hooks shouldn't be classified.
+/// Their keys aren't stored.
+///
+/// Only called for calls to the PoolManager.
+pub async fn record_v4_pool<DB: LibmdbxReader + DBWriter>(
+    call_info: &CallFrameInfo<'_>,
+    block: u64,
+    db: &DB,
+) -> Option<(UniswapV4PoolKey, Option<NormalizedPoolConfigUpdate>)> {
+    let (key, is_initialize) = decode_pool_key(call_info)?;
+    if key.has_hooks() {
+        return Some((key, None))
+    }
+
+    let pool_address = key.pool_address();
+    match db.try_fetch_v4_pool_key(pool_address) {
+        // each pool is only recorded on its first call
+        Ok(Some(stored)) if stored.pool_id == key.pool_id => return Some((key, None)),
+        Ok(Some(stored)) => {
+            error!(
+                pool_id=?key.pool_id,
+                stored_pool_id=?stored.pool_id,
+                "v4 pool address is already used by another pool"
+            );
+            return Some((key, None))
+        }
+        Ok(None) => {}
+        Err(e) => {
+            error!(pool_id=?key.pool_id, err=%e, "failed to fetch v4 pool key");
+            return Some((key, None))
+        }
+    }
+
+    if let Err(e) = db.write_v4_pool_key(key).await {
+        error!(pool_id=?key.pool_id, err=%e, "failed to store v4 pool key");
+        return Some((key, None))
+    }
+
+    // initialized pools are added through their classified config update
+    if is_initialize || db.get_protocol_details(pool_address).is_ok() {
+        return Some((key, None))
+    }
+
+    let tokens = key.tokens();
+    if db
+        .insert_pool(block, pool_address, &tokens, None, Protocol::UniswapV4)
+        .await
+        .is_err()
+    {
+        error!(pool=?pool_address, "failed to insert v4 pool");
+        return Some((key, None))
+    }
+
+    Some((
+        key,
+        Some(NormalizedPoolConfigUpdate {
+            trace_index: call_info.trace_idx,
+            protocol: Protocol::UniswapV4,
+            pool_address,
+            tokens: tokens.to_vec(),
+        }),
+    ))
+}
+
+/// The pool key of a PoolManager call & whether the call initializes the pool
+fn decode_pool_key(call_info: &CallFrameInfo<'_>) -> Option<(UniswapV4PoolKey, bool)> {
+    let call_data = &call_info.call_data;
+    let selector = call_data.get(..4)?;
+    let (key, is_initialize) = if selector == swapCall::SELECTOR {
+        (swapCall::abi_decode(call_data, false).ok()?.key, false)
+    } else if selector == modifyLiquidityCall::SELECTOR {
+        (modifyLiquidityCall::abi_decode(call_data, false).ok()?.key, false)
+    } else if selector == donateCall::SELECTOR {
+        (donateCall::abi_decode(call_data, false).ok()?.key, false)
+    } else if selector == initializeCall::SELECTOR {
+        (initializeCall::abi_decode(call_data, false).ok()?.key, true)
+    } else {
+        return None
+    };
+
+    Some((pool_key(&key), is_initialize))
+}
+
+fn pool_key(key: &PoolKey) -> UniswapV4PoolKey {
+    UniswapV4PoolKey {
+        pool_id:      keccak256(key.abi_encode()),
+        currency0:    key.currency0,
+        currency1:    key.currency1,
+        fee:          key.fee.to(),
+        tick_spacing: key.tickSpacing.as_i32(),
+        hooks:        key.hooks,
+    }
+}
+
+/// A `BalanceDelta` packs the currency0 delta into the upper 128 bits and the
+/// currency1 delta into the lower 128 bits.
+fn split_balance_delta(delta: I256) -> (i128, i128) {
+    let raw = delta.into_raw();
+    let amount0 = (raw >> 128).to::<u128>() as i128;
+    let amount1 = (raw & U256::from(u128::MAX)).to::<u128>() as i128;
+
+    (amount0, amount1)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{
+        aliases::{I24, U24},
+        Address, Bytes,
+    };
+    use brontes_types::{
+        constants::{USDC_ADDRESS, WETH_ADDRESS},
+        db::uniswap_v4::POOL_MANAGER_ADDRESS,
+    };
+
+    use super::*;
+    use crate::test_utils::ClassifierTestUtils;
+
+    fn donate_call(hooks: Address) -> Bytes {
+        donateCall {
+            key:      PoolKey {
+                currency0: Address::ZERO,
+                currency1: USDC_ADDRESS,
+                fee: U24::from(500),
+                tickSpacing: I24::try_from(10).unwrap(),
+                hooks,
+            },
+            amount0:  U256::from(1),
+            amount1:  U256::from(1),
+            hookData: Bytes::new(),
+        }
+        .abi_encode()
+        .into()
+    }
+
+    fn call_info(call_data: Bytes) -> CallFrameInfo<'static> {
+        CallFrameInfo {
+            trace_idx: 0,
+            call_data,
+            return_data: Bytes::new(),
+            target_address: POOL_MANAGER_ADDRESS,
+            from_address: Address::ZERO,
+            logs: &[],
+            delegate_logs: vec![],
+            msg_sender: Address::ZERO,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_record_v4_pool_stores_key() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::UniswapV4,
+            POOL_MANAGER_ADDRESS,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let db = classifier_utils.libmdbx;
+        let call_info = call_info(donate_call(Address::ZERO));
+
+        let (key, _) = record_v4_pool(&call_info, 1, db).await.unwrap();
+        assert_eq!(db.try_fetch_v4_pool_key(key.pool_address()).unwrap(), Some(key));
+
+        let details = db.get_protocol_details(key.pool_address()).unwrap();
+        assert_eq!(details.protocol, Protocol::UniswapV4);
+        assert_eq!([details.token0, details.token1], [WETH_ADDRESS, USDC_ADDRESS]);
+
+        // the pool is only discovered once
+        let (_, discovered) = record_v4_pool(&call_info, 2, db).await.unwrap();
+        assert!(discovered.is_none());
+    }
+
+    #[brontes_macros::test]
+    async fn test_record_v4_pool_skips_hooked_pools() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::UniswapV4,
+            POOL_MANAGER_ADDRESS,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let db = classifier_utils.libmdbx;
+        let call_info = call_info(donate_call(Address::with_last_byte(1)));
+
+        let (key, discovered) = record_v4_pool(&call_info, 1, db).await.unwrap();
+        assert!(key.has_hooks());
+        assert!(discovered.is_none());
+        assert_eq!(db.try_fetch_v4_pool_key(key.pool_address()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_split_balance_delta() {
+        let amount0: i128 = -1_000_000;
+        let amount1: i128 = 2_500;
+        let packed = (U256::from(amount0 as u128) << 128) | U256::from(amount1 as u128);
+
+        assert_eq!(split_balance_delta(I256::from_raw(packed)), (amount0, amount1));
+    }
+}
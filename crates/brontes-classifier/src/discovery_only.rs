@@ -9,6 +9,7 @@ use brontes_types::{
     structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
     traits::TracingProvider,
     tree::{root::NodeData, GasDetails, Node, Root},
+    Protocol,
 };
 use futures::future::join_all;
 use reth_primitives::{Address, Header};
@@ -17,8 +18,9 @@ use tracing::{error, trace};
 
 use self::erc20::try_decode_transfer;
 use crate::{
-    classifiers::*, tree_builder::utils::decode_transfer, ActionCollection,
-    FactoryDiscoveryDispatch,
+    classifiers::*,
+    tree_builder::utils::{decode_transfer, get_touched_calls},
+    ActionCollection, FactoryDiscoveryDispatch,
 };
 
 #[derive(Debug)]
@@ -83,6 +85,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> DiscoveryOnlyClassif
                         tx_hash: trace.tx_hash,
                         private: false,
                         total_msg_value_transfers: vec![],
+                        touched_calls: get_touched_calls(trace.trace.iter()),
                         gas_details: GasDetails {
                             coinbase_transfer:   None,
                             gas_used:            trace.gas_used,
@@ -212,6 +215,14 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> DiscoveryOnlyClassif
             }
         }
 
+        if self.libmdbx.get_protocol(call_info.target_address).ok() == Some(Protocol::UniswapV4) {
+            if let Some((key, _)) = record_v4_pool(&call_info, block, self.libmdbx).await {
+                if key.has_hooks() {
+                    return
+                }
+            }
+        }
+
         if let Some(results) =
             ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx)
         {
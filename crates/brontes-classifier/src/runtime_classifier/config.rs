@@ -0,0 +1,153 @@
+use std::{path::Path, str::FromStr};
+
+use alloy_primitives::{Address, U256};
+use brontes_types::Protocol;
+use serde::Deserialize;
+
+/// A set of classifiers that are defined in a config file instead of being
+/// compiled in. The config can be written in either toml or json.
+///
+/// ```toml
+/// [[classifier]]
+/// address = "0x..."
+/// protocol = "UniswapV2"
+/// abi = "abis/MyPool.json"
+/// function = "swap"
+/// action = "swap"
+/// events = ["Swap"]
+///
+/// [classifier.fields]
+/// token_in = "call.tokenIn"
+/// token_out = "call.tokenOut"
+/// amount_in = "call.amountIn"
+/// amount_out = "log.Swap.amountOut"
+/// recipient = "call.to"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RuntimeClassifierConfig {
+    #[serde(default, rename = "classifier")]
+    pub classifiers: Vec<RuntimeClassifierEntry>,
+}
+
+impl RuntimeClassifierConfig {
+    /// Reads the config from `path`. Files ending in `.json` are parsed as
+    /// json, everything else as toml.
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Ok(serde_json::from_str(&contents)?)
+        } else {
+            Ok(toml::from_str(&contents)?)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuntimeClassifierEntry {
+    /// The contract that is called
+    pub address:  Address,
+    #[serde(default)]
+    pub protocol: Protocol,
+    /// Path to the json abi of the contract, relative to the config file
+    pub abi:      String,
+    /// The name of the function that is classified or its `0x` prefixed
+    /// selector if the function is overloaded
+    pub function: String,
+    pub action:   RuntimeAction,
+    /// Events emitted in the call frame that fields can be read from
+    #[serde(default)]
+    pub events:   Vec<String>,
+    #[serde(default)]
+    pub fields:   FieldMapping,
+}
+
+/// The normalized action that a runtime classifier produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeAction {
+    Swap,
+    Mint,
+    Burn,
+    Transfer,
+}
+
+/// Where each field of the normalized action is read from. Fields that are
+/// optional for an action fall back to the call frame, e.g. `pool` to the
+/// called contract and `from` to the caller.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FieldMapping {
+    pub pool:       Option<ValueSource>,
+    pub from:       Option<ValueSource>,
+    pub recipient:  Option<ValueSource>,
+    // swap
+    pub token_in:   Option<ValueSource>,
+    pub token_out:  Option<ValueSource>,
+    pub amount_in:  Option<ValueSource>,
+    pub amount_out: Option<ValueSource>,
+    // mint & burn
+    pub tokens:     Option<ValueSource>,
+    pub amounts:    Option<ValueSource>,
+    // transfer
+    pub token:      Option<ValueSource>,
+    pub to:         Option<ValueSource>,
+    pub amount:     Option<ValueSource>,
+}
+
+/// A value of the call frame. Parsed from one of:
+/// - `call.<param>` / `return.<param>`, a named (or positional) input or output
+///   of the function
+/// - `log.<Event>.<param>`, a param of an event emitted in the call frame
+/// - `trace.from`, `trace.to`, `trace.msg_sender` or `trace.msg_value`
+/// - a literal address or number
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ValueSource {
+    Call(String),
+    Return(String),
+    Log { event: String, param: String },
+    From,
+    To,
+    MsgSender,
+    MsgValue,
+    Address(Address),
+    Uint(U256),
+}
+
+impl FromStr for ValueSource {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(param) = s.strip_prefix("call.") {
+            return Ok(Self::Call(param.to_string()))
+        }
+        if let Some(param) = s.strip_prefix("return.") {
+            return Ok(Self::Return(param.to_string()))
+        }
+        if let Some(rest) = s.strip_prefix("log.") {
+            let (event, param) = rest
+                .split_once('.')
+                .ok_or_else(|| eyre::eyre!("expected log.<Event>.<param>, got {s}"))?;
+            return Ok(Self::Log { event: event.to_string(), param: param.to_string() })
+        }
+
+        match s {
+            "trace.from" => Ok(Self::From),
+            "trace.to" => Ok(Self::To),
+            "trace.msg_sender" => Ok(Self::MsgSender),
+            "trace.msg_value" => Ok(Self::MsgValue),
+            _ if s.len() == 42 && s.starts_with("0x") => Ok(Self::Address(s.parse()?)),
+            _ => U256::from_str(s)
+                .map(Self::Uint)
+                .map_err(|_| eyre::eyre!("invalid value source {s}")),
+        }
+    }
+}
+
+impl TryFrom<String> for ValueSource {
+    type Error = eyre::Report;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
@@ -0,0 +1,122 @@
+use alloy_dyn_abi::{DynSolValue, EventExt, JsonAbiExt};
+use alloy_json_abi::{Event, Function, Param};
+use alloy_primitives::Log;
+
+/// Decodes the call data and return data of a call to `function` into the
+/// named values of its inputs and outputs.
+pub fn decode_function_values(
+    function: &Function,
+    call_data: &[u8],
+    return_data: &[u8],
+) -> alloy_dyn_abi::Result<(Vec<(String, DynSolValue)>, Vec<(String, DynSolValue)>)> {
+    let inputs = function.abi_decode_input(call_data.get(4..).unwrap_or_default(), false)?;
+    let outputs = if return_data.is_empty() {
+        vec![]
+    } else {
+        function.abi_decode_output(return_data, false)?
+    };
+
+    Ok((named_values(&function.inputs, inputs), named_values(&function.outputs, outputs)))
+}
+
+/// Decodes a log into the named values of `event`. Returns `None` if the log
+/// wasn't emitted as `event`.
+pub fn decode_event_values(
+    event: &Event,
+    log: &Log,
+) -> alloy_dyn_abi::Result<Option<Vec<(String, DynSolValue)>>> {
+    if event.anonymous || log.topics().first() != Some(&event.selector()) {
+        return Ok(None)
+    }
+
+    let decoded = event.decode_log_parts(log.topics().iter().copied(), &log.data.data, false)?;
+    let mut indexed = decoded.indexed.into_iter();
+    let mut body = decoded.body.into_iter();
+
+    Ok(Some(
+        event
+            .inputs
+            .iter()
+            .filter_map(|param| {
+                let value = if param.indexed { indexed.next() } else { body.next() }?;
+                Some((param.name.clone(), value))
+            })
+            .collect(),
+    ))
+}
+
+fn named_values(params: &[Param], values: Vec<DynSolValue>) -> Vec<(String, DynSolValue)> {
+    params
+        .iter()
+        .map(|param| param.name.clone())
+        .zip(values)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, LogData, U256};
+    use alloy_sol_macro::sol;
+    use alloy_sol_types::{SolCall, SolEvent};
+
+    use super::*;
+
+    sol!(
+        interface IPool {
+            event Swap(address indexed sender, uint256 amountIn, uint256 amountOut);
+            function swap(address recipient, uint256 amountIn) external returns (uint256);
+        }
+    );
+
+    #[test]
+    fn test_decode_function_values() {
+        let function =
+            Function::parse("swap(address recipient, uint256 amountIn)(uint256)").unwrap();
+        let recipient = Address::with_last_byte(1);
+        let call_data = IPool::swapCall { recipient, amountIn: U256::from(5) }.abi_encode();
+        let return_data = IPool::swapCall::abi_encode_returns(&(U256::from(7),));
+
+        let (call, ret) = decode_function_values(&function, &call_data, &return_data).unwrap();
+        assert_eq!(
+            call,
+            vec![
+                ("recipient".to_string(), DynSolValue::Address(recipient)),
+                ("amountIn".to_string(), DynSolValue::Uint(U256::from(5), 256)),
+            ]
+        );
+        assert_eq!(ret, vec![(String::new(), DynSolValue::Uint(U256::from(7), 256))]);
+
+        // calls that revert don't return anything
+        let (_, ret) = decode_function_values(&function, &call_data, &[]).unwrap();
+        assert!(ret.is_empty());
+        // call data that doesn't match the function
+        assert!(decode_function_values(&function, &call_data[..20], &[]).is_err());
+    }
+
+    #[test]
+    fn test_decode_event_values() {
+        let event =
+            Event::parse("event Swap(address indexed sender, uint256 amountIn, uint256 amountOut)")
+                .unwrap();
+        let sender = Address::with_last_byte(1);
+        let log = Log {
+            address: Address::ZERO,
+            data:    IPool::Swap { sender, amountIn: U256::from(5), amountOut: U256::from(7) }
+                .encode_log_data(),
+        };
+
+        assert_eq!(
+            decode_event_values(&event, &log).unwrap().unwrap(),
+            vec![
+                ("sender".to_string(), DynSolValue::Address(sender)),
+                ("amountIn".to_string(), DynSolValue::Uint(U256::from(5), 256)),
+                ("amountOut".to_string(), DynSolValue::Uint(U256::from(7), 256)),
+            ]
+        );
+
+        // a log of another event
+        let other =
+            Log { address: Address::ZERO, data: LogData::new_unchecked(vec![], vec![].into()) };
+        assert_eq!(decode_event_values(&event, &other).unwrap(), None);
+    }
+}
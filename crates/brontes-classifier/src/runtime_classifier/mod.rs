@@ -0,0 +1,594 @@
+//! Classifiers that are loaded from a config file at runtime. They allow
+//! classifying contracts that brontes doesn't ship a classifier for without
+//! rebuilding. The calls are decoded with the dynamic abi decoder and run as
+//! a fallback after the compiled classifiers.
+mod config;
+mod decode;
+
+use std::path::Path;
+
+use alloy_dyn_abi::DynSolValue;
+use alloy_json_abi::{Event, Function, JsonAbi};
+use alloy_primitives::{Address, U256};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_pricing::types::{DexPriceMsg, PoolUpdate};
+use brontes_types::{
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedMint, NormalizedSwap, NormalizedTransfer,
+    },
+    structured_trace::CallFrameInfo,
+    FastHashMap, Protocol, ToScaledRational,
+};
+pub use config::*;
+use malachite::{num::basic::traits::Zero, Rational};
+use tracing::debug;
+
+use self::decode::{decode_event_values, decode_function_values};
+
+/// All runtime classifiers, keyed by the contract they classify
+#[derive(Debug, Default)]
+pub struct RuntimeClassifierRegistry {
+    classifiers: FastHashMap<Address, Vec<RuntimeClassifier>>,
+}
+
+impl RuntimeClassifierRegistry {
+    /// Loads the registry from a toml or json config. Abi paths are resolved
+    /// relative to the config file.
+    pub fn load(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let config = RuntimeClassifierConfig::from_file(path)?;
+
+        Self::from_config(config, path.parent().unwrap_or(Path::new(".")))
+    }
+
+    pub fn from_config(config: RuntimeClassifierConfig, abi_dir: &Path) -> eyre::Result<Self> {
+        let mut classifiers = FastHashMap::<Address, Vec<RuntimeClassifier>>::default();
+
+        for entry in config.classifiers {
+            let abi_path = abi_dir.join(&entry.abi);
+            let abi: JsonAbi = serde_json::from_str(&std::fs::read_to_string(&abi_path)?)?;
+            let classifier = RuntimeClassifier::new(entry, &abi)?;
+
+            classifiers
+                .entry(classifier.address)
+                .or_default()
+                .push(classifier);
+        }
+
+        Ok(Self { classifiers })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classifiers.is_empty()
+    }
+}
+
+impl RuntimeClassifierRegistry {
+    /// Classifies a call with the classifier registered for its contract and
+    /// function. Transfers don't change the state of a pool, so only the other
+    /// actions are sent to the pricer.
+    pub fn dispatch<DB: LibmdbxReader>(
+        &self,
+        call_info: CallFrameInfo<'_>,
+        db_tx: &DB,
+        block: u64,
+        tx_idx: u64,
+    ) -> Option<(Option<DexPriceMsg>, Action)> {
+        let selector = call_info.call_data.get(..4)?;
+        let classifier = self
+            .classifiers
+            .get(&call_info.target_address)?
+            .iter()
+            .find(|classifier| classifier.function.selector() == selector)?;
+
+        match classifier.classify(&call_info, db_tx) {
+            Ok(action) => {
+                let update = (!action.is_transfer()).then(|| {
+                    DexPriceMsg::Update(PoolUpdate {
+                        block,
+                        tx_idx,
+                        logs: call_info.logs.to_vec(),
+                        action: action.clone(),
+                    })
+                });
+
+                Some((update, action))
+            }
+            Err(e) => {
+                debug!(
+                    target: "brontes_classifier::runtime",
+                    address = ?call_info.target_address,
+                    function = %classifier.function.name,
+                    err = %e,
+                    "runtime classifier failed"
+                );
+                None
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RuntimeClassifier {
+    address:  Address,
+    protocol: Protocol,
+    function: Function,
+    events:   Vec<Event>,
+    action:   RuntimeAction,
+    fields:   FieldMapping,
+}
+
+impl RuntimeClassifier {
+    fn new(entry: RuntimeClassifierEntry, abi: &JsonAbi) -> eyre::Result<Self> {
+        let function = if entry.function.starts_with("0x") {
+            let selector = entry.function.parse::<alloy_primitives::Selector>()?;
+            abi.functions()
+                .find(|function| function.selector() == selector)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("no function with selector {}", entry.function))?
+        } else {
+            match abi.function(&entry.function).map(Vec::as_slice) {
+                Some([function]) => function.clone(),
+                Some(_) => eyre::bail!("{} is overloaded, use its selector", entry.function),
+                None => eyre::bail!("no function named {}", entry.function),
+            }
+        };
+
+        let events = entry
+            .events
+            .iter()
+            .map(|name| {
+                abi.event(name)
+                    .and_then(|events| events.first())
+                    .cloned()
+                    .ok_or_else(|| eyre::eyre!("no event named {name}"))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            address: entry.address,
+            protocol: entry.protocol,
+            function,
+            events,
+            action: entry.action,
+            fields: entry.fields,
+        })
+    }
+
+    fn classify<DB: LibmdbxReader>(
+        &self,
+        call_info: &CallFrameInfo<'_>,
+        db: &DB,
+    ) -> eyre::Result<Action> {
+        let frame = DecodedFrame::new(self, call_info)?;
+        let fields = &self.fields;
+
+        let action = match self.action {
+            RuntimeAction::Swap => {
+                let token_in =
+                    db.try_fetch_token_info(frame.address(&fields.token_in, "token_in")?)?;
+                let token_out =
+                    db.try_fetch_token_info(frame.address(&fields.token_out, "token_out")?)?;
+                let amount_in = frame
+                    .uint(&fields.amount_in, "amount_in")?
+                    .to_scaled_rational(token_in.decimals);
+                let amount_out = frame
+                    .uint(&fields.amount_out, "amount_out")?
+                    .to_scaled_rational(token_out.decimals);
+
+                Action::Swap(NormalizedSwap {
+                    protocol: self.protocol,
+                    trace_index: call_info.trace_idx,
+                    from: frame.address_or(&fields.from, call_info.from_address)?,
+                    recipient: frame.address_or(&fields.recipient, call_info.from_address)?,
+                    pool: frame.address_or(&fields.pool, call_info.target_address)?,
+                    token_in,
+                    token_out,
+                    amount_in,
+                    amount_out,
+                    msg_value: call_info.msg_value,
+                })
+            }
+            RuntimeAction::Mint | RuntimeAction::Burn => {
+                let tokens = frame.addresses(&fields.tokens, "tokens")?;
+                let amounts = frame.uints(&fields.amounts, "amounts")?;
+                if tokens.len() != amounts.len() {
+                    eyre::bail!("got {} tokens but {} amounts", tokens.len(), amounts.len());
+                }
+
+                let (token, amount) = tokens
+                    .into_iter()
+                    .zip(amounts)
+                    .map(|(token, amount)| {
+                        let token = db.try_fetch_token_info(token)?;
+                        let amount = amount.to_scaled_rational(token.decimals);
+                        Ok((token, amount))
+                    })
+                    .collect::<eyre::Result<(Vec<_>, Vec<_>)>>()?;
+
+                let from = frame.address_or(&fields.from, call_info.from_address)?;
+                let recipient = frame.address_or(&fields.recipient, call_info.from_address)?;
+                let pool = frame.address_or(&fields.pool, call_info.target_address)?;
+
+                if self.action == RuntimeAction::Mint {
+                    Action::Mint(NormalizedMint {
+                        protocol: self.protocol,
+                        trace_index: call_info.trace_idx,
+                        from,
+                        recipient,
+                        pool,
+                        token,
+                        amount,
+                    })
+                } else {
+                    Action::Burn(NormalizedBurn {
+                        protocol: self.protocol,
+                        trace_index: call_info.trace_idx,
+                        from,
+                        recipient,
+                        pool,
+                        token,
+                        amount,
+                    })
+                }
+            }
+            RuntimeAction::Transfer => {
+                let token = db.try_fetch_token_info(
+                    frame.address_or(&fields.token, call_info.target_address)?,
+                )?;
+                let amount = frame
+                    .uint(&fields.amount, "amount")?
+                    .to_scaled_rational(token.decimals);
+
+                Action::Transfer(NormalizedTransfer {
+                    trace_index: call_info.trace_idx,
+                    from: frame.address_or(&fields.from, call_info.from_address)?,
+                    to: frame.address(&fields.to, "to")?,
+                    token,
+                    amount,
+                    fee: Rational::ZERO,
+                    msg_value: call_info.msg_value,
+                })
+            }
+        };
+
+        Ok(action)
+    }
+}
+
+/// The decoded values of a call frame that field sources are resolved against
+struct DecodedFrame<'a, 'b> {
+    call:      Vec<(String, DynSolValue)>,
+    ret:       Vec<(String, DynSolValue)>,
+    logs:      FastHashMap<&'a str, Vec<(String, DynSolValue)>>,
+    call_info: &'a CallFrameInfo<'b>,
+}
+
+impl<'a, 'b> DecodedFrame<'a, 'b> {
+    fn new(
+        classifier: &'a RuntimeClassifier,
+        call_info: &'a CallFrameInfo<'b>,
+    ) -> eyre::Result<Self> {
+        let (call, ret) = decode_function_values(
+            &classifier.function,
+            &call_info.call_data,
+            &call_info.return_data,
+        )
+        .map_err(|e| eyre::eyre!("failed to decode call: {e}"))?;
+
+        // first log of each event in the frame
+        let logs = classifier
+            .events
+            .iter()
+            .filter_map(|event| {
+                call_info
+                    .logs
+                    .iter()
+                    .chain(call_info.delegate_logs.iter().copied())
+                    .find_map(|log| decode_event_values(event, log).ok().flatten())
+                    .map(|values| (event.name.as_str(), values))
+            })
+            .collect();
+
+        Ok(Self { call, ret, logs, call_info })
+    }
+
+    fn resolve(&self, source: &ValueSource) -> eyre::Result<DynSolValue> {
+        let value = match source {
+            ValueSource::Call(param) => find_param(&self.call, param),
+            ValueSource::Return(param) => find_param(&self.ret, param),
+            ValueSource::Log { event, param } => self
+                .logs
+                .get(event.as_str())
+                .and_then(|values| find_param(values, param)),
+            ValueSource::From => Some(DynSolValue::Address(self.call_info.from_address)),
+            ValueSource::To => Some(DynSolValue::Address(self.call_info.target_address)),
+            ValueSource::MsgSender => Some(DynSolValue::Address(self.call_info.msg_sender)),
+            ValueSource::MsgValue => Some(DynSolValue::Uint(self.call_info.msg_value, 256)),
+            ValueSource::Address(address) => Some(DynSolValue::Address(*address)),
+            ValueSource::Uint(value) => Some(DynSolValue::Uint(*value, 256)),
+        };
+
+        value.ok_or_else(|| eyre::eyre!("{source:?} not found in call frame"))
+    }
+
+    fn address(&self, source: &Option<ValueSource>, field: &str) -> eyre::Result<Address> {
+        let source = source
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("missing field {field}"))?;
+        to_address(&self.resolve(source)?)
+    }
+
+    fn address_or(&self, source: &Option<ValueSource>, default: Address) -> eyre::Result<Address> {
+        source
+            .as_ref()
+            .map(|source| to_address(&self.resolve(source)?))
+            .unwrap_or(Ok(default))
+    }
+
+    fn uint(&self, source: &Option<ValueSource>, field: &str) -> eyre::Result<U256> {
+        let source = source
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("missing field {field}"))?;
+        to_uint(&self.resolve(source)?)
+    }
+
+    fn addresses(&self, source: &Option<ValueSource>, field: &str) -> eyre::Result<Vec<Address>> {
+        self.list(source, field)?.iter().map(to_address).collect()
+    }
+
+    fn uints(&self, source: &Option<ValueSource>, field: &str) -> eyre::Result<Vec<U256>> {
+        self.list(source, field)?.iter().map(to_uint).collect()
+    }
+
+    fn list(&self, source: &Option<ValueSource>, field: &str) -> eyre::Result<Vec<DynSolValue>> {
+        let source = source
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("missing field {field}"))?;
+
+        Ok(match self.resolve(source)? {
+            DynSolValue::Array(values)
+            | DynSolValue::FixedArray(values)
+            | DynSolValue::Tuple(values) => values,
+            value => vec![value],
+        })
+    }
+}
+
+/// Finds a param by name, falling back to its position for unnamed params
+fn find_param(values: &[(String, DynSolValue)], param: &str) -> Option<DynSolValue> {
+    values
+        .iter()
+        .find(|(name, _)| name == param)
+        .or_else(|| values.get(param.parse::<usize>().ok()?))
+        .map(|(_, value)| value.clone())
+}
+
+fn to_address(value: &DynSolValue) -> eyre::Result<Address> {
+    value
+        .as_address()
+        .ok_or_else(|| eyre::eyre!("expected an address, got {value:?}"))
+}
+
+/// Signed values are taken as their magnitude, as pools often report deltas
+fn to_uint(value: &DynSolValue) -> eyre::Result<U256> {
+    value
+        .as_uint()
+        .map(|(value, _)| value)
+        .or_else(|| value.as_int().map(|(value, _)| value.unsigned_abs()))
+        .ok_or_else(|| eyre::eyre!("expected a number, got {value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_dyn_abi::JsonAbiExt;
+    use alloy_primitives::{hex, Bytes, Log, LogData};
+    use brontes_types::constants::{USDC_ADDRESS, WETH_ADDRESS};
+
+    use super::*;
+    use crate::test_utils::ClassifierTestUtils;
+
+    const POOL: Address = Address::new(hex!("00000000000000000000000000000000000000aa"));
+    const CALLER: Address = Address::new(hex!("00000000000000000000000000000000000000bb"));
+    const RECIPIENT: Address = Address::new(hex!("00000000000000000000000000000000000000cc"));
+
+    fn registry(action: RuntimeAction, fields: FieldMapping) -> RuntimeClassifierRegistry {
+        let abi = JsonAbi::parse([
+            "function swap(address recipient, uint256 amountIn) returns (uint256 amountOut)",
+            "event Swap(address indexed sender, uint256 amountIn, uint256 amountOut)",
+        ])
+        .unwrap();
+        let entry = RuntimeClassifierEntry {
+            address: POOL,
+            protocol: Protocol::UniswapV2,
+            abi: String::new(),
+            function: "swap".to_string(),
+            action,
+            events: vec!["Swap".to_string()],
+            fields,
+        };
+        let classifier = RuntimeClassifier::new(entry, &abi).unwrap();
+
+        RuntimeClassifierRegistry {
+            classifiers: FastHashMap::from_iter([(POOL, vec![classifier])]),
+        }
+    }
+
+    /// A swap of 1 WETH for 3000 USDC
+    fn swap_frame(logs: &[Log]) -> CallFrameInfo<'_> {
+        let abi = JsonAbi::parse([
+            "function swap(address recipient, uint256 amountIn) returns (uint256 amountOut)"
+        ])
+        .unwrap();
+        let call_data = abi.functions().next().unwrap().abi_encode_input(&[
+            DynSolValue::Address(RECIPIENT),
+            DynSolValue::Uint(U256::from(10u128.pow(18)), 256),
+        ]);
+
+        CallFrameInfo {
+            trace_idx: 0,
+            call_data: Bytes::from(call_data.unwrap()),
+            return_data: Bytes::from(
+                DynSolValue::Uint(U256::from(3000 * 10u128.pow(6)), 256).abi_encode(),
+            ),
+            target_address: POOL,
+            from_address: CALLER,
+            logs,
+            delegate_logs: vec![],
+            msg_sender: CALLER,
+            msg_value: U256::ZERO,
+        }
+    }
+
+    fn swap_log() -> Log {
+        let event =
+            Event::parse("event Swap(address indexed sender, uint256 amountIn, uint256 amountOut)")
+                .unwrap();
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(10u128.pow(18)), 256),
+            DynSolValue::Uint(U256::from(3000 * 10u128.pow(6)), 256),
+        ])
+        .abi_encode_params();
+
+        Log {
+            address: POOL,
+            data:    LogData::new_unchecked(
+                vec![event.selector(), CALLER.into_word()],
+                data.into(),
+            ),
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_classify_swap() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let registry = registry(
+            RuntimeAction::Swap,
+            FieldMapping {
+                token_in: Some(ValueSource::Address(WETH_ADDRESS)),
+                token_out: Some(ValueSource::Address(USDC_ADDRESS)),
+                amount_in: Some(ValueSource::Call("amountIn".to_string())),
+                amount_out: Some(ValueSource::Log {
+                    event: "Swap".to_string(),
+                    param: "amountOut".to_string(),
+                }),
+                recipient: Some(ValueSource::Call("recipient".to_string())),
+                ..Default::default()
+            },
+        );
+
+        let logs = vec![swap_log()];
+        let (update, action) = registry
+            .dispatch(swap_frame(&logs), classifier_utils.libmdbx, 1, 0)
+            .unwrap();
+        assert!(matches!(update, Some(DexPriceMsg::Update(_))));
+
+        let swap = action.force_swap();
+        assert_eq!(swap.protocol, Protocol::UniswapV2);
+        assert_eq!((swap.pool, swap.from, swap.recipient), (POOL, CALLER, RECIPIENT));
+        assert_eq!(swap.token_in.address, WETH_ADDRESS);
+        assert_eq!(swap.amount_in, U256::from(10u128.pow(18)).to_scaled_rational(18));
+        assert_eq!(swap.token_out.address, USDC_ADDRESS);
+        assert_eq!(swap.amount_out, U256::from(3000 * 10u128.pow(6)).to_scaled_rational(6));
+    }
+
+    #[brontes_macros::test]
+    async fn test_classify_missing_field() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let registry = registry(
+            RuntimeAction::Swap,
+            FieldMapping {
+                token_out: Some(ValueSource::Address(USDC_ADDRESS)),
+                amount_in: Some(ValueSource::Call("amountIn".to_string())),
+                amount_out: Some(ValueSource::Return("amountOut".to_string())),
+                ..Default::default()
+            },
+        );
+
+        let logs = vec![];
+        assert!(registry
+            .dispatch(swap_frame(&logs), classifier_utils.libmdbx, 1, 0)
+            .is_none());
+    }
+
+    #[brontes_macros::test]
+    async fn test_transfer_isnt_a_pool_update() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let registry = registry(
+            RuntimeAction::Transfer,
+            FieldMapping {
+                token: Some(ValueSource::Address(USDC_ADDRESS)),
+                to: Some(ValueSource::Call("recipient".to_string())),
+                amount: Some(ValueSource::Return("amountOut".to_string())),
+                ..Default::default()
+            },
+        );
+
+        let logs = vec![];
+        let (update, action) = registry
+            .dispatch(swap_frame(&logs), classifier_utils.libmdbx, 1, 0)
+            .unwrap();
+        assert!(update.is_none());
+
+        let transfer = action.force_transfer();
+        assert_eq!((transfer.from, transfer.to), (CALLER, RECIPIENT));
+        assert_eq!(transfer.amount, U256::from(3000 * 10u128.pow(6)).to_scaled_rational(6));
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: RuntimeClassifierConfig = toml::from_str(
+            r#"
+            [[classifier]]
+            address = "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640"
+            protocol = "UniswapV3"
+            abi = "abis/Pool.json"
+            function = "0x128acb08"
+            action = "swap"
+            events = ["Swap"]
+
+            [classifier.fields]
+            recipient = "call.recipient"
+            amount_in = "log.Swap.amount0"
+            pool = "trace.to"
+            "#,
+        )
+        .unwrap();
+
+        let entry = &config.classifiers[0];
+        assert_eq!(entry.protocol, Protocol::UniswapV3);
+        assert_eq!(entry.action, RuntimeAction::Swap);
+        assert_eq!(entry.fields.recipient, Some(ValueSource::Call("recipient".to_string())));
+        assert_eq!(
+            entry.fields.amount_in,
+            Some(ValueSource::Log { event: "Swap".to_string(), param: "amount0".to_string() })
+        );
+        assert_eq!(entry.fields.pool, Some(ValueSource::To));
+        assert_eq!(entry.fields.token_in, None);
+    }
+
+    #[test]
+    fn test_value_source_literals() {
+        assert_eq!(
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+                .parse::<ValueSource>()
+                .unwrap(),
+            ValueSource::Address(Address::new(hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")))
+        );
+        assert_eq!("1000".parse::<ValueSource>().unwrap(), ValueSource::Uint(U256::from(1000)));
+        assert!("log.Swap".parse::<ValueSource>().is_err());
+        assert!("trace.nonce".parse::<ValueSource>().is_err());
+    }
+
+    #[test]
+    fn test_find_param() {
+        let values = vec![
+            ("amountIn".to_string(), DynSolValue::Uint(U256::from(5), 256)),
+            (String::new(), DynSolValue::Uint(U256::from(7), 256)),
+        ];
+
+        assert_eq!(to_uint(&find_param(&values, "amountIn").unwrap()).unwrap(), U256::from(5));
+        assert_eq!(to_uint(&find_param(&values, "1").unwrap()).unwrap(), U256::from(7));
+        assert!(find_param(&values, "amountOut").is_none());
+    }
+}
@@ -17,6 +17,7 @@ use brontes_database::{
 };
 use brontes_pricing::{
     types::{DexPriceMsg, PoolUpdate},
+    uniswap_v4::db_pool_key_lookup,
     BrontesBatchPricer, GraphManager, Protocol,
 };
 use brontes_types::{
@@ -83,7 +84,7 @@ impl ClassifierTestUtils {
                 .flat_map(|(_, pools)| {
                     pools
                         .into_iter()
-                        .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                        .map(|(addr, protocol, pairs)| (addr, (protocol, pairs)))
                         .collect::<Vec<_>>()
                 })
                 .collect::<FastHashMap<_, _>>()
@@ -106,6 +107,7 @@ impl ClassifierTestUtils {
                 created_pools,
                 ctr.clone(),
                 None,
+                db_pool_key_lookup(self.libmdbx),
                 ex,
             ),
         ))
@@ -9,7 +9,7 @@ use brontes_types::{
         NormalizedEthTransfer, NormalizedTransfer,
     },
     tree::root::NodeData,
-    ToScaledRational,
+    Protocol, ToScaledRational,
 };
 use malachite::{num::basic::traits::Zero, Rational};
 
@@ -31,12 +31,12 @@ use reth_rpc_types::trace::parity::{Action as TraceAction, CallType};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, trace};
 use tree_pruning::{account_for_tax_tokens, remove_possible_transfer_double_counts};
-use utils::{decode_transfer, get_coinbase_transfer};
+use utils::{decode_transfer, get_coinbase_transfer, get_reverted_tx, get_touched_calls};
 
 use self::erc20::try_decode_transfer;
 use crate::{
-    classifiers::*, multi_frame_classification::parse_multi_frame_requests, ActionCollection,
-    FactoryDiscoveryDispatch,
+    classifiers::*, multi_frame_classification::parse_multi_frame_requests,
+    runtime_classifier::RuntimeClassifierRegistry, ActionCollection, FactoryDiscoveryDispatch,
 };
 
 //TODO: Document this module
@@ -45,6 +45,7 @@ pub struct Classifier<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> {
     libmdbx:               &'db DB,
     provider:              Arc<T>,
     pricing_update_sender: UnboundedSender<DexPriceMsg>,
+    runtime_classifiers:   Option<Arc<RuntimeClassifierRegistry>>,
 }
 
 impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, DB> {
@@ -53,7 +54,17 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         pricing_update_sender: UnboundedSender<DexPriceMsg>,
         provider: Arc<T>,
     ) -> Self {
-        Self { libmdbx, pricing_update_sender, provider }
+        Self { libmdbx, pricing_update_sender, provider, runtime_classifiers: None }
+    }
+
+    /// Classifiers loaded from config that are tried for calls that none of
+    /// the compiled classifiers match
+    pub fn with_runtime_classifiers(
+        mut self,
+        runtime_classifiers: Option<Arc<RuntimeClassifierRegistry>>,
+    ) -> Self {
+        self.runtime_classifiers = runtime_classifiers;
+        self
     }
 
     pub fn block_load_failure(&self, number: u64) {
@@ -75,8 +86,15 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 .unwrap();
         }
 
+        let reverted_txs = traces
+            .iter()
+            .enumerate()
+            .filter_map(|(tx_idx, trace)| get_reverted_tx(tx_idx, trace, &header))
+            .collect_vec();
+
         let tx_roots = self.build_tx_trees(traces, &header).await;
         let mut tree = BlockTree::new(header, tx_roots.len());
+        tree.reverted_txs = reverted_txs;
 
         // send out all updates
         let further_classification_requests =
@@ -180,6 +198,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                         tx_hash: trace.tx_hash,
                         private: false,
                         total_msg_value_transfers,
+                        touched_calls: get_touched_calls(trace.trace.iter()),
                         gas_details: GasDetails {
                             coinbase_transfer:   None,
                             gas_used:            trace.gas_used,
@@ -386,6 +405,33 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
             }
         }
 
+        match self.libmdbx.get_protocol(call_info.target_address) {
+            // calls that act on multiple positions at once are classified into an
+            // action per position
+            Ok(Protocol::CompoundV3) => {
+                if let Some(liquidations) = classify_absorb(&call_info, self.libmdbx) {
+                    return (vec![], liquidations)
+                }
+            }
+            Ok(Protocol::UniswapV4) => {
+                if let Some((key, discovered)) =
+                    record_v4_pool(&call_info, block, self.libmdbx).await
+                {
+                    if key.has_hooks() {
+                        return (vec![], vec![Action::Unclassified(trace)])
+                    }
+                    if let Some(pool) = discovered {
+                        self.pricing_update_sender
+                            .send(DexPriceMsg::DiscoveredPool(pool))
+                            .unwrap();
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let runtime_call_info = self.runtime_classifiers.as_ref().map(|_| call_info.clone());
+
         if let Some(results) =
             ProtocolClassifier::default().dispatch(call_info, self.libmdbx, block, tx_idx)
         {
@@ -405,6 +451,15 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
             }
 
             (vec![results.0], vec![results.1])
+        } else if let Some(results) = self
+            .runtime_classifiers
+            .as_ref()
+            .zip(runtime_call_info)
+            .and_then(|(registry, call_info)| {
+                registry.dispatch(call_info, self.libmdbx, block, tx_idx)
+            })
+        {
+            (results.0.into_iter().collect(), vec![results.1])
         } else if let Some(transfer) = self
             .classify_transfer(tx_idx, trace_index, &trace, block)
             .await
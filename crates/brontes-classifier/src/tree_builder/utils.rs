@@ -1,5 +1,11 @@
 use alloy_primitives::{Address, FixedBytes, Log, B256, U256};
+use brontes_types::{
+    structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
+    tree::{GasDetails, RevertedTx, TouchedCall},
+};
 use hex_literal::hex;
+use itertools::Itertools;
+use reth_primitives::Header;
 use reth_rpc_types::trace::parity::Action;
 
 pub(crate) fn get_coinbase_transfer(builder: Address, action: &Action) -> Option<u128> {
@@ -14,6 +20,52 @@ pub(crate) fn get_coinbase_transfer(builder: Address, action: &Action) -> Option
     }
 }
 
+/// Every call into a contract made by the given traces. Plain eth transfers
+/// don't call into a contract, so they are skipped.
+pub(crate) fn get_touched_calls<'a>(
+    traces: impl Iterator<Item = &'a TransactionTraceWithLogs>,
+) -> Vec<TouchedCall> {
+    traces
+        .filter_map(|trace| match &trace.trace.action {
+            Action::Call(call) if !call.input.is_empty() => {
+                Some(TouchedCall { to: call.to, input: call.input.clone() })
+            }
+            _ => None,
+        })
+        .unique()
+        .collect_vec()
+}
+
+/// Summarizes a transaction whose top level call failed, these are filtered
+/// out of the tree so we keep the calls it made along the way.
+pub(crate) fn get_reverted_tx(
+    tx_idx: usize,
+    trace: &TxTrace,
+    header: &Header,
+) -> Option<RevertedTx> {
+    if trace.is_success {
+        return None
+    }
+    let root = trace.trace.first()?;
+
+    let touched_calls = get_touched_calls(trace.trace.iter());
+
+    Some(RevertedTx {
+        tx_hash: trace.tx_hash,
+        position: tx_idx,
+        from: root.get_from_addr(),
+        to: root.get_to_address(),
+        gas_details: GasDetails {
+            coinbase_transfer:   None,
+            gas_used:            trace.gas_used,
+            effective_gas_price: trace.effective_price,
+            priority_fee:        trace.effective_price
+                - (header.base_fee_per_gas.unwrap_or_default() as u128),
+        },
+        touched_calls,
+    })
+}
+
 const TRANSFER_TOPIC: B256 =
     FixedBytes(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"));
 
@@ -0,0 +1,167 @@
+use std::fmt::Debug;
+
+use ::clickhouse::DbRow;
+use alloy_primitives::Address;
+use brontes_types::db::{block_times::BlockTimes, metadata::Metadata};
+#[cfg(feature = "local-clickhouse")]
+use db_interfaces::clickhouse::client::ClickhouseClient;
+use itertools::Itertools;
+use reth_primitives::{BlockHash, TxHash};
+use serde::Deserialize;
+
+use super::CexFileSource;
+#[cfg(feature = "local-clickhouse")]
+use crate::clickhouse::{BrontesClickhouseTables, ClickhouseCritTableCount};
+use crate::{
+    clickhouse::ClickhouseHandle,
+    libmdbx::{cex_utils::CexRangeOrArbitrary, types::LibmdbxData, LibmdbxReadWriter},
+    BlockInfo, CompressedTable,
+};
+
+/// Wraps a [`ClickhouseHandle`] so that cex quotes and trades are loaded from
+/// local files instead of being downloaded. Block times are taken from the
+/// local `BlockInfo` table. Without a file source everything is passed
+/// through to the wrapped handle. With a file source the clickhouse isn't
+/// loaded, so all other data has to already be in libmdbx.
+pub struct CexFileHandle<CH: ClickhouseHandle> {
+    inner:   Option<CH>,
+    files:   Option<CexFileSource>,
+    libmdbx: &'static LibmdbxReadWriter,
+}
+
+impl<CH: ClickhouseHandle> CexFileHandle<CH> {
+    pub fn new(
+        inner: Option<CH>,
+        files: Option<CexFileSource>,
+        libmdbx: &'static LibmdbxReadWriter,
+    ) -> Self {
+        Self { inner, files, libmdbx }
+    }
+
+    fn clickhouse(&self) -> eyre::Result<&CH> {
+        self.inner.as_ref().ok_or_else(|| {
+            eyre::eyre!("the clickhouse isn't loaded when cex data is read from files")
+        })
+    }
+
+    /// The block times of the requested blocks, widened by the run time
+    /// window for trades
+    fn block_times(
+        &self,
+        files: &CexFileSource,
+        range_or_arbitrary: CexRangeOrArbitrary,
+        with_window: bool,
+    ) -> eyre::Result<Vec<BlockTimes>> {
+        let (before, after) = if with_window { files.config().run_time_window } else { (0, 0) };
+
+        let blocks = match range_or_arbitrary {
+            CexRangeOrArbitrary::Range(start, end) => {
+                (start.saturating_sub(before)..end + after).collect_vec()
+            }
+            CexRangeOrArbitrary::Arbitrary(blocks) => blocks
+                .iter()
+                .flat_map(|block| block.saturating_sub(before)..=block + after)
+                .unique()
+                .collect_vec(),
+            CexRangeOrArbitrary::Timestamp { block_number, block_timestamp } => {
+                return Ok(vec![BlockTimes { block_number, timestamp: block_timestamp * 1000000 }])
+            }
+        };
+
+        self.libmdbx.db.view_db(|tx| {
+            blocks
+                .into_iter()
+                .filter_map(|block_number| {
+                    tx.get::<BlockInfo>(block_number)
+                        .map(|info| {
+                            info.map(|info| BlockTimes {
+                                block_number,
+                                timestamp: info.block_timestamp * 1000000,
+                            })
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Into::into)
+        })
+    }
+}
+
+impl<CH: ClickhouseHandle> ClickhouseHandle for CexFileHandle<CH> {
+    async fn get_metadata(
+        &self,
+        block_num: u64,
+        block_timestamp: u64,
+        block_hash: BlockHash,
+        tx_hashes_in_block: Vec<TxHash>,
+        quote_asset: Address,
+    ) -> eyre::Result<Metadata> {
+        self.clickhouse()?
+            .get_metadata(block_num, block_timestamp, block_hash, tx_hashes_in_block, quote_asset)
+            .await
+    }
+
+    async fn get_cex_prices(
+        &self,
+        range_or_arbitrary: CexRangeOrArbitrary,
+    ) -> eyre::Result<Vec<crate::CexPriceData>> {
+        let Some(files) = &self.files else {
+            return self.clickhouse()?.get_cex_prices(range_or_arbitrary).await
+        };
+
+        files.cex_prices(self.block_times(files, range_or_arbitrary, false)?)
+    }
+
+    async fn get_cex_trades(
+        &self,
+        range_or_arbitrary: CexRangeOrArbitrary,
+    ) -> eyre::Result<Vec<crate::CexTradesData>> {
+        let Some(files) = &self.files else {
+            return self.clickhouse()?.get_cex_trades(range_or_arbitrary).await
+        };
+
+        files.cex_trades(self.block_times(files, range_or_arbitrary, true)?)
+    }
+
+    async fn query_many_range<T, D>(&self, start_block: u64, end_block: u64) -> eyre::Result<Vec<D>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+        D: LibmdbxData<T> + DbRow + for<'de> Deserialize<'de> + Send + Debug + Unpin + 'static,
+    {
+        self.clickhouse()?
+            .query_many_range::<T, D>(start_block, end_block)
+            .await
+    }
+
+    async fn query_many_arbitrary<T, D>(&self, range: &'static [u64]) -> eyre::Result<Vec<D>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+        D: LibmdbxData<T> + DbRow + for<'de> Deserialize<'de> + Send + Debug + Unpin + 'static,
+    {
+        self.clickhouse()?.query_many_arbitrary::<T, D>(range).await
+    }
+
+    async fn query_many<T, D>(&self) -> eyre::Result<Vec<D>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+        D: LibmdbxData<T> + DbRow + for<'de> Deserialize<'de> + Send + Debug + Unpin + 'static,
+    {
+        self.clickhouse()?.query_many::<T, D>().await
+    }
+
+    #[cfg(feature = "local-clickhouse")]
+    fn inner(&self) -> &ClickhouseClient<BrontesClickhouseTables> {
+        self.inner
+            .as_ref()
+            .expect("the clickhouse isn't loaded when cex data is read from files")
+            .inner()
+    }
+
+    #[cfg(feature = "local-clickhouse")]
+    async fn get_init_crit_tables(&self) -> eyre::Result<ClickhouseCritTableCount> {
+        self.clickhouse()?.get_init_crit_tables().await
+    }
+}
@@ -0,0 +1,435 @@
+//! A file backed source for cex quotes and trades, so that cex-dex analysis
+//! can run on market data that isn't in the Sorella clickhouse.
+//!
+//! The data directory is laid out as:
+//! ```text
+//! <dir>/symbols.{csv,parquet}   exchange, symbol, base_address, quote_address
+//! <dir>/trades/*.{csv,parquet}  tardis trades: exchange, symbol, timestamp, side, price, amount
+//! <dir>/quotes/*.{csv,parquet}  tardis quotes: exchange, symbol, timestamp, ask_amount,
+//!                               ask_price, bid_price, bid_amount
+//! <dir>/book_snapshots/*.{csv,parquet}
+//!                               optional tardis book_snapshot_<depth>: exchange, symbol,
+//!                               timestamp, asks[0].price, asks[0].amount, bids[0].price, ...
+//! <dir>/derivative_ticker/*.{csv,parquet}
+//!                               optional tardis derivative_ticker of the perp exchanges:
+//!                               exchange, symbol, timestamp, funding_rate, mark_price,
+//!                               index_price
+//! ```
+//! All timestamps are in microseconds. The data files are indexed by their
+//! time range when the source is created, so they shouldn't change while
+//! brontes is running.
+mod handle;
+mod reader;
+
+use std::path::{Path, PathBuf};
+
+use brontes_types::{
+    db::{
+        block_times::BlockTimes,
+        cex::{
+            quotes::{CexOrderBookMap, CexQuotesConverter, RawCexQuotes},
+            trades::{CexTradesConverter, RawCexTrades},
+            BestCexPerPair, CexExchange, CexInstrument, CexSymbols, PerpTickerMap,
+        },
+    },
+    pair::Pair,
+    FastHashMap,
+};
+pub use handle::CexFileHandle;
+use itertools::Itertools;
+use reader::*;
+
+use crate::{clickhouse::cex_config::CexDownloadConfig, CexPriceData, CexTradesData};
+
+const SECONDS_TO_US: u64 = 1_000_000;
+/// Trades are assigned to blocks within this many seconds of the block time
+const TRADE_WINDOW: u64 = 6;
+/// Order books are loaded for this many seconds before the block
+const BOOK_WINDOW: u64 = 2;
+/// Perp tickers are loaded for this many seconds before the block
+const TICKER_WINDOW: u64 = 60;
+/// Quotes are kept for markouts up to this many seconds after the block
+const MAX_MARKOUT_TIME: u64 = 300;
+
+#[derive(Debug, Clone)]
+pub struct CexFileSource {
+    dir:     PathBuf,
+    config:  CexDownloadConfig,
+    symbols: Vec<CexSymbols>,
+    trades:  Vec<DataFile>,
+    quotes:  Vec<DataFile>,
+    books:   Vec<DataFile>,
+    tickers: Vec<DataFile>,
+}
+
+impl CexFileSource {
+    pub fn new(dir: impl Into<PathBuf>, config: CexDownloadConfig) -> eyre::Result<Self> {
+        let dir = dir.into();
+
+        let symbols_file = ["symbols.parquet", "symbols.csv"]
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.exists())
+            .ok_or_else(|| eyre::eyre!("no symbols file found in {}", dir.display()))?;
+
+        let symbols = read_batches(&symbols_file)?
+            .iter()
+            .map(symbols_from_batch)
+            .flatten_ok()
+            .filter_ok(|symbol| config.exchanges_to_use.contains(&symbol.exchange))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let trades = index_files(&dir.join("trades"))?;
+        let quotes = index_files(&dir.join("quotes"))?;
+        let books = index_files(&dir.join("book_snapshots"))?;
+        let tickers = index_files(&dir.join("derivative_ticker"))?;
+
+        Ok(Self { dir, config, symbols, trades, quotes, books, tickers })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn config(&self) -> &CexDownloadConfig {
+        &self.config
+    }
+
+    pub fn symbols(&self) -> &[CexSymbols] {
+        &self.symbols
+    }
+
+    /// Reads all trades of the configured exchanges in `start_time..end_time`
+    pub fn read_trades(&self, start_time: u64, end_time: u64) -> eyre::Result<Vec<RawCexTrades>> {
+        let mut trades = Vec::new();
+        for file in files_in(&self.trades, start_time, end_time) {
+            for batch in read_batches(file)? {
+                trades.extend(
+                    trades_from_batch(&batch, start_time, end_time)?
+                        .into_iter()
+                        .filter(|trade| self.config.exchanges_to_use.contains(&trade.exchange)),
+                );
+            }
+        }
+        trades.sort_by_key(|trade| trade.timestamp);
+
+        Ok(trades)
+    }
+
+    /// Reads all quotes of the configured exchanges in `start_time..end_time`
+    pub fn read_quotes(&self, start_time: u64, end_time: u64) -> eyre::Result<Vec<RawCexQuotes>> {
+        let mut quotes = Vec::new();
+        for file in files_in(&self.quotes, start_time, end_time) {
+            for batch in read_batches(file)? {
+                quotes.extend(
+                    quotes_from_batch(&batch, start_time, end_time)?
+                        .into_iter()
+                        .filter(|quote| self.config.exchanges_to_use.contains(&quote.exchange)),
+                );
+            }
+        }
+        quotes.sort_by_key(|quote| quote.timestamp);
+
+        Ok(quotes)
+    }
+
+    /// The order book snapshots in the seconds leading up to the block. Books
+    /// of symbols without a known address pair are dropped.
+    pub fn cex_order_books(&self, block_timestamp: u64) -> eyre::Result<CexOrderBookMap> {
+        let start_time = block_timestamp.saturating_sub(BOOK_WINDOW * SECONDS_TO_US);
+        let end_time = block_timestamp + 1;
+
+        let symbols = self.symbol_pairs();
+
+        let mut books = Vec::new();
+        for file in files_in(&self.books, start_time, end_time) {
+            for batch in read_batches(file)? {
+                books.extend(
+                    books_from_batch(&batch, start_time, end_time)?
+                        .into_iter()
+                        .filter_map(|(symbol, book)| {
+                            Some((*symbols.get(&(book.exchange, symbol.as_str()))?, book))
+                        }),
+                );
+            }
+        }
+
+        Ok(CexOrderBookMap::from_books(books))
+    }
+
+    /// The perp tickers in the minute leading up to the block. Tickers of
+    /// symbols without a known address pair are dropped.
+    pub fn perp_tickers(&self, block_timestamp: u64) -> eyre::Result<PerpTickerMap> {
+        let start_time = block_timestamp.saturating_sub(TICKER_WINDOW * SECONDS_TO_US);
+        let end_time = block_timestamp + 1;
+
+        let symbols = self.symbol_pairs();
+
+        let mut tickers = Vec::new();
+        for file in files_in(&self.tickers, start_time, end_time) {
+            for batch in read_batches(file)? {
+                tickers.extend(
+                    tickers_from_batch(&batch, start_time, end_time)?
+                        .into_iter()
+                        .filter_map(|(symbol, ticker)| {
+                            Some((*symbols.get(&(ticker.exchange, symbol.as_str()))?, ticker))
+                        }),
+                );
+            }
+        }
+
+        Ok(PerpTickerMap::from_tickers(tickers))
+    }
+
+    fn symbol_pairs(&self) -> FastHashMap<(CexExchange, &str), Pair> {
+        self.symbols
+            .iter()
+            .map(|symbol| ((symbol.exchange, symbol.symbol_pair.as_str()), symbol.address_pair))
+            .collect()
+    }
+
+    pub fn cex_trades(&self, block_times: Vec<BlockTimes>) -> eyre::Result<Vec<CexTradesData>> {
+        let (start_time, end_time) = time_range(&block_times, TRADE_WINDOW)?;
+        let trades = self.read_trades(start_time, end_time)?;
+
+        Ok(CexTradesConverter::new(block_times, self.symbols.clone(), trades)
+            .convert_to_trades()
+            .into_iter()
+            .map(|(block_num, trade_map)| CexTradesData::new(block_num, trade_map))
+            .collect())
+    }
+
+    pub fn cex_prices(&self, block_times: Vec<BlockTimes>) -> eyre::Result<Vec<CexPriceData>> {
+        let (start_time, end_time) = time_range(&block_times, MAX_MARKOUT_TIME)?;
+        let quotes = self.read_quotes(start_time, end_time)?;
+        let trades = self.read_trades(start_time, end_time)?;
+        let symbol_rank = rank_exchanges(&quotes, &trades, start_time);
+
+        Ok(CexQuotesConverter::new(block_times, self.symbols.clone(), quotes, symbol_rank)
+            .convert_to_prices()
+            .into_iter()
+            .map(|(block_num, price_map)| CexPriceData::new(block_num, price_map))
+            .collect())
+    }
+}
+
+/// The indexed files with rows in `start_time..end_time`
+fn files_in(files: &[DataFile], start_time: u64, end_time: u64) -> impl Iterator<Item = &Path> {
+    files
+        .iter()
+        .filter(move |file| file.overlaps(start_time, end_time))
+        .map(|file| file.path.as_path())
+}
+
+/// The time range covered by the blocks, padded by `window` seconds
+fn time_range(block_times: &[BlockTimes], window: u64) -> eyre::Result<(u64, u64)> {
+    let (Some(start), Some(end)) = (
+        block_times.iter().map(|b| b.timestamp).min(),
+        block_times.iter().map(|b| b.timestamp).max(),
+    ) else {
+        eyre::bail!("No block times found");
+    };
+
+    Ok((start.saturating_sub(window * SECONDS_TO_US), end + window * SECONDS_TO_US))
+}
+
+/// Ranks the spot exchanges of each symbol by their traded volume. Without
+/// trades, exchanges are ranked by how many quotes they published.
+fn rank_exchanges(
+    quotes: &[RawCexQuotes],
+    trades: &[RawCexTrades],
+    timestamp: u64,
+) -> Vec<BestCexPerPair> {
+    let mut volumes = FastHashMap::default();
+
+    if trades.is_empty() {
+        for quote in quotes
+            .iter()
+            .filter(|quote| quote.exchange.instrument() == CexInstrument::Spot)
+        {
+            *volumes
+                .entry(quote.symbol.clone())
+                .or_insert_with(FastHashMap::default)
+                .entry(quote.exchange)
+                .or_insert(0.0) += 1.0;
+        }
+    } else {
+        for trade in trades
+            .iter()
+            .filter(|trade| trade.exchange.instrument() == CexInstrument::Spot)
+        {
+            *volumes
+                .entry(trade.symbol.clone())
+                .or_insert_with(FastHashMap::default)
+                .entry(trade.exchange)
+                .or_insert(0.0) += trade.price * trade.amount;
+        }
+    }
+
+    volumes
+        .into_iter()
+        .map(|(symbol, exchanges)| BestCexPerPair {
+            symbol,
+            exchange: exchanges
+                .into_iter()
+                .sorted_by(|a, b| b.1.total_cmp(&a.1))
+                .map(|(exchange, _)| exchange)
+                .collect(),
+            timestamp,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn write_file(dir: &Path, name: &str, contents: &str) {
+        std::fs::create_dir_all(dir.join(name).parent().unwrap()).unwrap();
+        let mut file = std::fs::File::create(dir.join(name)).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_cex_trades_from_csv() {
+        brontes_types::init_thread_pools(2);
+        let dir = std::env::temp_dir().join("brontes_cex_files_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        write_file(
+            &dir,
+            "symbols.csv",
+            "exchange,symbol,base_address,quote_address\nbinance,ETHUSDT,\
+             0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2,\
+             0xdac17f958d2ee523a2206206994597c13d831ec7\n",
+        );
+        write_file(
+            &dir,
+            "trades/binance.csv",
+            "exchange,symbol,timestamp,local_timestamp,id,side,price,amount\nbinance,ETHUSDT,\
+             1700000000000000,1700000000000100,1,buy,2000.5,1.5\nbinance,ETHUSDT,1700000020000000,\
+             1700000020000100,2,sell,2001.0,2.0\nbinance,BTCUSDT,1700000000000000,\
+             1700000000000100,3,buy,35000.0,0.1\n",
+        );
+        write_file(
+            &dir,
+            "trades/binance_later.csv",
+            "exchange,symbol,timestamp,local_timestamp,id,side,price,amount\nbinance,ETHUSDT,\
+             1700001000000000,1700001000000100,4,buy,2100.0,1.0\n",
+        );
+
+        let source =
+            CexFileSource::new(&dir, CexDownloadConfig::new((6, 6), vec![CexExchange::Binance]))
+                .unwrap();
+        assert_eq!(source.symbols().len(), 1);
+        assert_eq!(source.trades.len(), 2);
+        assert_eq!(
+            (source.trades[0].start, source.trades[0].end),
+            (1_700_000_000_000_000, 1_700_000_020_000_000)
+        );
+        assert_eq!(
+            files_in(&source.trades, 1_699_999_990_000_000, 1_700_000_010_000_000).count(),
+            1
+        );
+
+        let trades = source
+            .read_trades(1_699_999_990_000_000, 1_700_000_010_000_000)
+            .unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].price, 2000.5);
+        let trades = source
+            .read_trades(1_700_000_500_000_000, 1_700_001_500_000_000)
+            .unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].price, 2100.0);
+
+        let data = source
+            .cex_trades(vec![BlockTimes { block_number: 1, timestamp: 1_700_000_001_000_000 }])
+            .unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].key, 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cex_books_skip_non_finite_rows() {
+        brontes_types::init_thread_pools(2);
+        let dir = std::env::temp_dir().join("brontes_cex_books_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        write_file(
+            &dir,
+            "symbols.csv",
+            "exchange,symbol,base_address,quote_address\nbinance,ETHUSDT,\
+             0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2,\
+             0xdac17f958d2ee523a2206206994597c13d831ec7\n",
+        );
+        write_file(
+            &dir,
+            "book_snapshots/binance.csv",
+            "exchange,symbol,timestamp,local_timestamp,asks[0].price,asks[0].amount,bids[0].price,\
+             bids[0].amount\nbinance,ETHUSDT,1700000000000000,1700000000000100,2001.0,1.0,2000.0,\
+             2.0\nbinance,ETHUSDT,1700000000500000,1700000000500100,inf,1.0,2000.0,2.0\n",
+        );
+
+        let source =
+            CexFileSource::new(&dir, CexDownloadConfig::new((6, 6), vec![CexExchange::Binance]))
+                .unwrap();
+        let books = source.cex_order_books(1_700_000_001_000_000).unwrap();
+
+        let books = books.0[&CexExchange::Binance].values().next().unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].timestamp, 1_700_000_000_000_000);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_perp_tickers_skip_non_finite_rows() {
+        brontes_types::init_thread_pools(2);
+        let dir = std::env::temp_dir().join("brontes_perp_tickers_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        write_file(
+            &dir,
+            "symbols.csv",
+            "exchange,symbol,base_address,quote_address\nbinancefutures,ETHUSDT,\
+             0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2,\
+             0xdac17f958d2ee523a2206206994597c13d831ec7\n",
+        );
+        write_file(
+            &dir,
+            "derivative_ticker/binancefutures.csv",
+            "exchange,symbol,timestamp,local_timestamp,funding_rate,mark_price,index_price\\
+             nbinancefutures,ETHUSDT,1700000000000000,1700000000000100,0.0001,2001.0,2000.0\\
+             nbinancefutures,ETHUSDT,1700000000500000,1700000000500100,NaN,2001.0,2000.0\\
+             nbinancefutures,ETHUSDT,1700000000600000,1700000000600100,0.0001,inf,2000.0\n",
+        );
+
+        let source = CexFileSource::new(
+            &dir,
+            CexDownloadConfig::new((6, 6), vec![CexExchange::BinanceFutures]),
+        )
+        .unwrap();
+        assert_eq!(source.tickers.len(), 1);
+
+        let tickers = source.perp_tickers(1_700_000_001_000_000).unwrap();
+        let tickers = tickers.0[&CexExchange::BinanceFutures]
+            .values()
+            .next()
+            .unwrap();
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(tickers[0].timestamp, 1_700_000_000_000_000);
+
+        // outside of the ticker window
+        assert!(source
+            .perp_tickers(1_700_000_000_000_000 + 120 * SECONDS_TO_US)
+            .unwrap()
+            .is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    fs::File,
+    io::Seek,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alloy_primitives::Address;
+use arrow::{
+    array::{Array, AsArray},
+    compute::cast,
+    csv::{reader::Format, ReaderBuilder},
+    datatypes::{DataType, Float64Type, UInt64Type},
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::cex::{
+        quotes::{BookLevel, CexOrderBook, RawCexQuotes},
+        trades::{RawCexTrades, TradeType},
+        CexExchange, CexSymbols, PerpTicker,
+    },
+    pair::Pair,
+};
+use malachite::Rational;
+use parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ProjectionMask};
+
+/// Reads all record batches of a csv or parquet file, based on its extension
+pub(crate) fn read_batches(path: &Path) -> eyre::Result<Vec<RecordBatch>> {
+    let mut file = File::open(path)?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("parquet") => Ok(ParquetRecordBatchReaderBuilder::try_new(file)?
+            .build()?
+            .collect::<Result<Vec<_>, _>>()?),
+        Some("csv") => {
+            let (schema, _) = Format::default()
+                .with_header(true)
+                .infer_schema(&mut file, Some(1000))?;
+            file.rewind()?;
+
+            Ok(ReaderBuilder::new(Arc::new(schema))
+                .with_header(true)
+                .build(file)?
+                .collect::<Result<Vec<_>, _>>()?)
+        }
+        _ => eyre::bail!("unsupported cex data file {}", path.display()),
+    }
+}
+
+/// All csv and parquet files in `dir`, sorted by name. A missing directory
+/// has no files.
+pub(crate) fn data_files(dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(vec![])
+    }
+
+    let mut files = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .filter(|path| {
+            path.as_ref().map_or(true, |path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "csv" || ext == "parquet")
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    files.sort();
+
+    Ok(files)
+}
+
+/// A data file and the time range of its rows
+#[derive(Debug, Clone)]
+pub(crate) struct DataFile {
+    pub(crate) path:  PathBuf,
+    pub(crate) start: u64,
+    pub(crate) end:   u64,
+}
+
+impl DataFile {
+    /// Whether the file can have rows in `start_time..end_time`
+    pub(crate) fn overlaps(&self, start_time: u64, end_time: u64) -> bool {
+        self.start < end_time && start_time <= self.end
+    }
+}
+
+/// The data files in `dir` with the time range of their rows, so that a
+/// query only reads the files it needs. Files without rows are skipped.
+pub(crate) fn index_files(dir: &Path) -> eyre::Result<Vec<DataFile>> {
+    let mut files = Vec::new();
+    for path in data_files(dir)? {
+        let mut range: Option<(u64, u64)> = None;
+        for batch in timestamp_batches(&path)? {
+            for timestamp in u64_column(&batch, "timestamp")? {
+                range = Some(range.map_or((timestamp, timestamp), |(start, end)| {
+                    (start.min(timestamp), end.max(timestamp))
+                }));
+            }
+        }
+
+        if let Some((start, end)) = range {
+            files.push(DataFile { path, start, end });
+        }
+    }
+
+    Ok(files)
+}
+
+/// Reads only the timestamp column of parquet files
+fn timestamp_batches(path: &Path) -> eyre::Result<Vec<RecordBatch>> {
+    if path.extension().is_some_and(|ext| ext == "parquet") {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+        let column = builder.schema().index_of("timestamp")?;
+        let mask = ProjectionMask::roots(builder.parquet_schema(), [column]);
+
+        return Ok(builder
+            .with_projection(mask)
+            .build()?
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    read_batches(path)
+}
+
+/// Trades in the tardis `trades` format. A `trade_type` column is optional,
+/// trades default to taker trades.
+pub(crate) fn trades_from_batch(
+    batch: &RecordBatch,
+    start_time: u64,
+    end_time: u64,
+) -> eyre::Result<Vec<RawCexTrades>> {
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let timestamp = u64_column(batch, "timestamp")?;
+    let side = string_column(batch, "side")?;
+    let price = f64_column(batch, "price")?;
+    let amount = f64_column(batch, "amount")?;
+    let trade_type = batch
+        .column_by_name("trade_type")
+        .map(|_| string_column(batch, "trade_type"))
+        .transpose()?;
+
+    Ok((0..batch.num_rows())
+        .filter(|&i| (start_time..end_time).contains(&timestamp[i]))
+        .map(|i| RawCexTrades {
+            exchange:   CexExchange::from(exchange[i].as_str()),
+            trade_type: match &trade_type {
+                Some(ty) if ty[i].eq_ignore_ascii_case("maker") => TradeType::Maker,
+                _ => TradeType::Taker,
+            },
+            symbol:     symbol[i].clone(),
+            timestamp:  timestamp[i],
+            side:       side[i].clone(),
+            price:      price[i],
+            amount:     amount[i],
+        })
+        .collect())
+}
+
+/// Quotes in the tardis `quotes` format
+pub(crate) fn quotes_from_batch(
+    batch: &RecordBatch,
+    start_time: u64,
+    end_time: u64,
+) -> eyre::Result<Vec<RawCexQuotes>> {
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let timestamp = u64_column(batch, "timestamp")?;
+    let ask_amount = f64_column(batch, "ask_amount")?;
+    let ask_price = f64_column(batch, "ask_price")?;
+    let bid_price = f64_column(batch, "bid_price")?;
+    let bid_amount = f64_column(batch, "bid_amount")?;
+
+    Ok((0..batch.num_rows())
+        .filter(|&i| (start_time..end_time).contains(&timestamp[i]))
+        .map(|i| RawCexQuotes {
+            exchange:   CexExchange::from(exchange[i].as_str()),
+            symbol:     symbol[i].clone(),
+            timestamp:  timestamp[i],
+            ask_amount: ask_amount[i],
+            ask_price:  ask_price[i],
+            bid_price:  bid_price[i],
+            bid_amount: bid_amount[i],
+        })
+        .collect())
+}
+
+/// Order books in the tardis `book_snapshot_<depth>` format, where each
+/// level has an `asks[i].price`, `asks[i].amount`, `bids[i].price` and
+/// `bids[i].amount` column. Empty levels are skipped, as are rows with NaN or
+/// infinite levels.
+pub(crate) fn books_from_batch(
+    batch: &RecordBatch,
+    start_time: u64,
+    end_time: u64,
+) -> eyre::Result<Vec<(String, CexOrderBook)>> {
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let timestamp = u64_column(batch, "timestamp")?;
+
+    let mut asks = Vec::new();
+    let mut bids = Vec::new();
+    for (side, levels) in [("asks", &mut asks), ("bids", &mut bids)] {
+        let mut i = 0;
+        while batch
+            .column_by_name(&format!("{side}[{i}].price"))
+            .is_some()
+        {
+            levels.push((
+                f64_column(batch, &format!("{side}[{i}].price"))?,
+                f64_column(batch, &format!("{side}[{i}].amount"))?,
+            ));
+            i += 1;
+        }
+    }
+
+    // the first empty level ends the book, a level that isn't a positive
+    // finite number invalidates the whole row
+    let book_levels = |levels: &[(Vec<f64>, Vec<f64>)], row: usize| {
+        levels
+            .iter()
+            .map(|(price, amount)| (price[row], amount[row]))
+            .take_while(|(price, amount)| *price != 0.0 && *amount != 0.0)
+            .map(|(price, amount)| {
+                if price < 0.0 || amount < 0.0 {
+                    return None
+                }
+
+                Some(BookLevel {
+                    price:  Rational::try_from_float_simplest(price).ok()?,
+                    amount: Rational::try_from_float_simplest(amount).ok()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+
+    Ok((0..batch.num_rows())
+        .filter(|&i| (start_time..end_time).contains(&timestamp[i]))
+        .filter_map(|i| {
+            Some((
+                symbol[i].clone(),
+                CexOrderBook {
+                    exchange:  CexExchange::from(exchange[i].as_str()),
+                    timestamp: timestamp[i],
+                    bids:      book_levels(&bids, i)?,
+                    asks:      book_levels(&asks, i)?,
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Perp tickers in the tardis `derivative_ticker` format. Rows without a mark
+/// or index price or with a value that isn't finite are skipped, a missing
+/// funding rate is read as zero.
+pub(crate) fn tickers_from_batch(
+    batch: &RecordBatch,
+    start_time: u64,
+    end_time: u64,
+) -> eyre::Result<Vec<(String, PerpTicker)>> {
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let timestamp = u64_column(batch, "timestamp")?;
+    let funding_rate = f64_column(batch, "funding_rate")?;
+    let mark_price = f64_column(batch, "mark_price")?;
+    let index_price = f64_column(batch, "index_price")?;
+
+    Ok((0..batch.num_rows())
+        .filter(|&i| (start_time..end_time).contains(&timestamp[i]))
+        .filter(|&i| mark_price[i] > 0.0 && index_price[i] > 0.0)
+        .filter_map(|i| {
+            Some((
+                symbol[i].clone(),
+                PerpTicker {
+                    exchange:     CexExchange::from(exchange[i].as_str()),
+                    timestamp:    timestamp[i],
+                    funding_rate: Rational::try_from_float_simplest(funding_rate[i]).ok()?,
+                    mark_price:   Rational::try_from_float_simplest(mark_price[i]).ok()?,
+                    index_price:  Rational::try_from_float_simplest(index_price[i]).ok()?,
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Maps an exchange symbol to the token addresses it trades
+pub(crate) fn symbols_from_batch(batch: &RecordBatch) -> eyre::Result<Vec<CexSymbols>> {
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let base = string_column(batch, "base_address")?;
+    let quote = string_column(batch, "quote_address")?;
+
+    (0..batch.num_rows())
+        .map(|i| {
+            Ok(CexSymbols {
+                exchange:     CexExchange::from(exchange[i].as_str()),
+                symbol_pair:  symbol[i].clone(),
+                address_pair: Pair(base[i].parse::<Address>()?, quote[i].parse::<Address>()?),
+            })
+        })
+        .collect()
+}
+
+fn column(batch: &RecordBatch, name: &str, ty: &DataType) -> eyre::Result<Arc<dyn Array>> {
+    let column = batch
+        .column_by_name(name)
+        .ok_or_else(|| eyre::eyre!("cex data file is missing the {name} column"))?;
+
+    Ok(cast(column, ty)?)
+}
+
+fn string_column(batch: &RecordBatch, name: &str) -> eyre::Result<Vec<String>> {
+    let column = column(batch, name, &DataType::Utf8)?;
+
+    Ok(column
+        .as_string::<i32>()
+        .iter()
+        .map(|value| value.unwrap_or_default().to_string())
+        .collect())
+}
+
+fn u64_column(batch: &RecordBatch, name: &str) -> eyre::Result<Vec<u64>> {
+    let column = column(batch, name, &DataType::UInt64)?;
+
+    Ok(column
+        .as_primitive::<UInt64Type>()
+        .iter()
+        .map(Option::unwrap_or_default)
+        .collect())
+}
+
+fn f64_column(batch: &RecordBatch, name: &str) -> eyre::Result<Vec<f64>> {
+    let column = column(batch, name, &DataType::Float64)?;
+
+    Ok(column
+        .as_primitive::<Float64Type>()
+        .iter()
+        .map(Option::unwrap_or_default)
+        .collect())
+}
@@ -61,6 +61,26 @@ use crate::{
 const SECONDS_TO_US: f64 = 1_000_000.0;
 const MAX_MARKOUT_TIME: f64 = 300.0;
 
+/// The tables that hold results keyed by block number, which are cleared when
+/// a block is rolled back
+const BLOCK_KEYED_TABLES: &[&str] = &[
+    "mev.mev_blocks",
+    "mev.bundle_header",
+    "mev.searcher_tx",
+    "mev.cex_dex",
+    "mev.cex_dex_quotes",
+    "mev.liquidations",
+    "mev.jit_sandwich",
+    "mev.jit",
+    "mev.sandwiches",
+    "mev.atomic_arbs",
+    "mev.generalized_frontrun",
+    "brontes.dex_price_mapping",
+    "brontes.block_analysis",
+    "brontes.tree",
+    "brontes.tx_traces",
+];
+
 #[derive(Clone)]
 pub struct Clickhouse {
     pub tip:                 bool,
@@ -182,6 +202,9 @@ impl Clickhouse {
                     BundleData::Liquidation(s) => {
                         tx.send(vec![(s, self.tip, self.run_id).into()])?
                     }
+                    BundleData::GeneralizedFrontrun(s) => {
+                        tx.send(vec![(s, self.tip, self.run_id).into()])?
+                    }
                     BundleData::Unknown(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
                 };
 
@@ -275,7 +298,35 @@ impl Clickhouse {
         Ok(())
     }
 
-    pub async fn save_traces(&self, _block: u64, _traces: Vec<TxTrace>) -> eyre::Result<()> {
+    pub async fn save_traces(&self, _block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
+        if traces.is_empty() {
+            return Ok(())
+        }
+
+        if let Some(tx) = self.buffered_insert_tx.as_ref() {
+            tx.send(
+                traces
+                    .into_iter()
+                    .map(|trace| (trace, self.tip).into())
+                    .collect(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes all rows of the block. At tip every insert is forced, so the
+    /// rows of a processed block are already written when this is called
+    pub async fn rollback_block(&self, block_number: u64) -> eyre::Result<()> {
+        for table in BLOCK_KEYED_TABLES {
+            self.client
+                .execute_remote(
+                    format!("ALTER TABLE {table} DELETE WHERE block_number = ?"),
+                    &(block_number),
+                )
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -1000,15 +1051,20 @@ mod tests {
     use brontes_classifier::test_utils::ClassifierTestUtils;
     use brontes_types::{
         block_metadata::RelayBlockMetadata,
-        db::{cex::CexExchange, dex::DexPrices, DbDataWithRunId},
+        db::{
+            cex::{CexExchange, FeeAssumption},
+            dex::DexPrices,
+            DbDataWithRunId,
+        },
         init_thread_pools,
         mev::{
-            ArbDetails, AtomicArb, BundleHeader, CexDex, CexDexQuote, JitLiquidity,
-            JitLiquiditySandwich, Liquidation, OptimisticTrade, PossibleMev, PossibleMevCollection,
-            Sandwich,
+            ArbDetails, AtomicArb, BundleHeader, CexDex, CexDexQuote, GeneralizedFrontrun,
+            JitLiquidity, JitLiquiditySandwich, Liquidation, OptimisticTrade, PossibleMev,
+            PossibleMevCollection, Sandwich,
         },
         normalized_actions::{
             NormalizedBurn, NormalizedLiquidation, NormalizedMint, NormalizedSwap,
+            NormalizedTransfer,
         },
         pair::Pair,
         FastHashMap, GasDetails,
@@ -1233,6 +1289,7 @@ mod tests {
             t60_mid_price:     vec![0.0006263290093187073],
             t300_mid_price:    vec![0.0006263290093187073],
             exchange:          CexExchange::Binance,
+            fee_assumption:    FeeAssumption::BestTier,
             pnl:               12951.829205242997,
             gas_details:       GasDetails {
                 coinbase_transfer:   Some(11419369165096275986),
@@ -1299,6 +1356,18 @@ mod tests {
             .unwrap();
     }
 
+    async fn generalized_frontrun(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let case0 = GeneralizedFrontrun {
+            frontrun_transfers: vec![NormalizedTransfer::default()],
+            victim_reverted: true,
+            ..GeneralizedFrontrun::default()
+        };
+
+        db.insert_one::<MevGeneralized_Frontrun>(&DbDataWithRunId::new_with_run_id(case0, 0))
+            .await
+            .unwrap();
+    }
+
     async fn bundle_header(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
         let case0 = BundleHeader::default();
 
@@ -1381,12 +1450,19 @@ mod tests {
         db.insert_many::<BrontesTree>(&roots).await.unwrap();
     }
 
+    async fn tx_traces(db: &ClickhouseTestClient<BrontesClickhouseTables>) {
+        let case0 = TxTrace::default();
+
+        db.insert_one::<BrontesTx_Traces>(&case0).await.unwrap();
+    }
+
     async fn run_all(database: &ClickhouseTestClient<BrontesClickhouseTables>) {
         pools(database).await;
         atomic_arb(database).await;
         sandwich(database).await;
         bundle_header(database).await;
         liquidations(database).await;
+        generalized_frontrun(database).await;
         jit_sandwich(database).await;
         jit(database).await;
         cex_dex(database).await;
@@ -1396,6 +1472,7 @@ mod tests {
         token_info(database).await;
         tree(database).await;
         block_analysis(database).await;
+        tx_traces(database).await;
     }
 
     #[brontes_macros::test]
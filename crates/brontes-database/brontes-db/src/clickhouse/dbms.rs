@@ -5,6 +5,7 @@ use brontes_types::{
         token_info::TokenInfoWithAddress, DbDataWithRunId, RunId,
     },
     mev::*,
+    structured_trace::TxTrace,
 };
 use db_interfaces::{clickhouse_dbms, remote_clickhouse_table};
 
@@ -24,10 +25,12 @@ clickhouse_dbms!(
         MevJit,
         MevSandwiches,
         MevAtomic_Arbs,
+        MevGeneralized_Frontrun,
         BrontesToken_Info,
         EthereumPools,
         BrontesTree,
-        BrontesRun_Id
+        BrontesRun_Id,
+        BrontesTx_Traces
     ]
 );
 
@@ -37,6 +40,7 @@ impl BrontesClickhouseTables {
             self,
             BrontesClickhouseTables::BrontesDex_Price_Mapping
                 | BrontesClickhouseTables::BrontesTree
+                | BrontesClickhouseTables::BrontesTx_Traces
         )
     }
 }
@@ -125,6 +129,13 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Mev, Generalized_Frontrun],
+    DbDataWithRunId<GeneralizedFrontrun>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 remote_clickhouse_table!(
     BrontesClickhouseTables,
     [Brontes, Token_Info],
@@ -153,6 +164,13 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Brontes, Tx_Traces],
+    TxTrace,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 pub struct BrontesClickhouseData {
     pub data:         BrontesClickhouseTableDataTypes,
     pub force_insert: bool,
@@ -240,9 +258,11 @@ db_types!(
     (JitLiquidity, MevJit, true),
     (Sandwich, MevSandwiches, true),
     (AtomicArb, MevAtomic_Arbs, true),
+    (GeneralizedFrontrun, MevGeneralized_Frontrun, true),
     (TokenInfoWithAddress, BrontesToken_Info, false),
     (ProtocolInfoClickhouse, EthereumPools, false),
     (TransactionRoot, BrontesTree, true),
     (BlockAnalysis, BrontesBlock_Analysis, true),
-    (RunId, BrontesRun_Id, false)
+    (RunId, BrontesRun_Id, false),
+    (TxTrace, BrontesTx_Traces, false)
 );
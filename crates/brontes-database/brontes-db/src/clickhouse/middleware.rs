@@ -7,12 +7,15 @@ use brontes_types::{
         address_to_protocol_info::ProtocolInfo,
         block_analysis::BlockAnalysis,
         builder::BuilderInfo,
+        builder_auction::BuilderAuction,
         dex::DexQuotes,
-        metadata::Metadata,
+        metadata::{BlockMetadataInner, Metadata},
         mev_block::MevBlockWithClassified,
         searcher::SearcherInfo,
+        searcher_inventory::SearcherInventory,
         token_info::TokenInfoWithAddress,
         traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -81,6 +84,12 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
             .await
     }
 
+    async fn rollback_block(&self, block_number: u64) -> eyre::Result<()> {
+        self.client.rollback_block(block_number).await?;
+
+        self.inner().rollback_block(block_number).await
+    }
+
     async fn save_mev_blocks(
         &self,
         block_number: u64,
@@ -317,6 +326,22 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.fetch_all_builder_info()
     }
 
+    fn fetch_all_searcher_inventories(&self) -> eyre::Result<Vec<(Address, SearcherInventory)>> {
+        self.inner.fetch_all_searcher_inventories()
+    }
+
+    fn try_fetch_builder_auction(&self, block_num: u64) -> eyre::Result<Option<BuilderAuction>> {
+        self.inner.try_fetch_builder_auction(block_num)
+    }
+
+    fn try_fetch_v4_pool_key(&self, pool: Address) -> eyre::Result<Option<UniswapV4PoolKey>> {
+        self.inner.try_fetch_v4_pool_key(pool)
+    }
+
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        self.inner.try_fetch_mev_block(block_num)
+    }
+
     //TODO: JOE
     fn try_fetch_mev_blocks(
         &self,
@@ -337,6 +362,10 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.get_metadata(block_num, quote_asset)
     }
 
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>> {
+        self.inner.try_fetch_block_info(block_num)
+    }
+
     fn try_fetch_address_metadata(
         &self,
         address: Address,
@@ -366,7 +395,7 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
     fn protocols_created_before(
         &self,
         start_block: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+    ) -> eyre::Result<FastHashMap<(Address, Protocol), Vec<Pair>>> {
         self.inner.protocols_created_before(start_block)
     }
 
@@ -431,6 +460,15 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
             .await
     }
 
+    /// block info is only stored in libmdbx
+    async fn write_block_info(&self, _: u64, _: BlockMetadataInner) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    async fn rollback_block(&self, block_number: u64) -> eyre::Result<()> {
+        self.client.rollback_block(block_number).await
+    }
+
     async fn save_mev_blocks(
         &self,
         block_number: u64,
@@ -472,6 +510,22 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
             .await
     }
 
+    /// searcher inventories are only stored in libmdbx
+    async fn write_searcher_inventory(&self, _: Address, _: SearcherInventory) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// builder auctions are only stored in libmdbx
+    async fn write_builder_auction(&self, _: u64, _: BuilderAuction) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// the pricing engine loads v4 pools through their pool key, so these are
+    /// written to libmdbx even though nothing else is
+    async fn write_v4_pool_key(&self, key: UniswapV4PoolKey) -> eyre::Result<()> {
+        self.inner.write_v4_pool_key(key).await
+    }
+
     async fn insert_pool(
         &self,
         block: u64,
@@ -639,6 +693,22 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.fetch_all_builder_info()
     }
 
+    fn fetch_all_searcher_inventories(&self) -> eyre::Result<Vec<(Address, SearcherInventory)>> {
+        self.inner.fetch_all_searcher_inventories()
+    }
+
+    fn try_fetch_builder_auction(&self, block_num: u64) -> eyre::Result<Option<BuilderAuction>> {
+        self.inner.try_fetch_builder_auction(block_num)
+    }
+
+    fn try_fetch_v4_pool_key(&self, pool: Address) -> eyre::Result<Option<UniswapV4PoolKey>> {
+        self.inner.try_fetch_v4_pool_key(pool)
+    }
+
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        self.inner.try_fetch_mev_block(block_num)
+    }
+
     //TODO: JOE
     fn try_fetch_mev_blocks(
         &self,
@@ -659,6 +729,10 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.get_metadata(block_num, quote_asset)
     }
 
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>> {
+        self.inner.try_fetch_block_info(block_num)
+    }
+
     fn try_fetch_address_metadata(
         &self,
         address: Address,
@@ -688,7 +762,7 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
     fn protocols_created_before(
         &self,
         start_block: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+    ) -> eyre::Result<FastHashMap<(Address, Protocol), Vec<Pair>>> {
         self.inner.protocols_created_before(start_block)
     }
 
@@ -115,12 +115,14 @@ impl ClickhouseBuffered {
             (MevSandwiches, Sandwich),
             (MevAtomic_Arbs, AtomicArb),
             (MevLiquidations, Liquidation),
+            (MevGeneralized_Frontrun, GeneralizedFrontrun),
             (BrontesDex_Price_Mapping, DexQuotesWithBlockNumber),
             (BrontesToken_Info, TokenInfoWithAddress),
             (EthereumPools, ProtocolInfoClickhouse),
             (BrontesTree, TransactionRoot),
             (BrontesBlock_Analysis, BlockAnalysis),
-            (BrontesRun_Id, RunId)
+            (BrontesRun_Id, RunId),
+            (BrontesTx_Traces, TxTrace)
         );
 
         Ok(())
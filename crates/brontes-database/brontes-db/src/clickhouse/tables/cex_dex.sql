@@ -4,6 +4,7 @@ CREATE TABLE mev.cex_dex ON CLUSTER eth_cluster0
     `block_timestamp` UInt64,
     `block_number` UInt64,
     `header_pnl_methodology` String,
+    `fee_assumption` String,
     `swaps` Nested(
         `trace_idx` UInt64,
         `from` String,
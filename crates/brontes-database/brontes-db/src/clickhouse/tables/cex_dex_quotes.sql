@@ -20,6 +20,7 @@ CREATE TABLE mev.cex_dex_quotes ON CLUSTER eth_cluster0
     `t60_mid_price` Array(Float64),
     `t300_mid_price` Array(Float64),
     `exchange` String,
+    `fee_assumption` String,
     `pnl` Float64,
     `gas_details` Tuple(
         `coinbase_transfer` Nullable(UInt128),
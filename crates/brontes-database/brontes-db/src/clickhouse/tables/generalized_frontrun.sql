@@ -0,0 +1,22 @@
+CREATE TABLE mev.generalized_frontrun ON CLUSTER eth_cluster0
+(
+    `block_number` UInt64,
+    `frontrun_tx_hash` String,
+    `frontrun_transfers` Nested(
+        `trace_idx` UInt64,
+        `from` String,
+        `to` String,
+        `token` Tuple(String, String),
+        `amount` Tuple(UInt256, UInt256),
+        `fee` Tuple(UInt256, UInt256)
+    ),
+    `frontrun_gas_details` Tuple(Nullable(UInt128), UInt128, UInt128, UInt128),
+    `victim_tx_hash` String,
+    `victim_reverted` Bool,
+    `victim_profit_usd` Float64,
+    `victim_gas_details` Tuple(Nullable(UInt128), UInt128, UInt128, UInt128),
+    `run_id` UInt64
+) 
+ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/generalized_frontrun', '{replica}')
+PRIMARY KEY (`block_number`,`frontrun_tx_hash`)
+ORDER BY (`block_number`, `frontrun_tx_hash`)
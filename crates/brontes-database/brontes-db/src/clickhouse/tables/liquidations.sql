@@ -2,6 +2,10 @@ CREATE TABLE mev.liquidations ON CLUSTER eth_cluster0
 (
     `liquidation_tx_hash` String,
     `block_number` UInt64,
+    `trigger` String,
+    `trigger_block` Nullable(UInt64),
+    `trigger_tx_index` Nullable(UInt64),
+    `blocks_to_liquidate` Nullable(UInt64),
     `liquidation_swaps` Nested(
         `trace_idx` UInt64,
         `from` String,
@@ -43,6 +43,18 @@ CREATE TABLE mev.mev_blocks ON CLUSTER eth_cluster0
         `triggers.coinbase_transfer` Bool,
         `triggers.high_priority_fee` Bool
     ),
+    `builder_auction` Nested (
+        `slot` UInt64,
+        `bid_count` UInt64,
+        `builder_count` UInt64,
+        `winning_builder` String,
+        `winning_relay` String,
+        `winning_bid` UInt128,
+        `winning_bid_ms` Int64,
+        `runner_up_builder` Nullable(String),
+        `runner_up_bid` Nullable(UInt128),
+        `winning_margin` Nullable(UInt128)
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/mev_blocks', '{replica}')
@@ -57,6 +57,16 @@ CREATE TABLE mev.sandwiches ON CLUSTER eth_cluster0
         `gas_used` UInt128,
         `effective_gas_price` UInt128
     ),
+    `is_multi_block` Bool DEFAULT false,
+    `victim_loss` Nested(
+        `tx_hash` String,
+        `victim_eoa` String,
+        `token` Tuple(String, String),
+        `amount_lost` Float64,
+        `amount_lost_usd` Float64,
+        `simulated_amount_lost` Nullable(Float64),
+        `simulated_amount_lost_usd` Nullable(Float64)
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedMergeTree('/clickhouse/eth_cluster0/tables/all/mev/sandwiches', '{replica}')
@@ -3,9 +3,11 @@
 #![feature(const_trait_impl)]
 #![feature(noop_waker)]
 
+pub mod cex_files;
 pub mod clickhouse;
 pub mod libmdbx;
 pub mod parquet;
+pub mod sql;
 pub use libmdbx::{
     tables::*,
     types::{CompressedTable, IntoTableKey},
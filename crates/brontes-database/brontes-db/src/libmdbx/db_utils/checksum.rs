@@ -0,0 +1,85 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use alloy_primitives::hex;
+use fs_extra::dir::get_dir_content;
+use sha2::{Digest, Sha256};
+
+/// Suffix of the file a partition's checksum is published in, next to its
+/// tarball
+pub const CHECKSUM_PATH: &str = "checksum.txt";
+
+/// libmdbx rewrites the lock file whenever the db is opened
+const SKIPPED_FILES: &[&str] = &["mdbx.lck"];
+
+/// Sha256 over the relative path & contents of every file of an unpacked
+/// partition, in path order. As the tarball itself isn't kept, this allows
+/// partitions to be verified after they were unpacked.
+pub fn partition_checksum(partition: &Path) -> eyre::Result<String> {
+    let mut files = get_dir_content(partition)?
+        .files
+        .into_iter()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(partition)?;
+        if relative
+            .file_name()
+            .is_some_and(|name| SKIPPED_FILES.iter().any(|skip| name == *skip))
+        {
+            continue
+        }
+
+        hasher.update(relative.to_string_lossy().as_bytes());
+        std::io::copy(&mut BufReader::new(File::open(&file)?), &mut hasher)?;
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Errors if the checksum of the partition doesn't match the published one
+pub fn verify_partition(partition: &Path, expected: &str) -> eyre::Result<()> {
+    let checksum = partition_checksum(partition)?;
+    if checksum != expected.trim() {
+        eyre::bail!(
+            "checksum mismatch for partition {}\nexpected: {}\nfound: {}",
+            partition.display(),
+            expected.trim(),
+            checksum
+        )
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_checksum() {
+        let partition = std::env::temp_dir().join("brontes-checksum-test-partition");
+        let _ = std::fs::remove_dir_all(&partition);
+        std::fs::create_dir_all(&partition).unwrap();
+        std::fs::write(partition.join("mdbx.dat"), b"tables").unwrap();
+        std::fs::write(partition.join("mdbx.lck"), b"lock").unwrap();
+
+        let checksum = partition_checksum(&partition).unwrap();
+        verify_partition(&partition, &checksum).unwrap();
+
+        // the lock file isn't part of the checksum
+        std::fs::write(partition.join("mdbx.lck"), b"relocked").unwrap();
+        verify_partition(&partition, &checksum).unwrap();
+
+        std::fs::write(partition.join("mdbx.dat"), b"corrupted").unwrap();
+        assert!(verify_partition(&partition, &checksum).is_err());
+
+        std::fs::remove_dir_all(&partition).unwrap();
+    }
+}
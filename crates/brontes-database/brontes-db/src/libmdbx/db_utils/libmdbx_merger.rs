@@ -4,7 +4,10 @@ use brontes_types::BrontesTaskExecutor;
 use fs_extra::dir::get_dir_content;
 use rayon::iter::*;
 
-use crate::{libmdbx::LibmdbxReadWriter, move_tables_to_partition, *};
+use crate::{
+    libmdbx::{migrations::TableVersionError, LibmdbxReadWriter},
+    move_tables_to_partition, *,
+};
 
 pub fn merge_libmdbx_dbs(
     final_db: LibmdbxReadWriter,
@@ -17,8 +20,16 @@ pub fn merge_libmdbx_dbs(
         .directories
         .par_iter()
         .filter(|dir_name| *dir_name != partition_db_folder.to_str().unwrap())
-        .filter_map(|path| LibmdbxReadWriter::init_db(path, None, &executor, false).ok())
+        .filter_map(|path| match LibmdbxReadWriter::init_db(path, None, &executor, false) {
+            Ok(db) => Some(Ok(db)),
+            // partitions written with a different layout can't be merged
+            Err(e) if e.downcast_ref::<TableVersionError>().is_some() => {
+                Some(Err(e.wrap_err(format!("failed to merge partition {path}"))))
+            }
+            Err(_) => None,
+        })
         .try_for_each(|db| {
+            let db = db?;
             move_tables_to_partition!(FULL_RANGE db, final_db,
             CexPrice,
             CexTrades,
@@ -27,11 +38,14 @@ pub fn merge_libmdbx_dbs(
             InitializedState,
             PoolCreationBlocks,
             TxTraces,
+            BuilderAuctions,
             AddressMeta,
             SearcherEOAs,
             SearcherContracts,
+            SearcherInventories,
             Builder,
             AddressToProtocolInfo,
+            UniswapV4PoolKeys,
             TokenDecimals,
             DexPrice
             );
@@ -11,7 +11,7 @@ use libmdbx::libmdbx_writer::InitTables;
 use rayon::iter::*;
 use tokio::sync::Notify;
 
-use super::rclone_wrapper::BlockRangeList;
+use super::snapshot_publisher::BlockRangeList;
 use crate::{
     libmdbx::{types::LibmdbxData, LibmdbxInit, LibmdbxReadWriter},
     *,
@@ -78,6 +78,7 @@ impl LibmdbxPartitioner {
             ranges.push(BlockRangeList {
                 start_block,
                 end_block: start_block + DEFAULT_PARTITION_SIZE,
+                checksum: None,
             });
 
             start_block += DEFAULT_PARTITION_SIZE
@@ -87,7 +88,7 @@ impl LibmdbxPartitioner {
         // because we are just doing read operations. we can do all this in parallel
         ranges
             .par_iter()
-            .try_for_each(|BlockRangeList { start_block, end_block }| {
+            .try_for_each(|BlockRangeList { start_block, end_block, .. }| {
                 let mut path = self.partition_db_folder.clone();
                 path.push(format!("{PARTITION_FILE_NAME}-{start_block}-{end_block}/"));
                 tracing::info!(?path, "creating path");
@@ -107,7 +108,8 @@ impl LibmdbxPartitioner {
                     MevBlocks,
                     InitializedState,
                     PoolCreationBlocks,
-                    TxTraces
+                    TxTraces,
+                    BuilderAuctions
                 );
                 // manually dex pricing
                 self.parent_db
@@ -127,8 +129,10 @@ impl LibmdbxPartitioner {
             AddressMeta,
             SearcherEOAs,
             SearcherContracts,
+            SearcherInventories,
             Builder,
             AddressToProtocolInfo,
+            UniswapV4PoolKeys,
             TokenDecimals
         );
 
@@ -1,7 +1,10 @@
+pub mod checksum;
+pub use checksum::*;
+
 pub mod libmdbx_merger;
 pub use libmdbx_merger::*;
 
 pub mod libmdbx_partitioning;
 pub use libmdbx_partitioning::*;
 
-pub mod rclone_wrapper;
+pub mod snapshot_publisher;
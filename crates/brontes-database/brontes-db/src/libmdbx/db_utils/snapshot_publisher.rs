@@ -0,0 +1,425 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use backon::{ExponentialBuilder, Retryable};
+use bytes::Bytes;
+use eyre::eyre;
+use flate2::{write::GzEncoder, Compression};
+use fs_extra::dir::{get_dir_content, CopyOptions};
+use futures::{StreamExt, TryStreamExt};
+use object_store::{aws::AmazonS3Builder, path::Path as ObjectPath, ObjectStore, RetryConfig};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use super::{partition_checksum, CHECKSUM_PATH, PARTITION_FILE_NAME};
+
+const FULL_RANGE_TABLES: &str = "brontes-db-partition-full-range-tables";
+const SIZE_PATH: &str = "byte-count.txt";
+const RANGES_AVAILABLE: &str = "brontes-available-ranges.json";
+
+/// Retries per file. Single requests are already retried by the store, this
+/// covers uploads that fail part way through
+const UPLOAD_RETRIES: usize = 5;
+
+/// Publishes the partitions made by the [`LibmdbxPartitioner`] to an object
+/// store, along with the manifest of the available ranges the snapshot
+/// downloader reads.
+///
+/// [`LibmdbxPartitioner`]: super::LibmdbxPartitioner
+pub struct SnapshotPublisher {
+    store:       Arc<dyn ObjectStore>,
+    /// where partitions are copied to & tarballed before they are uploaded
+    staging_dir: PathBuf,
+}
+
+impl SnapshotPublisher {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store, staging_dir: std::env::temp_dir() }
+    }
+
+    /// Publishes to a S3 compatible bucket, e.g. R2 or MinIO. Credentials are
+    /// read from `AWS_ACCESS_KEY_ID` & `AWS_SECRET_ACCESS_KEY`
+    pub fn s3(bucket: &str, endpoint: Option<&str>, region: &str) -> eyre::Result<Self> {
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(bucket)
+            .with_region(region)
+            .with_retry(RetryConfig { max_retries: 10, ..Default::default() });
+
+        if let Some(endpoint) = endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+
+        Ok(Self::new(Arc::new(builder.build()?)))
+    }
+
+    pub async fn get_most_recent_partition_block(&self) -> eyre::Result<u64> {
+        self.get_blockrange_list()
+            .await?
+            .into_iter()
+            .map(|range| range.end_block)
+            .max()
+            .ok_or_else(|| eyre!("no partitions found in the bucket"))
+    }
+
+    /// The ranges of all partitions in the bucket, without their checksums
+    pub async fn get_blockrange_list(&self) -> eyre::Result<Vec<BlockRangeList>> {
+        let mut ranges = self
+            .store
+            .list(None)
+            .map_ok(|object| {
+                object
+                    .location
+                    .filename()
+                    .and_then(BlockRangeList::from_tarball_name)
+            })
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        ranges.sort_by_key(|range| range.start_block);
+
+        Ok(ranges)
+    }
+
+    /// The published checksum of a partition, if there is one
+    async fn get_checksum(&self, directory_name: &str) -> eyre::Result<Option<String>> {
+        let location = ObjectPath::from(format!("{directory_name}-{CHECKSUM_PATH}"));
+        let checksum = match self.store.get(&location).await {
+            Ok(object) => object.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Some(String::from_utf8(checksum.to_vec())?.trim().to_string()))
+    }
+
+    async fn upload_full_range_tables(&self, partition_folder: &Path) -> eyre::Result<()> {
+        let directory = get_dir_content(partition_folder)?
+            .directories
+            .into_iter()
+            .find(|path| path.ends_with(FULL_RANGE_TABLES))
+            .ok_or_else(|| eyre!("no full range tables found in {:?}", partition_folder))?;
+
+        self.tar_ball_dir(&PathBuf::from(directory), None).await
+    }
+
+    /// Tarballs the directory, under `new_name` if set, and uploads it along
+    /// with its byte count & checksum
+    pub async fn tar_ball_dir(&self, directory: &Path, new_name: Option<&str>) -> eyre::Result<()> {
+        let directory = directory.to_path_buf();
+        let new_name = new_name.map(str::to_string);
+        let staging_dir = self.staging_dir.clone();
+
+        let directory_name = tokio::task::spawn_blocking(move || {
+            Self::stage_dir(&directory, new_name, &staging_dir)
+        })
+        .await??;
+
+        let uploaded = self.upload_staged(&directory_name).await;
+        // the staged copies are as large as the partition, so they are removed
+        // whether or not the upload succeeded
+        self.remove_staged(&directory_name);
+
+        uploaded
+    }
+
+    async fn upload_staged(&self, directory_name: &str) -> eyre::Result<()> {
+        for file in Self::staged_files(directory_name) {
+            let path = self.staging_dir.join(&file);
+            let location = ObjectPath::from(file);
+            self.upload_with_retry(&path, &location).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The tarball, byte count & checksum written for a staged directory
+    fn staged_files(directory_name: &str) -> [String; 3] {
+        [
+            format!("{directory_name}.tar.gz"),
+            format!("{directory_name}-{SIZE_PATH}"),
+            format!("{directory_name}-{CHECKSUM_PATH}"),
+        ]
+    }
+
+    fn remove_staged(&self, directory_name: &str) {
+        let staged = self.staging_dir.join(directory_name);
+        if let Err(e) = std::fs::remove_dir_all(&staged) {
+            tracing::warn!(?staged, "failed to remove the staged directory - {:?}", e);
+        }
+
+        for file in Self::staged_files(directory_name) {
+            let path = self.staging_dir.join(file);
+            if let Err(e) = std::fs::remove_file(&path) {
+                tracing::warn!(?path, "failed to remove the staged file - {:?}", e);
+            }
+        }
+    }
+
+    /// Copies the directory to the staging dir and writes its tarball, byte
+    /// count & checksum next to it. Returns the name of the staged directory
+    fn stage_dir(
+        directory: &Path,
+        new_name: Option<String>,
+        staging_dir: &Path,
+    ) -> eyre::Result<String> {
+        let mut directory_name = directory
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| eyre!("invalid directory {:?}", directory))?
+            .to_string();
+
+        tracing::info!(?directory, ?directory_name);
+
+        // move to the staging dir for zipping and zip
+        let copy = CopyOptions::new().overwrite(true);
+
+        let staged = staging_dir.join(&directory_name);
+        fs_extra::dir::create_all(&staged, true)?;
+        tracing::info!(from=?directory, to=?staged, "copying to staging location");
+
+        // copy the data to the staging dir
+        fs_extra::dir::copy(directory, staging_dir, &copy)?;
+
+        // if we have a name change request,
+        if let Some(new_directory_name) = new_name {
+            let _ = std::fs::remove_dir_all(staging_dir.join(&new_directory_name));
+            std::fs::rename(&staged, staging_dir.join(&new_directory_name))?;
+            directory_name = new_directory_name;
+        }
+        let staged = staging_dir.join(&directory_name);
+
+        // checksum the copy, so that the partition can be verified once it's unpacked
+        let checksum = partition_checksum(&staged)?;
+        let mut file = File::create(staging_dir.join(format!("{directory_name}-{CHECKSUM_PATH}")))?;
+        write!(&mut file, "{checksum}")?;
+
+        let tarball = staging_dir.join(format!("{directory_name}.tar.gz"));
+        let mut builder =
+            tar::Builder::new(GzEncoder::new(File::create(&tarball)?, Compression::default()));
+        builder.append_dir_all(&directory_name, &staged)?;
+        builder.into_inner()?.finish()?;
+
+        // get the tarball file size and write that
+        let file_size = filesize::file_real_size(&tarball)?;
+
+        let mut file = File::create(staging_dir.join(format!("{directory_name}-{SIZE_PATH}")))?;
+        write!(&mut file, "{}", file_size)?;
+
+        Ok(directory_name)
+    }
+
+    async fn upload_with_retry(&self, path: &Path, location: &ObjectPath) -> eyre::Result<()> {
+        let retry_strategy = ExponentialBuilder::default()
+            .with_max_times(UPLOAD_RETRIES)
+            .with_min_delay(Duration::from_secs(1))
+            .with_max_delay(Duration::from_secs(60));
+
+        (|| async { self.upload_file(path, location).await })
+            .retry(&retry_strategy)
+            .notify(
+                |e, after| tracing::warn!(%location, ?after, "upload failed, retrying - {:?}", e),
+            )
+            .await
+    }
+
+    /// Uploads the file in parts, so that large tarballs are never held in
+    /// memory. The upload is aborted on failure to not leave dangling parts
+    /// in the bucket
+    async fn upload_file(&self, path: &Path, location: &ObjectPath) -> eyre::Result<()> {
+        tracing::info!(?path, %location, "uploading");
+        let (id, mut writer) = self.store.put_multipart(location).await?;
+
+        let upload = async {
+            let mut file = tokio::fs::File::open(path).await?;
+            tokio::io::copy(&mut file, &mut writer).await?;
+            writer.shutdown().await
+        };
+
+        if let Err(e) = upload.await {
+            if let Err(abort_err) = self.store.abort_multipart(location, &id).await {
+                tracing::warn!(%location, "failed to abort upload - {:?}", abort_err);
+            }
+            return Err(e.into())
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites the manifest of the available ranges from the partitions in
+    /// the bucket
+    async fn update_block_range_file(&self) -> eyre::Result<()> {
+        let mut ranges = self.get_blockrange_list().await?;
+        for range in &mut ranges {
+            range.checksum = self
+                .get_checksum(&format!(
+                    "{PARTITION_FILE_NAME}-{}-{}",
+                    range.start_block, range.end_block
+                ))
+                .await?;
+        }
+
+        let manifest = serde_json::to_vec(&ranges)?;
+        self.store
+            .put(&ObjectPath::from(RANGES_AVAILABLE), Bytes::from(manifest))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn tar_ball_and_upload_files(
+        &self,
+        partition_folder: PathBuf,
+        start_block: u64,
+    ) -> eyre::Result<()> {
+        tracing::info!(?partition_folder);
+        self.upload_full_range_tables(&partition_folder).await?;
+
+        let partitions = get_dir_content(&partition_folder)?
+            .directories
+            .into_iter()
+            .map(PathBuf::from)
+            // ensure partition is in range
+            .filter(|directory| {
+                directory
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(BlockRangeList::from_partition_name)
+                    .is_some_and(|range| range.start_block >= start_block)
+            })
+            .collect::<Vec<_>>();
+
+        futures::stream::iter(partitions)
+            .map(|directory| async move {
+                tracing::info!(?directory, "tar balling directory");
+                self.tar_ball_dir(&directory, None).await
+            })
+            .buffer_unordered(5)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        // upload ranges for downloader
+        self.update_block_range_file().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BlockRangeList {
+    pub start_block: u64,
+    pub end_block:   u64,
+    /// [`partition_checksum`] of the partition. Missing for partitions
+    /// published before checksums were
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum:    Option<String>,
+}
+
+impl BlockRangeList {
+    /// Parses the range of a `brontes-db-partition-{start}-{end}` directory
+    pub fn from_partition_name(name: &str) -> Option<Self> {
+        let (start_block, end_block) = name
+            .strip_prefix(PARTITION_FILE_NAME)?
+            .strip_prefix('-')?
+            .split_once('-')?;
+
+        Some(Self {
+            start_block: u64::from_str(start_block).ok()?,
+            end_block:   u64::from_str(end_block).ok()?,
+            checksum:    None,
+        })
+    }
+
+    fn from_tarball_name(name: &str) -> Option<Self> {
+        Self::from_partition_name(name.strip_suffix(".tar.gz")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_partitions() {
+        let partition_folder = std::env::temp_dir().join("brontes-publish-test");
+        let staging_dir = std::env::temp_dir().join("brontes-publish-test-staging");
+        let _ = std::fs::remove_dir_all(&partition_folder);
+        let _ = std::fs::remove_dir_all(&staging_dir);
+        std::fs::create_dir_all(&staging_dir).unwrap();
+
+        let partitions = [
+            format!("{PARTITION_FILE_NAME}-1000-1999"),
+            format!("{PARTITION_FILE_NAME}-2000-2999"),
+            FULL_RANGE_TABLES.to_string(),
+        ];
+        for partition in &partitions {
+            let dir = partition_folder.join(partition);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("mdbx.dat"), partition.as_bytes()).unwrap();
+        }
+
+        // stand-in for the bucket
+        let store = Arc::new(InMemory::new());
+        let publisher =
+            SnapshotPublisher { store: store.clone(), staging_dir: staging_dir.clone() };
+        publisher
+            .tar_ball_and_upload_files(partition_folder.clone(), 2000)
+            .await
+            .unwrap();
+
+        // partitions before the start block aren't republished
+        let ranges = publisher.get_blockrange_list().await.unwrap();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(publisher.get_most_recent_partition_block().await.unwrap(), 2999);
+
+        publisher
+            .tar_ball_and_upload_files(partition_folder.clone(), 0)
+            .await
+            .unwrap();
+
+        let manifest = store
+            .get(&ObjectPath::from(RANGES_AVAILABLE))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        let manifest: Vec<BlockRangeList> = serde_json::from_slice(&manifest).unwrap();
+
+        assert_eq!(
+            manifest
+                .iter()
+                .map(|range| (range.start_block, range.end_block))
+                .collect::<Vec<_>>(),
+            vec![(1000, 1999), (2000, 2999)]
+        );
+        for range in &manifest {
+            let partition = partition_folder
+                .join(format!("{PARTITION_FILE_NAME}-{}-{}", range.start_block, range.end_block));
+            assert_eq!(range.checksum, Some(partition_checksum(&partition).unwrap()));
+        }
+
+        let full_range_tables = store
+            .head(&ObjectPath::from(format!("{FULL_RANGE_TABLES}.tar.gz")))
+            .await;
+        assert!(full_range_tables.is_ok());
+
+        // nothing is left behind in the staging dir once uploaded
+        assert_eq!(std::fs::read_dir(&staging_dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&partition_folder).unwrap();
+        std::fs::remove_dir_all(&staging_dir).unwrap();
+    }
+}
@@ -1,4 +1,8 @@
-use std::{ops::RangeInclusive, path::Path, sync::Arc};
+use std::{
+    ops::{Bound, RangeBounds, RangeInclusive},
+    path::Path,
+    sync::Arc,
+};
 
 use alloy_primitives::Address;
 use brontes_metrics::db_reads::LibmdbxMetrics;
@@ -9,7 +13,8 @@ use brontes_types::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
         builder::BuilderInfo,
-        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        builder_auction::BuilderAuction,
+        cex::{quotes::CexPriceMap, trades::CexTradeMap, FeeLookup},
         dex::{make_filter_key_range, DexPrices, DexQuotes},
         initialized_state::{
             InitializedStateMeta, CEX_QUOTES_FLAG, CEX_TRADES_FLAG, DATA_NOT_PRESENT_NOT_AVAILABLE,
@@ -18,8 +23,10 @@ use brontes_types::{
         metadata::{BlockMetadata, BlockMetadataInner, Metadata},
         mev_block::MevBlockWithClassified,
         searcher::SearcherInfo,
+        searcher_inventory::SearcherInventory,
         token_info::{TokenInfo, TokenInfoWithAddress},
-        traits::{DBWriter, LibmdbxReader},
+        traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -92,6 +99,25 @@ pub trait LibmdbxInit: LibmdbxReader + DBWriter {
     fn get_db_range(&self) -> eyre::Result<(u64, u64)>;
 }
 
+/// Raw reads of the libmdbx tables, for the consumers that need the tables as
+/// they are stored such as the parquet export
+pub trait LibmdbxTableReader: LibmdbxReader {
+    /// Reads all entries of a table with a key in `range`
+    fn read_table_range<T>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> eyre::Result<Vec<(T::Key, T::DecompressedValue)>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>;
+
+    /// The first & last key of a table, if it isn't empty
+    fn table_key_bounds<T>(&self) -> eyre::Result<Option<(T::Key, T::Key)>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>;
+}
+
 #[derive(Clone)]
 pub struct LibmdbxReadWriter {
     pub db:  Arc<Libmdbx>,
@@ -142,6 +168,27 @@ impl LibmdbxReadWriter {
     }
 }
 
+impl LibmdbxTableReader for LibmdbxReadWriter {
+    fn read_table_range<T>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> eyre::Result<Vec<(T::Key, T::DecompressedValue)>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.db.read_table_range::<T>(range)
+    }
+
+    fn table_key_bounds<T>(&self) -> eyre::Result<Option<(T::Key, T::Key)>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.db.table_key_bounds::<T>()
+    }
+}
+
 impl LibmdbxInit for LibmdbxReadWriter {
     /// Initializes a table for a given range of blocks
     async fn initialize_table<T: TracingProvider, CH: ClickhouseHandle>(
@@ -508,6 +555,11 @@ impl LibmdbxReader for LibmdbxReadWriter {
         })
     }
 
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>> {
+        self.db
+            .view_db(|tx| tx.get::<BlockInfo>(block_num).map_err(ErrReport::from))
+    }
+
     #[brontes_macros::metrics_call(ptr=metrics,scope, db_read, "try_fetch_token_info")]
     fn try_fetch_token_info(&self, og_address: Address) -> eyre::Result<TokenInfoWithAddress> {
         let address = if og_address == ETH_ADDRESS { WETH_ADDRESS } else { og_address };
@@ -695,7 +747,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
     fn protocols_created_before(
         &self,
         block_num: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+    ) -> eyre::Result<FastHashMap<(Address, Protocol), Vec<Pair>>> {
         self.db.view_db(|tx| {
         let mut cursor = tx.cursor_read::<PoolCreationBlocks>()?;
         let mut map = FastHashMap::default();
@@ -707,10 +759,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
                     continue;
                 };
 
-                map.insert(
-                    (addr, protocol_info.protocol),
-                    Pair(protocol_info.token0, protocol_info.token1),
-                );
+                map.insert((addr, protocol_info.protocol), protocol_info.pricing_pairs(addr));
             }
         }
 
@@ -724,7 +773,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
         &self,
         start_block: u64,
         end_block: u64,
-    ) -> eyre::Result<FastHashMap<u64, Vec<(Address, Protocol, Pair)>>> {
+    ) -> eyre::Result<ProtocolCreatedRange> {
         self.db.view_db(|tx| {
         let mut cursor = tx.cursor_read::<PoolCreationBlocks>()?;
         let mut map = FastHashMap::default();
@@ -739,7 +788,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
                 map.entry(block).or_insert(vec![]).push((
                     addr,
                     protocol_info.protocol,
-                    Pair(protocol_info.token0, protocol_info.token1),
+                    protocol_info.pricing_pairs(addr),
                 ));
             }
         }
@@ -831,15 +880,11 @@ impl LibmdbxReader for LibmdbxReadWriter {
     }
 
     #[instrument(level = "error", skip_all)]
-    fn try_fetch_mev_blocks(
-        &self,
-        start_block: Option<u64>,
-        end_block: u64,
-    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+    fn fetch_all_searcher_inventories(&self) -> eyre::Result<Vec<(Address, SearcherInventory)>> {
         self.db.export_db(
-            start_block,
+            None,
             |start_key, tx| {
-                let mut cur = tx.cursor_read::<MevBlocks>()?;
+                let mut cur = tx.cursor_read::<SearcherInventories>()?;
                 if let Some(key) = start_key {
                     let _ = cur.seek(key);
                 } else {
@@ -849,14 +894,54 @@ impl LibmdbxReader for LibmdbxReadWriter {
                 }
                 Ok(cur)
             },
-            |cursor| {
-                Ok(cursor
-                    .next()
-                    .map(|inner| inner.filter(|f| f.0 <= end_block).map(|i| i.1))?)
-            },
+            |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
         )
     }
 
+    #[instrument(level = "error", skip_all)]
+    fn try_fetch_builder_auction(&self, block_num: u64) -> eyre::Result<Option<BuilderAuction>> {
+        self.db.view_db(|tx| {
+            tx.get::<BuilderAuctions>(block_num)
+                .map_err(ErrReport::from)
+        })
+    }
+
+    fn try_fetch_v4_pool_key(&self, pool: Address) -> eyre::Result<Option<UniswapV4PoolKey>> {
+        if let Some(key) = self.cache.v4_pool_key(true, |handle| handle.get(&pool)) {
+            return Ok(key)
+        }
+
+        let key = self
+            .db
+            .view_db(|tx| tx.get::<UniswapV4PoolKeys>(pool).map_err(ErrReport::from))?;
+        self.cache.v4_pool_key(false, |handle| {
+            handle.get_with(pool, || key);
+        });
+
+        Ok(key)
+    }
+
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        self.db
+            .view_db(|tx| tx.get::<MevBlocks>(block_num).map_err(ErrReport::from))
+    }
+
+    #[instrument(level = "error", skip_all)]
+    fn try_fetch_mev_blocks(
+        &self,
+        start_block: Option<u64>,
+        end_block: u64,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        let start = start_block.map_or(Bound::Unbounded, Bound::Included);
+
+        Ok(self
+            .db
+            .read_table_range::<MevBlocks>((start, Bound::Included(end_block)))?
+            .into_iter()
+            .map(|(_, mev_block)| mev_block)
+            .collect())
+    }
+
     #[instrument(level = "error", skip_all)]
     fn fetch_all_mev_blocks(
         &self,
@@ -967,6 +1052,51 @@ impl DBWriter for LibmdbxReadWriter {
         )?)
     }
 
+    async fn write_searcher_inventory(
+        &self,
+        searcher: Address,
+        inventory: SearcherInventory,
+    ) -> eyre::Result<()> {
+        Ok(self.tx.send(
+            WriterMessage::SearcherInventory { searcher, inventory: Box::new(inventory) }.stamp(),
+        )?)
+    }
+
+    async fn write_builder_auction(
+        &self,
+        block_number: u64,
+        auction: BuilderAuction,
+    ) -> eyre::Result<()> {
+        Ok(self.tx.send(
+            WriterMessage::BuilderAuction { block_number, auction: Box::new(auction) }.stamp(),
+        )?)
+    }
+
+    /// The pool key is cached before it is written, so that the pricing engine
+    /// can load the pool as soon as the block it was seen in is classified.
+    async fn write_v4_pool_key(&self, key: UniswapV4PoolKey) -> eyre::Result<()> {
+        let address = key.pool_address();
+        match self.try_fetch_v4_pool_key(address)? {
+            Some(stored) if stored.pool_id == key.pool_id => return Ok(()),
+            Some(stored) => {
+                return Err(eyre!(
+                    "v4 pool address {address:?} of pool {:?} is already used by pool {:?}",
+                    key.pool_id,
+                    stored.pool_id
+                ))
+            }
+            None => {}
+        }
+
+        self.cache.v4_pool_key(false, |handle| {
+            handle.insert(address, Some(key));
+        });
+
+        Ok(self
+            .tx
+            .send(WriterMessage::V4PoolKey { address, key: Box::new(key) }.stamp())?)
+    }
+
     async fn write_address_meta(
         &self,
         address: Address,
@@ -981,6 +1111,22 @@ impl DBWriter for LibmdbxReadWriter {
             .send(WriterMessage::AddressMeta { address, metadata: Box::new(metadata) }.stamp())?)
     }
 
+    async fn write_block_info(
+        &self,
+        block_number: u64,
+        block_info: BlockMetadataInner,
+    ) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::BlockInfo { block_number, info: Box::new(block_info) }.stamp())?)
+    }
+
+    async fn rollback_block(&self, block_number: u64) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::Rollback { block_number }.stamp())?)
+    }
+
     async fn save_mev_blocks(
         &self,
         block_number: u64,
@@ -1315,6 +1461,7 @@ pub fn determine_eth_prices(
                 &Pair(quote_asset, WETH_ADDRESS),
                 block_timestamp,
                 None,
+                FeeLookup::default(),
             )?
             .maker_taker_mid()
             .0,
@@ -12,13 +12,17 @@ use brontes_types::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
         builder::BuilderInfo,
+        builder_auction::BuilderAuction,
         dex::{make_key, DexQuoteWithIndex, DexQuotes},
-        initialized_state::{DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
+        initialized_state::{DATA_NOT_PRESENT_UNKNOWN, DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
+        metadata::BlockMetadataInner,
         mev_block::MevBlockWithClassified,
         pool_creation_block::PoolsToAddresses,
         searcher::SearcherInfo,
+        searcher_inventory::SearcherInventory,
         token_info::TokenInfo,
         traces::TxTracesInner,
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     structured_trace::TxTrace,
@@ -78,6 +82,18 @@ pub enum WriterMessage {
         searcher_contract: Address,
         searcher_info:     Box<SearcherInfo>,
     },
+    SearcherInventory {
+        searcher:  Address,
+        inventory: Box<SearcherInventory>,
+    },
+    BuilderAuction {
+        block_number: u64,
+        auction:      Box<BuilderAuction>,
+    },
+    V4PoolKey {
+        address: Address,
+        key:     Box<UniswapV4PoolKey>,
+    },
     BuilderInfo {
         builder_address: Address,
         builder_info:    Box<BuilderInfo>,
@@ -97,6 +113,13 @@ pub enum WriterMessage {
         block:  u64,
         traces: Vec<TxTrace>,
     },
+    BlockInfo {
+        block_number: u64,
+        info:         Box<BlockMetadataInner>,
+    },
+    Rollback {
+        block_number: u64,
+    },
     Init(InitTables, Arc<Notify>),
 }
 
@@ -214,7 +237,10 @@ init!(
     MevBlocks,
     SearcherEOAs,
     SearcherContracts,
-    InitializedState
+    InitializedState,
+    SearcherInventories,
+    BuilderAuctions,
+    UniswapV4PoolKeys
 );
 
 /// due to libmdbx's 1 write tx limit. it makes sense
@@ -283,6 +309,26 @@ impl LibmdbxWriter {
                 self.write_searcher_contract_info(searcher_contract, *searcher_info)?;
                 "searchercontractinfo"
             }
+            WriterMessage::SearcherInventory { searcher, inventory } => {
+                self.write_searcher_inventory(searcher, *inventory)?;
+                "searcherinventory"
+            }
+            WriterMessage::BuilderAuction { block_number, auction } => {
+                self.write_builder_auction(block_number, *auction)?;
+                "builderauction"
+            }
+            WriterMessage::V4PoolKey { address, key } => {
+                self.write_v4_pool_key(address, *key)?;
+                "v4poolkey"
+            }
+            WriterMessage::BlockInfo { block_number, info } => {
+                self.write_block_info(block_number, *info)?;
+                "blockinfo"
+            }
+            WriterMessage::Rollback { block_number } => {
+                self.rollback_block(block_number)?;
+                "rollback"
+            }
             WriterMessage::Init(init, not) => {
                 init.write_data(self.db.clone())?;
                 not.notify_one();
@@ -542,6 +588,77 @@ impl LibmdbxWriter {
         Ok(())
     }
 
+    #[instrument(target = "libmdbx_read_write::write_searcher_inventory", skip_all, level = "warn")]
+    fn write_searcher_inventory(
+        &self,
+        searcher: Address,
+        inventory: SearcherInventory,
+    ) -> eyre::Result<()> {
+        let data = SearcherInventoriesData::new(searcher, inventory);
+        self.instrumented_write::<SearcherInventories, SearcherInventoriesData>(&[data])
+            .expect("libmdbx write failure");
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_builder_auction", skip_all, level = "warn")]
+    fn write_builder_auction(
+        &self,
+        block_number: u64,
+        auction: BuilderAuction,
+    ) -> eyre::Result<()> {
+        let data = BuilderAuctionsData::new(block_number, auction);
+        self.instrumented_write::<BuilderAuctions, BuilderAuctionsData>(&[data])
+            .expect("libmdbx write failure");
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_v4_pool_key", skip_all, level = "warn")]
+    fn write_v4_pool_key(&self, address: Address, key: UniswapV4PoolKey) -> eyre::Result<()> {
+        let data = UniswapV4PoolKeysData::new(address, key);
+        self.instrumented_write::<UniswapV4PoolKeys, UniswapV4PoolKeysData>(&[data])
+            .expect("libmdbx write failure");
+        Ok(())
+    }
+
+    #[instrument(target = "libmdbx_read_write::write_block_info", skip_all, level = "warn")]
+    fn write_block_info(&self, block_number: u64, info: BlockMetadataInner) -> eyre::Result<()> {
+        let data = BlockInfoData::new(block_number, info);
+        self.instrumented_write::<BlockInfo, BlockInfoData>(&[data])
+            .expect("libmdbx write failure");
+        Ok(())
+    }
+
+    /// Deletes the data the processing of the block wrote & resets its
+    /// initialized state so that the block can be processed again. The block
+    /// info is kept, it is overwritten when the block is processed again.
+    #[instrument(target = "libmdbx_read_write::rollback_block", skip_all, level = "warn")]
+    fn rollback_block(&mut self, block_number: u64) -> eyre::Result<()> {
+        // anything still queued for the block would be written after the delete
+        self.insert_remaining();
+
+        self.db.update_db(|tx| {
+            tx.delete::<MevBlocks>(block_number, None)?;
+            tx.delete::<TxTraces>(block_number, None)?;
+
+            let mut cursor = tx.cursor_write::<DexPrice>()?;
+            let mut walker =
+                cursor.walk_range(make_key(block_number, 0)..=make_key(block_number, u16::MAX))?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+
+            if let Some(mut state) = tx.get::<InitializedState>(block_number)? {
+                state.set(DEX_PRICE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+                state.set(TRACE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+                tx.put::<InitializedState>(block_number, state)?;
+            }
+
+            Ok::<(), DatabaseError>(())
+        })??;
+
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::init_state_updating", skip_all, level = "warn")]
     fn init_state_updating(&mut self, block: u64, flag: u16) -> eyre::Result<()> {
         let tx = self.db.ro_tx()?;
@@ -685,3 +802,87 @@ impl Future for LibmdbxWriter {
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::db::{
+        initialized_state::{InitializedStateMeta, CEX_QUOTES_FLAG},
+        traits::LibmdbxReader,
+    };
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+    use crate::libmdbx::LibmdbxReadWriter;
+
+    #[test]
+    fn test_rollback_block() {
+        let path = std::env::temp_dir().join("brontes-rollback-test-db");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let db = LibmdbxReadWriter::init_db_tests(&path).unwrap();
+        let (_tx, rx) = unbounded_channel();
+        let mut writer = LibmdbxWriter::new(
+            db.db.clone(),
+            UnboundedYapperReceiver::new(rx, 1500, "rollback test".to_string()),
+            false,
+        );
+
+        let mev_blocks = (1..=2)
+            .map(|block| MevBlocksData::new(block, MevBlockWithClassified::default()))
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<MevBlocks, MevBlocksData>(&mev_blocks)
+            .unwrap();
+        let block_info = BlockInfoData::new(2, BlockMetadataInner::default());
+        db.db
+            .write_table::<BlockInfo, BlockInfoData>(&[block_info])
+            .unwrap();
+        let dex_prices = [make_key(2, 0), make_key(2, 1), make_key(3, 0)]
+            .into_iter()
+            .map(|key| DexPriceData::new(key, DexQuoteWithIndex::default()))
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<DexPrice, DexPriceData>(&dex_prices)
+            .unwrap();
+        let state = InitializedStateMeta::new(
+            DATA_PRESENT,
+            DATA_PRESENT,
+            DATA_PRESENT,
+            DATA_PRESENT,
+            DATA_PRESENT,
+        );
+        db.db
+            .write_table::<InitializedState, InitializedStateData>(&[InitializedStateData::new(
+                2, state,
+            )])
+            .unwrap();
+
+        writer.rollback_block(2).unwrap();
+
+        assert!(db.try_fetch_mev_block(1).unwrap().is_some());
+        assert!(db.try_fetch_mev_block(2).unwrap().is_none());
+        // the block info is overwritten when the block is processed again
+        assert!(db.try_fetch_block_info(2).unwrap().is_some());
+
+        let dex_keys = db
+            .db
+            .read_table_range::<DexPrice>(..)
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        assert_eq!(dex_keys, vec![make_key(3, 0)]);
+
+        let (_, state) = db
+            .db
+            .read_table_range::<InitializedState>(2..=2)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert!(!state.is_initialized(DEX_PRICE_FLAG));
+        assert!(!state.is_initialized(TRACE_FLAG));
+        assert!(state.is_initialized(CEX_QUOTES_FLAG));
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
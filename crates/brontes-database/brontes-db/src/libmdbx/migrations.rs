@@ -0,0 +1,510 @@
+//! Layout versions of the libmdbx tables.
+//!
+//! Table values are stored as rkyv archives, which can't be read back once
+//! the layout of the value changes. Each table declares the version of its
+//! layout with [`CompressedTable::VERSION`] and the version a db was written
+//! with is recorded in the [`TableVersions`] table. Opening a db that was
+//! written with a different layout errors instead of returning garbage, and
+//! `brontes db migrate` runs the [`MIGRATIONS`] that rewrite the outdated
+//! tables.
+use std::{fmt, ops::Bound};
+
+use brontes_types::db::{
+    layout_v0::{
+        MevBlockWithClassifiedV0, MevBlockWithClassifiedV0Redefined, SearcherInfoV0,
+        SearcherInfoV0Redefined,
+    },
+    mev_block::MevBlockWithClassified,
+    searcher::SearcherInfo,
+    table_version::TableVersion,
+};
+use itertools::Itertools;
+use reth_db::{table::Table, DatabaseError};
+
+use super::{
+    tables::{MevBlocks, SearcherContracts, SearcherEOAs, TableVersions, Tables},
+    types::CompressedTable,
+    Libmdbx,
+};
+
+/// Rows rewritten per write transaction
+const MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// Rewrites a table from the layout of version `from` to the layout of
+/// version `from + 1`
+pub struct Migration {
+    pub table:   Tables,
+    pub from:    u16,
+    pub migrate: fn(&Libmdbx) -> eyre::Result<()>,
+}
+
+/// Every table needs a migration for each version bump, otherwise the table
+/// has to be cleared & re-initialized
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { table: Tables::MevBlocks, from: 0, migrate: migrate_mev_blocks_v0 },
+    Migration { table: Tables::SearcherEOAs, from: 0, migrate: migrate_searcher_eoas_v0 },
+    Migration {
+        table:   Tables::SearcherContracts,
+        from:    0,
+        migrate: migrate_searcher_contracts_v0,
+    },
+];
+
+fn migrate_mev_blocks_v0(db: &Libmdbx) -> eyre::Result<()> {
+    db.rewrite_table::<MevBlocksV0, MevBlocks>(MevBlockWithClassified::from)
+}
+
+fn migrate_searcher_eoas_v0(db: &Libmdbx) -> eyre::Result<()> {
+    db.rewrite_table::<SearcherEOAsV0, SearcherEOAs>(SearcherInfo::from)
+}
+
+fn migrate_searcher_contracts_v0(db: &Libmdbx) -> eyre::Result<()> {
+    db.rewrite_table::<SearcherContractsV0, SearcherContracts>(SearcherInfo::from)
+}
+
+/// A table as it was stored at a previous version, which reads the rows of the
+/// current table in their old layout
+macro_rules! previous_layout {
+    ($name:ident, $table:ident, $value:ident, $compressed_value:ident, $version:literal) => {
+        #[doc = concat!("[`", stringify!($table), "`] at version ", stringify!($version))]
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $name;
+
+        impl Table for $name {
+            type Key = <$table as Table>::Key;
+            type Value = $compressed_value;
+
+            const NAME: &'static str = <$table as Table>::NAME;
+            // not used, the table is opened by its name
+            const TABLE: reth_db::Tables = reth_db::Tables::CanonicalHeaders;
+        }
+
+        impl CompressedTable for $name {
+            type DecompressedValue = $value;
+
+            const HTTP_ENDPOINT: Option<&'static str> = None;
+            const INIT_CHUNK_SIZE: Option<usize> = None;
+            const INIT_FLAG: Option<u16> = None;
+            const INIT_QUERY: Option<&'static str> = None;
+            const VERSION: u16 = $version;
+        }
+    };
+}
+
+previous_layout!(
+    MevBlocksV0,
+    MevBlocks,
+    MevBlockWithClassifiedV0,
+    MevBlockWithClassifiedV0Redefined,
+    0
+);
+previous_layout!(SearcherEOAsV0, SearcherEOAs, SearcherInfoV0, SearcherInfoV0Redefined, 0);
+previous_layout!(
+    SearcherContractsV0,
+    SearcherContracts,
+    SearcherInfoV0,
+    SearcherInfoV0Redefined,
+    0
+);
+
+/// The version every table declares
+pub fn declared_table_versions() -> Vec<(Tables, u16)> {
+    Tables::ALL
+        .into_iter()
+        .map(|table| (table, table.version()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionMismatch {
+    pub table:    Tables,
+    pub found:    u16,
+    pub expected: u16,
+}
+
+impl VersionMismatch {
+    /// Whether there is a migration for every version between the found and
+    /// the expected one
+    pub fn is_migratable(&self, migrations: &[Migration]) -> bool {
+        self.found < self.expected
+            && (self.found..self.expected).all(|from| {
+                migrations
+                    .iter()
+                    .any(|migration| migration.table == self.table && migration.from == from)
+            })
+    }
+}
+
+/// The command that clears the given tables so they can be re-initialized
+fn clear_command<'a>(tables: impl Iterator<Item = &'a Tables>) -> String {
+    format!("brontes db clear --tables {}", tables.map(Tables::name).join(","))
+}
+
+#[derive(Debug)]
+pub struct TableVersionError(pub Vec<VersionMismatch>);
+
+impl fmt::Display for TableVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "libmdbx tables don't match the layout of this version of brontes:")?;
+        for VersionMismatch { table, found, expected } in &self.0 {
+            writeln!(f, "    {table}: found version {found}, expected version {expected}")?;
+        }
+
+        if self.0.iter().any(|m| m.is_migratable(MIGRATIONS)) {
+            writeln!(f, "run `brontes db migrate` to upgrade the outdated tables")?;
+        }
+
+        let unmigratable = self
+            .0
+            .iter()
+            .filter(|m| m.found < m.expected && !m.is_migratable(MIGRATIONS))
+            .map(|m| &m.table)
+            .collect::<Vec<_>>();
+        if !unmigratable.is_empty() {
+            writeln!(
+                f,
+                "there is no migration for some of the outdated tables, clear them with `{}` and \
+                 re-initialize them",
+                clear_command(unmigratable.into_iter())
+            )?;
+        }
+        if self.0.iter().any(|m| m.found > m.expected) {
+            writeln!(
+                f,
+                "the db was written by a newer version of brontes, upgrade brontes to read it"
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for TableVersionError {}
+
+impl Libmdbx {
+    /// Compares the recorded version of each table to the declared one.
+    /// Empty tables have nothing to decode, so they are stamped with the
+    /// declared version.
+    pub(crate) fn table_version_mismatches(
+        &self,
+        declared: &[(Tables, u16)],
+    ) -> eyre::Result<Vec<VersionMismatch>> {
+        Ok(self.update_db(|tx| {
+            let mut mismatches = Vec::new();
+            for &(table, expected) in declared {
+                let recorded = tx
+                    .get::<TableVersions>(table.name().to_string())?
+                    .map(|version| version.0);
+
+                let found = match recorded {
+                    _ if table.entries(tx)? == 0 => expected,
+                    Some(version) => version,
+                    // written before tables were versioned
+                    None => 0,
+                };
+
+                if found != expected {
+                    mismatches.push(VersionMismatch { table, found, expected });
+                } else if recorded != Some(expected) {
+                    tx.put::<TableVersions>(table.name().to_string(), TableVersion(expected))?;
+                }
+            }
+
+            Ok::<_, DatabaseError>(mismatches)
+        })??)
+    }
+
+    /// Runs the migrations of every outdated table. Each version is recorded
+    /// as soon as it's reached, so an interrupted migration picks up where
+    /// it stopped.
+    pub(crate) fn migrate_tables(
+        &self,
+        declared: &[(Tables, u16)],
+        migrations: &[Migration],
+    ) -> eyre::Result<()> {
+        let mismatches = self.table_version_mismatches(declared)?;
+        if mismatches.is_empty() {
+            tracing::info!("all tables are up to date");
+            return Ok(())
+        }
+
+        if let Some(VersionMismatch { table, found, expected }) =
+            mismatches.iter().find(|m| m.found > m.expected)
+        {
+            eyre::bail!(
+                "{table} was written by a newer version of brontes (found version {found}, \
+                 expected version {expected}) and can't be migrated"
+            )
+        }
+
+        // checked up front so nothing is rewritten unless every table can be migrated
+        let unmigratable = mismatches
+            .iter()
+            .filter(|m| !m.is_migratable(migrations))
+            .map(|m| &m.table)
+            .collect::<Vec<_>>();
+        if !unmigratable.is_empty() {
+            eyre::bail!(
+                "there is no migration for {}, clear them with `{}` and re-initialize them",
+                unmigratable.iter().map(|table| table.name()).join(", "),
+                clear_command(unmigratable.iter().copied())
+            )
+        }
+
+        for VersionMismatch { table, found, expected } in mismatches {
+            for from in found..expected {
+                let migration = migrations
+                    .iter()
+                    .find(|migration| migration.table == table && migration.from == from)
+                    .expect("checked above");
+
+                tracing::info!(%table, from, to = from + 1, "migrating table");
+                (migration.migrate)(self)?;
+                self.update_db(|tx| {
+                    tx.put::<TableVersions>(table.name().to_string(), TableVersion(from + 1))
+                })??;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites every row of `New` from the layout of `Old`, a table with the
+    /// same name whose value is the previous layout
+    pub fn rewrite_table<Old, New>(
+        &self,
+        convert: impl Fn(Old::DecompressedValue) -> New::DecompressedValue,
+    ) -> eyre::Result<()>
+    where
+        Old: CompressedTable + Table<Key = <New as Table>::Key>,
+        Old::Value: From<Old::DecompressedValue> + Into<Old::DecompressedValue>,
+        New: CompressedTable,
+        New::Value: From<New::DecompressedValue> + Into<New::DecompressedValue>,
+    {
+        if Old::NAME != New::NAME {
+            eyre::bail!("can't rewrite {} from the layout of {}", New::NAME, Old::NAME)
+        }
+
+        let mut start = Bound::Unbounded;
+        loop {
+            let rows = self.view_db(|tx| {
+                Ok(tx
+                    .cursor_read::<Old>()?
+                    .walk_range((start.clone(), Bound::Unbounded))?
+                    .take(MIGRATION_BATCH_SIZE)
+                    .map(|row| row.map(|row| (row.0, row.1)))
+                    .collect::<Result<Vec<_>, DatabaseError>>()?)
+            })?;
+
+            let Some((last, _)) = rows.last() else { return Ok(()) };
+            start = Bound::Excluded(last.clone());
+
+            self.update_db(|tx| {
+                rows.into_iter()
+                    .try_for_each(|(key, value)| tx.put::<New>(key, convert(value)))
+            })??;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+    use brontes_types::{
+        db::{
+            initialized_state::{InitializedStateMeta, DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
+            layout_v0::{
+                BundleDataV0, BundleV0, LiquidationV0, MevBlockV0, MevCountV0, SandwichV0,
+            },
+        },
+        mev::{BundleData, BundleHeader, MevType},
+    };
+
+    use super::*;
+    use crate::libmdbx::tables::InitializedState;
+
+    fn outdated_initialized_state() -> Vec<(Tables, u16)> {
+        declared_table_versions()
+            .into_iter()
+            .map(|(table, version)| {
+                (table, if table == Tables::InitializedState { version + 1 } else { version })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_migrate_tables() {
+        let path = std::env::temp_dir().join("brontes-migration-test-db");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let db = Libmdbx::init_db(&path, None).unwrap();
+        db.update_db(|tx| {
+            tx.put::<InitializedState>(1, InitializedStateMeta::new(DATA_PRESENT, 0, 0, 0, 0))
+        })
+        .unwrap()
+        .unwrap();
+
+        // stamped on open
+        assert!(db
+            .table_version_mismatches(&declared_table_versions())
+            .unwrap()
+            .is_empty());
+
+        let declared = outdated_initialized_state();
+        let expected = declared_table_versions()
+            .into_iter()
+            .find(|(table, _)| *table == Tables::InitializedState)
+            .unwrap()
+            .1;
+        assert_eq!(
+            db.table_version_mismatches(&declared).unwrap(),
+            vec![VersionMismatch {
+                table:    Tables::InitializedState,
+                found:    expected,
+                expected: expected + 1,
+            }]
+        );
+
+        // no migration registered
+        assert!(db
+            .migrate_tables(&declared, &[])
+            .unwrap_err()
+            .to_string()
+            .contains("brontes db clear --tables InitializedState"));
+
+        let migrations = [Migration {
+            table:   Tables::InitializedState,
+            from:    expected,
+            migrate: |db| {
+                db.rewrite_table::<InitializedState, InitializedState>(|mut state| {
+                    state.set(TRACE_FLAG, DATA_PRESENT);
+                    state
+                })
+            },
+        }];
+        db.migrate_tables(&declared, &migrations).unwrap();
+
+        assert!(db.table_version_mismatches(&declared).unwrap().is_empty());
+        let state = db
+            .view_db(|tx| Ok(tx.get::<InitializedState>(1)?))
+            .unwrap()
+            .unwrap();
+        assert!(state.is_initialized(DEX_PRICE_FLAG));
+        assert!(state.is_initialized(TRACE_FLAG));
+
+        // the db is now ahead of the declared versions
+        let err = TableVersionError(
+            db.table_version_mismatches(&declared_table_versions())
+                .unwrap(),
+        );
+        assert!(err.to_string().contains("newer version of brontes"));
+
+        drop(db);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_v0_tables() {
+        let path = std::env::temp_dir().join("brontes-v0-migration-test-db");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let mev_block = MevBlockWithClassifiedV0 {
+            block: MevBlockV0 {
+                block_number: 1,
+                mev_count: MevCountV0 {
+                    bundle_count: 2,
+                    sandwich_count: Some(1),
+                    liquidation_count: Some(1),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            mev:   vec![
+                BundleV0 {
+                    header: BundleHeader {
+                        block_number: 1,
+                        mev_type: MevType::Sandwich,
+                        ..Default::default()
+                    },
+                    data:   BundleDataV0::Sandwich(SandwichV0 {
+                        block_number: 1,
+                        ..Default::default()
+                    }),
+                },
+                BundleV0 {
+                    header: BundleHeader {
+                        block_number: 1,
+                        mev_type: MevType::Liquidation,
+                        ..Default::default()
+                    },
+                    data:   BundleDataV0::Liquidation(LiquidationV0 {
+                        block_number: 1,
+                        ..Default::default()
+                    }),
+                },
+            ],
+        };
+        let searcher = SearcherInfoV0 {
+            name: Some("searcher".to_string()),
+            mev_count: MevCountV0 {
+                bundle_count: 3,
+                sandwich_count: Some(3),
+                ..Default::default()
+            },
+            config_labels: vec![MevType::Sandwich],
+            ..Default::default()
+        };
+
+        // rows written before the tables were versioned, so without a version record
+        let db = Libmdbx::init_db_unchecked(&path, None).unwrap();
+        db.update_db(|tx| {
+            tx.put::<MevBlocksV0>(1, mev_block.clone())?;
+            tx.put::<SearcherEOAsV0>(Address::ZERO, searcher)
+        })
+        .unwrap()
+        .unwrap();
+        drop(db);
+
+        let err = Libmdbx::init_db(&path, None).err().unwrap();
+        assert_eq!(
+            err.downcast_ref::<TableVersionError>().unwrap().0,
+            vec![
+                VersionMismatch { table: Tables::MevBlocks, found: 0, expected: 1 },
+                VersionMismatch { table: Tables::SearcherEOAs, found: 0, expected: 1 },
+            ]
+        );
+        assert!(err.to_string().contains("run `brontes db migrate`"));
+
+        drop(Libmdbx::migrate_db(&path, None).unwrap());
+        let db = Libmdbx::init_db(&path, None).unwrap();
+
+        let migrated = db
+            .view_db(|tx| Ok(tx.get::<MevBlocks>(1)?))
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated, MevBlockWithClassified::from(mev_block));
+        assert_eq!(migrated.block.mev_count.sandwich_count, Some(1));
+        assert_eq!(migrated.block.builder_auction, None);
+        let BundleData::Sandwich(sandwich) = &migrated.mev[0].data else {
+            panic!("expected a sandwich")
+        };
+        assert!(!sandwich.is_multi_block);
+        assert!(sandwich.victim_loss.is_empty());
+        let BundleData::Liquidation(liquidation) = &migrated.mev[1].data else {
+            panic!("expected a liquidation")
+        };
+        assert_eq!(liquidation.blocks_to_liquidate, None);
+
+        let migrated = db
+            .view_db(|tx| Ok(tx.get::<SearcherEOAs>(Address::ZERO)?))
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated.name.as_deref(), Some("searcher"));
+        assert_eq!(migrated.mev_count.sandwich_count, Some(3));
+        assert_eq!(migrated.mev_count.gen_frontrun_count, None);
+        assert_eq!(migrated.config_labels, vec![MevType::Sandwich]);
+
+        drop(db);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
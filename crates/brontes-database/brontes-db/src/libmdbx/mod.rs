@@ -3,6 +3,7 @@
 
 use std::{
     ffi::c_int,
+    ops::RangeBounds,
     path::Path,
     time::{Duration, Instant},
 };
@@ -18,14 +19,16 @@ pub mod libmdbx_writer;
 
 pub mod initialize;
 mod libmdbx_read_write;
+pub mod migrations;
 use brontes_libmdbx::{RO, RW};
 use env::{DatabaseArguments, DatabaseEnv, DatabaseEnvKind};
 use eyre::Context;
 use implementation::compressed_wrappers::tx::CompressedLibmdbxTx;
 use initialize::LibmdbxInitializer;
 pub use libmdbx_read_write::{
-    determine_eth_prices, LibmdbxInit, LibmdbxReadWriter, StateToInitialize,
+    determine_eth_prices, LibmdbxInit, LibmdbxReadWriter, LibmdbxTableReader, StateToInitialize,
 };
+use migrations::{declared_table_versions, TableVersionError, MIGRATIONS};
 use reth_db::{
     is_database_empty,
     models::client_version::ClientVersion,
@@ -67,8 +70,34 @@ pub(crate) fn mdbx_result(err_code: c_int) -> eyre::Result<bool> {
 
 impl Libmdbx {
     /// Opens up an existing database or creates a new one at the specified
-    /// path. Creates tables if necessary. Opens in read/write mode.
+    /// path. Creates tables if necessary. Opens in read/write mode. Errors if
+    /// the tables were written with a different layout than they declare.
     pub fn init_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Result<Self> {
+        let this = Self::init_db_unchecked(path, log_level)?;
+
+        let mismatches = this.table_version_mismatches(&declared_table_versions())?;
+        if !mismatches.is_empty() {
+            return Err(TableVersionError(mismatches).into())
+        }
+
+        Ok(this)
+    }
+
+    /// Opens up the database at the specified path and migrates its tables
+    /// to the layout they declare
+    pub fn migrate_db<P: AsRef<Path>>(path: P, log_level: Option<LogLevel>) -> eyre::Result<Self> {
+        let this = Self::init_db_unchecked(path, log_level)?;
+        this.migrate_tables(&declared_table_versions(), MIGRATIONS)?;
+
+        Ok(this)
+    }
+
+    /// Opens up the database without checking the table versions. Only for
+    /// tools that don't decode the tables, e.g. clearing an outdated table
+    pub fn init_db_unchecked<P: AsRef<Path>>(
+        path: P,
+        log_level: Option<LogLevel>,
+    ) -> eyre::Result<Self> {
         let rpath = path.as_ref();
         if is_database_empty(rpath) {
             std::fs::create_dir_all(rpath).wrap_err_with(|| {
@@ -204,6 +233,39 @@ impl Libmdbx {
         }
     }
 
+    /// Reads all entries of a table with a key in `range`
+    pub fn read_table_range<T>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> eyre::Result<Vec<(T::Key, T::DecompressedValue)>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.view_db(|tx| {
+            Ok(tx
+                .cursor_read::<T>()?
+                .walk_range(range)?
+                .map(|row| row.map(|row| (row.0, row.1)))
+                .collect::<Result<Vec<_>, DatabaseError>>()?)
+        })
+    }
+
+    /// The first & last key of a table, if it isn't empty
+    pub fn table_key_bounds<T>(&self) -> eyre::Result<Option<(T::Key, T::Key)>>
+    where
+        T: CompressedTable,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+    {
+        self.view_db(|tx| {
+            let mut cursor = tx.cursor_read::<T>()?;
+            let Some((first, _)) = cursor.first()? else { return Ok(None) };
+            let Some((last, _)) = cursor.last()? else { return Ok(None) };
+
+            Ok(Some((first, last)))
+        })
+    }
+
     pub fn view_db<F, R>(&self, f: F) -> eyre::Result<R>
     where
         F: FnOnce(&CompressedLibmdbxTx<RO>) -> eyre::Result<R>,
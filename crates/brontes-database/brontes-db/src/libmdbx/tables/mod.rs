@@ -6,11 +6,13 @@ use std::{
     sync::Arc,
 };
 
+use brontes_libmdbx::TransactionKind;
 use brontes_types::{
     db::{
         address_metadata::{AddressMetadata, AddressMetadataRedefined},
         address_to_protocol_info::{ProtocolInfo, ProtocolInfoRedefined},
         builder::{BuilderInfo, BuilderInfoRedefined},
+        builder_auction::{BuilderAuction, BuilderAuctionRedefined},
         cex::{
             quotes::{CexPriceMap, CexPriceMapRedefined},
             trades::{CexTradeMap, CexTradeMapRedefined},
@@ -25,9 +27,12 @@ use brontes_types::{
         mev_block::{MevBlockWithClassified, MevBlockWithClassifiedRedefined},
         pool_creation_block::{PoolsToAddresses, PoolsToAddressesRedefined},
         searcher::{SearcherInfo, SearcherInfoRedefined},
+        searcher_inventory::{SearcherInventory, SearcherInventoryRedefined},
+        table_version::TableVersion,
         token_info::TokenInfo,
         traces::{TxTracesInner, TxTracesInnerRedefined},
         traits::LibmdbxReader,
+        uniswap_v4::{UniswapV4PoolKey, UniswapV4PoolKeyRedefined},
     },
     serde_utils::*,
     traits::TracingProvider,
@@ -40,7 +45,9 @@ use serde_with::serde_as;
 
 use crate::{
     clickhouse::ClickhouseHandle,
-    libmdbx::{types::ReturnKV, utils::protocol_info, LibmdbxData, LibmdbxReadWriter},
+    libmdbx::{
+        types::ReturnKV, utils::protocol_info, LibmdbxData, LibmdbxReadWriter, LibmdbxTableReader,
+    },
     parquet::ParquetExporter,
 };
 mod const_sql;
@@ -51,14 +58,14 @@ use alloy_primitives::Address;
 // use brontes_types::db::initialized_state::CEX_TRADES_FLAG;
 use const_sql::*;
 use paste::paste;
-use reth_db::TableType;
+use reth_db::{DatabaseError, TableType};
 
 use super::{
-    initialize::LibmdbxInitializer, libmdbx_writer::WriterMessage, types::IntoTableKey,
-    CompressedTable,
+    initialize::LibmdbxInitializer, libmdbx_writer::WriterMessage, tx::CompressedLibmdbxTx,
+    types::IntoTableKey, CompressedTable,
 };
 
-pub const NUM_TABLES: usize = 14;
+pub const NUM_TABLES: usize = 18;
 
 macro_rules! tables {
     ($($table:ident),*) => {
@@ -85,6 +92,25 @@ macro_rules! tables {
                 }
             }
 
+            /// The layout version the given table declares
+            pub const fn version(&self) -> u16 {
+                match self {
+                    $(Tables::$table => {
+                        <$table as CompressedTable>::VERSION
+                    },)*
+                }
+            }
+
+            /// The number of entries in the given table
+            pub fn entries<K: TransactionKind>(
+                &self,
+                tx: &CompressedLibmdbxTx<K>,
+            ) -> Result<usize, DatabaseError> {
+                match self {
+                    $(Tables::$table => tx.entries::<$table>(),)*
+                }
+            }
+
             /// The type of the given table in database
             pub const fn table_type(&self) -> TableType {
                 match self {
@@ -276,7 +302,13 @@ impl Tables {
                     )
                     .await
             }
-            Tables::SearcherEOAs | Tables::SearcherContracts | Tables::InitializedState => Ok(()),
+            Tables::SearcherEOAs
+            | Tables::SearcherContracts
+            | Tables::SearcherInventories
+            | Tables::BuilderAuctions
+            | Tables::UniswapV4PoolKeys
+            | Tables::InitializedState
+            | Tables::TableVersions => Ok(()),
             _ => unimplemented!("'initialize_table' not implemented for {:?}", self),
         }
     }
@@ -366,14 +398,27 @@ impl Tables {
         exporter: Arc<ParquetExporter<DB>>,
     ) -> eyre::Result<()>
     where
-        DB: LibmdbxReader,
+        DB: LibmdbxReader + LibmdbxTableReader,
     {
         match self {
             Self::AddressMeta => exporter.export_address_metadata().await,
             Self::MevBlocks => exporter.export_mev_blocks().await,
             Self::SearcherContracts | Self::SearcherEOAs => exporter.export_searcher_info().await,
             Self::Builder => exporter.export_builder_info().await,
-            _ => unreachable!("Parquet export not yet supported for this table"),
+            Self::SearcherInventories => exporter.export_searcher_inventories().await,
+            Self::DexPrice => exporter.export_dex_prices().await,
+            Self::CexPrice => exporter.export_cex_prices().await,
+            Self::CexTrades => exporter.export_cex_trades().await,
+            Self::TxTraces => exporter.export_tx_traces().await,
+            Self::TokenDecimals => exporter.export_token_decimals().await,
+            Self::AddressToProtocolInfo => exporter.export_protocol_info().await,
+            Self::PoolCreationBlocks => exporter.export_pool_creation_blocks().await,
+            Self::BlockInfo => exporter.export_block_info().await,
+            Self::BuilderAuctions => exporter.export_builder_auctions().await,
+            Self::UniswapV4PoolKeys => exporter.export_v4_pool_keys().await,
+            Self::InitializedState | Self::TableVersions => {
+                Err(eyre::eyre!("{} can't be exported to parquet", self.name()))
+            }
         }
     }
 
@@ -537,9 +582,24 @@ tables!(
     SearcherEOAs,
     SearcherContracts,
     InitializedState,
-    CexTrades
+    CexTrades,
+    SearcherInventories,
+    BuilderAuctions,
+    UniswapV4PoolKeys,
+    TableVersions
 );
 
+/// Tables that don't declare a version are still in the layout they had
+/// when versioning was introduced
+macro_rules! table_version {
+    () => {
+        0
+    };
+    ($version:literal) => {
+        $version
+    };
+}
+
 /// Must be in this order when defining
 /// Table {
 ///     Data {
@@ -685,7 +745,8 @@ macro_rules! compressed_table {
     };
     ($(#[$attrs:meta])* $table_name:ident, $c_val:ident, $decompressed_value:ident, $key:ident
      { $($acc:tt)* } Init { init_size: $init_chunk_size:expr, init_method: Clickhouse,
-                              http_endpoint: $http_endpoint:expr, init_flag: $init_flag:expr },
+                              http_endpoint: $http_endpoint:expr, init_flag: $init_flag:expr
+                              $(, version: $version:literal)? },
 
      $($tail:tt)*) => {
         compressed_table!($(#[$attrs])* $table_name, $c_val, $decompressed_value, $key {
@@ -696,12 +757,13 @@ macro_rules! compressed_table {
             const INIT_QUERY: Option<&'static str> = Some(paste! {[<$table_name InitQuery>]});
             const HTTP_ENDPOINT: Option<&'static str> = $http_endpoint;
             const INIT_FLAG: Option<u16> = $init_flag;
+            const VERSION: u16 = table_version!($($version)?);
         }
         } $($tail)*);
     };
     ($(#[$attrs:meta])* $table_name:ident, $c_val:ident, $decompressed_value:ident, $key:ident
      { $($acc:tt)* } Init { init_size: $init_chunk_size:expr, init_method: Other,
-     http_endpoint: $http_endpoint:expr  },
+     http_endpoint: $http_endpoint:expr $(, version: $version:literal)? },
      $($tail:tt)*) => {
         compressed_table!($(#[$attrs])* $table_name, $c_val, $decompressed_value, $key {
             $($acc)*
@@ -711,6 +773,7 @@ macro_rules! compressed_table {
             const INIT_QUERY: Option<&'static str> = None;
             const HTTP_ENDPOINT: Option<&'static str> = $http_endpoint;
             const INIT_FLAG: Option<u16> = None;
+            const VERSION: u16 = table_version!($($version)?);
         }
         } $($tail)*);
     };
@@ -884,7 +947,8 @@ compressed_table!(
             init_size: None,
             init_method: Clickhouse,
             http_endpoint: None,
-            init_flag:None
+            init_flag:None,
+            version: 1
         },
         CLI {
             can_insert: False
@@ -904,7 +968,64 @@ compressed_table!(
             init_size: None,
             init_method: Clickhouse,
             http_endpoint: None,
-            init_flag:None
+            init_flag:None,
+            version: 1
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table SearcherInventories {
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: SearcherInventory,
+            compressed_value: SearcherInventoryRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table BuilderAuctions {
+        Data {
+            key: u64,
+            value: BuilderAuction,
+            compressed_value: BuilderAuctionRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table UniswapV4PoolKeys {
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: UniswapV4PoolKey,
+            compressed_value: UniswapV4PoolKeyRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
         },
         CLI {
             can_insert: False
@@ -985,7 +1106,8 @@ compressed_table!(
         Init {
             init_size: None,
             init_method: Other,
-            http_endpoint: None
+            http_endpoint: None,
+            version: 1
         },
         CLI {
             can_insert: False
@@ -1031,3 +1153,20 @@ compressed_table!(
         }
     }
 );
+
+compressed_table!(
+    Table TableVersions {
+        Data {
+            key: String,
+            value: TableVersion
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
@@ -55,4 +55,7 @@ where
     const INIT_QUERY: Option<&'static str>;
     const HTTP_ENDPOINT: Option<&'static str>;
     const INIT_FLAG: Option<u16>;
+    /// Version of the layout the values are stored in. Has to be bumped,
+    /// along with a migration, whenever the layout of the value changes
+    const VERSION: u16;
 }
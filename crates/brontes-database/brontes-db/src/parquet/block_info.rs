@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::UInt64Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::metadata::BlockMetadataInner;
+use itertools::Itertools;
+
+use super::utils::{
+    build_string_array, build_uint64_array, get_list_string_array_from_owned,
+    get_string_array_from_owned, u128_to_binary_array,
+};
+
+pub fn block_info_to_record_batch(
+    blocks: Vec<(u64, BlockMetadataInner)>,
+) -> Result<RecordBatch, ArrowError> {
+    let block_number_array = build_uint64_array(
+        blocks
+            .iter()
+            .map(|(block_number, _)| *block_number)
+            .collect(),
+    );
+    let block_hash_array = build_string_array(
+        blocks
+            .iter()
+            .map(|(_, meta)| format!("{:#066x}", meta.block_hash))
+            .collect(),
+    );
+    let block_timestamp_array = build_uint64_array(
+        blocks
+            .iter()
+            .map(|(_, meta)| meta.block_timestamp)
+            .collect(),
+    );
+    let relay_timestamp_array = UInt64Array::from(
+        blocks
+            .iter()
+            .map(|(_, meta)| meta.relay_timestamp)
+            .collect_vec(),
+    );
+    let p2p_timestamp_array = UInt64Array::from(
+        blocks
+            .iter()
+            .map(|(_, meta)| meta.p2p_timestamp)
+            .collect_vec(),
+    );
+    let proposer_fee_recipient_array = get_string_array_from_owned(
+        blocks
+            .iter()
+            .map(|(_, meta)| {
+                meta.proposer_fee_recipient
+                    .map(|address| address.to_string())
+            })
+            .collect(),
+    );
+    let proposer_mev_reward_array = u128_to_binary_array(
+        blocks
+            .iter()
+            .map(|(_, meta)| meta.proposer_mev_reward.unwrap_or_default())
+            .collect(),
+    );
+    let private_flow_array = get_list_string_array_from_owned(
+        blocks
+            .iter()
+            .map(|(_, meta)| {
+                meta.private_flow
+                    .iter()
+                    .map(|tx_hash| tx_hash.to_string())
+                    .collect_vec()
+            })
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("block_hash", DataType::Utf8, false),
+        Field::new("block_timestamp", DataType::UInt64, false),
+        Field::new("relay_timestamp", DataType::UInt64, true),
+        Field::new("p2p_timestamp", DataType::UInt64, true),
+        Field::new("proposer_fee_recipient", DataType::Utf8, true),
+        Field::new("proposer_mev_reward", DataType::Binary, false),
+        Field::new(
+            "private_flow",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(block_hash_array),
+            Arc::new(block_timestamp_array),
+            Arc::new(relay_timestamp_array),
+            Arc::new(p2p_timestamp_array),
+            Arc::new(proposer_fee_recipient_array),
+            Arc::new(proposer_mev_reward_array),
+            Arc::new(private_flow_array),
+        ],
+    )
+}
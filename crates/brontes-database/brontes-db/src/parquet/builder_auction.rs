@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Int64Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::builder_auction::{AuctionBid, BuilderAuction};
+use itertools::Itertools;
+
+use super::utils::{build_string_array, build_uint64_array, u128_to_binary_array};
+
+/// One row per bid the relays received for each block
+pub fn builder_auctions_to_record_batch(
+    auctions: Vec<(u64, BuilderAuction)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows: Vec<(u64, &BuilderAuction, &AuctionBid)> = auctions
+        .iter()
+        .flat_map(|(block_number, auction)| {
+            auction
+                .bids
+                .iter()
+                .map(move |bid| (*block_number, auction, bid))
+        })
+        .collect_vec();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|row| row.0).collect());
+    let slot_array = build_uint64_array(rows.iter().map(|row| row.1.slot).collect());
+    let relay_array = build_string_array(rows.iter().map(|row| row.2.relay.clone()).collect());
+    let builder_pubkey_array = build_string_array(
+        rows.iter()
+            .map(|row| row.2.builder_pubkey.clone())
+            .collect(),
+    );
+    let block_hash_array = build_string_array(
+        rows.iter()
+            .map(|row| row.2.block_hash.to_string())
+            .collect(),
+    );
+    let value_array = u128_to_binary_array(rows.iter().map(|row| row.2.value).collect());
+    let timestamp_ms_array =
+        build_uint64_array(rows.iter().map(|row| row.2.timestamp_ms).collect());
+    let ms_into_slot_array = Int64Array::from(
+        rows.iter()
+            .map(|row| row.1.ms_into_slot(row.2))
+            .collect_vec(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("slot", DataType::UInt64, false),
+        Field::new("relay", DataType::Utf8, false),
+        Field::new("builder_pubkey", DataType::Utf8, false),
+        Field::new("block_hash", DataType::Utf8, false),
+        Field::new("value", DataType::Binary, false),
+        Field::new("timestamp_ms", DataType::UInt64, false),
+        Field::new("ms_into_slot", DataType::Int64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(slot_array),
+            Arc::new(relay_array),
+            Arc::new(builder_pubkey_array),
+            Arc::new(block_hash_array),
+            Arc::new(value_array),
+            Arc::new(timestamp_ms_array),
+            Arc::new(ms_into_slot_array),
+        ],
+    )
+}
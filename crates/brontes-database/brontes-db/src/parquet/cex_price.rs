@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::cex::{
+        quotes::{CexPriceMap, CexQuote},
+        CexExchange,
+    },
+    pair::Pair,
+    ToFloatNearest,
+};
+use itertools::Itertools;
+
+use super::utils::{build_float64_array, build_string_array, build_uint64_array};
+
+/// One row per quote of each pair on each exchange
+pub fn cex_prices_to_record_batch(
+    prices: Vec<(u64, CexPriceMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows: Vec<(u64, &CexExchange, &Pair, &CexQuote)> = prices
+        .iter()
+        .flat_map(|(block_number, price_map)| {
+            price_map.quotes.iter().flat_map(move |(exchange, pairs)| {
+                pairs.iter().flat_map(move |(pair, quotes)| {
+                    quotes
+                        .iter()
+                        .map(move |quote| (*block_number, exchange, pair, quote))
+                })
+            })
+        })
+        .collect_vec();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|row| row.0).collect());
+    let exchange_array = build_string_array(rows.iter().map(|row| row.1.to_string()).collect());
+    let token0_array = build_string_array(rows.iter().map(|row| row.2 .0.to_string()).collect());
+    let token1_array = build_string_array(rows.iter().map(|row| row.2 .1.to_string()).collect());
+    let timestamp_array = build_uint64_array(rows.iter().map(|row| row.3.timestamp).collect());
+    let bid_price_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.price.0.clone().to_float())
+            .collect(),
+    );
+    let ask_price_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.price.1.clone().to_float())
+            .collect(),
+    );
+    let bid_amount_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.amount.0.clone().to_float())
+            .collect(),
+    );
+    let ask_amount_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.amount.1.clone().to_float())
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("bid_price", DataType::Float64, false),
+        Field::new("ask_price", DataType::Float64, false),
+        Field::new("bid_amount", DataType::Float64, false),
+        Field::new("ask_amount", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(exchange_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(timestamp_array),
+            Arc::new(bid_price_array),
+            Arc::new(ask_price_array),
+            Arc::new(bid_amount_array),
+            Arc::new(ask_amount_array),
+        ],
+    )
+}
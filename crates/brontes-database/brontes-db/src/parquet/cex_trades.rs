@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::cex::{
+        trades::{CexTradeMap, CexTrades},
+        CexExchange,
+    },
+    pair::Pair,
+    ToFloatNearest,
+};
+use itertools::Itertools;
+
+use super::utils::{build_float64_array, build_string_array, build_uint64_array};
+
+/// One row per trade of each pair on each exchange
+pub fn cex_trades_to_record_batch(
+    trades: Vec<(u64, CexTradeMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows: Vec<(u64, &CexExchange, &Pair, &CexTrades)> = trades
+        .iter()
+        .flat_map(|(block_number, trade_map)| {
+            trade_map.0.iter().flat_map(move |(exchange, pairs)| {
+                pairs.iter().flat_map(move |(pair, trades)| {
+                    trades
+                        .iter()
+                        .map(move |trade| (*block_number, exchange, pair, trade))
+                })
+            })
+        })
+        .collect_vec();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|row| row.0).collect());
+    let exchange_array = build_string_array(rows.iter().map(|row| row.1.to_string()).collect());
+    let token0_array = build_string_array(rows.iter().map(|row| row.2 .0.to_string()).collect());
+    let token1_array = build_string_array(rows.iter().map(|row| row.2 .1.to_string()).collect());
+    let timestamp_array = build_uint64_array(rows.iter().map(|row| row.3.timestamp).collect());
+    let price_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.price.clone().to_float())
+            .collect(),
+    );
+    let amount_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.amount.clone().to_float())
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("price", DataType::Float64, false),
+        Field::new("amount", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(exchange_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(timestamp_array),
+            Arc::new(price_array),
+            Arc::new(amount_array),
+        ],
+    )
+}
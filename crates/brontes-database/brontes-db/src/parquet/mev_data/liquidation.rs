@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use arrow::{
-    array::Array,
+    array::{Array, UInt64Array},
     datatypes::{DataType, Field, Schema},
     error::ArrowError,
     record_batch::RecordBatch,
@@ -34,6 +34,27 @@ pub fn liquidation_to_record_batch(
             .collect(),
     );
 
+    let trigger_block_array = UInt64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| liq.trigger_block)
+            .collect_vec(),
+    );
+
+    let trigger_tx_index_array = UInt64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| liq.trigger_tx_index)
+            .collect_vec(),
+    );
+
+    let blocks_to_liquidate_array = UInt64Array::from(
+        liquidations
+            .iter()
+            .map(|liq| liq.blocks_to_liquidate)
+            .collect_vec(),
+    );
+
     let liquidation_swaps_array = get_normalized_swap_list_array(
         liquidations
             .iter()
@@ -54,6 +75,9 @@ pub fn liquidation_to_record_batch(
     let schema = Schema::new(vec![
         Field::new("liquidation_tx_hash", DataType::Utf8, false),
         Field::new("trigger", DataType::Utf8, false),
+        Field::new("trigger_block", DataType::UInt64, true),
+        Field::new("trigger_tx_index", DataType::UInt64, true),
+        Field::new("blocks_to_liquidate", DataType::UInt64, true),
         Field::new("liquidation_swaps", liquidation_swaps_array.data_type().clone(), false),
         Field::new("liquidations", liquidations_array.data_type().clone(), false),
         Field::new("gas_details", gas_details_array.data_type().clone(), false),
@@ -64,6 +88,9 @@ pub fn liquidation_to_record_batch(
         vec![
             Arc::new(liquidation_tx_hash_array),
             Arc::new(trigger_array),
+            Arc::new(trigger_block_array),
+            Arc::new(trigger_tx_index_array),
+            Arc::new(blocks_to_liquidate_array),
             Arc::new(liquidation_swaps_array),
             Arc::new(liquidations_array),
             Arc::new(gas_details_array),
@@ -1,6 +1,7 @@
 mod atomic_arb;
 
 mod cex_dex;
+mod generalized_frontrun;
 mod jit;
 mod jit_sandwich;
 mod liquidation;
@@ -9,6 +10,7 @@ mod searcher_tx;
 
 pub use atomic_arb::*;
 //pub use cex_dex::*;
+pub use generalized_frontrun::*;
 pub use jit::*;
 pub use jit_sandwich::*;
 pub use liquidation::*;
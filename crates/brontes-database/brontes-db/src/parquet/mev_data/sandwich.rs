@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
 use arrow::{
-    array::Array,
+    array::{Array, BooleanArray},
     datatypes::{Field, Schema},
     error::ArrowError,
     record_batch::RecordBatch,
 };
-use brontes_types::mev::Sandwich;
+use brontes_types::{mev::Sandwich, ToFloatNearest};
 use itertools::Itertools;
 
 use crate::parquet::{
@@ -14,7 +14,10 @@ use crate::parquet::{
         gas_details::{get_gas_details_array, get_gas_details_list_array},
         swaps::get_normalized_swap_list_array,
     },
-    utils::{get_list_string_array_from_owned, get_string_array_from_owned},
+    utils::{
+        get_list_float_array_from_owned, get_list_option_float_array_from_owned,
+        get_list_string_array_from_owned, get_string_array_from_owned,
+    },
 };
 
 pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch, ArrowError> {
@@ -87,6 +90,45 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
     let backrun_gas_details_array =
         get_gas_details_array(sandwiches.iter().map(|s| s.backrun_gas_details).collect());
 
+    let is_multi_block_array =
+        BooleanArray::from(sandwiches.iter().map(|s| s.is_multi_block).collect_vec());
+
+    let victim_loss_tx_hash_array = get_list_string_array_from_owned(
+        sandwiches
+            .iter()
+            .map(|s| {
+                s.victim_loss
+                    .iter()
+                    .map(|loss| loss.tx_hash.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let victim_loss_usd_array = get_list_float_array_from_owned(
+        sandwiches
+            .iter()
+            .map(|s| {
+                s.victim_loss
+                    .iter()
+                    .map(|loss| loss.amount_lost_usd.clone().to_float())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let simulated_victim_loss_usd_array = get_list_option_float_array_from_owned(
+        sandwiches
+            .iter()
+            .map(|s| {
+                s.victim_loss
+                    .iter()
+                    .map(|loss| loss.simulated_amount_lost_usd.clone().map(|l| l.to_float()))
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
     let schema = Schema::new(vec![
         Field::new("frontrun_tx_hash", frontrun_tx_hash_array.data_type().clone(), false),
         Field::new("frontrun_swaps", frontrun_swaps_array.data_type().clone(), false),
@@ -105,6 +147,14 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
         Field::new("backrun_tx_hash", backrun_tx_hash_array.data_type().clone(), false),
         Field::new("backrun_swaps", backrun_swaps_array.data_type().clone(), false),
         Field::new("backrun_gas_details", backrun_gas_details_array.data_type().clone(), false),
+        Field::new("is_multi_block", is_multi_block_array.data_type().clone(), false),
+        Field::new("victim_loss_tx_hash", victim_loss_tx_hash_array.data_type().clone(), true),
+        Field::new("victim_loss_usd", victim_loss_usd_array.data_type().clone(), true),
+        Field::new(
+            "simulated_victim_loss_usd",
+            simulated_victim_loss_usd_array.data_type().clone(),
+            true,
+        ),
     ]);
 
     RecordBatch::try_new(
@@ -119,6 +169,10 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
             Arc::new(backrun_tx_hash_array),
             Arc::new(backrun_swaps_array),
             Arc::new(backrun_gas_details_array),
+            Arc::new(is_multi_block_array),
+            Arc::new(victim_loss_tx_hash_array),
+            Arc::new(victim_loss_usd_array),
+            Arc::new(simulated_victim_loss_usd_array),
         ],
     )
 }
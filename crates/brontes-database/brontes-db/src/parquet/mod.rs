@@ -1,12 +1,20 @@
 use std::{
     fs::File,
+    ops::Bound,
     path::{Path, PathBuf},
 };
 
-use arrow::record_batch::RecordBatch;
+use arrow::{error::ArrowError, record_batch::RecordBatch};
 use brontes_types::{
-    db::traits::LibmdbxReader,
-    mev::{BundleData, MevType},
+    db::{
+        dex::{decompose_key, make_key},
+        mev_block::MevBlockWithClassified,
+        traits::LibmdbxReader,
+    },
+    mev::{
+        AtomicArb, BundleData, BundleHeader, CexDex, GeneralizedFrontrun, JitLiquidity,
+        JitLiquiditySandwich, Liquidation, MevBlock, MevType, Sandwich, SearcherTx,
+    },
 };
 use chrono::Local;
 use eyre::{Error, Ok, Result, WrapErr};
@@ -18,35 +26,65 @@ use parquet::{
 };
 use tracing::error;
 
-use crate::Tables;
+use crate::{
+    libmdbx::LibmdbxTableReader, AddressToProtocolInfo, BlockInfo, BuilderAuctions, CexPrice,
+    CexTrades, CompressedTable, DexPrice, PoolCreationBlocks, Tables, TokenDecimals, TxTraces,
+    UniswapV4PoolKeys,
+};
 
 #[allow(dead_code)]
 mod address_meta;
+mod block_info;
 mod builder;
+mod builder_auction;
 mod bundle_header;
+mod cex_price;
+mod cex_trades;
+mod dex_price;
 mod mev_block;
 mod mev_data;
 mod normalized_actions;
+mod pool_creation;
+mod protocol_info;
 mod searcher;
+mod searcher_inventory;
+mod token_info;
+mod tx_traces;
+mod uniswap_v4;
 pub mod utils;
 
-use address_meta::address_metadata_to_record_batch;
-use builder::builder_info_to_record_batch;
-use bundle_header::bundle_headers_to_record_batch;
-use mev_block::mev_block_to_record_batch;
-use mev_data::*;
-use searcher::searcher_info_to_record_batch;
-
-pub struct ParquetExporter<DB: LibmdbxReader> {
-    pub start_block:   Option<u64>,
-    pub end_block:     Option<u64>,
-    pub base_dir_path: Option<String>,
-    pub db:            &'static DB,
+pub(crate) use address_meta::address_metadata_to_record_batch;
+pub(crate) use block_info::block_info_to_record_batch;
+pub(crate) use builder::builder_info_to_record_batch;
+pub(crate) use builder_auction::builder_auctions_to_record_batch;
+pub(crate) use bundle_header::bundle_headers_to_record_batch;
+pub(crate) use cex_price::cex_prices_to_record_batch;
+pub(crate) use cex_trades::cex_trades_to_record_batch;
+pub(crate) use dex_price::dex_prices_to_record_batch;
+pub(crate) use mev_block::mev_block_to_record_batch;
+pub(crate) use mev_data::*;
+pub(crate) use pool_creation::pool_creation_blocks_to_record_batch;
+pub(crate) use protocol_info::protocol_info_to_record_batch;
+pub(crate) use searcher::searcher_info_to_record_batch;
+pub(crate) use searcher_inventory::searcher_inventories_to_record_batch;
+pub(crate) use token_info::token_decimals_to_record_batch;
+pub(crate) use tx_traces::tx_traces_to_record_batch;
+pub(crate) use uniswap_v4::v4_pool_keys_to_record_batch;
+
+/// Number of blocks written to each file of the block keyed tables
+pub const DEFAULT_PARTITION_SIZE: u64 = 10_000;
+
+pub struct ParquetExporter<DB: LibmdbxReader + LibmdbxTableReader> {
+    pub start_block:    Option<u64>,
+    pub end_block:      Option<u64>,
+    pub base_dir_path:  Option<String>,
+    pub partition_size: u64,
+    pub db:             &'static DB,
 }
 
 impl<DB> ParquetExporter<DB>
 where
-    DB: LibmdbxReader,
+    DB: LibmdbxReader + LibmdbxTableReader,
 {
     pub fn new(
         start_block: Option<u64>,
@@ -54,7 +92,12 @@ where
         base_dir_path: Option<String>,
         db: &'static DB,
     ) -> Self {
-        Self { start_block, end_block, base_dir_path, db }
+        Self { start_block, end_block, base_dir_path, partition_size: DEFAULT_PARTITION_SIZE, db }
+    }
+
+    pub fn with_partition_size(mut self, partition_size: u64) -> Self {
+        self.partition_size = partition_size.max(1);
+        self
     }
 
     pub async fn export_mev_blocks(&self) -> Result<(), Error> {
@@ -73,61 +116,18 @@ where
             return Err(Error::msg("No MEV blocks fetched for the given range."))
         }
 
-        let mev_blocks_iter = mev_blocks.into_iter();
-        let (
+        let MevBlockSplit {
             blocks,
             bundle_headers,
-            _cex_dex_arbs,
+            cex_dex: _cex_dex_arbs,
             atomic_arbs,
             jit,
             sandwich,
             jit_sandwich,
             searcher_tx,
             liquidation,
-        ) = {
-            let mut blocks = Vec::new();
-            let mut bundle_headers = Vec::new();
-            let mut cex_dex_arbs = Vec::new();
-            let mut atomic_arbs = Vec::new();
-            let mut jit = Vec::new();
-            let mut sandwich = Vec::new();
-            let mut jit_sandwich = Vec::new();
-            let mut searcher_tx = Vec::new();
-            let mut liquidation = Vec::new();
-
-            for mb in mev_blocks_iter {
-                blocks.push(mb.block);
-                for bundle in mb.mev {
-                    bundle_headers.push(bundle.header);
-                    match bundle.data {
-                        BundleData::CexDex(cex_dex) => cex_dex_arbs.push(cex_dex),
-                        BundleData::AtomicArb(atomic_arb) => atomic_arbs.push(atomic_arb),
-                        BundleData::Jit(jit_data) => jit.push(jit_data),
-                        BundleData::Sandwich(sandwich_data) => sandwich.push(sandwich_data),
-                        BundleData::JitSandwich(jit_sandwich_data) => {
-                            jit_sandwich.push(jit_sandwich_data)
-                        }
-                        BundleData::Unknown(searcher_tx_data) => searcher_tx.push(searcher_tx_data),
-                        BundleData::Liquidation(liquidation_data) => {
-                            liquidation.push(liquidation_data)
-                        }
-                        _ => continue,
-                    }
-                }
-            }
-
-            (
-                blocks,
-                bundle_headers,
-                cex_dex_arbs,
-                atomic_arbs,
-                jit,
-                sandwich,
-                jit_sandwich,
-                searcher_tx,
-                liquidation,
-            )
-        };
+            generalized_frontrun,
+        } = MevBlockSplit::new(mev_blocks);
 
         let base_dir_path = self.base_dir_path.clone();
 
@@ -245,6 +245,26 @@ where
             }));
         }
 
+        if !generalized_frontrun.is_empty() {
+            bundle_futures.push(tokio::task::spawn_blocking({
+                let base_dir_path = base_dir_path.clone();
+                move || {
+                    let generalized_frontrun_batch = generalized_frontrun_to_record_batch(
+                        generalized_frontrun,
+                    )
+                    .wrap_err("Failed to convert Generalized Frontrun data to record batch")?;
+                    sync_write_parquet(
+                        generalized_frontrun_batch,
+                        get_path(
+                            base_dir_path,
+                            Tables::MevBlocks,
+                            Some(MevType::GeneralizedFrontrun),
+                        )?,
+                    )
+                }
+            }));
+        }
+
         if !bundle_headers.is_empty() {
             bundle_futures.push(tokio::task::spawn_blocking({
                 let base_dir_path = base_dir_path.clone();
@@ -311,6 +331,30 @@ where
         Ok(())
     }
 
+    pub async fn export_searcher_inventories(&self) -> Result<(), Error> {
+        let inventories = self
+            .db
+            .fetch_all_searcher_inventories()
+            .wrap_err("Failed to query searcher inventory table")?;
+
+        if inventories.is_empty() {
+            error!("Searcher inventory table is empty.");
+            return Err(Error::msg("No searcher inventories"))
+        }
+
+        let inventory_batch = searcher_inventories_to_record_batch(inventories)
+            .wrap_err("Failed to convert searcher inventories to record batch")?;
+
+        write_parquet(
+            inventory_batch,
+            get_path(self.base_dir_path.clone(), Tables::SearcherInventories, None)?,
+        )
+        .await
+        .wrap_err("Failed to write searcher inventories to parquet file")?;
+
+        Ok(())
+    }
+
     pub async fn export_builder_info(&self) -> Result<(), Error> {
         let builder_info = self
             .db
@@ -334,6 +378,259 @@ where
 
         Ok(())
     }
+
+    pub async fn export_dex_prices(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<DexPrice, _>(Tables::DexPrice, dex_prices_to_record_batch)
+            .await
+    }
+
+    pub async fn export_cex_prices(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<CexPrice, _>(Tables::CexPrice, cex_prices_to_record_batch)
+            .await
+    }
+
+    pub async fn export_cex_trades(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<CexTrades, _>(Tables::CexTrades, cex_trades_to_record_batch)
+            .await
+    }
+
+    pub async fn export_tx_traces(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<TxTraces, _>(Tables::TxTraces, tx_traces_to_record_batch)
+            .await
+    }
+
+    pub async fn export_block_info(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<BlockInfo, _>(Tables::BlockInfo, block_info_to_record_batch)
+            .await
+    }
+
+    pub async fn export_builder_auctions(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<BuilderAuctions, _>(
+            Tables::BuilderAuctions,
+            builder_auctions_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_pool_creation_blocks(&self) -> Result<(), Error> {
+        self.export_block_partitioned::<PoolCreationBlocks, _>(
+            Tables::PoolCreationBlocks,
+            pool_creation_blocks_to_record_batch,
+        )
+        .await
+    }
+
+    pub async fn export_token_decimals(&self) -> Result<(), Error> {
+        let tokens = self
+            .db
+            .read_table_range::<TokenDecimals>(..)
+            .wrap_err("Failed to query token decimals table")?;
+
+        if tokens.is_empty() {
+            error!("Token decimals table is empty.");
+            return Err(Error::msg("No token info"))
+        }
+
+        let token_batch = token_decimals_to_record_batch(tokens)
+            .wrap_err("Failed to convert token info to record batch")?;
+
+        write_parquet(
+            token_batch,
+            get_path(self.base_dir_path.clone(), Tables::TokenDecimals, None)?,
+        )
+        .await
+        .wrap_err("Failed to write token info to parquet file")?;
+
+        Ok(())
+    }
+
+    pub async fn export_protocol_info(&self) -> Result<(), Error> {
+        let protocols = self
+            .db
+            .read_table_range::<AddressToProtocolInfo>(..)
+            .wrap_err("Failed to query address to protocol info table")?;
+
+        if protocols.is_empty() {
+            error!("Address to protocol info table is empty.");
+            return Err(Error::msg("No protocol info"))
+        }
+
+        let protocol_batch = protocol_info_to_record_batch(protocols)
+            .wrap_err("Failed to convert protocol info to record batch")?;
+
+        write_parquet(
+            protocol_batch,
+            get_path(self.base_dir_path.clone(), Tables::AddressToProtocolInfo, None)?,
+        )
+        .await
+        .wrap_err("Failed to write protocol info to parquet file")?;
+
+        Ok(())
+    }
+
+    pub async fn export_v4_pool_keys(&self) -> Result<(), Error> {
+        let keys = self
+            .db
+            .read_table_range::<UniswapV4PoolKeys>(..)
+            .wrap_err("Failed to query uniswap v4 pool key table")?;
+
+        if keys.is_empty() {
+            error!("Uniswap v4 pool key table is empty.");
+            return Err(Error::msg("No uniswap v4 pool keys"))
+        }
+
+        let key_batch = v4_pool_keys_to_record_batch(keys)
+            .wrap_err("Failed to convert uniswap v4 pool keys to record batch")?;
+
+        write_parquet(
+            key_batch,
+            get_path(self.base_dir_path.clone(), Tables::UniswapV4PoolKeys, None)?,
+        )
+        .await
+        .wrap_err("Failed to write uniswap v4 pool keys to parquet file")?;
+
+        Ok(())
+    }
+
+    /// Exports a block keyed table over the block range, writing a file for
+    /// every `partition_size` blocks that have data. Without a start or end
+    /// block the range is bounded by the first & last block in the table.
+    async fn export_block_partitioned<T, F>(&self, table: Tables, to_batch: F) -> Result<(), Error>
+    where
+        T: BlockPartitioned,
+        T::Value: From<T::DecompressedValue> + Into<T::DecompressedValue>,
+        F: Fn(Vec<(T::Key, T::DecompressedValue)>) -> Result<RecordBatch, ArrowError>,
+    {
+        let Some((first_key, last_key)) = self
+            .db
+            .table_key_bounds::<T>()
+            .wrap_err_with(|| format!("Failed to query the {} table", table.name()))?
+        else {
+            error!("{} table is empty.", table.name());
+            return Err(Error::msg(format!("No {} data", table.name())))
+        };
+
+        let start_block = self.start_block.unwrap_or(T::block_number(&first_key));
+        let end_block = self.end_block.unwrap_or(T::block_number(&last_key));
+        let dir = get_partition_dir(self.base_dir_path.clone(), table)?;
+
+        let mut written = 0;
+        for partition_start in (start_block..=end_block).step_by(self.partition_size as usize) {
+            let partition_end = (partition_start + self.partition_size - 1).min(end_block);
+
+            let rows = self
+                .db
+                .read_table_range::<T>(T::block_key_range(partition_start, partition_end))
+                .wrap_err_with(|| format!("Failed to query the {} table", table.name()))?;
+
+            if rows.is_empty() {
+                continue
+            }
+
+            let batch = to_batch(rows).wrap_err_with(|| {
+                format!("Failed to convert {} data to record batch", table.name())
+            })?;
+
+            write_parquet(batch, dir.join(format!("{partition_start}-{partition_end}.parquet")))
+                .await
+                .wrap_err_with(|| format!("Failed to write {} to parquet file", table.name()))?;
+            written += 1;
+        }
+
+        if written == 0 {
+            error!("No {} data for the given range.", table.name());
+            return Err(Error::msg(format!("No {} data for the given range.", table.name())))
+        }
+
+        Ok(())
+    }
+}
+
+/// A table keyed by block number, that is exported in block range partitions
+pub trait BlockPartitioned: CompressedTable {
+    fn block_number(key: &Self::Key) -> u64;
+
+    /// The keys of all entries from `start_block` up to & including
+    /// `end_block`
+    fn block_key_range(start_block: u64, end_block: u64) -> (Bound<Self::Key>, Bound<Self::Key>);
+}
+
+macro_rules! block_number_keyed {
+    ($($table:ident),*) => {
+        $(
+            impl BlockPartitioned for $table {
+                fn block_number(key: &u64) -> u64 {
+                    *key
+                }
+
+                fn block_key_range(start_block: u64, end_block: u64) -> (Bound<u64>, Bound<u64>) {
+                    (Bound::Included(start_block), Bound::Included(end_block))
+                }
+            }
+        )*
+    };
+}
+
+block_number_keyed!(CexPrice, CexTrades, TxTraces, BlockInfo, PoolCreationBlocks, BuilderAuctions);
+
+impl BlockPartitioned for DexPrice {
+    fn block_number(key: &Self::Key) -> u64 {
+        decompose_key(*key).0
+    }
+
+    fn block_key_range(start_block: u64, end_block: u64) -> (Bound<Self::Key>, Bound<Self::Key>) {
+        (Bound::Included(make_key(start_block, 0)), Bound::Included(make_key(end_block, u16::MAX)))
+    }
+}
+
+/// The mev blocks of a range split into the blocks, the bundle headers & the
+/// bundles of each mev type
+#[derive(Debug, Default)]
+pub(crate) struct MevBlockSplit {
+    pub blocks:               Vec<MevBlock>,
+    pub bundle_headers:       Vec<BundleHeader>,
+    pub cex_dex:              Vec<CexDex>,
+    pub atomic_arbs:          Vec<AtomicArb>,
+    pub jit:                  Vec<JitLiquidity>,
+    pub sandwich:             Vec<Sandwich>,
+    pub jit_sandwich:         Vec<JitLiquiditySandwich>,
+    pub searcher_tx:          Vec<SearcherTx>,
+    pub liquidation:          Vec<Liquidation>,
+    pub generalized_frontrun: Vec<GeneralizedFrontrun>,
+}
+
+impl MevBlockSplit {
+    pub(crate) fn new(mev_blocks: Vec<MevBlockWithClassified>) -> Self {
+        let mut this = Self::default();
+
+        for mb in mev_blocks {
+            this.blocks.push(mb.block);
+            for bundle in mb.mev {
+                this.bundle_headers.push(bundle.header);
+                match bundle.data {
+                    BundleData::CexDex(cex_dex) => this.cex_dex.push(cex_dex),
+                    BundleData::AtomicArb(atomic_arb) => this.atomic_arbs.push(atomic_arb),
+                    BundleData::Jit(jit_data) => this.jit.push(jit_data),
+                    BundleData::Sandwich(sandwich_data) => this.sandwich.push(sandwich_data),
+                    BundleData::JitSandwich(jit_sandwich_data) => {
+                        this.jit_sandwich.push(jit_sandwich_data)
+                    }
+                    BundleData::Unknown(searcher_tx_data) => {
+                        this.searcher_tx.push(searcher_tx_data)
+                    }
+                    BundleData::Liquidation(liquidation_data) => {
+                        this.liquidation.push(liquidation_data)
+                    }
+                    BundleData::GeneralizedFrontrun(frontrun_data) => {
+                        this.generalized_frontrun.push(frontrun_data)
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        this
+    }
 }
 
 async fn write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<()> {
@@ -391,7 +688,7 @@ pub fn get_path(
         .unwrap_or("../brontes-notebook/data/brontes-exports");
 
     let mut path = PathBuf::from(base_path);
-    path.push(batch_type.get_default_path());
+    path.push(batch_type.get_default_path()?);
 
     if batch_type == Tables::MevBlocks && mev_type.is_none() {
         path.push("blocks");
@@ -402,6 +699,23 @@ pub fn get_path(
     create_file_path(path)
 }
 
+/// The directory the partitions of a block keyed table are written to
+/// "data_exports/dex_price/03-19/14-05"
+pub fn get_partition_dir(custom_path: Option<String>, batch_type: Tables) -> Result<PathBuf> {
+    let base_path = custom_path
+        .as_deref()
+        .unwrap_or("../brontes-notebook/data/brontes-exports");
+
+    let now = Local::now();
+    let dir_path = PathBuf::from(base_path)
+        .join(batch_type.get_default_path()?)
+        .join(now.format("%m-%d").to_string())
+        .join(now.format("%H-%M").to_string());
+    std::fs::create_dir_all(&dir_path)?;
+
+    Ok(dir_path)
+}
+
 pub fn create_file_path<P: AsRef<Path>>(base_dir: P) -> Result<PathBuf> {
     let now = Local::now();
     let date_str = now.format("%m-%d").to_string();
@@ -417,15 +731,39 @@ pub fn create_file_path<P: AsRef<Path>>(base_dir: P) -> Result<PathBuf> {
 }
 
 impl Tables {
-    pub fn get_default_path(&self) -> &'static str {
-        match self {
+    /// The tables exported when no tables are given. The searcher eoas are
+    /// exported together with the searcher contracts.
+    pub fn parquet_exports() -> Vec<Tables> {
+        Tables::ALL
+            .into_iter()
+            .filter(|table| table.get_default_path().is_ok() && *table != Tables::SearcherEOAs)
+            .collect()
+    }
+
+    pub fn get_default_path(&self) -> Result<&'static str> {
+        let path = match self {
             Tables::MevBlocks => DEFAULT_BLOCK_DIR,
             Tables::AddressMeta => DEFAULT_METADATA_DIR,
             Tables::SearcherEOAs => DEFAULT_SEARCHER_INFO_DIR,
             Tables::SearcherContracts => DEFAULT_SEARCHER_INFO_DIR,
             Tables::Builder => DEFAULT_BUILDER_INFO_DIR,
-            _ => panic!("Unsupported table type"),
-        }
+            Tables::SearcherInventories => DEFAULT_SEARCHER_INVENTORY_DIR,
+            Tables::DexPrice => DEFAULT_DEX_PRICE_DIR,
+            Tables::CexPrice => DEFAULT_CEX_PRICE_DIR,
+            Tables::CexTrades => DEFAULT_CEX_TRADES_DIR,
+            Tables::TxTraces => DEFAULT_TX_TRACES_DIR,
+            Tables::TokenDecimals => DEFAULT_TOKEN_INFO_DIR,
+            Tables::AddressToProtocolInfo => DEFAULT_PROTOCOL_INFO_DIR,
+            Tables::PoolCreationBlocks => DEFAULT_POOL_CREATION_DIR,
+            Tables::BlockInfo => DEFAULT_BLOCK_INFO_DIR,
+            Tables::BuilderAuctions => DEFAULT_BUILDER_AUCTION_DIR,
+            Tables::UniswapV4PoolKeys => DEFAULT_V4_POOL_KEY_DIR,
+            Tables::InitializedState | Tables::TableVersions => {
+                return Err(eyre::eyre!("{} can't be exported to parquet", self.name()))
+            }
+        };
+
+        Ok(path)
     }
 }
 pub const DEFAULT_SEARCHER_STATS: &str = "searcher_stats";
@@ -433,3 +771,116 @@ pub const DEFAULT_BLOCK_DIR: &str = "mev";
 pub const DEFAULT_METADATA_DIR: &str = "address_metadata";
 pub const DEFAULT_SEARCHER_INFO_DIR: &str = "searcher_info";
 pub const DEFAULT_BUILDER_INFO_DIR: &str = "builder-info";
+pub const DEFAULT_SEARCHER_INVENTORY_DIR: &str = "searcher_inventory";
+pub const DEFAULT_DEX_PRICE_DIR: &str = "dex_price";
+pub const DEFAULT_CEX_PRICE_DIR: &str = "cex_price";
+pub const DEFAULT_CEX_TRADES_DIR: &str = "cex_trades";
+pub const DEFAULT_TX_TRACES_DIR: &str = "tx_traces";
+pub const DEFAULT_TOKEN_INFO_DIR: &str = "token_info";
+pub const DEFAULT_PROTOCOL_INFO_DIR: &str = "protocol_info";
+pub const DEFAULT_POOL_CREATION_DIR: &str = "pool_creation";
+pub const DEFAULT_BLOCK_INFO_DIR: &str = "block_info";
+pub const DEFAULT_BUILDER_AUCTION_DIR: &str = "builder_auction";
+pub const DEFAULT_V4_POOL_KEY_DIR: &str = "uniswap_v4_pool_keys";
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use brontes_types::db::builder_auction::{AuctionBid, BuilderAuction};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::{libmdbx::LibmdbxReadWriter, BuilderAuctionsData};
+
+    fn parquet_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files = std::fs::read_dir(dir)
+            .unwrap()
+            .flat_map(|entry| {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    parquet_files(&path)
+                } else {
+                    vec![path]
+                }
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[tokio::test]
+    async fn test_export_block_partitioned() {
+        let db_path = std::env::temp_dir().join("brontes-export-test-db");
+        let out_path = std::env::temp_dir().join("brontes-export-test-out");
+        let _ = std::fs::remove_dir_all(&db_path);
+        let _ = std::fs::remove_dir_all(&out_path);
+
+        let db: &'static LibmdbxReadWriter =
+            Box::leak(Box::new(LibmdbxReadWriter::init_db_tests(&db_path).unwrap()));
+        let auctions = (1..=5)
+            .map(|block| {
+                BuilderAuctionsData::new(
+                    block,
+                    BuilderAuction::new(block, vec![AuctionBid::default()]),
+                )
+            })
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<BuilderAuctions, BuilderAuctionsData>(&auctions)
+            .unwrap();
+
+        let exporter = Arc::new(
+            ParquetExporter::new(Some(2), None, Some(out_path.to_string_lossy().to_string()), db)
+                .with_partition_size(2),
+        );
+        Tables::BuilderAuctions
+            .export_to_parquet(exporter.clone())
+            .await
+            .unwrap();
+
+        let files = parquet_files(&out_path);
+        let names = files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["2-3.parquet", "4-5.parquet"]);
+
+        let rows = files
+            .iter()
+            .map(|file| {
+                ParquetRecordBatchReaderBuilder::try_new(File::open(file).unwrap())
+                    .unwrap()
+                    .build()
+                    .unwrap()
+                    .map(|batch| batch.unwrap().num_rows())
+                    .sum::<usize>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![2, 2]);
+
+        // empty tables & the tables without a parquet layout fail the export
+        assert!(Tables::DexPrice
+            .export_to_parquet(exporter.clone())
+            .await
+            .is_err());
+        assert!(Tables::InitializedState
+            .export_to_parquet(exporter)
+            .await
+            .is_err());
+
+        std::fs::remove_dir_all(&db_path).unwrap();
+        std::fs::remove_dir_all(&out_path).unwrap();
+    }
+
+    #[test]
+    fn test_parquet_exports() {
+        let tables = Tables::parquet_exports();
+
+        assert!(tables.contains(&Tables::MevBlocks));
+        assert!(tables.contains(&Tables::SearcherContracts));
+        assert!(!tables.contains(&Tables::SearcherEOAs));
+        assert!(!tables.contains(&Tables::InitializedState));
+        assert!(!tables.contains(&Tables::TableVersions));
+    }
+}
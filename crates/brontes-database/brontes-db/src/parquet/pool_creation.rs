@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::pool_creation_block::PoolsToAddresses;
+
+use super::utils::{build_string_array, build_uint64_array};
+
+/// One row per pool created in each block
+pub fn pool_creation_blocks_to_record_batch(
+    pools: Vec<(u64, PoolsToAddresses)>,
+) -> Result<RecordBatch, ArrowError> {
+    let (block_numbers, addresses): (Vec<u64>, Vec<String>) = pools
+        .iter()
+        .flat_map(|(block_number, pools)| {
+            pools
+                .0
+                .iter()
+                .map(move |pool| (*block_number, pool.to_string()))
+        })
+        .unzip();
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("pool_address", DataType::Utf8, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(build_uint64_array(block_numbers)), Arc::new(build_string_array(addresses))],
+    )
+}
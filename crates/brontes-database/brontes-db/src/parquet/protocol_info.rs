@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::address_to_protocol_info::ProtocolInfo;
+
+use super::utils::{build_string_array, build_uint64_array, get_string_array_from_owned};
+
+pub fn protocol_info_to_record_batch(
+    protocols: Vec<(Address, ProtocolInfo)>,
+) -> Result<RecordBatch, ArrowError> {
+    let optional_address = |f: fn(&ProtocolInfo) -> Option<Address>| {
+        get_string_array_from_owned(
+            protocols
+                .iter()
+                .map(|(_, info)| f(info).map(|address| address.to_string()))
+                .collect(),
+        )
+    };
+
+    let address_array = build_string_array(
+        protocols
+            .iter()
+            .map(|(address, _)| address.to_string())
+            .collect(),
+    );
+    let protocol_array = build_string_array(
+        protocols
+            .iter()
+            .map(|(_, info)| info.protocol.to_string())
+            .collect(),
+    );
+    let token0_array = build_string_array(
+        protocols
+            .iter()
+            .map(|(_, info)| info.token0.to_string())
+            .collect(),
+    );
+    let token1_array = build_string_array(
+        protocols
+            .iter()
+            .map(|(_, info)| info.token1.to_string())
+            .collect(),
+    );
+    let token2_array = optional_address(|info| info.token2);
+    let token3_array = optional_address(|info| info.token3);
+    let token4_array = optional_address(|info| info.token4);
+    let curve_lp_token_array = optional_address(|info| info.curve_lp_token);
+    let init_block_array =
+        build_uint64_array(protocols.iter().map(|(_, info)| info.init_block).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("protocol", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("token2", DataType::Utf8, true),
+        Field::new("token3", DataType::Utf8, true),
+        Field::new("token4", DataType::Utf8, true),
+        Field::new("curve_lp_token", DataType::Utf8, true),
+        Field::new("init_block", DataType::UInt64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(address_array),
+            Arc::new(protocol_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(token2_array),
+            Arc::new(token3_array),
+            Arc::new(token4_array),
+            Arc::new(curve_lp_token_array),
+            Arc::new(init_block_array),
+        ],
+    )
+}
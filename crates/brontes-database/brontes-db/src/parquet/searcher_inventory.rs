@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use arrow::{
+    array::{BooleanArray, Float64Array, UInt64Array},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::{searcher::Fund, searcher_inventory::SearcherInventory};
+use itertools::Itertools;
+
+use super::utils::{build_string_array, get_string_array_from_owned};
+
+/// One row per token position of each searcher
+pub fn searcher_inventories_to_record_batch(
+    inventories: Vec<(Address, SearcherInventory)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = inventories
+        .iter()
+        .flat_map(|(address, inventory)| {
+            inventory
+                .positions
+                .iter()
+                .map(move |position| (address, inventory, position))
+        })
+        .collect_vec();
+
+    let address_array = build_string_array(
+        rows.iter()
+            .map(|(address, ..)| address.to_string())
+            .collect(),
+    );
+    let is_contract_array =
+        BooleanArray::from(rows.iter().map(|(_, inv, _)| inv.is_contract).collect_vec());
+    let fund_array = get_string_array_from_owned(
+        rows.iter()
+            .map(|(_, inv, _)| (inv.fund != Fund::None).then(|| inv.fund.to_string()))
+            .collect(),
+    );
+    let first_block_array =
+        UInt64Array::from(rows.iter().map(|(_, inv, _)| inv.first_block).collect_vec());
+    let last_block_array =
+        UInt64Array::from(rows.iter().map(|(_, inv, _)| inv.last_block).collect_vec());
+    let bundle_count_array = UInt64Array::from(
+        rows.iter()
+            .map(|(_, inv, _)| inv.bundle_count)
+            .collect_vec(),
+    );
+    let token_array =
+        build_string_array(rows.iter().map(|(.., pos)| pos.token.to_string()).collect());
+    let dex_bought_array =
+        Float64Array::from(rows.iter().map(|(.., pos)| pos.dex_bought).collect_vec());
+    let dex_sold_array =
+        Float64Array::from(rows.iter().map(|(.., pos)| pos.dex_sold).collect_vec());
+    let cex_bought_array =
+        Float64Array::from(rows.iter().map(|(.., pos)| pos.cex_bought).collect_vec());
+    let cex_sold_array =
+        Float64Array::from(rows.iter().map(|(.., pos)| pos.cex_sold).collect_vec());
+    let net_position_array = Float64Array::from(
+        rows.iter()
+            .map(|(.., pos)| pos.net_position())
+            .collect_vec(),
+    );
+    let unexplained_ratio_array = Float64Array::from(
+        rows.iter()
+            .map(|(.., pos)| pos.unexplained_ratio())
+            .collect_vec(),
+    );
+    let is_unexplained_array = BooleanArray::from(
+        rows.iter()
+            .map(|(_, inv, pos)| inv.unexplained_tokens.contains(&pos.token))
+            .collect_vec(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("is_contract", DataType::Boolean, false),
+        Field::new("fund", DataType::Utf8, true),
+        Field::new("first_block", DataType::UInt64, false),
+        Field::new("last_block", DataType::UInt64, false),
+        Field::new("bundle_count", DataType::UInt64, false),
+        Field::new("token", DataType::Utf8, false),
+        Field::new("dex_bought", DataType::Float64, false),
+        Field::new("dex_sold", DataType::Float64, false),
+        Field::new("cex_bought", DataType::Float64, false),
+        Field::new("cex_sold", DataType::Float64, false),
+        Field::new("net_position", DataType::Float64, false),
+        Field::new("unexplained_ratio", DataType::Float64, false),
+        Field::new("is_unexplained", DataType::Boolean, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(address_array),
+            Arc::new(is_contract_array),
+            Arc::new(fund_array),
+            Arc::new(first_block_array),
+            Arc::new(last_block_array),
+            Arc::new(bundle_count_array),
+            Arc::new(token_array),
+            Arc::new(dex_bought_array),
+            Arc::new(dex_sold_array),
+            Arc::new(cex_bought_array),
+            Arc::new(cex_sold_array),
+            Arc::new(net_position_array),
+            Arc::new(unexplained_ratio_array),
+            Arc::new(is_unexplained_array),
+        ],
+    )
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use arrow::{
+    array::UInt8Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::token_info::TokenInfo;
+use itertools::Itertools;
+
+use super::utils::build_string_array;
+
+pub fn token_decimals_to_record_batch(
+    tokens: Vec<(Address, TokenInfo)>,
+) -> Result<RecordBatch, ArrowError> {
+    let address_array = build_string_array(
+        tokens
+            .iter()
+            .map(|(address, _)| address.to_string())
+            .collect(),
+    );
+    let symbol_array =
+        build_string_array(tokens.iter().map(|(_, info)| info.symbol.clone()).collect());
+    let decimals_array =
+        UInt8Array::from(tokens.iter().map(|(_, info)| info.decimals).collect_vec());
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("symbol", DataType::Utf8, false),
+        Field::new("decimals", DataType::UInt8, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(address_array), Arc::new(symbol_array), Arc::new(decimals_array)],
+    )
+}
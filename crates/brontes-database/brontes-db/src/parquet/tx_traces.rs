@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{BooleanArray, ListBuilder, UInt64Builder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::traces::TxTracesInner,
+    structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
+};
+use itertools::Itertools;
+use reth_rpc_types::trace::parity::Action;
+
+use super::utils::{
+    build_string_array, build_uint64_array, get_string_array_from_owned, u128_to_binary_array,
+};
+
+/// One row per trace of each transaction
+pub fn tx_traces_to_record_batch(
+    traces: Vec<(u64, TxTracesInner)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows: Vec<(&TxTrace, &TransactionTraceWithLogs)> = traces
+        .iter()
+        .flat_map(|(_, inner)| inner.traces.iter().flatten())
+        .flat_map(|tx| tx.trace.iter().map(move |trace| (tx, trace)))
+        .collect_vec();
+
+    let block_number_array =
+        build_uint64_array(rows.iter().map(|(tx, _)| tx.block_number).collect());
+    let tx_hash_array =
+        build_string_array(rows.iter().map(|(tx, _)| tx.tx_hash.to_string()).collect());
+    let tx_index_array = build_uint64_array(rows.iter().map(|(tx, _)| tx.tx_index).collect());
+    let gas_used_array = u128_to_binary_array(rows.iter().map(|(tx, _)| tx.gas_used).collect());
+    let effective_price_array =
+        u128_to_binary_array(rows.iter().map(|(tx, _)| tx.effective_price).collect());
+    let is_success_array =
+        BooleanArray::from(rows.iter().map(|(tx, _)| tx.is_success).collect_vec());
+    let trace_idx_array =
+        build_uint64_array(rows.iter().map(|(_, trace)| trace.trace_idx).collect());
+
+    let mut trace_address_builder = ListBuilder::new(UInt64Builder::new());
+    for (_, trace) in &rows {
+        trace_address_builder.values().append_slice(
+            &trace
+                .get_trace_address()
+                .into_iter()
+                .map(|a| a as u64)
+                .collect_vec(),
+        );
+        trace_address_builder.append(true);
+    }
+    let trace_address_array = trace_address_builder.finish();
+
+    let action_type_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| {
+                match trace.action_type() {
+                    Action::Call(_) => "call",
+                    Action::Create(_) => "create",
+                    Action::Selfdestruct(_) => "selfdestruct",
+                    Action::Reward(_) => "reward",
+                }
+                .to_string()
+            })
+            .collect(),
+    );
+    let from_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_from_addr().to_string())
+            .collect(),
+    );
+    let to_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_to_address().to_string())
+            .collect(),
+    );
+    let msg_sender_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.msg_sender.to_string())
+            .collect(),
+    );
+    let value_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_msg_value().to_string())
+            .collect(),
+    );
+    let input_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_calldata().to_string())
+            .collect(),
+    );
+    let output_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_return_calldata().to_string())
+            .collect(),
+    );
+    let error_array = get_string_array_from_owned(
+        rows.iter()
+            .map(|(_, trace)| trace.trace.error.clone())
+            .collect(),
+    );
+    let function_name_array = get_string_array_from_owned(
+        rows.iter()
+            .map(|(_, trace)| {
+                trace
+                    .decoded_data
+                    .as_ref()
+                    .map(|data| data.function_name.clone())
+            })
+            .collect(),
+    );
+    let log_count_array = build_uint64_array(
+        rows.iter()
+            .map(|(_, trace)| trace.logs.len() as u64)
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_index", DataType::UInt64, false),
+        Field::new("gas_used", DataType::Binary, false),
+        Field::new("effective_price", DataType::Binary, false),
+        Field::new("is_success", DataType::Boolean, false),
+        Field::new("trace_idx", DataType::UInt64, false),
+        Field::new(
+            "trace_address",
+            DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+            true,
+        ),
+        Field::new("action_type", DataType::Utf8, false),
+        Field::new("from", DataType::Utf8, false),
+        Field::new("to", DataType::Utf8, false),
+        Field::new("msg_sender", DataType::Utf8, false),
+        Field::new("value", DataType::Utf8, false),
+        Field::new("input", DataType::Utf8, false),
+        Field::new("output", DataType::Utf8, false),
+        Field::new("error", DataType::Utf8, true),
+        Field::new("function_name", DataType::Utf8, true),
+        Field::new("log_count", DataType::UInt64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(tx_hash_array),
+            Arc::new(tx_index_array),
+            Arc::new(gas_used_array),
+            Arc::new(effective_price_array),
+            Arc::new(is_success_array),
+            Arc::new(trace_idx_array),
+            Arc::new(trace_address_array),
+            Arc::new(action_type_array),
+            Arc::new(from_array),
+            Arc::new(to_array),
+            Arc::new(msg_sender_array),
+            Arc::new(value_array),
+            Arc::new(input_array),
+            Arc::new(output_array),
+            Arc::new(error_array),
+            Arc::new(function_name_array),
+            Arc::new(log_count_array),
+        ],
+    )
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use arrow::{
+    array::{Int32Array, UInt32Array},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::uniswap_v4::UniswapV4PoolKey;
+use itertools::Itertools;
+
+use super::utils::build_string_array;
+
+pub fn v4_pool_keys_to_record_batch(
+    keys: Vec<(Address, UniswapV4PoolKey)>,
+) -> Result<RecordBatch, ArrowError> {
+    let string_column = |f: fn(&UniswapV4PoolKey) -> String| {
+        build_string_array(keys.iter().map(|(_, key)| f(key)).collect())
+    };
+
+    let address_array = build_string_array(
+        keys.iter()
+            .map(|(address, _)| address.to_string())
+            .collect(),
+    );
+    let pool_id_array = string_column(|key| key.pool_id.to_string());
+    let currency0_array = string_column(|key| key.currency0.to_string());
+    let currency1_array = string_column(|key| key.currency1.to_string());
+    let fee_array = UInt32Array::from(keys.iter().map(|(_, key)| key.fee).collect_vec());
+    let tick_spacing_array =
+        Int32Array::from(keys.iter().map(|(_, key)| key.tick_spacing).collect_vec());
+    let hooks_array = string_column(|key| key.hooks.to_string());
+
+    let schema = Schema::new(vec![
+        Field::new("address", DataType::Utf8, false),
+        Field::new("pool_id", DataType::Utf8, false),
+        Field::new("currency0", DataType::Utf8, false),
+        Field::new("currency1", DataType::Utf8, false),
+        Field::new("fee", DataType::UInt32, false),
+        Field::new("tick_spacing", DataType::Int32, false),
+        Field::new("hooks", DataType::Utf8, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(address_array),
+            Arc::new(pool_id_array),
+            Arc::new(currency0_array),
+            Arc::new(currency1_array),
+            Arc::new(fee_array),
+            Arc::new(tick_spacing_array),
+            Arc::new(hooks_array),
+        ],
+    )
+}
@@ -0,0 +1,226 @@
+//! An embedded sql engine over the libmdbx tables.
+//!
+//! The tables a query references are read from libmdbx, converted to arrow
+//! record batches with the same conversions as the parquet export, and
+//! registered with a datafusion [`SessionContext`]. This allows ad-hoc joins
+//! and aggregations to run locally, without exporting to parquet or standing
+//! up clickhouse. Queries always run over a block range, the block keyed
+//! tables are only read for the blocks in it.
+use std::ops::RangeInclusive;
+
+use arrow::record_batch::RecordBatch;
+use brontes_types::db::{
+    dex::{make_key, DexKey},
+    traits::LibmdbxReader,
+};
+use datafusion::{
+    dataframe::DataFrame,
+    prelude::{SessionConfig, SessionContext},
+};
+
+use crate::{
+    libmdbx::LibmdbxReadWriter,
+    parquet::{
+        address_metadata_to_record_batch, atomic_arb_to_record_batch,
+        builder_auctions_to_record_batch, builder_info_to_record_batch,
+        bundle_headers_to_record_batch, cex_prices_to_record_batch, dex_prices_to_record_batch,
+        generalized_frontrun_to_record_batch, jit_sandwich_to_record_batch, jit_to_record_batch,
+        liquidation_to_record_batch, mev_block_to_record_batch, sandwich_to_record_batch,
+        searcher_info_to_record_batch, searcher_inventories_to_record_batch,
+        searcher_tx_to_record_batch, token_decimals_to_record_batch, MevBlockSplit,
+    },
+    BuilderAuctions, CexPrice, DexPrice, TokenDecimals,
+};
+
+/// The tables that are built from the `MevBlocks` table
+pub const MEV_SQL_TABLES: &[&str] = &[
+    "mev_blocks",
+    "bundle_header",
+    "atomic_arb",
+    "jit",
+    "jit_sandwich",
+    "sandwich",
+    "searcher_tx",
+    "liquidation",
+    "generalized_frontrun",
+];
+
+pub struct LibmdbxSql {
+    db:          &'static LibmdbxReadWriter,
+    start_block: u64,
+    end_block:   u64,
+    ctx:         SessionContext,
+}
+
+impl LibmdbxSql {
+    /// Creates the engine for the blocks in `start_block..=end_block`
+    pub fn new(
+        db: &'static LibmdbxReadWriter,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Self> {
+        if start_block > end_block {
+            eyre::bail!("start block {start_block} is after end block {end_block}")
+        }
+
+        let config = SessionConfig::new().with_information_schema(true);
+
+        Ok(Self { db, start_block, end_block, ctx: SessionContext::new_with_config(config) })
+    }
+
+    /// Plans the query, loading the tables it references first
+    pub async fn sql(&self, query: &str) -> eyre::Result<DataFrame> {
+        let state = self.ctx.state();
+        let statement = state.sql_to_statement(query, "generic")?;
+
+        for table in state.resolve_table_references(&statement)? {
+            self.register_table(table.table())?;
+        }
+
+        Ok(self.ctx.sql(query).await?)
+    }
+
+    /// Loads a table into the context. Names that aren't a libmdbx table are
+    /// left for datafusion to resolve.
+    fn register_table(&self, name: &str) -> eyre::Result<()> {
+        if self.ctx.table_exist(name)? {
+            return Ok(())
+        }
+
+        if MEV_SQL_TABLES.contains(&name) {
+            return self.register_mev_tables()
+        }
+
+        let batch = match name {
+            "dex_price" => dex_prices_to_record_batch(
+                self.db
+                    .db
+                    .read_table_range::<DexPrice>(self.dex_key_range())?,
+            ),
+            "cex_price" => cex_prices_to_record_batch(
+                self.db
+                    .db
+                    .read_table_range::<CexPrice>(self.block_range())?,
+            ),
+            "builder_auctions" => builder_auctions_to_record_batch(
+                self.db
+                    .db
+                    .read_table_range::<BuilderAuctions>(self.block_range())?,
+            ),
+            "address_meta" => {
+                address_metadata_to_record_batch(self.db.fetch_all_address_metadata()?)
+            }
+            "searcher_eoas" => {
+                searcher_info_to_record_batch(self.db.fetch_all_searcher_eoa_info()?, vec![])
+            }
+            "searcher_contracts" => {
+                searcher_info_to_record_batch(vec![], self.db.fetch_all_searcher_contract_info()?)
+            }
+            "searcher_inventories" => {
+                searcher_inventories_to_record_batch(self.db.fetch_all_searcher_inventories()?)
+            }
+            "builder" => builder_info_to_record_batch(self.db.fetch_all_builder_info()?),
+            "token_decimals" => {
+                token_decimals_to_record_batch(self.db.db.read_table_range::<TokenDecimals>(..)?)
+            }
+            _ => return Ok(()),
+        }?;
+
+        self.register_batch(name, batch)
+    }
+
+    /// The mev tables are all built from the same blocks, so they are loaded
+    /// together
+    fn register_mev_tables(&self) -> eyre::Result<()> {
+        let mev_blocks = self
+            .db
+            .try_fetch_mev_blocks(Some(self.start_block), self.end_block)?;
+
+        let split = MevBlockSplit::new(mev_blocks);
+
+        self.register_batch("mev_blocks", mev_block_to_record_batch(split.blocks)?)?;
+        self.register_batch(
+            "bundle_header",
+            bundle_headers_to_record_batch(split.bundle_headers)?,
+        )?;
+        self.register_batch("atomic_arb", atomic_arb_to_record_batch(split.atomic_arbs)?)?;
+        self.register_batch("jit", jit_to_record_batch(split.jit)?)?;
+        self.register_batch("jit_sandwich", jit_sandwich_to_record_batch(split.jit_sandwich)?)?;
+        self.register_batch("sandwich", sandwich_to_record_batch(split.sandwich)?)?;
+        self.register_batch("searcher_tx", searcher_tx_to_record_batch(split.searcher_tx)?)?;
+        self.register_batch("liquidation", liquidation_to_record_batch(split.liquidation)?)?;
+        self.register_batch(
+            "generalized_frontrun",
+            generalized_frontrun_to_record_batch(split.generalized_frontrun)?,
+        )?;
+
+        Ok(())
+    }
+
+    fn register_batch(&self, name: &str, batch: RecordBatch) -> eyre::Result<()> {
+        tracing::debug!(table = name, rows = batch.num_rows(), "registered sql table");
+        self.ctx.register_batch(name, batch)?;
+
+        Ok(())
+    }
+
+    fn dex_key_range(&self) -> RangeInclusive<DexKey> {
+        make_key(self.start_block, 0)..=make_key(self.end_block, u16::MAX)
+    }
+
+    fn block_range(&self) -> RangeInclusive<u64> {
+        self.start_block..=self.end_block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{array::AsArray, datatypes::UInt64Type};
+    use brontes_types::db::builder_auction::{AuctionBid, BuilderAuction};
+
+    use super::*;
+    use crate::BuilderAuctionsData;
+
+    #[tokio::test]
+    async fn test_sql_reads_the_block_range() {
+        let path = std::env::temp_dir().join("brontes-sql-test-db");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let db: &'static LibmdbxReadWriter =
+            Box::leak(Box::new(LibmdbxReadWriter::init_db_tests(&path).unwrap()));
+        let auctions = (1..=5)
+            .map(|block| {
+                BuilderAuctionsData::new(
+                    block,
+                    BuilderAuction::new(block, vec![AuctionBid::default()]),
+                )
+            })
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<BuilderAuctions, BuilderAuctionsData>(&auctions)
+            .unwrap();
+
+        let blocks = LibmdbxSql::new(db, 2, 3)
+            .unwrap()
+            .sql("SELECT block_number FROM builder_auctions ORDER BY block_number")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap()
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, vec![2, 3]);
+
+        assert!(LibmdbxSql::new(db, 3, 2).is_err());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
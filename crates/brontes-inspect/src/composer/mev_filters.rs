@@ -109,6 +109,7 @@ define_mev_precedence!(
     Unknown, SearcherTx => CexDexQuotes;
     Unknown, SearcherTx => CexDexTrades;
     Unknown, SearcherTx => AtomicArb;
+    Unknown, SearcherTx, AtomicArb => GeneralizedFrontrun;
     Unknown, SearcherTx, AtomicArb => Jit;
     Unknown, SearcherTx, AtomicArb, CexDexQuotes,CexDexTrades  => Liquidation;
     Unknown, SearcherTx, AtomicArb, CexDexQuotes,CexDexTrades  => Sandwich;
@@ -50,6 +50,12 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
         .try_fetch_builder_info(pre_processing.builder_address)
         .unwrap()
         .and_then(|b| b.name);
+    // loaded with the metadata at the tip, backfilled into the db otherwise
+    let builder_auction = metadata
+        .builder_auction
+        .clone()
+        .or_else(|| db.try_fetch_builder_auction(metadata.block_num).unwrap())
+        .and_then(|auction| auction.summary(metadata.block_hash.into()));
 
     MevBlock {
         block_hash: metadata.block_hash.into(),
@@ -79,6 +85,7 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
         proposer_profit_usd,
         total_mev_profit_usd,
         possible_mev,
+        builder_auction,
     }
 }
 
@@ -191,6 +198,7 @@ fn update_mev_count(mev_count: &mut MevCount, mev_type: MevType, count: u64) {
         MevType::AtomicArb => mev_count.atomic_backrun_count = Some(count),
         MevType::Liquidation => mev_count.liquidation_count = Some(count),
         MevType::SearcherTx => mev_count.searcher_tx_count = Some(count),
+        MevType::GeneralizedFrontrun => mev_count.gen_frontrun_count = Some(count),
         MevType::Unknown => (),
     }
 }
@@ -81,6 +81,7 @@ use brontes_types::{
     MultiBlockData,
 };
 use cex_dex::{markout::CexDexMarkoutInspector, quotes::CexDexQuotesInspector};
+use generalized_frontrun::GeneralizedFrontrunInspector;
 use jit::JitCexDex;
 use liquidations::LiquidationInspector;
 use sandwich::SandwichInspector;
@@ -112,6 +113,8 @@ pub enum Inspectors {
     SearcherActivity,
     CexDexMarkout,
     JitCexDex,
+    MultiBlockSandwich,
+    GeneralizedFrontrun,
 }
 
 type DynMevInspector = &'static (dyn Inspector<Result = Vec<Bundle>> + 'static);
@@ -138,6 +141,7 @@ impl Inspectors {
                 db,
                 cex_exchanges,
                 trade_config.quote_offset_from_block_us,
+                trade_config.cex_fee_schedule,
                 metrics,
             )) as DynMevInspector,
             Self::Sandwich => {
@@ -167,6 +171,16 @@ impl Inspectors {
                 ),
                 jit:     JitInspector::new(quote_token, db, metrics),
             }) as DynMevInspector,
+            Self::MultiBlockSandwich => static_object(SandwichInspector::new_multi_block(
+                quote_token,
+                db,
+                MULTI_BLOCK_SANDWICH_WINDOW,
+                metrics,
+            )) as DynMevInspector,
+            Self::GeneralizedFrontrun => {
+                static_object(GeneralizedFrontrunInspector::new(quote_token, db, metrics))
+                    as DynMevInspector
+            }
         }
     }
 }
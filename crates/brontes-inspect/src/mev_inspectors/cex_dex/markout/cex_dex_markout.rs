@@ -7,13 +7,16 @@ use alloy_primitives::FixedBytes;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
-    db::cex::{
-        trades::{
-            config::CexDexTradeConfig,
-            optimistic::OptimisticPrice,
-            time_window_vwam::{ExchangePath, WindowExchangePrice},
+    db::{
+        cex::{
+            trades::{
+                config::CexDexTradeConfig,
+                optimistic::OptimisticPrice,
+                time_window_vwam::{ExchangePath, WindowExchangePrice},
+            },
+            CexExchange, CexInstrument, PerpTickerMap,
         },
-        CexExchange,
+        searcher::Fund,
     },
     display::utils::format_etherscan_url,
     mev::{Bundle, BundleData, MevType, OptimisticTrade},
@@ -49,9 +52,13 @@ pub const FILTER_THRESHOLD: u64 = 20;
 use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
 
 pub struct CexDexMarkoutInspector<'db, DB: LibmdbxReader> {
-    pub utils:     SharedInspectorUtils<'db, DB>,
-    trade_config:  CexDexTradeConfig,
-    cex_exchanges: Vec<CexExchange>,
+    pub utils:      SharedInspectorUtils<'db, DB>,
+    trade_config:   CexDexTradeConfig,
+    /// The spot exchanges
+    cex_exchanges:  Vec<CexExchange>,
+    /// The perp exchanges, only used with `--perp-hedge` when spot liquidity
+    /// is thin
+    perp_exchanges: Vec<CexExchange>,
 }
 
 impl<'db, DB: LibmdbxReader> CexDexMarkoutInspector<'db, DB> {
@@ -62,10 +69,22 @@ impl<'db, DB: LibmdbxReader> CexDexMarkoutInspector<'db, DB> {
         trade_config: CexDexTradeConfig,
         metrics: Option<OutlierMetrics>,
     ) -> Self {
+        let (mut perp_exchanges, cex_exchanges): (Vec<_>, Vec<_>) = cex_exchanges
+            .iter()
+            .copied()
+            .partition(|exchange| exchange.instrument() == CexInstrument::Perpetual);
+
+        // perp prices are only used once they are adjusted to spot, so without
+        // `--perp-hedge` the perp venues aren't used at all
+        if !trade_config.perp_hedge_on_thin_spot {
+            perp_exchanges.clear();
+        }
+
         Self {
             utils: SharedInspectorUtils::new(quote, db, metrics),
             trade_config,
-            cex_exchanges: cex_exchanges.to_owned(),
+            cex_exchanges,
+            perp_exchanges,
         }
     }
 }
@@ -297,8 +316,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         marked_cex_dex: bool,
         tx_info: &TxInfo,
     ) -> Option<CexDexProcessing> {
-        let cex_prices =
-            self.cex_prices_for_swaps(dex_swaps, metadata, marked_cex_dex, tx_info.tx_hash);
+        let cex_prices = self.cex_prices_for_swaps(dex_swaps, metadata, marked_cex_dex, tx_info);
 
         let merged_swaps = cex_prices.dex_swaps.clone();
 
@@ -307,10 +325,18 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
 
         let per_exchange_pnl = self.process_per_exchange(&cex_prices, metadata, tx_info);
 
+        let order_book_res = self.process_order_book(&cex_prices, metadata, tx_info);
+
         let optimstic_res: Option<OptimisticDetails> =
             self.process_optimistic(cex_prices, metadata, tx_info);
 
-        CexDexProcessing::new(merged_swaps, global_vwam, per_exchange_pnl, optimstic_res)
+        CexDexProcessing::new(
+            merged_swaps,
+            global_vwam,
+            per_exchange_pnl,
+            optimstic_res,
+            order_book_res,
+        )
     }
 
     fn process_global_vwam(
@@ -347,8 +373,10 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         metadata: &Metadata,
         tx_info: &TxInfo,
     ) -> Vec<Option<PossibleCexDex>> {
+        // perp venues only have prices in the windows of thin spot swaps, which
+        // were adjusted to spot in `calculate_perp_price`
         cex_prices
-            .per_exchange_trades(self.cex_exchanges.as_slice())
+            .per_exchange_trades(&[self.cex_exchanges.as_slice(), &self.perp_exchanges].concat())
             .into_iter()
             .map(|(exchange, exchange_paths)| {
                 let arb_legs: Vec<Option<ArbLeg>> = cex_prices
@@ -374,6 +402,65 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
             .collect()
     }
 
+    /// Prices each leg by walking the order book of the exchange that gives
+    /// the best execution for the full swap size, so that hedges larger than
+    /// the top of the book are charged their slippage
+    fn process_order_book(
+        &self,
+        cex_prices: &CexPricesForSwaps,
+        metadata: &Metadata,
+        tx_info: &TxInfo,
+    ) -> Option<PossibleCexDex> {
+        let books = metadata.cex_books.as_ref()?;
+        let fees = self.trade_config.fee_lookup(tx_info.fund());
+
+        PossibleCexDex::from_arb_legs(
+            cex_prices
+                .dex_swaps
+                .iter()
+                .map(|dex_swap| {
+                    let pair = Pair(dex_swap.token_in.address, dex_swap.token_out.address);
+                    let fill = books.best_fill(
+                        &self.cex_exchanges,
+                        pair,
+                        &dex_swap.amount_out,
+                        metadata.microseconds_block_timestamp(),
+                        fees,
+                    )?;
+
+                    // the rest of a partial fill can't be priced from the snapshot
+                    if !fill.is_full() {
+                        trace!(
+                            target: "brontes::cex-dex-markout",
+                            exchange = %fill.exchange,
+                            "order book too thin for the swap\n Tx: {}",
+                            format_etherscan_url(&tx_info.tx_hash)
+                        );
+                        return None
+                    }
+
+                    trace!(
+                        target: "brontes::cex-dex-markout",
+                        exchange = %fill.exchange,
+                        slippage = fill.slippage.clone().to_float(),
+                        "walked order book\n Tx: {}",
+                        format_etherscan_url(&tx_info.tx_hash)
+                    );
+
+                    self.profit_classifier(
+                        dex_swap,
+                        vec![pair],
+                        &fill.exchange_path(fees.fees(fill.exchange, &pair)),
+                        fill.exchange,
+                        metadata,
+                        tx_info,
+                        PriceCalcType::OrderBookDepth,
+                    )
+                })
+                .collect(),
+        )
+    }
+
     //TODO: Remove horendous clones, just getting ouput for debugging purposes
     // right now
     pub fn process_optimistic(
@@ -446,6 +533,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
             .unwrap()
             .calculate_time_window_vwam(
                 self.trade_config,
+                tx_info.fund(),
                 &self.cex_exchanges,
                 pair,
                 &vol,
@@ -516,7 +604,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         dex_swaps: Vec<NormalizedSwap>,
         metadata: &Metadata,
         marked_cex_dex: bool,
-        tx_hash: FixedBytes<32>,
+        tx_info: &TxInfo,
     ) -> CexPricesForSwaps {
         let merged_swaps = SharedInspectorUtils::<DB>::cex_merge_possible_swaps(dex_swaps);
 
@@ -524,7 +612,15 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
             .clone()
             .iter()
             .filter(|swap| swap.amount_out != Rational::ZERO)
-            .map(|swap| self.calculate_cex_price(swap, metadata, marked_cex_dex, tx_hash))
+            .map(|swap| {
+                self.calculate_cex_price(
+                    swap,
+                    metadata,
+                    tx_info.fund(),
+                    marked_cex_dex,
+                    tx_info.tx_hash,
+                )
+            })
             .unzip();
 
         CexPricesForSwaps { dex_swaps: merged_swaps, time_window_vwam, optimistic }
@@ -534,6 +630,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         &self,
         swap: &NormalizedSwap,
         metadata: &Metadata,
+        fund: Fund,
         marked_cex_dex: bool,
         tx_hash: FixedBytes<32>,
     ) -> (Option<WindowExchangePrice>, Option<OptimisticPrice>) {
@@ -546,6 +643,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
                 .unwrap()
                 .calculate_time_window_vwam(
                     self.trade_config,
+                    fund,
                     &self.cex_exchanges,
                     pair,
                     &swap.amount_out,
@@ -556,7 +654,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
                 )
         };
 
-        let window = self
+        let mut window = self
             .utils
             .get_metrics()
             .map(|m| m.run_cex_price_window(window_fn))
@@ -565,6 +663,7 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         let optimistic = || {
             metadata.cex_trades.as_ref().unwrap().get_optimistic_vmap(
                 self.trade_config,
+                fund,
                 &self.cex_exchanges,
                 pair,
                 &swap.amount_out,
@@ -576,12 +675,32 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
             )
         };
 
-        let optimistic = self
+        let mut optimistic = self
             .utils
             .get_metrics()
             .map(|m| m.run_cex_price_vol(optimistic))
             .unwrap_or_else(optimistic);
 
+        let thin_spot = window
+            .as_ref()
+            .map_or(true, |window| window.global.volume < swap.amount_out);
+
+        if self.trade_config.perp_hedge_on_thin_spot && thin_spot && !self.perp_exchanges.is_empty()
+        {
+            let (perp_window, perp_optimistic) =
+                self.calculate_perp_price(swap, metadata, fund, marked_cex_dex, tx_hash);
+
+            if perp_window.is_some() {
+                trace!(
+                    target: "brontes::cex-dex-markout",
+                    "thin spot liquidity, hedging on perps\n Tx: {}",
+                    format_etherscan_url(&tx_hash)
+                );
+                window = perp_window;
+                optimistic = perp_optimistic.or(optimistic);
+            }
+        }
+
         if (window.is_none() || optimistic.is_none()) && marked_cex_dex {
             self.utils
                 .get_metrics()
@@ -591,6 +710,57 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         (window, optimistic)
     }
 
+    /// Prices the hedge on the perp exchanges, converted back to its spot
+    /// equivalent with the perp tickers of the block. Without tickers the
+    /// perps are assumed to trade at the index with no funding.
+    fn calculate_perp_price(
+        &self,
+        swap: &NormalizedSwap,
+        metadata: &Metadata,
+        fund: Fund,
+        marked_cex_dex: bool,
+        tx_hash: FixedBytes<32>,
+    ) -> (Option<WindowExchangePrice>, Option<OptimisticPrice>) {
+        let pair = Pair(swap.token_in.address, swap.token_out.address);
+        let timestamp = metadata.microseconds_block_timestamp();
+        let horizon = self.trade_config.perp_hedge_horizon_us;
+
+        let no_tickers = PerpTickerMap::default();
+        let tickers = metadata.perp_tickers.as_ref().unwrap_or(&no_tickers);
+        let cex_trades = metadata.cex_trades.as_ref().unwrap();
+
+        let window = cex_trades
+            .calculate_time_window_vwam(
+                self.trade_config,
+                fund,
+                &self.perp_exchanges,
+                pair,
+                &swap.amount_out,
+                timestamp,
+                marked_cex_dex,
+                swap,
+                tx_hash,
+            )
+            .map(|window| tickers.adjust_window(window, timestamp, horizon));
+
+        let optimistic = cex_trades
+            .get_optimistic_vmap(
+                self.trade_config,
+                fund,
+                &self.perp_exchanges,
+                pair,
+                &swap.amount_out,
+                timestamp,
+                None,
+                marked_cex_dex,
+                swap,
+                tx_hash,
+            )
+            .map(|price| tickers.adjust_optimistic(price, timestamp, horizon));
+
+        (window, optimistic)
+    }
+
     /// Accounts for gas costs in the calculation of potential arbitrage
     /// profits. This function calculates the final pnl for the transaction by
     /// subtracting gas costs from the total potential arbitrage profits.
@@ -115,6 +115,7 @@ pub struct CexDexProcessing {
     pub per_exchange_pnl:    Vec<Option<PossibleCexDex>>,
     pub max_profit:          Option<PossibleCexDex>,
     pub optimistic_details:  Option<OptimisticDetails>,
+    pub order_book_depth:    Option<PossibleCexDex>,
 }
 
 impl CexDexProcessing {
@@ -123,6 +124,7 @@ impl CexDexProcessing {
         global_vmam_cex_dex: Option<PossibleCexDex>,
         per_exchange_pnl: Vec<Option<PossibleCexDex>>,
         optimistic_details: Option<OptimisticDetails>,
+        order_book_depth: Option<PossibleCexDex>,
     ) -> Option<Self> {
         let mut this = Self {
             per_exchange_pnl,
@@ -130,6 +132,7 @@ impl CexDexProcessing {
             max_profit: None,
             global_vmam_cex_dex,
             optimistic_details,
+            order_book_depth,
         };
         this.construct_max_profit_route()?;
         Some(this)
@@ -189,6 +192,10 @@ impl CexDexProcessing {
         if let Some(arb) = self.global_vmam_cex_dex.as_mut() {
             arb.adjust_for_gas_cost(gas_cost)
         }
+
+        if let Some(arb) = self.order_book_depth.as_mut() {
+            arb.adjust_for_gas_cost(gas_cost)
+        }
     }
 
     pub fn into_bundle(
@@ -211,27 +218,55 @@ impl CexDexProcessing {
             .as_ref()
             .map(|v| v.aggregate_pnl_maker.clone());
 
-        let (header_pnl, header_pnl_methodology) = [
-            (max_profit, CexMethodology::OptimalRouteVWAP),
-            (optimistic, CexMethodology::Optimistic),
-            (window, CexMethodology::GlobalWWAP),
-        ]
-        .into_iter()
-        .filter_map(|(pnl, methodology)| pnl.map(|p| (p, methodology)))
-        .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-        .unwrap_or_else(|| {
-            (
-                self.max_profit
-                    .as_ref()
-                    .expect(
-                        "Max profit should always exist, CexDex inspector should have returned \
-                         early",
-                    )
-                    .aggregate_pnl_maker
-                    .clone(),
-                CexMethodology::OptimalRouteVWAP,
-            )
-        });
+        // the trade based methodologies assume the full hedge fills at the
+        // observed price, so when we can walk the books for every leg we use
+        // the depth aware pnl instead
+        let order_book_depth = self
+            .order_book_depth
+            .as_ref()
+            .filter(|o| o.arb_legs.iter().all(Option::is_some))
+            .map(|o| (o.aggregate_pnl_maker.clone(), CexMethodology::OrderBookDepth));
+
+        let (header_pnl, header_pnl_methodology) = order_book_depth
+            .or_else(|| {
+                [
+                    (max_profit, CexMethodology::OptimalRouteVWAP),
+                    (optimistic, CexMethodology::Optimistic),
+                    (window, CexMethodology::GlobalWWAP),
+                ]
+                .into_iter()
+                .filter_map(|(pnl, methodology)| pnl.map(|p| (p, methodology)))
+                .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            })
+            .unwrap_or_else(|| {
+                (
+                    self.max_profit
+                        .as_ref()
+                        .expect(
+                            "Max profit should always exist, CexDex inspector should have \
+                             returned early",
+                        )
+                        .aggregate_pnl_maker
+                        .clone(),
+                    CexMethodology::OptimalRouteVWAP,
+                )
+            });
+
+        // the fees of the exchanges the header pnl was routed through
+        let header_legs = match header_pnl_methodology {
+            CexMethodology::OrderBookDepth => self.order_book_depth.as_ref().map(|o| &o.arb_legs),
+            CexMethodology::OptimalRouteVWAP => self.max_profit.as_ref().map(|m| &m.arb_legs),
+            CexMethodology::Optimistic => self.optimistic_details.as_ref().map(|o| &o.arb_legs),
+            CexMethodology::GlobalWWAP => self.global_vmam_cex_dex.as_ref().map(|g| &g.arb_legs),
+            CexMethodology::None => None,
+        };
+        let fee_assumption = header_legs
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|leg| leg.price.fee_assumption)
+            .max()
+            .unwrap_or_default();
 
         Some((
             header_pnl.to_float(),
@@ -240,6 +275,7 @@ impl CexDexProcessing {
                 block_timestamp: meta.microseconds_block_timestamp(),
                 tx_hash: tx_info.tx_hash,
                 header_pnl_methodology,
+                fee_assumption,
                 global_vmap_pnl_maker: self
                     .global_vmam_cex_dex
                     .as_ref()
@@ -772,4 +808,5 @@ pub enum PriceCalcType {
     Optimistic,
     TimeWindowGlobal,
     TimeWindowPerEx,
+    OrderBookDepth,
 }
@@ -50,7 +50,7 @@ use alloy_primitives::Address;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
-    db::cex::{quotes::FeeAdjustedQuote, CexExchange},
+    db::cex::{quotes::FeeAdjustedQuote, CexExchange, CexFeeSchedule, FeeLookup},
     display::utils::format_etherscan_url,
     mev::{Bundle, BundleData, MevType},
     normalized_actions::{accounting::ActionAccounting, Action, NormalizedSwap},
@@ -78,6 +78,7 @@ pub struct CexDexQuotesInspector<'db, DB: LibmdbxReader> {
     utils:                SharedInspectorUtils<'db, DB>,
     _quotes_fetch_offset: u64,
     _cex_exchanges:       Vec<CexExchange>,
+    fee_schedule:         Option<&'static CexFeeSchedule>,
 }
 
 impl<'db, DB: LibmdbxReader> CexDexQuotesInspector<'db, DB> {
@@ -89,17 +90,21 @@ impl<'db, DB: LibmdbxReader> CexDexQuotesInspector<'db, DB> {
     /// * `db` - Database reader to our local libmdbx database
     /// * `cex_exchanges` - List of centralized exchanges to consider for
     ///   arbitrage.
+    /// * `cex_fee_schedule` - The cex fees to apply, the best tier fees of each
+    ///   exchange are used without one
     pub fn new(
         quote: Address,
         db: &'db DB,
         cex_exchanges: &[CexExchange],
         quotes_fetch_offset: u64,
+        cex_fee_schedule: Option<&'static CexFeeSchedule>,
         metrics: Option<OutlierMetrics>,
     ) -> Self {
         Self {
             utils:                SharedInspectorUtils::new(quote, db, metrics),
             _quotes_fetch_offset: quotes_fetch_offset,
             _cex_exchanges:       cex_exchanges.to_owned(),
+            fee_schedule:         cex_fee_schedule,
         }
     }
 }
@@ -268,7 +273,8 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
         //
         let swaps = SharedInspectorUtils::<DB>::cex_merge_possible_swaps(dex_swaps);
 
-        let quotes = self.cex_quotes_for_swap(&swaps, metadata, 0, None);
+        let fees = FeeLookup::new(self.fee_schedule, tx_info.fund());
+        let quotes = self.cex_quotes_for_swap(&swaps, metadata, 0, None, fees);
         let cex_dex = self.detect_cex_dex_opportunity(&swaps, quotes, metadata, tx_info)?;
         let cex_dex_processing = CexDexProcessing { dex_swaps: swaps, pnl: cex_dex };
         Some(cex_dex_processing)
@@ -335,6 +341,7 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 &Pair(swap.token_in.address, self.utils.quote),
                 metadata.microseconds_block_timestamp(),
                 None,
+                FeeLookup::default(),
             )?
             .maker_taker_mid()
             .0;
@@ -397,6 +404,7 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
         metadata: &Metadata,
         time_delta: u64,
         max_time_diff: Option<u64>,
+        fees: FeeLookup,
     ) -> Vec<Option<FeeAdjustedQuote>> {
         dex_swaps
             .iter()
@@ -409,6 +417,7 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                         &pair,
                         metadata.microseconds_block_timestamp() + (time_delta * 1_000_000),
                         max_time_diff,
+                        fees,
                     )
                     .or_else(|| {
                         debug!(
@@ -465,8 +474,9 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
             && should_include_if_know_cex_dex;
 
         if is_cex_dex_based_on_historical_activity || should_include_based_on_pnl {
+            let fees = FeeLookup::new(self.fee_schedule, info.fund());
             let t2 = self
-                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 2, None)
+                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 2, None, fees)
                 .into_iter()
                 .map(|quote_option| {
                     quote_option.map_or(0.0, |quote| quote.maker_taker_mid().0.to_float())
@@ -474,7 +484,7 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 .collect_vec();
 
             let t12 = self
-                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 12, Some(500_000))
+                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 12, Some(500_000), fees)
                 .into_iter()
                 .map(|quote_option| {
                     quote_option.map_or(0.0, |quote| quote.maker_taker_mid().0.to_float())
@@ -482,7 +492,13 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 .collect_vec();
 
             let t30 = self
-                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 30, Some(2_000_000))
+                .cex_quotes_for_swap(
+                    &possible_cex_dex.dex_swaps,
+                    metadata,
+                    30,
+                    Some(2_000_000),
+                    fees,
+                )
                 .into_iter()
                 .map(|quote_option| {
                     quote_option.map_or(0.0, |quote| quote.maker_taker_mid().0.to_float())
@@ -490,7 +506,13 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 .collect_vec();
 
             let t60 = self
-                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 60, Some(4_000_000))
+                .cex_quotes_for_swap(
+                    &possible_cex_dex.dex_swaps,
+                    metadata,
+                    60,
+                    Some(4_000_000),
+                    fees,
+                )
                 .into_iter()
                 .map(|quote_option| {
                     quote_option.map_or(0.0, |quote| quote.maker_taker_mid().0.to_float())
@@ -498,14 +520,29 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
                 .collect_vec();
 
             let t300 = self
-                .cex_quotes_for_swap(&possible_cex_dex.dex_swaps, metadata, 300, Some(15_000_000))
+                .cex_quotes_for_swap(
+                    &possible_cex_dex.dex_swaps,
+                    metadata,
+                    300,
+                    Some(15_000_000),
+                    fees,
+                )
                 .into_iter()
                 .map(|quote_option| {
                     quote_option.map_or(0.0, |quote| quote.maker_taker_mid().0.to_float())
                 })
                 .collect_vec();
 
-            possible_cex_dex.into_bundle(info, metadata.block_timestamp, t2, t12, t30, t60, t300)
+            possible_cex_dex.into_bundle(
+                info,
+                metadata.block_timestamp,
+                fees,
+                t2,
+                t12,
+                t30,
+                t60,
+                t300,
+            )
         } else {
             None
         }
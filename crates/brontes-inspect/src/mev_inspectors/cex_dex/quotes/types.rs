@@ -1,7 +1,8 @@
 use brontes_types::{
-    db::cex::CexExchange,
+    db::cex::{CexExchange, FeeLookup},
     mev::{BundleData, CexDexQuote},
     normalized_actions::NormalizedSwap,
+    pair::Pair,
     ToFloatNearest, TxInfo,
 };
 use malachite::{num::basic::traits::Zero, Rational};
@@ -51,12 +52,26 @@ impl CexDexProcessing {
         self,
         tx_info: &TxInfo,
         block_timestamp: u64,
+        fees: FeeLookup,
         t2_mid_price: Vec<f64>,
         t12_mid_price: Vec<f64>,
         t30_mid_price: Vec<f64>,
         t60_mid_price: Vec<f64>,
         t300_mid_price: Vec<f64>,
     ) -> Option<(f64, BundleData)> {
+        // the legs are in the order of the swaps they hedge
+        let fee_assumption = self
+            .pnl
+            .arb_legs
+            .iter()
+            .zip(&self.dex_swaps)
+            .filter_map(|(leg, swap)| {
+                let pair = Pair(swap.token_in.address, swap.token_out.address);
+                Some(fees.fees(leg.as_ref()?.exchange, &pair).assumption)
+            })
+            .max()
+            .unwrap_or_default();
+
         Some((
             self.pnl.aggregate_pnl,
             BundleData::CexDexQuote(CexDexQuote {
@@ -76,6 +91,7 @@ impl CexDexProcessing {
                 t300_mid_price,
                 pnl: self.pnl.aggregate_pnl,
                 exchange: self.pnl.arb_legs[0].as_ref()?.exchange,
+                fee_assumption,
                 gas_details: tx_info.gas_details,
                 swaps: self.dex_swaps,
             }),
@@ -0,0 +1,450 @@
+use std::sync::Arc;
+
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    db::dex::PriceAt,
+    mev::{Bundle, BundleData, GeneralizedFrontrun, MevType},
+    normalized_actions::{
+        accounting::{ActionAccounting, AddressDeltas},
+        Action,
+    },
+    tree::{BlockTree, GasDetails, TouchedCall},
+    ActionIter, BlockData, FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
+};
+use itertools::multizip;
+use malachite::Rational;
+use reth_primitives::{Address, B256};
+
+use super::{MAX_PROFIT, MIN_PROFIT};
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+
+/// Minimum share of calls two transactions need to have in common for one to
+/// be considered a copy of the other.
+const MIN_CALL_TREE_SIMILARITY: f64 = 0.8;
+/// Transactions that make fewer calls than this are too generic to say
+/// anything about them being copied.
+const MIN_TOUCHED_CALLS: usize = 2;
+/// The victim is only flagged if it made less than this share of the
+/// frontrunners profit.
+const MAX_VICTIM_PROFIT_SHARE: f64 = 0.1;
+
+pub struct GeneralizedFrontrunInspector<'db, DB: LibmdbxReader> {
+    utils: SharedInspectorUtils<'db, DB>,
+}
+
+impl<'db, DB: LibmdbxReader> GeneralizedFrontrunInspector<'db, DB> {
+    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for GeneralizedFrontrunInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    fn get_id(&self) -> &str {
+        "GeneralizedFrontrun"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn inspect_block(&self, mut data: MultiBlockData) -> Self::Result {
+        let Some(BlockData { metadata, tree }) = data.per_block_data.pop() else { return vec![] };
+        self.utils
+            .get_metrics()
+            .map(|m| {
+                m.run_inspector(MevType::GeneralizedFrontrun, || {
+                    self.inspect_block_inner(tree.clone(), metadata.clone())
+                })
+            })
+            .unwrap_or_else(|| self.inspect_block_inner(tree, metadata))
+    }
+}
+
+/// A transaction in the block along with everything needed to compare it to
+/// the others.
+struct TxProfile {
+    info:    TxInfo,
+    to:      Address,
+    calls:   FastHashSet<TouchedCall>,
+    actions: Vec<Action>,
+    deltas:  AddressDeltas,
+    profit:  Option<Rational>,
+}
+
+/// A later transaction with the same call tree as a frontrun.
+struct Victim {
+    tx_hash:     B256,
+    reverted:    bool,
+    profit_usd:  f64,
+    gas_details: GasDetails,
+}
+
+impl<DB: LibmdbxReader> GeneralizedFrontrunInspector<'_, DB> {
+    fn inspect_block_inner(
+        &self,
+        tree: Arc<BlockTree<Action>>,
+        metadata: Arc<Metadata>,
+    ) -> Vec<Bundle> {
+        let search_args = TreeSearchBuilder::default()
+            .with_actions([Action::is_transfer, Action::is_eth_transfer]);
+
+        let (hashes, actions): (Vec<_>, Vec<_>) = tree.clone().collect_all(search_args).unzip();
+        let tx_info = tree.get_tx_info_batch(&hashes, self.utils.db);
+
+        let profiles = multizip((hashes, actions, tx_info))
+            .filter_map(|(tx_hash, actions, info)| {
+                let root = tree.get_root(tx_hash)?;
+                let info = info?;
+                let to = root.get_to_address();
+                let calls = normalize_calls(&root.touched_calls, info.eoa, to);
+
+                if calls.len() < MIN_TOUCHED_CALLS {
+                    return None
+                }
+
+                self.build_profile(info, to, calls, actions, metadata.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let mut claimed_victims = FastHashSet::default();
+
+        profiles
+            .iter()
+            .filter_map(|frontrun| {
+                let profit = frontrun.profit.as_ref()?.to_float();
+                if profit <= 0.0 {
+                    return None
+                }
+
+                let victim = self.find_victim(
+                    frontrun,
+                    profit,
+                    &profiles,
+                    &tree,
+                    &metadata,
+                    &claimed_victims,
+                )?;
+                claimed_victims.insert(victim.tx_hash);
+
+                Some(self.build_bundle(frontrun, profit, victim, metadata.clone()))
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn build_profile(
+        &self,
+        info: TxInfo,
+        to: Address,
+        calls: FastHashSet<TouchedCall>,
+        actions: Vec<Action>,
+        metadata: Arc<Metadata>,
+    ) -> Option<TxProfile> {
+        let deltas = actions
+            .clone()
+            .into_iter()
+            .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
+            .account_for_actions();
+
+        let profit = self
+            .utils
+            .get_deltas_usd(
+                info.tx_index,
+                PriceAt::After,
+                &info.collect_address_set_for_accounting(),
+                &deltas,
+                metadata.clone(),
+                false,
+            )
+            .map(|rev| {
+                rev - metadata.get_gas_price_usd(info.gas_details.gas_paid(), self.utils.quote)
+            })
+            .filter(|profit| profit < &MAX_PROFIT && profit > &MIN_PROFIT);
+
+        Some(TxProfile { info, to, calls, actions, deltas, profit })
+    }
+
+    /// Finds the most similar later transaction from a different sender that
+    /// reverted or made a fraction of the frontruns profit. Private victims are
+    /// skipped as they couldn't have been copied from the mempool.
+    fn find_victim(
+        &self,
+        frontrun: &TxProfile,
+        frontrun_profit: f64,
+        profiles: &[TxProfile],
+        tree: &BlockTree<Action>,
+        metadata: &Metadata,
+        claimed: &FastHashSet<B256>,
+    ) -> Option<Victim> {
+        let is_candidate = |tx_hash: &B256, tx_index: u64, eoa: Address| {
+            tx_index > frontrun.info.tx_index
+                && eoa != frontrun.info.eoa
+                && !claimed.contains(tx_hash)
+                && !metadata.private_flow.contains(tx_hash)
+        };
+
+        let executed = profiles
+            .iter()
+            .filter(|victim| {
+                is_candidate(&victim.info.tx_hash, victim.info.tx_index, victim.info.eoa)
+            })
+            .filter_map(|victim| {
+                let victim_profit = victim.profit.as_ref()?.to_float();
+                if victim_profit >= frontrun_profit * MAX_VICTIM_PROFIT_SHARE {
+                    return None
+                }
+
+                let similarity = call_tree_similarity(
+                    (frontrun.to, &frontrun.calls),
+                    (victim.to, &victim.calls),
+                );
+
+                Some((
+                    similarity,
+                    Victim {
+                        tx_hash:     victim.info.tx_hash,
+                        reverted:    false,
+                        profit_usd:  victim_profit,
+                        gas_details: victim.info.gas_details,
+                    },
+                ))
+            });
+
+        let reverted = tree
+            .reverted_txs
+            .iter()
+            .filter(|victim| is_candidate(&victim.tx_hash, victim.position as u64, victim.from))
+            .map(|victim| {
+                let calls = normalize_calls(&victim.touched_calls, victim.from, victim.to);
+                let similarity =
+                    call_tree_similarity((frontrun.to, &frontrun.calls), (victim.to, &calls));
+                let gas_paid = metadata
+                    .get_gas_price_usd(victim.gas_details.gas_paid(), self.utils.quote)
+                    .to_float();
+
+                (
+                    similarity,
+                    Victim {
+                        tx_hash:     victim.tx_hash,
+                        reverted:    true,
+                        profit_usd:  -gas_paid,
+                        gas_details: victim.gas_details,
+                    },
+                )
+            });
+
+        executed
+            .chain(reverted)
+            .filter(|(similarity, _)| *similarity >= MIN_CALL_TREE_SIMILARITY)
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, victim)| victim)
+    }
+
+    fn build_bundle(
+        &self,
+        frontrun: &TxProfile,
+        profit: f64,
+        victim: Victim,
+        metadata: Arc<Metadata>,
+    ) -> Bundle {
+        let header = self.utils.build_bundle_header(
+            vec![frontrun.deltas.clone()],
+            vec![frontrun.info.tx_hash],
+            &frontrun.info,
+            profit,
+            &[frontrun.info.gas_details],
+            metadata.clone(),
+            MevType::GeneralizedFrontrun,
+            false,
+            |this, token, amount| {
+                this.get_token_value_dex(
+                    frontrun.info.tx_index as usize,
+                    PriceAt::After,
+                    token,
+                    &amount,
+                    &metadata,
+                )
+            },
+        );
+
+        Bundle {
+            header,
+            data: BundleData::GeneralizedFrontrun(GeneralizedFrontrun {
+                block_number:         metadata.block_num,
+                frontrun_tx_hash:     frontrun.info.tx_hash,
+                frontrun_transfers:   frontrun
+                    .actions
+                    .clone()
+                    .into_iter()
+                    .collect_action_vec(Action::try_transfer),
+                frontrun_gas_details: frontrun.info.gas_details,
+                victim_tx_hash:       victim.tx_hash,
+                victim_reverted:      victim.reverted,
+                victim_profit_usd:    victim.profit_usd,
+                victim_gas_details:   victim.gas_details,
+            }),
+        }
+    }
+}
+
+/// Zeroes the arguments of each call that hold the sender or the contract the
+/// transaction was sent to. A copier swaps these for its own addresses when
+/// it replays a call, the rest of the input is left as is.
+fn normalize_calls(calls: &[TouchedCall], eoa: Address, to: Address) -> FastHashSet<TouchedCall> {
+    calls
+        .iter()
+        .map(|call| {
+            let mut input = call.input.to_vec();
+            if let Some(args) = input.get_mut(4..) {
+                args.chunks_exact_mut(32)
+                    .filter(|word| {
+                        word[..12].iter().all(|byte| *byte == 0)
+                            && (word[12..] == eoa[..] || word[12..] == to[..])
+                    })
+                    .for_each(|word| word.fill(0));
+            }
+
+            TouchedCall { to: call.to, input: input.into() }
+        })
+        .collect()
+}
+
+/// Jaccard similarity of the calls, with their selector and input, made by two
+/// transactions, each given with the contract it was sent to. A copy replays
+/// a call the victim made into the contract it was sent to, either directly or
+/// through the copiers own contract, so the frontrun has to make the same
+/// call. Otherwise txes that only call into the same contracts would match,
+/// such as two competing arbs or two unrelated swaps over the same pools.
+fn call_tree_similarity(
+    (frontrun_to, frontrun): (Address, &FastHashSet<TouchedCall>),
+    (victim_to, victim): (Address, &FastHashSet<TouchedCall>),
+) -> f64 {
+    if !victim
+        .iter()
+        .any(|call| call.to == victim_to && frontrun.contains(call))
+    {
+        return 0.0
+    }
+
+    // the calls into the copiers contract are left out, as the victim never
+    // makes them
+    let frontrun = frontrun
+        .iter()
+        .filter(|call| frontrun_to == victim_to || call.to != frontrun_to)
+        .collect::<FastHashSet<_>>();
+    let victim = victim.iter().collect::<FastHashSet<_>>();
+
+    let total = frontrun.union(&victim).count();
+    if total < MIN_TOUCHED_CALLS {
+        return 0.0
+    }
+
+    frontrun.intersection(&victim).count() as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, U256};
+    use brontes_types::{tree::TouchedCall, FastHashSet};
+
+    use super::{call_tree_similarity, normalize_calls};
+
+    /// A call with a selector and one word per argument
+    fn call(to: Address, selector: u8, args: &[U256]) -> TouchedCall {
+        let mut input = vec![selector; 4];
+        args.iter()
+            .for_each(|arg| input.extend(arg.to_be_bytes::<32>()));
+
+        TouchedCall { to, input: input.into() }
+    }
+
+    fn address_arg(address: Address) -> U256 {
+        U256::from_be_slice(&address[..])
+    }
+
+    #[test]
+    fn test_call_tree_similarity_copied_call() {
+        let [pool0, pool1, token, copier, original, copier_eoa, victim_eoa] =
+            [1u8, 2, 3, 4, 5, 6, 7].map(Address::with_last_byte);
+
+        let arb = |eoa: Address, contract: Address| {
+            [
+                call(original, 1, &[U256::from(10), address_arg(eoa)]),
+                call(pool0, 2, &[U256::from(10), address_arg(contract)]),
+                call(pool1, 2, &[U256::from(20), address_arg(contract)]),
+                call(token, 3, &[address_arg(pool0), U256::from(10)]),
+            ]
+        };
+        let victim = normalize_calls(&arb(victim_eoa, original), victim_eoa, original);
+
+        // replayed through the copiers contract, which the victim never calls
+        let mut frontrun = arb(copier_eoa, copier).to_vec();
+        frontrun.insert(0, call(copier, 9, &[]));
+        let frontrun = normalize_calls(&frontrun, copier_eoa, copier);
+        assert_eq!(call_tree_similarity((copier, &frontrun), (original, &victim)), 1.0);
+
+        // replayed directly
+        let frontrun = normalize_calls(&arb(copier_eoa, original), copier_eoa, original);
+        assert_eq!(call_tree_similarity((original, &frontrun), (original, &victim)), 1.0);
+
+        // partial copy
+        let mut frontrun = arb(copier_eoa, copier).to_vec();
+        frontrun.remove(2);
+        let frontrun = normalize_calls(&frontrun, copier_eoa, copier);
+        assert_eq!(call_tree_similarity((copier, &frontrun), (original, &victim)), 0.75);
+    }
+
+    #[test]
+    fn test_call_tree_similarity_unrelated_swaps() {
+        let [router, pool, token, eoa0, eoa1] = [1u8, 2, 3, 4, 5].map(Address::with_last_byte);
+
+        // two users swapping different amounts on the same pool through the
+        // same router
+        let swap = |eoa: Address, amount: u64| {
+            normalize_calls(
+                &[
+                    call(router, 1, &[U256::from(amount), address_arg(eoa)]),
+                    call(token, 2, &[address_arg(eoa), address_arg(pool), U256::from(amount)]),
+                    call(pool, 3, &[U256::from(amount * 2), address_arg(eoa)]),
+                ],
+                eoa,
+                router,
+            )
+        };
+
+        assert_eq!(call_tree_similarity((router, &swap(eoa0, 10)), (router, &swap(eoa1, 30))), 0.0);
+        assert_eq!(call_tree_similarity((router, &swap(eoa0, 10)), (router, &swap(eoa1, 10))), 1.0);
+    }
+
+    #[test]
+    fn test_call_tree_similarity_competing_arbs() {
+        let [pool0, pool1, token0, bot0, bot1] = [1u8, 2, 3, 4, 5].map(Address::with_last_byte);
+
+        // both arbs run through the same pools, but from their own contracts
+        let arb = |bot: Address| {
+            normalize_calls(
+                &[
+                    call(bot, 1, &[]),
+                    call(pool0, 2, &[U256::from(10), address_arg(bot)]),
+                    call(pool1, 2, &[U256::from(20), address_arg(bot)]),
+                    call(token0, 3, &[address_arg(pool0), U256::from(10)]),
+                ],
+                Address::ZERO,
+                bot,
+            )
+        };
+
+        assert_eq!(call_tree_similarity((bot0, &arb(bot0)), (bot1, &arb(bot1))), 0.0);
+        assert_eq!(call_tree_similarity((bot1, &arb(bot1)), (bot0, &arb(bot0))), 0.0);
+    }
+
+    #[test]
+    fn test_call_tree_similarity_needs_min_calls() {
+        let contract = Address::with_last_byte(1);
+        let calls: FastHashSet<_> = [call(contract, 1, &[])].into_iter().collect();
+
+        assert_eq!(call_tree_similarity((contract, &calls), (contract, &calls)), 0.0);
+    }
+}
@@ -6,11 +6,13 @@ use brontes_types::{
     db::dex::PriceAt,
     mev::{Bundle, BundleData, Liquidation, MevType},
     normalized_actions::{accounting::ActionAccounting, Action},
-    ActionIter, BlockData, FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
+    structured_trace::{TraceActions, TxTrace},
+    ActionIter, BlockData, BlockTree, FastHashSet, MultiBlockData, ToFloatNearest,
+    TreeSearchBuilder, TxInfo,
 };
 use itertools::multizip;
 use malachite::{num::basic::traits::Zero, Rational};
-use reth_primitives::{b256, Address};
+use reth_primitives::{Address, B256};
 
 use super::{MAX_PROFIT, MIN_PROFIT};
 use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
@@ -37,8 +39,8 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
     }
 
     fn inspect_block(&self, mut data: MultiBlockData) -> Self::Result {
-        let block = data.per_block_data.pop().expect("no blocks");
-        let BlockData { metadata, tree } = block;
+        let Some(BlockData { metadata, tree }) = data.per_block_data.pop() else { return vec![] };
+        let oracle_updates = self.oracle_updates(&tree, metadata.block_num);
 
         let ex = || {
             let (tx, liq): (Vec<_>, Vec<_>) = tree
@@ -61,7 +63,18 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
                         .flatten_nested_actions_default(liq.into_iter())
                         .collect::<Vec<_>>();
 
-                    self.calculate_liquidation(info, metadata.clone(), actions)
+                    let touched: FastHashSet<_> = tree
+                        .get_root(info.tx_hash)
+                        .map(|root| root.touched_contracts().collect())
+                        .unwrap_or_default();
+                    let trigger = find_oracle_trigger(
+                        &oracle_updates,
+                        metadata.block_num,
+                        info.tx_index,
+                        &touched,
+                    );
+
+                    self.calculate_liquidation(info, metadata.clone(), actions, trigger)
                 })
                 .collect::<Vec<_>>()
         };
@@ -73,11 +86,39 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
 }
 
 impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
+    /// Collects the oracle updates of the previous and the current block,
+    /// ordered from oldest to newest. The previous block is read from its
+    /// stored traces so the inspector doesn't need a second block in its
+    /// window.
+    fn oracle_updates(&self, tree: &BlockTree<Action>, block_number: u64) -> Vec<OracleTrigger> {
+        let previous = block_number
+            .checked_sub(1)
+            .map(|previous| match self.utils.db.load_trace(previous) {
+                Ok(traces) => oracle_updates_from_traces(&traces, |address| {
+                    self.utils
+                        .db
+                        .get_protocol_details(address)
+                        .is_ok_and(|info| info.protocol.is_oracle())
+                }),
+                Err(e) => {
+                    tracing::debug!(block = previous, err=%e, "no traces for the previous block");
+                    vec![]
+                }
+            })
+            .unwrap_or_default();
+
+        previous
+            .into_iter()
+            .chain(oracle_updates_from_tree(tree, block_number))
+            .collect()
+    }
+
     fn calculate_liquidation(
         &self,
         info: TxInfo,
         metadata: Arc<Metadata>,
         actions: Vec<Action>,
+        trigger: Option<&OracleTrigger>,
     ) -> Option<Bundle> {
         let (swaps, liqs): (Vec<_>, Vec<_>) = actions
             .clone()
@@ -146,7 +187,10 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
         let new_liquidation = Liquidation {
             block_number:        metadata.block_num,
             liquidation_tx_hash: info.tx_hash,
-            trigger:             b256!(),
+            trigger:             trigger.map(|t| t.tx_hash).unwrap_or_default(),
+            trigger_block:       trigger.map(|t| t.block_number),
+            trigger_tx_index:    trigger.map(|t| t.tx_index),
+            blocks_to_liquidate: trigger.map(|t| metadata.block_num - t.block_number),
             liquidation_swaps:   swaps,
             liquidations:        liqs,
             gas_details:         info.gas_details,
@@ -156,11 +200,107 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
     }
 }
 
+/// A transaction that pushed a new price to one or more oracles.
+#[derive(Debug, Clone, PartialEq)]
+struct OracleTrigger {
+    tx_hash:      B256,
+    block_number: u64,
+    tx_index:     u64,
+    oracles:      FastHashSet<Address>,
+}
+
+/// Collects the oracle updates of a classified block. A forwarded transmission
+/// is classified on the call to the feed, so each update is only seen once.
+fn oracle_updates_from_tree(tree: &BlockTree<Action>, block_number: u64) -> Vec<OracleTrigger> {
+    tree.tx_roots
+        .iter()
+        .filter_map(|root| {
+            let oracles = root
+                .collect(&TreeSearchBuilder::default().with_action(Action::is_oracle_update))
+                .into_iter()
+                .filter_map(Action::try_oracle_update)
+                .map(|update| update.oracle)
+                .collect::<FastHashSet<_>>();
+
+            (!oracles.is_empty()).then(|| OracleTrigger {
+                tx_hash: root.tx_hash,
+                block_number,
+                tx_index: root.position as u64,
+                oracles,
+            })
+        })
+        .collect()
+}
+
+/// Collects the oracle updates of a block from its raw traces, these are the
+/// state changing calls to an oracle that emitted a log. Reads of the price
+/// are static calls, so they are never picked up.
+fn oracle_updates_from_traces(
+    traces: &[TxTrace],
+    is_oracle: impl Fn(Address) -> bool,
+) -> Vec<OracleTrigger> {
+    traces
+        .iter()
+        .filter(|tx| tx.is_success)
+        .filter_map(|tx| {
+            let oracles = tx
+                .trace
+                .iter()
+                .filter(|trace| {
+                    trace.trace.action.is_call()
+                        && !trace.is_static_call()
+                        && !trace.is_delegate_call()
+                        && !trace.logs.is_empty()
+                })
+                .map(|trace| trace.get_to_address())
+                .filter(|address| is_oracle(*address))
+                .collect::<FastHashSet<_>>();
+
+            (!oracles.is_empty()).then(|| OracleTrigger {
+                tx_hash: tx.tx_hash,
+                block_number: tx.block_number,
+                tx_index: tx.tx_index,
+                oracles,
+            })
+        })
+        .collect()
+}
+
+/// Finds the most recent oracle update before the liquidation for an oracle
+/// the liquidation read from. `touched` are the call targets of the
+/// liquidation's trace, which includes the static calls made to read the
+/// price. The liquidation tx itself can carry the update when the liquidator
+/// pushes the price themselves.
+fn find_oracle_trigger<'a>(
+    updates: &'a [OracleTrigger],
+    block_number: u64,
+    tx_index: u64,
+    touched: &FastHashSet<Address>,
+) -> Option<&'a OracleTrigger> {
+    updates
+        .iter()
+        .rev()
+        .filter(|update| {
+            update.block_number < block_number
+                || (update.block_number == block_number && update.tx_index <= tx_index)
+        })
+        .find(|update| !update.oracles.is_disjoint(touched))
+}
+
 #[cfg(test)]
 mod tests {
 
-    use alloy_primitives::hex;
+    use alloy_primitives::{hex, Address, Bytes, Log, LogData, B256};
+    use brontes_types::{
+        structured_trace::{TransactionTraceWithLogs, TxTrace},
+        FastHashSet,
+    };
+    use itertools::Itertools;
+    use reth_rpc_types::trace::parity::{
+        Action as TraceAction, CallAction, CallType, TransactionTrace,
+    };
 
+    use super::{find_oracle_trigger, oracle_updates_from_traces, OracleTrigger};
     use crate::{
         test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS},
         Inspectors,
@@ -203,4 +343,89 @@ mod tests {
 
         inspector_util.run_inspector(config, None).await.unwrap();
     }
+
+    #[test]
+    fn test_find_oracle_trigger_picks_latest_read_oracle() {
+        let [feed, other_feed, pool] = [1u8, 2, 3].map(Address::with_last_byte);
+        let update = |block_number, tx_index, oracle| OracleTrigger {
+            tx_hash: B256::with_last_byte(tx_index as u8),
+            block_number,
+            tx_index,
+            oracles: [oracle].into_iter().collect(),
+        };
+
+        let updates = vec![
+            update(10, 4, feed),
+            update(11, 1, feed),
+            update(11, 2, other_feed),
+            update(11, 9, feed),
+        ];
+        let touched: FastHashSet<_> = [feed, pool].into_iter().collect();
+
+        let trigger = find_oracle_trigger(&updates, 11, 5, &touched).unwrap();
+        assert_eq!((trigger.block_number, trigger.tx_index), (11, 1));
+
+        let trigger = find_oracle_trigger(&updates, 11, 0, &touched).unwrap();
+        assert_eq!((trigger.block_number, trigger.tx_index), (10, 4));
+
+        let untouched: FastHashSet<_> = [pool].into_iter().collect();
+        assert!(find_oracle_trigger(&updates, 11, 5, &untouched).is_none());
+    }
+
+    #[test]
+    fn test_oracle_trigger_in_previous_block() {
+        let [feed, other_feed, pool, sender] = [1u8, 2, 3, 4].map(Address::with_last_byte);
+        let call = |to, call_type, emits_log: bool| TransactionTraceWithLogs {
+            trace:        TransactionTrace {
+                action:        TraceAction::Call(CallAction {
+                    from: sender,
+                    call_type,
+                    gas: Default::default(),
+                    input: Bytes::default(),
+                    to,
+                    value: Default::default(),
+                }),
+                error:         None,
+                result:        None,
+                subtraces:     0,
+                trace_address: vec![],
+            },
+            logs:         emits_log
+                .then(|| Log { address: to, data: LogData::new_unchecked(vec![], Bytes::new()) })
+                .into_iter()
+                .collect(),
+            msg_sender:   sender,
+            trace_idx:    0,
+            decoded_data: None,
+        };
+        let tx = |tx_index: u64, trace, is_success| {
+            TxTrace::new(
+                10,
+                vec![trace],
+                B256::with_last_byte(tx_index as u8),
+                tx_index,
+                0,
+                0,
+                is_success,
+            )
+        };
+
+        let traces = vec![
+            tx(1, call(feed, CallType::Call, true), true),
+            // reading the price
+            tx(2, call(feed, CallType::StaticCall, false), true),
+            tx(3, call(pool, CallType::Call, true), true),
+            tx(4, call(other_feed, CallType::Call, true), true),
+            tx(5, call(feed, CallType::Call, true), false),
+        ];
+        let updates =
+            oracle_updates_from_traces(&traces, |address| address == feed || address == other_feed);
+        assert_eq!(updates.iter().map(|update| update.tx_index).collect_vec(), vec![1, 4]);
+
+        // liquidated at the top of the next block after reading the feed
+        let touched: FastHashSet<_> = [feed, pool].into_iter().collect();
+        let trigger = find_oracle_trigger(&updates, 11, 0, &touched).unwrap();
+        assert_eq!(trigger.tx_hash, B256::with_last_byte(1));
+        assert_eq!(trigger.block_number, 10);
+    }
 }
@@ -1,6 +1,7 @@
 pub mod atomic_arb;
 pub mod cex_dex;
 
+pub mod generalized_frontrun;
 pub mod jit;
 pub mod liquidations;
 pub mod sandwich;
@@ -11,3 +12,5 @@ use malachite::Rational;
 
 pub(crate) const MAX_PROFIT: Rational = Rational::const_from_unsigned(15_000_000);
 pub(crate) const MIN_PROFIT: Rational = Rational::const_from_signed(-15_000_000);
+/// The amount of blocks the multi block sandwich inspector searches over.
+pub(crate) const MULTI_BLOCK_SANDWICH_WINDOW: usize = 3;
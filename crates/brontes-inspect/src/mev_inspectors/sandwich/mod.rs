@@ -6,11 +6,12 @@ use std::{
 use alloy_primitives::TxHash;
 use tracing::trace;
 mod types;
+mod victim_simulation;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
     db::dex::PriceAt,
-    mev::{Bundle, BundleData, MevType, Sandwich},
+    mev::{Bundle, BundleData, MevType, Sandwich, VictimLossAmount},
     normalized_actions::{
         accounting::ActionAccounting, Action, NormalizedSwap, NormalizedTransfer,
     },
@@ -22,6 +23,7 @@ use itertools::Itertools;
 use malachite::{num::basic::traits::Zero, Rational};
 use reth_primitives::{Address, B256};
 use types::{PossibleSandwich, PossibleSandwichWithTxInfo};
+pub use victim_simulation::simulate_victim_loss;
 
 use super::MAX_PROFIT;
 use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata, MIN_PROFIT};
@@ -37,20 +39,48 @@ const MAX_PRICE_DIFF: Rational = Rational::const_from_unsigneds(99995, 100000);
 const MAX_NON_SWAP_FRONTRUN: Rational = Rational::const_from_unsigned(5000);
 
 pub struct SandwichInspector<'db, DB: LibmdbxReader> {
-    utils: SharedInspectorUtils<'db, DB>,
+    utils:        SharedInspectorUtils<'db, DB>,
+    /// the amount of blocks we search over. If this is greater than one, only
+    /// sandwiches where the frontrun and backrun are in different blocks
+    /// are returned.
+    block_window: usize,
 }
 
 impl<'db, DB: LibmdbxReader> SandwichInspector<'db, DB> {
     pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+        Self { utils: SharedInspectorUtils::new(quote, db, metrics), block_window: 1 }
+    }
+
+    /// Searches for sandwiches that have the frontrun in a prior block and the
+    /// backrun in the most recent block of the window. This mostly happens
+    /// when the same builder builds consecutive blocks.
+    pub fn new_multi_block(
+        quote: Address,
+        db: &'db DB,
+        block_window: usize,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, db, metrics), block_window }
+    }
+
+    fn is_multi_block(&self) -> bool {
+        self.block_window > 1
     }
 }
 
 impl<DB: LibmdbxReader> Inspector for SandwichInspector<'_, DB> {
     type Result = Vec<Bundle>;
 
+    fn block_window(&self) -> usize {
+        self.block_window
+    }
+
     fn get_id(&self) -> &str {
-        "Sandwich"
+        if self.is_multi_block() {
+            "MultiBlockSandwich"
+        } else {
+            "Sandwich"
+        }
     }
 
     fn get_quote_token(&self) -> Address {
@@ -58,26 +88,32 @@ impl<DB: LibmdbxReader> Inspector for SandwichInspector<'_, DB> {
     }
 
     fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
-        let BlockData { metadata, tree } = data.get_most_recent_block();
-
         self.utils
             .get_metrics()
-            .map(|m| {
-                m.run_inspector(MevType::Sandwich, || {
-                    self.inspect_block_inner(tree.clone(), metadata.clone())
-                })
-            })
-            .unwrap_or_else(|| self.inspect_block_inner(tree.clone(), metadata.clone()))
+            .map(|m| m.run_inspector(MevType::Sandwich, || self.inspect_block_inner(&data)))
+            .unwrap_or_else(|| self.inspect_block_inner(&data))
     }
 }
 
 impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
-    fn inspect_block_inner(
-        &self,
-        tree: Arc<BlockTree<Action>>,
-        metadata: Arc<Metadata>,
-    ) -> Vec<Bundle> {
+    fn inspect_block_inner(&self, data: &MultiBlockData) -> Vec<Bundle> {
         tracing::trace!("starting sandwich");
+        let BlockData { metadata, tree } = data.get_most_recent_block();
+
+        // when searching over multiple blocks, we merge the trees of the window so
+        // that frontruns and backruns can be matched across block boundaries.
+        let tree = if data.per_block_data.len() > 1 {
+            Self::merge_window_trees(data)
+        } else {
+            tree.clone()
+        };
+
+        let window_metadata = data
+            .per_block_data
+            .iter()
+            .map(|block| (block.tree.header.number, block.metadata.clone()))
+            .collect::<FastHashMap<_, _>>();
+
         let search_args = TreeSearchBuilder::default().with_actions([
             Action::is_swap,
             Action::is_transfer,
@@ -85,7 +121,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             Action::is_nested_action,
         ]);
 
-        self.get_possible_sandwich(tree.clone())
+        self.get_possible_sandwich(tree.clone(), data)
             .into_iter()
             .filter_map(|ps| {
                 self.collect_baseline_sandwich_data(
@@ -93,18 +129,50 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                     search_args.clone(),
                     ps,
                     metadata.clone(),
+                    &window_metadata,
                 )
             })
             .flatten()
+            // single block sandwiches are covered by the default sandwich inspector
+            .filter(|bundle| {
+                !self.is_multi_block()
+                    || matches!(&bundle.data, BundleData::Sandwich(s) if s.is_multi_block)
+            })
             .collect::<Vec<_>>()
     }
 
+    /// Creates a single tree holding the transactions of every block in the
+    /// window, ordered by block and then by position. The positions of the
+    /// roots restart every block, so they can't be used to order the txes
+    /// of the merged tree, [`TxInfo::block_position`] has to be used instead.
+    fn merge_window_trees(data: &MultiBlockData) -> Arc<BlockTree<Action>> {
+        let latest = &data.get_most_recent_block().tree;
+        let tx_count = data
+            .per_block_data
+            .iter()
+            .map(|block| block.tree.tx_roots.len())
+            .sum();
+
+        let mut tree = BlockTree::new(latest.header.clone(), tx_count);
+        data.per_block_data
+            .iter()
+            .sorted_by_key(|block| block.block_number())
+            .flat_map(|block| block.tree.tx_roots.iter().cloned())
+            .for_each(|root| tree.insert_root(root));
+
+        tree.avg_priority_fee = latest.avg_priority_fee;
+        tree.priority_fee_std_dev = latest.priority_fee_std_dev;
+
+        Arc::new(tree)
+    }
+
     fn collect_baseline_sandwich_data(
         &self,
         tree: Arc<BlockTree<Action>>,
         search_args: TreeSearchBuilder<Action>,
         ps: PossibleSandwichWithTxInfo,
         metadata: Arc<Metadata>,
+        window_metadata: &FastHashMap<u64, Arc<Metadata>>,
     ) -> Option<Vec<Bundle>> {
         let PossibleSandwichWithTxInfo {
             inner:
@@ -155,6 +223,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         self.calculate_sandwich(
             tree.clone(),
             metadata.clone(),
+            window_metadata,
             possible_frontruns_info,
             possible_backrun_info,
             searcher_actions,
@@ -169,6 +238,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         &self,
         tree: Arc<BlockTree<Action>>,
         metadata: Arc<Metadata>,
+        window_metadata: &FastHashMap<u64, Arc<Metadata>>,
         possible_front_runs_info: Vec<TxInfo>,
         backrun_info: TxInfo,
         mut searcher_actions: Vec<Vec<Action>>,
@@ -241,6 +311,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             return self.recursive_possible_sandwiches(
                 tree.clone(),
                 metadata.clone(),
+                window_metadata,
                 &possible_front_runs_info,
                 backrun_info,
                 &back_run_actions,
@@ -252,6 +323,13 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             )
         }
 
+        // sandwiches are only reported from the block that holds the backrun. Any
+        // sandwich that was backrun in an earlier block of the window has already
+        // been covered.
+        if backrun_info.block_number != tree.header.number {
+            return None
+        }
+
         // if we reach this part of the code, we have found a sandwich and
         // are now going to collect the details for the given sandwich
         let victim_swaps = victim_actions.into_iter().flatten().collect::<Vec<_>>();
@@ -312,8 +390,14 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         // ensure valid pricing
         let mut has_dex_price = true;
         for (swaps, info) in front_run_swaps.iter().zip(&possible_front_runs_info) {
+            // the frontrun might be in a prior block of the window
+            let frontrun_metadata = window_metadata
+                .get(&info.block_number)
+                .cloned()
+                .unwrap_or_else(|| metadata.clone());
+
             has_dex_price &= self.utils.valid_pricing(
-                metadata.clone(),
+                frontrun_metadata,
                 swaps,
                 searcher_deltas
                     .values()
@@ -420,6 +504,17 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         );
 
         let victim_swaps = victim_swaps.into_iter().map(|(s, _)| s).collect_vec();
+        let victim_loss = self.calculate_victim_loss(
+            &possible_front_runs_info,
+            &victim_info,
+            &victim_swaps,
+            &metadata,
+            window_metadata,
+        );
+
+        let is_multi_block = possible_front_runs_info
+            .iter()
+            .any(|info| info.block_number != backrun_info.block_number);
 
         let sandwich = Sandwich {
             block_number: metadata.block_num,
@@ -432,12 +527,74 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             backrun_tx_hash: backrun_info.tx_hash,
             backrun_swaps: back_run_swaps,
             backrun_gas_details: backrun_info.gas_details,
+            is_multi_block,
+            victim_loss,
         };
         tracing::debug!("{:#?}\n{:#?}", header, sandwich);
 
         Some(vec![Bundle { header, data: BundleData::Sandwich(sandwich) }])
     }
 
+    /// Estimates how much each victim lost by pricing the tokens the victim
+    /// put in at the dex price before the frontrun it followed and comparing
+    /// that to the amount the victim actually received.
+    fn calculate_victim_loss(
+        &self,
+        frontruns: &[TxInfo],
+        victim_info: &[Vec<TxInfo>],
+        victim_swaps: &[Vec<NormalizedSwap>],
+        metadata: &Arc<Metadata>,
+        window_metadata: &FastHashMap<u64, Arc<Metadata>>,
+    ) -> Vec<VictimLossAmount> {
+        frontruns
+            .iter()
+            .zip(victim_info)
+            .flat_map(|(frontrun, victims)| victims.iter().map(move |info| (frontrun, info)))
+            .zip(victim_swaps)
+            .filter_map(|((frontrun, info), swaps)| {
+                let first = swaps.first()?;
+                let last = swaps.last()?;
+
+                // the position of the frontrun only refers to the prices of its own
+                // block, which might be a prior block of the window
+                let frontrun_metadata = window_metadata
+                    .get(&frontrun.block_number)
+                    .unwrap_or(metadata);
+                let frontrun_idx = frontrun.tx_index as usize;
+
+                let price_in = self.utils.get_token_price_on_dex(
+                    frontrun_idx,
+                    PriceAt::Before,
+                    first.token_in.address,
+                    frontrun_metadata,
+                )?;
+                let price_out = self.utils.get_token_price_on_dex(
+                    frontrun_idx,
+                    PriceAt::Before,
+                    last.token_out.address,
+                    frontrun_metadata,
+                )?;
+                if price_out == Rational::ZERO {
+                    return None
+                }
+
+                let expected_out = &first.amount_in * price_in / &price_out;
+                let token_amount_lost = expected_out - &last.amount_out;
+                let amount_lost_usd = &token_amount_lost * price_out;
+
+                Some(VictimLossAmount {
+                    tx_hash: info.tx_hash,
+                    vicitim_eoa: info.eoa,
+                    token: last.token_out.clone(),
+                    token_amount_lost,
+                    amount_lost_usd,
+                    simulated_amount_lost: None,
+                    simulated_amount_lost_usd: None,
+                })
+            })
+            .collect()
+    }
+
     /// For the given set of possible sandwich data.
     /// Calls with two different revisions.
     ///     1) front shrink
@@ -448,6 +605,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
         &self,
         tree: Arc<BlockTree<Action>>,
         metadata: Arc<Metadata>,
+        window_metadata: &FastHashMap<u64, Arc<Metadata>>,
         possible_front_runs_info: &[TxInfo],
         backrun_info: TxInfo,
         back_run_actions: &[Action],
@@ -493,6 +651,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                 self.calculate_sandwich(
                     tree.clone(),
                     metadata.clone(),
+                    window_metadata,
                     possible_front_runs_info,
                     back_run_info,
                     searcher_actions.to_vec(),
@@ -531,6 +690,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
                 self.calculate_sandwich(
                     tree.clone(),
                     metadata.clone(),
+                    window_metadata,
                     possible_front_runs_info,
                     backrun_info,
                     searcher_actions,
@@ -851,6 +1011,7 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
     fn get_possible_sandwich(
         &self,
         tree: Arc<BlockTree<Action>>,
+        data: &MultiBlockData,
     ) -> Vec<PossibleSandwichWithTxInfo> {
         if tree.tx_roots.len() < 3 {
             return vec![]
@@ -884,9 +1045,11 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             .unique()
             .collect::<Vec<_>>();
 
-        let tx_info_map = tree
-            .get_tx_info_batch(&tx_set, self.utils.db)
-            .into_iter()
+        // tx info is fetched per block so that each tx has its actual block number
+        let tx_info_map = data
+            .per_block_data
+            .iter()
+            .flat_map(|block| block.tree.get_tx_info_batch(&tx_set, self.utils.db))
             .flatten()
             .map(|info| (info.tx_hash, info))
             .collect::<FastHashMap<_, _>>();
@@ -1529,4 +1692,127 @@ mod tests {
 
         inspector_util.run_inspector(config, None).await.unwrap();
     }
+
+    #[brontes_macros::test]
+    async fn test_multi_block_sandwich() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        let frontrun = hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c");
+        let backrun = hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8");
+
+        let config = InspectorTxRunConfig::new(Inspectors::MultiBlockSandwich)
+            .with_mev_tx_hashes(vec![
+                frontrun.into(),
+                hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722").into(),
+                backrun.into(),
+            ])
+            // backrun in the block after the frontrun & victim
+            .with_next_block_from(backrun.into())
+            .with_dex_prices()
+            .needs_token(Address::new(hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82")))
+            .with_gas_paid_usd(16.64)
+            .with_expected_profit_usd(15.648);
+
+        inspector_util
+            .run_inspector(
+                config,
+                Some(Box::new(move |bundle: &Bundle| {
+                    let BundleData::Sandwich(ref sando) = bundle.data else {
+                        panic!("given bundle wasn't a sandwich");
+                    };
+                    assert!(sando.is_multi_block, "sandwich wasn't marked as multi block");
+                    assert_eq!(sando.frontrun_tx_hash, vec![B256::from(frontrun)]);
+                    assert_eq!(sando.backrun_tx_hash, B256::from(backrun));
+                })),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[brontes_macros::test]
+    async fn test_sandwich_victim_loss() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+
+        let victim = hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722");
+
+        let config = InspectorTxRunConfig::new(Inspectors::Sandwich)
+            .with_mev_tx_hashes(vec![
+                hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+                victim.into(),
+                hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8").into(),
+            ])
+            .with_dex_prices()
+            .needs_token(Address::new(hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82")))
+            .with_gas_paid_usd(16.64)
+            .with_expected_profit_usd(15.648);
+
+        inspector_util
+            .run_inspector(
+                config,
+                Some(Box::new(move |bundle: &Bundle| {
+                    let BundleData::Sandwich(ref sando) = bundle.data else {
+                        panic!("given bundle wasn't a sandwich");
+                    };
+                    assert!(!sando.is_multi_block);
+                    assert_eq!(sando.victim_loss.len(), 1, "{:#?}", sando.victim_loss);
+
+                    let loss = &sando.victim_loss[0];
+                    assert_eq!(loss.tx_hash, B256::from(victim));
+                    assert!(loss.token_amount_lost > Rational::ZERO);
+                    assert!(loss.amount_lost_usd > Rational::ZERO);
+                })),
+            )
+            .await
+            .unwrap();
+    }
+
+    fn tx_info(block_number: u64, tx_index: u64) -> TxInfo {
+        TxInfo::new(
+            block_number,
+            tx_index,
+            Address::ZERO,
+            None,
+            None,
+            B256::left_padding_from(&(block_number << 32 | tx_index).to_be_bytes()),
+            GasDetails::default(),
+            true,
+            false,
+            false,
+            false,
+            None,
+            None,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_possible_sandwich_ordered_across_blocks() {
+        // the backrun has a lower tx index than the frontrun, but is in the next block
+        let frontrun = tx_info(10, 5);
+        let victim = tx_info(10, 7);
+        let backrun = tx_info(11, 2);
+
+        let ps = |frontrun: &TxInfo, victim: &TxInfo, backrun: &TxInfo| PossibleSandwich {
+            eoa:                   Address::ZERO,
+            possible_frontruns:    vec![frontrun.tx_hash],
+            possible_backrun:      backrun.tx_hash,
+            mev_executor_contract: Address::ZERO,
+            victims:               vec![vec![victim.tx_hash]],
+        };
+        let info = [&frontrun, &victim, &backrun]
+            .into_iter()
+            .map(|info| (info.tx_hash, info.clone()))
+            .collect::<FastHashMap<_, _>>();
+
+        assert!(
+            PossibleSandwichWithTxInfo::from_ps(ps(&frontrun, &victim, &backrun), &info).is_some()
+        );
+        // the victim can't come after the backrun, even though its tx index is lower
+        assert!(
+            PossibleSandwichWithTxInfo::from_ps(ps(&frontrun, &backrun, &victim), &info).is_none()
+        );
+        assert!(
+            PossibleSandwichWithTxInfo::from_ps(ps(&backrun, &victim, &frontrun), &info).is_none()
+        );
+    }
 }
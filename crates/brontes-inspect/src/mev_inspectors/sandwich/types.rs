@@ -1,6 +1,7 @@
 use std::hash::Hash;
 
 use brontes_types::{FastHashMap, TxInfo};
+use itertools::Itertools;
 use reth_primitives::{Address, B256};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -39,6 +40,21 @@ impl PossibleSandwichWithTxInfo {
             victims.push(set);
         }
 
+        // the frontruns, victims & backrun have to be in order. When searching over
+        // multiple blocks, this is only true for the position across blocks.
+        let in_order = frontruns
+            .iter()
+            .zip(&victims)
+            .flat_map(|(frontrun, victims)| std::iter::once(frontrun).chain(victims))
+            .chain(std::iter::once(&backrun))
+            .map(TxInfo::block_position)
+            .tuple_windows()
+            .all(|(prev, next)| prev < next);
+
+        if !in_order {
+            return None
+        }
+
         Some(PossibleSandwichWithTxInfo {
             possible_backrun_info:   backrun,
             possible_frontruns_info: frontruns,
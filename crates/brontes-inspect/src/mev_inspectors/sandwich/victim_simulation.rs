@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use alloy_primitives::{b256, Address, Log, B256, U256};
+use brontes_types::{
+    db::{dex::PriceAt, metadata::Metadata},
+    mev::{Bundle, BundleData, Sandwich},
+    pair::Pair,
+    traits::TracingProvider,
+    MultiBlockData, ToScaledRational,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::One, Rational};
+
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Re-executes the victims of all sandwiches against the state before the
+/// first frontrun, skipping the frontruns, and stores the exact loss next to
+/// the heuristic estimate of each [`VictimLossAmount`].
+///
+/// [`VictimLossAmount`]: brontes_types::mev::VictimLossAmount
+pub async fn simulate_victim_loss<T: TracingProvider>(
+    tracer: &T,
+    quote: Address,
+    bundles: &mut [Bundle],
+    data: &MultiBlockData,
+) {
+    for bundle in bundles {
+        let BundleData::Sandwich(sandwich) = &mut bundle.data else { continue };
+
+        if let Err(e) = simulate_sandwich_victims(tracer, quote, sandwich, data).await {
+            tracing::warn!(
+                target: "brontes_inspect::sandwich",
+                err=%e,
+                backrun=?sandwich.backrun_tx_hash,
+                "failed to simulate sandwich victims"
+            );
+        }
+    }
+}
+
+async fn simulate_sandwich_victims<T: TracingProvider>(
+    tracer: &T,
+    quote: Address,
+    sandwich: &mut Sandwich,
+    data: &MultiBlockData,
+) -> eyre::Result<()> {
+    if sandwich.victim_loss.is_empty() {
+        return Ok(())
+    }
+
+    let Some(frontrun) = sandwich.frontrun_tx_hash.first() else { return Ok(()) };
+    let Some((block, frontrun_idx)) = data.per_block_data.iter().find_map(|block| {
+        block
+            .tree
+            .get_root(*frontrun)
+            .map(|root| (block, root.position))
+    }) else {
+        return Ok(())
+    };
+
+    let victims = sandwich
+        .victim_swaps_tx_hashes
+        .iter()
+        .flatten()
+        .copied()
+        .collect_vec();
+
+    // victims of multi block sandwiches can be in a different block than the
+    // frontrun, we can't replay those against the pre-frontrun state.
+    if !victims.iter().all(|v| block.tree.get_root(*v).is_some()) {
+        return Ok(())
+    }
+
+    let simulated = tracer
+        .simulate_transactions(block.metadata.block_num, frontrun_idx, victims.clone())
+        .await?;
+
+    for loss in &mut sandwich.victim_loss {
+        let Some(sim) = simulated
+            .iter()
+            .find(|sim| sim.tx_hash == loss.tx_hash && sim.success)
+        else {
+            continue
+        };
+
+        let Some(last_swap) = victims
+            .iter()
+            .position(|v| *v == loss.tx_hash)
+            .and_then(|idx| sandwich.victim_swaps.get(idx))
+            .and_then(|swaps| swaps.last())
+        else {
+            continue
+        };
+
+        let received = amount_received(&sim.logs, loss.token.address, last_swap.recipient);
+        // the output is sent somewhere we can't follow through transfer logs, e.g
+        // unwrapped to eth. We can't compare so we skip it.
+        if received.is_zero() {
+            continue
+        }
+
+        let simulated_lost =
+            received.to_scaled_rational(loss.token.decimals) - &last_swap.amount_out;
+
+        loss.simulated_amount_lost_usd =
+            token_price(quote, loss.token.address, frontrun_idx, &block.metadata)
+                .map(|price| &simulated_lost * price);
+        loss.simulated_amount_lost = Some(simulated_lost);
+    }
+
+    Ok(())
+}
+
+/// Sums all erc20 transfers of `token` to `recipient` in the given logs.
+fn amount_received(logs: &[Log], token: Address, recipient: Address) -> U256 {
+    logs.iter()
+        .filter(|log| log.address == token)
+        .filter(|log| {
+            log.topics().len() == 3
+                && log.topics()[0] == TRANSFER_TOPIC
+                && Address::from_word(log.topics()[2]) == recipient
+        })
+        .filter_map(|log| U256::try_from_be_slice(&log.data.data))
+        .fold(U256::ZERO, |acc, amount| acc.saturating_add(amount))
+}
+
+fn token_price(
+    quote: Address,
+    token: Address,
+    tx_idx: usize,
+    metadata: &Arc<Metadata>,
+) -> Option<Rational> {
+    if token == quote {
+        return Some(Rational::ONE)
+    }
+
+    Some(
+        metadata
+            .dex_quotes
+            .as_ref()?
+            .price_at(Pair(token, quote), tx_idx)?
+            .get_price(PriceAt::Before),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, LogData};
+    use brontes_types::{
+        db::dex::{DexPrices, DexQuotes},
+        FastHashMap,
+    };
+
+    use super::*;
+
+    fn transfer(token: Address, to: Address, amount: u64) -> Log {
+        Log {
+            address: token,
+            data:    LogData::new_unchecked(
+                vec![TRANSFER_TOPIC, Address::ZERO.into_word(), to.into_word()],
+                Bytes::from(U256::from(amount).to_be_bytes_vec()),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_amount_received() {
+        let token = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+
+        let mut approval = transfer(token, recipient, 1_000);
+        approval.data = LogData::new_unchecked(
+            vec![B256::with_last_byte(1), Address::ZERO.into_word(), recipient.into_word()],
+            approval.data.data.clone(),
+        );
+
+        let logs = vec![
+            transfer(token, recipient, 100),
+            transfer(token, recipient, 50),
+            // other recipient
+            transfer(token, Address::with_last_byte(3), 1_000),
+            // other token
+            transfer(Address::with_last_byte(4), recipient, 1_000),
+            approval,
+        ];
+
+        assert_eq!(amount_received(&logs, token, recipient), U256::from(150));
+        assert_eq!(amount_received(&[], token, recipient), U256::ZERO);
+    }
+
+    #[test]
+    fn test_token_price() {
+        let quote = Address::with_last_byte(1);
+        let token = Address::with_last_byte(2);
+
+        let mut metadata = Metadata::default();
+        assert_eq!(token_price(quote, quote, 0, &Arc::new(metadata.clone())), Some(Rational::ONE));
+        // no dex quotes
+        assert_eq!(token_price(quote, token, 0, &Arc::new(metadata.clone())), None);
+
+        let price = DexPrices {
+            pre_state:    Rational::from(2),
+            post_state:   Rational::from(3),
+            goes_through: Pair::default(),
+            is_transfer:  false,
+        };
+        let quotes = FastHashMap::from_iter([(Pair(token, quote), price)]);
+        metadata.dex_quotes = Some(DexQuotes(vec![Some(quotes)]));
+
+        // priced before the frontrun
+        assert_eq!(token_price(quote, token, 0, &Arc::new(metadata)), Some(Rational::from(2)));
+    }
+}
@@ -5,6 +5,7 @@ use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
     db::{
+        cex::FeeLookup,
         dex::{BlockPrice, PriceAt},
         metadata::Metadata,
         token_info::TokenInfoWithAddress,
@@ -78,6 +79,7 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
                             &pair,
                             metadata.microseconds_block_timestamp(),
                             Some(1_000_000),
+                            FeeLookup::default(),
                         )?
                         .price_maker
                         .1
@@ -351,11 +353,7 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
             })
             .sum::<f64>();
 
-        let fund = info
-            .get_searcher_contract_info()
-            .map(|i| i.fund)
-            .or_else(|| info.get_searcher_eao_info().map(|f| f.fund))
-            .unwrap_or_default();
+        let fund = info.fund();
 
         BundleHeader {
             block_number: metadata.block_num,
@@ -405,11 +403,7 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
                 .inspect(|m| m.inspector_100x_profit(mev_type));
         }
 
-        let fund = info
-            .get_searcher_contract_info()
-            .map(|i| i.fund)
-            .or_else(|| info.get_searcher_eao_info().map(|f| f.fund))
-            .unwrap_or_default();
+        let fund = info.fund();
 
         BundleHeader {
             block_number: metadata.block_num,
@@ -261,8 +261,7 @@ impl InspectorTestUtils {
             None,
         );
 
-        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
-        let multi = MultiBlockData { per_block_data: vec![data], blocks: 1 };
+        let multi = split_into_blocks(tree, metadata, config.next_block_from);
         let results = inspector.inspect_block(multi);
         let mut results = SharedInspectorUtils::<LibmdbxReadWriter>::dedup_bundles(results);
 
@@ -429,6 +428,39 @@ impl InspectorTestUtils {
     }
 }
 
+/// Moves the tx `next_block_from` and every tx after it into the following
+/// block. The txes keep their position, so the dex quotes of the block can be
+/// shared by both blocks.
+fn split_into_blocks(
+    mut tree: BlockTree<Action>,
+    metadata: Metadata,
+    next_block_from: Option<TxHash>,
+) -> MultiBlockData {
+    let Some(split) = next_block_from.and_then(|tx_hash| {
+        tree.tx_roots
+            .iter()
+            .position(|root| root.tx_hash == tx_hash)
+    }) else {
+        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
+        return MultiBlockData { per_block_data: vec![data], blocks: 1 }
+    };
+
+    let mut next_tree = tree.clone();
+    next_tree.tx_roots = tree.tx_roots.split_off(split);
+    next_tree.header.number += 1;
+
+    let mut next_metadata = metadata.clone();
+    next_metadata.block_metadata.block_num = next_tree.header.number;
+
+    MultiBlockData {
+        per_block_data: vec![
+            BlockData { metadata: metadata.into(), tree: tree.into() },
+            BlockData { metadata: next_metadata.into(), tree: next_tree.into() },
+        ],
+        blocks:         2,
+    }
+}
+
 /// This inspector test config is to configure an inspector test for a single
 /// bundle. MevTxHashes is a list of tx hashes that are expected be in the
 /// bundle.
@@ -443,6 +475,7 @@ pub struct InspectorTxRunConfig {
     pub needs_dex_prices: bool,
     pub needs_tokens: Vec<Address>,
     pub use_block_time_weights_for_cex_pricing: bool,
+    pub next_block_from: Option<TxHash>,
 }
 
 impl InspectorTxRunConfig {
@@ -457,6 +490,7 @@ impl InspectorTxRunConfig {
             needs_tokens: Vec::new(),
             needs_dex_prices: false,
             use_block_time_weights_for_cex_pricing: false,
+            next_block_from: None,
         }
     }
 
@@ -506,6 +540,13 @@ impl InspectorTxRunConfig {
         self.use_block_time_weights_for_cex_pricing = true;
         self
     }
+
+    /// Runs the inspector over two blocks, with the given tx & all txes after
+    /// it moved into the second block
+    pub fn with_next_block_from(mut self, tx_hash: TxHash) -> Self {
+        self.next_block_from = Some(tx_hash);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
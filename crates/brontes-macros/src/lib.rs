@@ -59,6 +59,12 @@ use crate::action_classifier::{ActionDispatch, ActionMacro};
 ///      log_data: UniswapV2mintCallLogs|  { <body> });
 /// ```
 ///
+/// # Dynamic Action Types
+/// if a call can result in different actions depending on its inputs, e.g
+/// adding or removing liquidity through the same function, use `Action` as
+/// the action type. The closure then returns the full
+/// `brontes_types::normalized_actions::Action` instead of a normalized type.
+///
 /// # Logs Config
 /// NOTE: all log modifiers are compatible with each_other
 /// ## Log Ignore Before
@@ -23,6 +23,7 @@ pub mod inspectors;
 pub mod pricing;
 pub mod prometheus_exporter;
 pub mod range;
+pub mod tip;
 pub mod trace;
 
 /// metric event for traces
@@ -24,6 +24,11 @@ pub struct DexPricingMetrics {
     pub poll_rate:           IntCounterVec,
     /// wants more blocks
     pub needs_more_data:     IntGaugeVec,
+    /// relative price difference between syncing pool state from actions and
+    /// from logs
+    pub pool_sync_diff:      Histogram,
+    /// pool updates where the action and log state diverged, by protocol
+    pub pool_sync_mismatch:  IntCounterVec,
 }
 impl Default for DexPricingMetrics {
     fn default() -> Self {
@@ -71,6 +76,14 @@ impl DexPricingMetrics {
         )
         .unwrap();
 
+        let pool_sync_diff = metrics::register_histogram!("dex_pricing_pool_sync_diff");
+        let pool_sync_mismatch = prometheus::register_int_counter_vec!(
+            "dex_pricing_pool_sync_mismatch",
+            "pool updates where syncing from actions diverged from the logs",
+            &["protocol"]
+        )
+        .unwrap();
+
         Self {
             needs_more_data,
             processed_blocks,
@@ -81,6 +94,8 @@ impl DexPricingMetrics {
             range_processing,
             function_call_count,
             poll_rate,
+            pool_sync_diff,
+            pool_sync_mismatch,
         }
     }
 
@@ -103,6 +118,13 @@ impl DexPricingMetrics {
             .inc()
     }
 
+    pub fn pool_sync_difference(&self, protocol: &str, price_diff: f64, mismatch: bool) {
+        self.pool_sync_diff.record(price_diff);
+        if mismatch {
+            self.pool_sync_mismatch.with_label_values(&[protocol]).inc();
+        }
+    }
+
     pub fn range_finished_block(&self, range_id: usize) {
         self.processed_blocks.increment(1);
         self.range_processing
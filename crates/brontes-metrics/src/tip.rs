@@ -0,0 +1,13 @@
+use metrics::{Counter, Histogram};
+use reth_metrics::Metrics;
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "brontes_tip")]
+pub struct TipMetrics {
+    /// reorgs of already processed blocks
+    pub reorgs:             Counter,
+    /// amount of processed blocks replaced by each reorg
+    pub reorg_depth:        Histogram,
+    /// total processed blocks that were rolled back & re-run
+    pub rolled_back_blocks: Counter,
+}
@@ -5,7 +5,7 @@ use std::{
 
 use alloy_primitives::Address;
 use brontes_classifier::test_utils::{ClassifierTestUtils, ClassifierTestUtilsError};
-use brontes_pricing::{types::ProtocolState, LoadState};
+use brontes_pricing::{types::ProtocolState, uniswap_v4::db_pool_key_lookup, LoadState};
 use brontes_types::{pair::Pair, Protocol};
 use criterion::{black_box, BenchmarkId, Criterion};
 use futures::StreamExt;
@@ -110,12 +110,14 @@ impl BrontesPricingBencher {
                 block_number,
                 pool_pair,
                 brontes_pricing::types::PairWithFirstPoolHop::from_pair_gt(pool_pair, pool_pair),
+                db_pool_key_lookup(self.inner.libmdbx),
             ))
             .unwrap()
             .2;
 
-        let pair_1 = pool_pair.0;
-        c.bench_function(bench_name, move |b| b.iter(|| black_box(state.price(pair_1).unwrap())));
+        c.bench_function(bench_name, move |b| {
+            b.iter(|| black_box(state.price(pool_pair.0, pool_pair.1).unwrap()))
+        });
 
         Ok(())
     }
@@ -141,6 +143,7 @@ impl BrontesPricingBencher {
                             brontes_pricing::types::PairWithFirstPoolHop::from_pair_gt(
                                 pool_pair, pool_pair,
                             ),
+                            db_pool_key_lookup(self.inner.libmdbx),
                         )
                         .await,
                 )
@@ -3,7 +3,9 @@ use std::sync::{atomic::AtomicBool, Arc};
 use alloy_primitives::{Address, TxHash};
 use brontes_classifier::Classifier;
 use brontes_core::test_utils::*;
-use brontes_pricing::{types::DexPriceMsg, BrontesBatchPricer, GraphManager};
+use brontes_pricing::{
+    types::DexPriceMsg, uniswap_v4::db_pool_key_lookup, BrontesBatchPricer, GraphManager,
+};
 use brontes_types::{
     normalized_actions::Action, traits::TracingProvider, tree::BlockTree, BrontesTaskManager,
     FastHashMap, UnboundedYapperReceiver,
@@ -47,7 +49,7 @@ impl PricingTestUtils {
                 .flat_map(|(_, pools)| {
                     pools
                         .into_iter()
-                        .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                        .map(|(addr, protocol, pairs)| (addr, (protocol, pairs)))
                         .collect::<Vec<_>>()
                 })
                 .collect::<FastHashMap<_, _>>()
@@ -66,6 +68,7 @@ impl PricingTestUtils {
             created_pools,
             Arc::new(AtomicBool::new(false)),
             None,
+            db_pool_key_lookup(self.tracer.libmdbx),
             ex,
         ))
     }
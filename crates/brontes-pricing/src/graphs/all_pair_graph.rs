@@ -72,7 +72,7 @@ pub struct AllPairGraph {
 }
 
 impl AllPairGraph {
-    pub fn init_from_hash_map(all_pool_data: FastHashMap<(Address, Protocol), Pair>) -> Self {
+    pub fn init_from_hash_map(all_pool_data: FastHashMap<(Address, Protocol), Vec<Pair>>) -> Self {
         let mut graph = UnGraph::<(), Vec<EdgeWithInsertBlock>, usize>::default();
 
         let mut token_to_index = FastHashMap::default();
@@ -84,10 +84,11 @@ impl AllPairGraph {
         all_pool_data
             .into_iter()
             .sorted()
-            .for_each(|((pool_addr, dex), pair)| {
-                if !dex.has_state_updater() {
-                    return
-                }
+            .filter(|((_, dex), _)| dex.has_state_updater())
+            .flat_map(|((pool_addr, dex), pairs)| {
+                pairs.into_iter().map(move |pair| (pool_addr, dex, pair))
+            })
+            .for_each(|(pool_addr, dex, pair)| {
                 // because this is undirected, doesn't matter what order the nodes are connected
                 // so we sort so we can just have a collection of edges for just one
                 // way
@@ -27,7 +27,7 @@ pub use self::{
 };
 use super::PoolUpdate;
 use crate::{
-    types::{PairWithFirstPoolHop, PoolState},
+    types::{PairWithFirstPoolHop, PoolState, PoolSyncMode},
     Protocol,
 };
 
@@ -68,7 +68,7 @@ pub struct GraphManager {
 
 impl GraphManager {
     pub fn init_from_db_state(
-        all_pool_data: FastHashMap<(Address, Protocol), Pair>,
+        all_pool_data: FastHashMap<(Address, Protocol), Vec<Pair>>,
         metrics: Option<DexPricingMetrics>,
     ) -> Self {
         let graph = AllPairGraph::init_from_hash_map(all_pool_data);
@@ -83,6 +83,12 @@ impl GraphManager {
         }
     }
 
+    /// Reads pool state transitions from normalized actions instead of logs
+    pub fn with_pool_sync_mode(mut self, sync_mode: PoolSyncMode) -> Self {
+        self.graph_state.set_sync_mode(sync_mode);
+        self
+    }
+
     /// used for testing and benching
     pub fn snapshot_state(&self) -> (SubGraphRegistry, SubgraphVerifier, StateTracker) {
         (self.sub_graph_registry.clone(), self.subgraph_verifier.clone(), self.graph_state.clone())
@@ -2,12 +2,13 @@ use std::ops::RangeInclusive;
 
 use alloy_primitives::Address;
 use brontes_metrics::pricing::DexPricingMetrics;
-use brontes_types::FastHashMap;
+use brontes_types::{FastHashMap, ToFloatNearest};
 use itertools::Itertools;
+use malachite::{num::arithmetic::traits::Abs, Rational};
 use tracing::debug;
 
 use crate::{
-    types::{PoolState, PoolUpdate},
+    types::{PoolState, PoolSyncMode, PoolUpdate},
     PoolPairInfoDirection, SubGraphEdge,
 };
 
@@ -35,10 +36,16 @@ pub struct StateTracker {
     finalized_edge_state:    FastHashMap<Address, StateWithDependencies>,
     /// state that verification is using
     verification_edge_state: FastHashMap<Address, PoolStateWithBlock>,
+    /// where pool state transitions are read from
+    sync_mode:               PoolSyncMode,
     /// state count
     metrics:                 Option<DexPricingMetrics>,
 }
 
+/// The relative price difference at which the action and log update paths are
+/// considered to have diverged
+const SYNC_MISMATCH_TOLERANCE: f64 = 0.0001;
+
 impl Drop for StateTracker {
     fn drop(&mut self) {
         let mut ver_byte_cnt = 0usize;
@@ -63,10 +70,15 @@ impl StateTracker {
         Self {
             finalized_edge_state: FastHashMap::default(),
             verification_edge_state: FastHashMap::default(),
+            sync_mode: PoolSyncMode::default(),
             metrics,
         }
     }
 
+    pub fn set_sync_mode(&mut self, sync_mode: PoolSyncMode) {
+        self.sync_mode = sync_mode;
+    }
+
     pub fn remove_finalized_state_dep(&mut self, pool: Address, amount: u64) {
         self.finalized_edge_state.retain(|i_pool, state| {
             if pool != *i_pool {
@@ -191,11 +203,50 @@ impl StateTracker {
     }
 
     pub fn update_pool_state(&mut self, address: Address, update: PoolUpdate) {
+        // curve metapools hold a copy of their base pool to price its coins
+        if !update.logs.is_empty() {
+            self.finalized_edge_state
+                .values_mut()
+                .filter(|state| state.state.base_pool() == Some(address))
+                .for_each(|state| state.state.sync_base_pool(&update));
+        }
+
         let Some(state) = self.finalized_edge_state.get_mut(&address) else {
             return;
         };
 
-        state.state.increment_state(update);
+        if self.sync_mode == PoolSyncMode::Logs || !state.state.syncs_from_actions() {
+            state.state.increment_state(update, PoolSyncMode::Logs);
+            return
+        }
+
+        let Some(metrics) = self.metrics.as_ref() else {
+            state.state.increment_state(update, PoolSyncMode::Actions);
+            return
+        };
+
+        // apply the update through both paths so that the classifier and the
+        // pricer can be checked against each other
+        let mut from_logs = state.state.clone();
+        from_logs.increment_state(update.clone(), PoolSyncMode::Logs);
+        state.state.increment_state(update, PoolSyncMode::Actions);
+
+        let pair = from_logs.pair();
+        let price_diff =
+            match (from_logs.get_price(pair.0, pair.1), state.state.get_price(pair.0, pair.1)) {
+                (Ok(logs), Ok(actions)) if logs != Rational::default() => {
+                    ((actions - &logs) / logs).abs().to_float()
+                }
+                (Err(_), Err(_)) => return,
+                (Ok(logs), Ok(actions)) if logs == actions => 0.0,
+                _ => 1.0,
+            };
+
+        metrics.pool_sync_difference(
+            &state.state.dex().to_string(),
+            price_diff,
+            price_diff > SYNC_MISMATCH_TOLERANCE,
+        );
     }
 
     pub fn new_state_for_verification(&mut self, address: Address, state: StateWithDependencies) {
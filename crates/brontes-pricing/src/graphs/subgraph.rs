@@ -91,7 +91,7 @@ pub struct PairSubGraph {
 
     /// if a nodes liquidity drops more than 50% from when validation
     /// was last ran on this subgraph. a re_query is triggered.
-    start_nodes_liq:        FastHashMap<Address, Rational>,
+    start_nodes_liq:        FastHashMap<(Address, Pair), Rational>,
     start_node:             u16,
     end_node:               u16,
     /// the last time this subgraph was used for pricing.
@@ -202,10 +202,10 @@ impl PairSubGraph {
             .edge_weights()
             .flat_map(|weight| {
                 weight.iter().filter_map(|edge| {
-                    let (r0, r1) = state.get(&edge.pool_addr)?.tvl(edge.token_0);
+                    let (r0, r1) = state.get(&edge.pool_addr)?.tvl(edge.token_0, edge.token_1);
                     let tvl_added = r0 + r1;
 
-                    Some((edge.pool_addr, tvl_added))
+                    Some(((edge.pool_addr, Pair(edge.token_0, edge.token_1)), tvl_added))
                 })
             })
             .collect::<FastHashMap<_, _>>();
@@ -222,9 +222,15 @@ impl PairSubGraph {
                 weight
                     .iter()
                     .map(|edge| {
-                        let (r0, r1) = state.get(&edge.pool_addr).unwrap().tvl(edge.token_0);
+                        let (r0, r1) = state
+                            .get(&edge.pool_addr)
+                            .unwrap()
+                            .tvl(edge.token_0, edge.token_1);
                         let tvl_added = r0 + r1;
-                        let start_tvl = self.start_nodes_liq.get(&edge.pool_addr).unwrap();
+                        let start_tvl = self
+                            .start_nodes_liq
+                            .get(&(edge.pool_addr, Pair(edge.token_0, edge.token_1)))
+                            .unwrap();
 
                         if tvl_added < *start_tvl && start_tvl != &Rational::ZERO {
                             tvl_added / start_tvl <= Rational::ONE_HALF
@@ -499,14 +505,15 @@ impl PairSubGraph {
                         continue;
                     };
 
-                    let Ok(pool_price) =
-                        pool_state.price(info.get_token_with_direction(is_outgoing))
-                    else {
+                    let base = info.get_token_with_direction(is_outgoing);
+                    let quote = info.get_token_with_direction(!is_outgoing);
+
+                    let Ok(pool_price) = pool_state.price(base, quote) else {
                         Self::bad_state(pair, info, Rational::ZERO, &mut removal_map.removal_state);
                         continue;
                     };
 
-                    let (t0, t1) = pool_state.tvl(info.get_token_with_direction(is_outgoing));
+                    let (t0, t1) = pool_state.tvl(base, quote);
                     let liq0 = prev_price.clone().reciprocal() * &t0;
 
                     let goes_through_arg = if ignore_goes_through {
@@ -913,11 +920,12 @@ impl PairSubGraph {
                         continue;
                     };
 
-                    let Ok(pool_price) = pool_state.price(info.get_base_token()) else {
+                    let (base, quote) = (info.get_base_token(), info.get_quote_token());
+                    let Ok(pool_price) = pool_state.price(base, quote) else {
                         continue;
                     };
 
-                    let (t0, t1) = pool_state.tvl(info.get_base_token());
+                    let (t0, t1) = pool_state.tvl(base, quote);
 
                     let t0xt1 = &t0 * &t1;
                     pxw += pool_price * &t0xt1;
@@ -1055,11 +1063,15 @@ pub mod test {
     }
 
     impl ProtocolState for MockPoolState {
-        fn price(&self, _base: Address) -> Result<Rational, crate::errors::ArithmeticError> {
+        fn price(
+            &self,
+            _base: Address,
+            _quote: Address,
+        ) -> Result<Rational, crate::errors::ArithmeticError> {
             Ok(self.price.clone())
         }
 
-        fn tvl(&self, _base: Address) -> (Rational, Rational) {
+        fn tvl(&self, _base: Address, _quote: Address) -> (Rational, Rational) {
             self.tvl.clone()
         }
     }
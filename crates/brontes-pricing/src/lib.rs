@@ -62,7 +62,10 @@ use malachite::{
     num::basic::traits::{One, Zero},
     Rational,
 };
-use protocols::lazy::{LazyExchangeLoader, LazyResult, LoadResult};
+use protocols::{
+    lazy::{LazyExchangeLoader, LazyResult, LoadResult},
+    uniswap_v4::V4PoolKeyLookup,
+};
 pub use protocols::{Protocol, *};
 use subgraph_query::*;
 use tracing::{debug, error, info};
@@ -96,7 +99,7 @@ pub struct BrontesBatchPricer<T: TracingProvider> {
     /// holds new graph nodes / edges that can be added at every given block.
     /// this is done to ensure any route from a base to our quote asset will
     /// only pass though valid created pools.
-    new_graph_pairs: FastHashMap<Address, (Protocol, Pair)>,
+    new_graph_pairs: FastHashMap<Address, (Protocol, Vec<Pair>)>,
     /// manages all graph related items
     graph_manager:   GraphManager,
     /// lazy loads dex pairs so we only fetch init state that is needed
@@ -123,9 +126,10 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         update_rx: UnboundedYapperReceiver<DexPriceMsg>,
         provider: Arc<T>,
         current_block: u64,
-        new_graph_pairs: FastHashMap<Address, (Protocol, Pair)>,
+        new_graph_pairs: FastHashMap<Address, (Protocol, Vec<Pair>)>,
         needs_more_data: Arc<AtomicBool>,
         metrics: Option<DexPricingMetrics>,
+        v4_pool_keys: V4PoolKeyLookup,
         executor: BrontesTaskExecutor,
     ) -> Self {
         Self {
@@ -138,7 +142,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
             update_rx,
             graph_manager,
             dex_quotes: FastHashMap::default(),
-            lazy_loader: LazyExchangeLoader::new(provider, executor),
+            lazy_loader: LazyExchangeLoader::new(provider, v4_pool_keys, executor),
             current_block,
             completed_block: current_block,
             overlap_update: None,
@@ -202,12 +206,14 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         updates
             .iter()
             .filter_map(|update| {
-                let (protocol, pair) = self.new_graph_pairs.remove(&update.get_pool_address())?;
-                Some((update.get_pool_address(), protocol, pair, update.block))
+                let (protocol, pairs) = self.new_graph_pairs.remove(&update.get_pool_address())?;
+                Some((update.get_pool_address(), protocol, pairs, update.block))
             })
-            .for_each(|(pool_addr, protocol, pair, block)| {
-                self.graph_manager
-                    .add_pool(pair, pool_addr, protocol, block);
+            .for_each(|(pool_addr, protocol, pairs, block)| {
+                for pair in pairs {
+                    self.graph_manager
+                        .add_pool(pair, pool_addr, protocol, block);
+                }
             });
 
         updates.iter().for_each(|msg| {
@@ -283,12 +289,14 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         updates
             .iter()
             .filter_map(|update| {
-                let (protocol, pair) = self.new_graph_pairs.remove(&update.get_pool_address())?;
-                Some((update.get_pool_address(), protocol, pair, update.block))
+                let (protocol, pairs) = self.new_graph_pairs.remove(&update.get_pool_address())?;
+                Some((update.get_pool_address(), protocol, pairs, update.block))
             })
-            .for_each(|(pool_addr, protocol, pair, block)| {
-                self.graph_manager
-                    .add_pool(pair, pool_addr, protocol, block);
+            .for_each(|(pool_addr, protocol, pairs, block)| {
+                for pair in pairs {
+                    self.graph_manager
+                        .add_pool(pair, pool_addr, protocol, block);
+                }
             });
 
         updates.into_iter().for_each(|update| {
@@ -591,7 +599,10 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                 } = load_result
                 {
                     self.new_graph_pairs
-                        .insert(pool_address, (protocol, pool_pair));
+                        .entry(pool_address)
+                        .or_insert_with(|| (protocol, vec![]))
+                        .1
+                        .push(pool_pair);
                     self.graph_manager
                         .remove_pair_graph_address(pool_pair, pool_address);
 
@@ -653,7 +664,11 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                                 .graph_manager
                                 .remove_pair_graph_address(bad_edge.pair, bad_edge.pool_address)
                             {
-                                self.new_graph_pairs.insert(addr, (protocol, pair));
+                                self.new_graph_pairs
+                                    .entry(addr)
+                                    .or_insert_with(|| (protocol, vec![]))
+                                    .1
+                                    .push(pair);
                             }
                         }
                     });
@@ -669,7 +684,11 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                                 .graph_manager
                                 .remove_pair_graph_address(bad_edge.pair, bad_edge.pool_address)
                             {
-                                self.new_graph_pairs.insert(addr, (protocol, pair));
+                                self.new_graph_pairs
+                                    .entry(addr)
+                                    .or_insert_with(|| (protocol, vec![]))
+                                    .1
+                                    .push(pair);
                             }
                         }
                     });
@@ -1239,8 +1258,12 @@ impl<T: TracingProvider> Stream for BrontesBatchPricer<T> {
                             ..
                         }) => {
                             if protocol.has_state_updater() {
+                                let tokens = tokens
+                                    .into_iter()
+                                    .filter(|token| *token != pool_address)
+                                    .collect::<Vec<_>>();
                                 self.new_graph_pairs
-                                    .insert(pool_address, (protocol, Pair(tokens[0], tokens[1])));
+                                    .insert(pool_address, (protocol, Pair::all_pairs(&tokens)));
                             };
                             Some(PollResult::DiscoveredPool)
                         }
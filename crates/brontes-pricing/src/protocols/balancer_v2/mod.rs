@@ -0,0 +1,350 @@
+pub mod stable;
+pub mod weighted;
+
+use std::sync::Arc;
+
+use alloy_primitives::{hex, Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+use brontes_types::{
+    normalized_actions::Action, queries::make_call_request, traits::TracingProvider,
+    ToScaledRational,
+};
+use futures::future::try_join_all;
+use malachite::Rational;
+
+use super::{get_decimals, to_raw_amount};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    UpdatableProtocol,
+};
+
+sol!(
+    interface IBalancerV2Vault {
+        function getPoolTokens(bytes32 poolId) external view returns (
+            address[] tokens,
+            uint256[] balances,
+            uint256 lastChangeBlock
+        );
+        event Swap(
+            bytes32 indexed poolId,
+            address indexed tokenIn,
+            address indexed tokenOut,
+            uint256 amountIn,
+            uint256 amountOut
+        );
+        event PoolBalanceChanged(
+            bytes32 indexed poolId,
+            address indexed liquidityProvider,
+            address[] tokens,
+            int256[] deltas,
+            uint256[] protocolFeeAmounts
+        );
+    }
+);
+
+sol!(
+    interface IBalancerV2Pool {
+        function getPoolId() external view returns (bytes32);
+        function getNormalizedWeights() external view returns (uint256[]);
+        function getAmplificationParameter() external view returns (
+            uint256 value,
+            bool isUpdating,
+            uint256 precision
+        );
+        function getScalingFactors() external view returns (uint256[]);
+    }
+);
+
+pub const BALANCER_V2_VAULT_ADDRESS: Address =
+    Address::new(hex!("BA12222222228d8Ba445958a75a0704d566BF2C8"));
+
+/// The invariant that a balancer pool prices its tokens with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalancerV2Invariant {
+    /// normalized weights, scaled by 1e18
+    Weighted { weights: Vec<U256> },
+    /// `amp` is `A * amp_precision`. The scaling factors normalize a balance
+    /// to 18 decimals and include the rate of tokens with a rate provider
+    Stable { amp: U256, amp_precision: U256, scaling_factors: Vec<U256> },
+}
+
+/// A balancer v2 weighted or (composable) stable pool. The balances are held
+/// by the vault, which also emits all of the events for the pool. The bpt of
+/// a composable stable pool is registered as a pool token, it isn't priced
+/// and is left out of the tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalancerV2Pool {
+    pub address:   Address,
+    pub pool_id:   B256,
+    pub tokens:    Vec<Address>,
+    pub decimals:  Vec<u8>,
+    pub balances:  Vec<U256>,
+    pub invariant: BalancerV2Invariant,
+}
+
+#[async_trait]
+impl UpdatableProtocol for BalancerV2Pool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    /// The vault emits the swap log from its own frame instead of from the
+    /// `onSwap` call of the pool, so swaps are applied from the classified
+    /// action.
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        if let Action::Swap(swap) = action {
+            if swap.pool != self.address {
+                return Ok(())
+            }
+
+            let amount_in = to_raw_amount(&swap.amount_in, swap.token_in.decimals);
+            let amount_out = to_raw_amount(&swap.amount_out, swap.token_out.decimals);
+            self.apply_swap(swap.token_in.address, amount_in, swap.token_out.address, amount_out);
+        }
+
+        Ok(())
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event_signature = log.topics()[0];
+
+        // the vault emits the events for all pools
+        if log.topics().get(1) != Some(&self.pool_id) {
+            return Ok(())
+        }
+
+        if event_signature == IBalancerV2Vault::Swap::SIGNATURE_HASH {
+            let swap_event = IBalancerV2Vault::Swap::decode_log_data(&log, false)?;
+            self.apply_swap(
+                swap_event.tokenIn,
+                swap_event.amountIn,
+                swap_event.tokenOut,
+                swap_event.amountOut,
+            );
+        } else if event_signature == IBalancerV2Vault::PoolBalanceChanged::SIGNATURE_HASH {
+            let balance_event = IBalancerV2Vault::PoolBalanceChanged::decode_log_data(&log, false)?;
+
+            for ((token, delta), protocol_fee) in balance_event
+                .tokens
+                .iter()
+                .zip(&balance_event.deltas)
+                .zip(&balance_event.protocolFeeAmounts)
+            {
+                let Some(i) = self.index_of(*token) else { continue };
+                let balance = &mut self.balances[i];
+
+                *balance = if delta.is_negative() {
+                    balance.saturating_sub(delta.unsigned_abs())
+                } else {
+                    *balance + delta.unsigned_abs()
+                }
+                .saturating_sub(*protocol_fee);
+            }
+        } else {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        Ok(())
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = if self.tokens[0] == base_token { self.tokens[1] } else { self.tokens[0] };
+
+        self.calculate_price_pair(base_token, quote)
+    }
+}
+
+impl BalancerV2Pool {
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let pool_id = make_call_request(
+            IBalancerV2Pool::getPoolIdCall::new(()),
+            &middleware,
+            address,
+            Some(block_number),
+        )
+        .await?
+        ._0;
+
+        let pool_tokens = make_call_request(
+            IBalancerV2Vault::getPoolTokensCall::new((pool_id,)),
+            &middleware,
+            BALANCER_V2_VAULT_ADDRESS,
+            Some(block_number),
+        )
+        .await?;
+
+        let invariant = Self::load_invariant(address, block_number, &middleware).await?;
+
+        // the bpt is registered as a token of composable stable pools
+        let bpt_index = pool_tokens
+            .tokens
+            .iter()
+            .position(|token| *token == address);
+        let without_bpt = |mut values: Vec<U256>| {
+            if let Some(bpt_index) = bpt_index.filter(|i| *i < values.len()) {
+                values.remove(bpt_index);
+            }
+            values
+        };
+
+        let mut tokens = pool_tokens.tokens;
+        if let Some(bpt_index) = bpt_index {
+            tokens.remove(bpt_index);
+        }
+        let balances = without_bpt(pool_tokens.balances);
+
+        if tokens.len() < 2 {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        let decimals = try_join_all(
+            tokens
+                .iter()
+                .map(|token| get_decimals(*token, block_number, middleware.clone())),
+        )
+        .await?;
+
+        let invariant = match invariant {
+            BalancerV2Invariant::Stable { amp, amp_precision, scaling_factors } => {
+                let mut scaling_factors = without_bpt(scaling_factors);
+                // older stable pools don't expose their scaling factors, which then
+                // only depend on the decimals
+                if scaling_factors.len() != tokens.len() {
+                    scaling_factors = decimals
+                        .iter()
+                        .map(|decimals| U256::from(10).pow(U256::from(36 - *decimals as u64)))
+                        .collect();
+                }
+
+                BalancerV2Invariant::Stable { amp, amp_precision, scaling_factors }
+            }
+            weighted => weighted,
+        };
+
+        Ok(Self { address, pool_id, tokens, decimals, balances, invariant })
+    }
+
+    /// Weighted pools are the only pools with normalized weights, otherwise the
+    /// pool is expected to be a stable pool
+    async fn load_invariant<M: 'static + TracingProvider>(
+        address: Address,
+        block_number: u64,
+        middleware: &Arc<M>,
+    ) -> Result<BalancerV2Invariant, AmmError> {
+        if let Ok(weights) = make_call_request(
+            IBalancerV2Pool::getNormalizedWeightsCall::new(()),
+            middleware,
+            address,
+            Some(block_number),
+        )
+        .await
+        {
+            return Ok(BalancerV2Invariant::Weighted { weights: weights._0 })
+        }
+
+        let amp = make_call_request(
+            IBalancerV2Pool::getAmplificationParameterCall::new(()),
+            middleware,
+            address,
+            Some(block_number),
+        )
+        .await?;
+
+        let scaling_factors = make_call_request(
+            IBalancerV2Pool::getScalingFactorsCall::new(()),
+            middleware,
+            address,
+            Some(block_number),
+        )
+        .await
+        .map(|res| res._0)
+        .unwrap_or_default();
+
+        Ok(BalancerV2Invariant::Stable {
+            amp: amp.value,
+            amp_precision: amp.precision,
+            scaling_factors,
+        })
+    }
+
+    /// Price of the base token in the quote token, excluding the swap fee
+    pub fn calculate_price_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let i = self
+            .index_of(base)
+            .ok_or(ArithmeticError::BalancerTokenNotInPool(base))?;
+        let j = self
+            .index_of(quote)
+            .ok_or(ArithmeticError::BalancerTokenNotInPool(quote))?;
+
+        match &self.invariant {
+            BalancerV2Invariant::Weighted { weights } => {
+                let balances = self
+                    .balances
+                    .iter()
+                    .zip(&self.decimals)
+                    .map(|(balance, decimals)| balance.to_scaled_rational(*decimals))
+                    .collect::<Vec<_>>();
+
+                weighted::spot_price(&balances, weights, i, j)
+            }
+            BalancerV2Invariant::Stable { amp, amp_precision, scaling_factors } => {
+                stable::spot_price(
+                    &self.balances,
+                    scaling_factors,
+                    &self.decimals,
+                    *amp,
+                    *amp_precision,
+                    i,
+                    j,
+                )
+            }
+        }
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        (self.balance_of(base), self.balance_of(quote))
+    }
+
+    fn balance_of(&self, token: Address) -> Rational {
+        self.index_of(token)
+            .map(|i| self.balances[i].to_scaled_rational(self.decimals[i]))
+            .unwrap_or_default()
+    }
+
+    fn index_of(&self, token: Address) -> Option<usize> {
+        self.tokens.iter().position(|t| *t == token)
+    }
+
+    /// The vault moves the full amount in to the pool, the swap fee stays in
+    /// the pool balance
+    fn apply_swap(
+        &mut self,
+        token_in: Address,
+        amount_in: U256,
+        token_out: Address,
+        amount_out: U256,
+    ) {
+        // joins and exits through a swap trade against the bpt, which isn't
+        // tracked
+        if let Some(i) = self.index_of(token_in) {
+            self.balances[i] += amount_in;
+        }
+        if let Some(j) = self.index_of(token_out) {
+            self.balances[j] = self.balances[j].saturating_sub(amount_out);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use alloy_primitives::U256;
+use brontes_types::ToScaledRational;
+use malachite::{num::arithmetic::traits::Pow, Rational};
+
+use crate::{
+    curve::stable_swap::{get_d, marginal_price},
+    errors::ArithmeticError,
+};
+
+const ONE: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// The balances scaled to 18 decimals, including the token rates
+pub fn upscale(balances: &[U256], scaling_factors: &[U256]) -> Vec<U256> {
+    balances
+        .iter()
+        .zip(scaling_factors)
+        .map(|(balance, factor)| balance * factor / ONE)
+        .collect()
+}
+
+/// The spot price of token `i` in token `j` of a stable pool, excluding the
+/// swap fee. Balancer's StableMath solves the same invariant as the curve
+/// StableSwap pools, only with an amplification precision of 1e3.
+pub fn spot_price(
+    balances: &[U256],
+    scaling_factors: &[U256],
+    decimals: &[u8],
+    amp: U256,
+    amp_precision: U256,
+    i: usize,
+    j: usize,
+) -> Result<Rational, ArithmeticError> {
+    let xp = upscale(balances, scaling_factors);
+    let d = get_d(&xp, amp, amp_precision)?;
+    let price = marginal_price(&xp, d, amp, amp_precision, i, j)?;
+
+    Ok(price * token_value(scaling_factors, decimals, i)
+        / token_value(scaling_factors, decimals, j))
+}
+
+/// The value of a whole token in upscaled units
+fn token_value(scaling_factors: &[U256], decimals: &[u8], i: usize) -> Rational {
+    (scaling_factors[i] * U256::from(10).pow(U256::from(decimals[i]))).to_scaled_rational(36)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedSwap},
+        Protocol,
+    };
+    use malachite::num::basic::traits::One;
+
+    use super::*;
+    use crate::{
+        protocols::balancer_v2::{BalancerV2Invariant, BalancerV2Pool},
+        types::{PoolState, PoolSyncMode, PoolUpdate, PoolVariants},
+    };
+
+    #[test]
+    fn test_spot_price() {
+        let amp = U256::from(200 * 1000);
+        let amp_precision = U256::from(1000);
+        let decimals = [6u8, 18u8];
+        let scaling_factors =
+            decimals.map(|decimals| U256::from(10).pow(U256::from(36 - decimals as u64)));
+
+        // a balanced usdc / dai pool
+        let balances = [
+            U256::from(1_000_000) * U256::from(10).pow(U256::from(6)),
+            U256::from(1_000_000) * U256::from(10).pow(U256::from(18)),
+        ];
+        let price =
+            spot_price(&balances, &scaling_factors, &decimals, amp, amp_precision, 0, 1).unwrap();
+        assert_eq!(price, Rational::ONE);
+
+        // the pool holds more usdc, so usdc is worth a little less than dai
+        let balances = [
+            U256::from(1_500_000) * U256::from(10).pow(U256::from(6)),
+            U256::from(500_000) * U256::from(10).pow(U256::from(18)),
+        ];
+        let price =
+            spot_price(&balances, &scaling_factors, &decimals, amp, amp_precision, 0, 1).unwrap();
+        assert!(price < Rational::ONE);
+        assert!(price > Rational::from_signeds(99, 100));
+    }
+
+    #[test]
+    fn test_swap_updates_state() {
+        let [pool, usdc, dai] = [1u8, 2, 3].map(Address::with_last_byte);
+        let token = |address, decimals| TokenInfoWithAddress {
+            address,
+            inner: TokenInfo { decimals, ..Default::default() },
+        };
+        let decimals = [6u8, 18u8];
+
+        // a balanced usdc / dai pool
+        let stable = BalancerV2Pool {
+            address:   pool,
+            pool_id:   B256::default(),
+            tokens:    vec![usdc, dai],
+            decimals:  decimals.to_vec(),
+            balances:  vec![
+                U256::from(1_000_000) * U256::from(10).pow(U256::from(6)),
+                U256::from(1_000_000) * U256::from(10).pow(U256::from(18)),
+            ],
+            invariant: BalancerV2Invariant::Stable {
+                amp:             U256::from(200 * 1000),
+                amp_precision:   U256::from(1000),
+                scaling_factors: decimals
+                    .iter()
+                    .map(|decimals| U256::from(10).pow(U256::from(36 - *decimals as u64)))
+                    .collect(),
+            },
+        };
+        let mut state = PoolState::new(PoolVariants::BalancerV2(Box::new(stable)), 0);
+        assert_eq!(state.get_price(usdc, dai).unwrap(), Rational::ONE);
+
+        // balancer pools sync from the swap action in either mode
+        let swap = NormalizedSwap {
+            protocol: Protocol::BalancerV2,
+            pool,
+            token_in: token(usdc, 6),
+            token_out: token(dai, 18),
+            amount_in: Rational::from(500_000),
+            amount_out: Rational::from(500_000),
+            ..Default::default()
+        };
+        let update =
+            PoolUpdate { block: 1, tx_idx: 0, logs: vec![], action: Action::Swap(swap) };
+        state.increment_state(update, PoolSyncMode::Logs);
+
+        assert_eq!(state.get_tvl(usdc, dai), (Rational::from(1_500_000), Rational::from(500_000)));
+
+        // the pool holds more usdc, so usdc is worth a little less than dai
+        let price = state.get_price(usdc, dai).unwrap();
+        assert!(price < Rational::ONE);
+        assert!(price > Rational::from_signeds(99, 100));
+        assert_eq!(state.last_update, 1);
+    }
+}
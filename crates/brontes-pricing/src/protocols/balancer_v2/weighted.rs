@@ -0,0 +1,101 @@
+use alloy_primitives::U256;
+use brontes_types::ToScaledRational;
+use malachite::Rational;
+
+use crate::errors::ArithmeticError;
+
+/// The spot price of token `i` in token `j` of a weighted pool, excluding the
+/// swap fee. The balances are in whole tokens and the weights are normalized
+/// and scaled by 1e18, `(B_j / w_j) / (B_i / w_i)`.
+pub fn spot_price(
+    balances: &[Rational],
+    weights: &[U256],
+    i: usize,
+    j: usize,
+) -> Result<Rational, ArithmeticError> {
+    let (Some(w_i), Some(w_j)) = (weights.get(i), weights.get(j)) else {
+        return Err(ArithmeticError::BalancerDivZero)
+    };
+    if balances[i] == Rational::default() || w_j.is_zero() {
+        return Err(ArithmeticError::BalancerDivZero)
+    }
+
+    Ok(&balances[j] * w_i.to_scaled_rational(18) / (&balances[i] * w_j.to_scaled_rational(18)))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedSwap},
+        Protocol,
+    };
+
+    use super::*;
+    use crate::{
+        protocols::balancer_v2::{BalancerV2Invariant, BalancerV2Pool},
+        types::{PoolState, PoolSyncMode, PoolUpdate, PoolVariants},
+    };
+
+    #[test]
+    fn test_spot_price() {
+        let weights = [
+            U256::from(8) * U256::from(10).pow(U256::from(17)),
+            U256::from(2) * U256::from(10).pow(U256::from(17)),
+        ];
+
+        let balances = [Rational::from(800), Rational::from(200)];
+        assert_eq!(spot_price(&balances, &weights, 0, 1).unwrap(), Rational::from(1));
+
+        let balances = [Rational::from(1600), Rational::from(200)];
+        assert_eq!(spot_price(&balances, &weights, 0, 1).unwrap(), Rational::from_signeds(1, 2));
+        assert_eq!(spot_price(&balances, &weights, 1, 0).unwrap(), Rational::from(2));
+    }
+
+    #[test]
+    fn test_swap_updates_state() {
+        let [pool, weth, usdc] = [1u8, 2, 3].map(Address::with_last_byte);
+        let token = |address, decimals| TokenInfoWithAddress {
+            address,
+            inner: TokenInfo { decimals, ..Default::default() },
+        };
+
+        let weighted = BalancerV2Pool {
+            address:   pool,
+            pool_id:   B256::default(),
+            tokens:    vec![weth, usdc],
+            decimals:  vec![18, 6],
+            balances:  vec![
+                U256::from(800) * U256::from(10).pow(U256::from(18)),
+                U256::from(200) * U256::from(10).pow(U256::from(6)),
+            ],
+            invariant: BalancerV2Invariant::Weighted {
+                weights: vec![
+                    U256::from(8) * U256::from(10).pow(U256::from(17)),
+                    U256::from(2) * U256::from(10).pow(U256::from(17)),
+                ],
+            },
+        };
+        let mut state = PoolState::new(PoolVariants::BalancerV2(Box::new(weighted)), 0);
+        assert_eq!(state.get_price(weth, usdc).unwrap(), Rational::from(1));
+
+        // balancer pools sync from the swap action in either mode
+        let swap = NormalizedSwap {
+            protocol: Protocol::BalancerV2,
+            pool,
+            token_in: token(usdc, 6),
+            token_out: token(weth, 18),
+            amount_in: Rational::from(200),
+            amount_out: Rational::from(100),
+            ..Default::default()
+        };
+        let update =
+            PoolUpdate { block: 1, tx_idx: 0, logs: vec![], action: Action::Swap(swap) };
+        state.increment_state(update, PoolSyncMode::Logs);
+
+        assert_eq!(state.get_tvl(weth, usdc), (Rational::from(700), Rational::from(400)));
+        assert_eq!(state.get_price(weth, usdc).unwrap(), Rational::from_signeds(16, 7));
+        assert_eq!(state.last_update, 1);
+    }
+}
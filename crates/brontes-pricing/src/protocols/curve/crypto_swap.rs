@@ -0,0 +1,433 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, U256};
+use alloy_sol_macro::sol;
+use async_trait::async_trait;
+use brontes_types::{
+    normalized_actions::Action, queries::make_call_request, traits::TracingProvider, Protocol,
+    ToScaledRational,
+};
+use futures::future::try_join_all;
+use malachite::{
+    num::{arithmetic::traits::Pow, basic::traits::One},
+    Rational,
+};
+
+use super::{event_signature, log_topic, CurveCoins, LogWords};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    UpdatableProtocol,
+};
+
+sol!(
+    interface ICurveCryptoSwap {
+        function A() external view returns (uint256);
+        function gamma() external view returns (uint256);
+        function D() external view returns (uint256);
+        function price_scale() external view returns (uint256);
+    }
+);
+
+sol!(
+    interface ICurveTriCrypto {
+        function price_scale(uint256 k) external view returns (uint256);
+    }
+);
+
+const PRECISION: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+const A_MULTIPLIER: U256 = U256::from_limbs([10_000, 0, 0, 0]);
+
+/// A curve CryptoSwap (two coin) or TriCrypto pool. Balances are moved onto
+/// the curve by `price_scale`, which is only repegged by the pool during
+/// trades and isn't part of the logs, so it is kept at the value it had when
+/// the pool was loaded while `D` is recomputed on every balance change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurveCryptoPool {
+    pub address:     Address,
+    pub protocol:    Protocol,
+    pub coins:       CurveCoins,
+    /// price of every coin after the first in the first coin, scaled by 1e18
+    pub price_scale: Vec<U256>,
+    /// `A * N^N * A_MULTIPLIER`
+    pub ann:         U256,
+    pub gamma:       U256,
+    pub d:           U256,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveCryptoPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Curve actions don't carry the balances the pool ended up at, so the
+    /// state is always synced from the pool events
+    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
+        Err(AmmError::SyncFromActionNotSupported(self.protocol))
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let topic = log_topic(&log)?;
+        let n = self.coins.coins.len();
+        let words = LogWords::new(&log);
+
+        if topic == event_signature("TokenExchange", "address,uint256,uint256,uint256,uint256") {
+            let (i, j) = (words.index(0, n)?, words.index(2, n)?);
+            self.coins.balances[i] = self.coins.balances[i]
+                .checked_add(words.get(1)?)
+                .ok_or(ArithmeticError::CurveOverflow)?;
+            self.coins.balances[j] = self.coins.balances[j].saturating_sub(words.get(3)?);
+        } else if topic
+            == event_signature("AddLiquidity", &format!("address,uint256[{n}],uint256,uint256"))
+        {
+            let amounts = words.slice(0, n)?;
+            self.coins.balances = self
+                .coins
+                .balances
+                .iter()
+                .zip(amounts)
+                .map(|(balance, amount)| {
+                    balance
+                        .checked_add(*amount)
+                        .ok_or(ArithmeticError::CurveOverflow)
+                })
+                .collect::<Result<_, _>>()?;
+        } else if topic
+            == event_signature("RemoveLiquidity", &format!("address,uint256[{n}],uint256"))
+        {
+            let amounts = words.slice(0, n)?;
+            for (balance, amount) in self.coins.balances.iter_mut().zip(amounts) {
+                *balance = balance.saturating_sub(*amount);
+            }
+        } else if topic == event_signature("RemoveLiquidityOne", "address,uint256,uint256,uint256")
+        {
+            // token_amount, coin_index, coin_amount
+            let i = words.index(1, n)?;
+            self.coins.balances[i] = self.coins.balances[i].saturating_sub(words.get(2)?);
+        } else {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        self.d = newton_d(self.ann, self.gamma, &self.xp())?;
+
+        Ok(())
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.coins.coins.clone()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = if self.coins.coins[0] == base_token {
+            self.coins.coins[1]
+        } else {
+            self.coins.coins[0]
+        };
+
+        self.calculate_price_pair(base_token, quote)
+    }
+}
+
+impl CurveCryptoPool {
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let (coins, ann, gamma, d) = futures::try_join!(
+            CurveCoins::load(address, block_number, middleware.clone()),
+            async {
+                Ok::<_, AmmError>(
+                    make_call_request(
+                        ICurveCryptoSwap::ACall::new(()),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await?
+                    ._0,
+                )
+            },
+            async {
+                Ok::<_, AmmError>(
+                    make_call_request(
+                        ICurveCryptoSwap::gammaCall::new(()),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await?
+                    ._0,
+                )
+            },
+            async {
+                Ok::<_, AmmError>(
+                    make_call_request(
+                        ICurveCryptoSwap::DCall::new(()),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await?
+                    ._0,
+                )
+            },
+        )?;
+
+        // two coin pools only have a single price so the getter takes no index
+        let price_scale = if coins.coins.len() == 2 {
+            vec![
+                make_call_request(
+                    ICurveCryptoSwap::price_scaleCall::new(()),
+                    &middleware,
+                    address,
+                    Some(block_number),
+                )
+                .await?
+                ._0,
+            ]
+        } else {
+            try_join_all((0..coins.coins.len() - 1).map(|k| {
+                let middleware = middleware.clone();
+                async move {
+                    make_call_request(
+                        ICurveTriCrypto::price_scaleCall::new((U256::from(k),)),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await
+                    .map(|res| res._0)
+                }
+            }))
+            .await?
+        };
+
+        Ok(Self { address, protocol, coins, price_scale, ann, gamma, d })
+    }
+
+    /// The balances normalized to 18 decimals and priced in the first coin
+    pub fn xp(&self) -> Vec<U256> {
+        self.coins
+            .balances
+            .iter()
+            .zip(&self.coins.decimals)
+            .enumerate()
+            .map(|(i, (balance, decimals))| {
+                let xp = balance * U256::from(10).pow(U256::from(18 - *decimals as u64));
+                if i == 0 {
+                    xp
+                } else {
+                    xp * self.price_scale[i - 1] / PRECISION
+                }
+            })
+            .collect()
+    }
+
+    /// Price of the base token in the quote token, excluding fees
+    pub fn calculate_price_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let i = self
+            .coins
+            .index_of(base)
+            .ok_or(ArithmeticError::CurveTokenNotInPool(base))?;
+        let j = self
+            .coins
+            .index_of(quote)
+            .ok_or(ArithmeticError::CurveTokenNotInPool(quote))?;
+
+        let price = marginal_price(&self.xp(), self.d, self.ann, self.gamma, i, j)?;
+
+        Ok(price * self.weight(i) / self.weight(j))
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let balance_of = |token: Address| {
+            self.coins
+                .index_of(token)
+                .map(|i| self.coins.balances[i].to_scaled_rational(self.coins.decimals[i]))
+                .unwrap_or_default()
+        };
+
+        (balance_of(base), balance_of(quote))
+    }
+
+    /// The value of a whole token in whole normalized units
+    fn weight(&self, i: usize) -> Rational {
+        if i == 0 {
+            Rational::ONE
+        } else {
+            self.price_scale[i - 1].to_scaled_rational(18)
+        }
+    }
+}
+
+fn geometric_mean(x: &[U256]) -> Result<U256, ArithmeticError> {
+    let n = U256::from(x.len());
+    let mut d = x[0];
+
+    for _ in 0..255 {
+        let d_prev = d;
+        let mut tmp = PRECISION;
+        for v in x {
+            tmp = tmp * v / d;
+        }
+        d = d * ((n - U256::from(1)) * PRECISION + tmp) / (n * PRECISION);
+
+        let diff = d.abs_diff(d_prev);
+        if diff <= U256::from(1) || diff * PRECISION < d {
+            return Ok(d)
+        }
+    }
+
+    Err(ArithmeticError::CurveNoConvergence)
+}
+
+/// Solves the CryptoSwap invariant for `D` the same way the pool contract does
+pub fn newton_d(ann: U256, gamma: U256, x_unsorted: &[U256]) -> Result<U256, ArithmeticError> {
+    if x_unsorted.iter().any(|x| x.is_zero()) {
+        return Err(ArithmeticError::CurveDivZero)
+    }
+
+    let mut x = x_unsorted.to_vec();
+    x.sort_unstable_by(|a, b| b.cmp(a));
+
+    let n = U256::from(x.len());
+    let s = x.iter().fold(U256::ZERO, |acc, x| acc + x);
+    let mut d = n * geometric_mean(&x)?;
+
+    for _ in 0..255 {
+        let d_prev = d;
+
+        let mut k0 = PRECISION;
+        for v in &x {
+            k0 = k0 * v * n / d;
+        }
+
+        let g1k0 = gamma + PRECISION;
+        let g1k0 = if g1k0 > k0 { g1k0 - k0 + U256::from(1) } else { k0 - g1k0 + U256::from(1) };
+
+        // D / (A * N**N) * g1k0**2 / gamma**2
+        let mul1 = PRECISION * d / gamma * g1k0 / gamma * g1k0 * A_MULTIPLIER / ann;
+        // 2 * N * K0 / g1k0
+        let mul2 = PRECISION * U256::from(2) * n * k0 / g1k0;
+
+        // the contract reverts on an underflow, so these are checked instead of
+        // panicking
+        let neg_fprime = ((s + s * mul2 / PRECISION) + mul1 * n / k0)
+            .checked_sub(mul2 * d / PRECISION)
+            .ok_or(ArithmeticError::CurveNoConvergence)?;
+
+        let d_plus = d * (neg_fprime + s) / neg_fprime;
+        let mut d_minus = d * d / neg_fprime;
+        if PRECISION > k0 {
+            d_minus = d_minus
+                .checked_add(d * (mul1 / neg_fprime) / PRECISION * (PRECISION - k0) / k0)
+                .ok_or(ArithmeticError::CurveNoConvergence)?;
+        } else {
+            d_minus = d_minus
+                .checked_sub(d * (mul1 / neg_fprime) / PRECISION * (k0 - PRECISION) / k0)
+                .ok_or(ArithmeticError::CurveNoConvergence)?;
+        }
+
+        d = if d_plus > d_minus { d_plus - d_minus } else { (d_minus - d_plus) / U256::from(2) };
+
+        if d.abs_diff(d_prev) * U256::from(100_000_000_000_000u64)
+            < d.max(PRECISION / U256::from(100))
+        {
+            return Ok(d)
+        }
+    }
+
+    Err(ArithmeticError::CurveNoConvergence)
+}
+
+/// The amount of coin `j` received for an infinitesimal amount of coin `i`, in
+/// normalized units. This is the ratio of the partial derivatives of the
+/// invariant `K D^(N-1) sum(x) + prod(x) = K D^N + (D/N)^N` where
+/// `K = A K0 gamma^2 / (gamma + 1 - K0)^2` and `K0 = prod(x) N^N / D^N`.
+fn marginal_price(
+    xp: &[U256],
+    d: U256,
+    ann: U256,
+    gamma: U256,
+    i: usize,
+    j: usize,
+) -> Result<Rational, ArithmeticError> {
+    if d.is_zero() || xp.iter().any(|x| x.is_zero()) {
+        return Err(ArithmeticError::CurveDivZero)
+    }
+
+    let n = xp.len() as u64;
+    let n_pow_n = Rational::from(n).pow(n);
+    let d = d.to_scaled_rational(0);
+    let xp = xp
+        .iter()
+        .map(|x| x.to_scaled_rational(0))
+        .collect::<Vec<_>>();
+
+    let a = ann.to_scaled_rational(0) / (A_MULTIPLIER.to_scaled_rational(0) * &n_pow_n);
+    let gamma = gamma.to_scaled_rational(18);
+    let sum = xp.iter().fold(Rational::default(), |acc, x| acc + x);
+    let prod = xp.iter().fold(Rational::ONE, |acc, x| acc * x);
+
+    let k0 = &prod * n_pow_n / d.clone().pow(n);
+    let g1k0 = &gamma + Rational::ONE - &k0;
+    let g2 = gamma.clone().pow(2u64);
+    let k = &a * &k0 * &g2 / g1k0.clone().pow(2u64);
+    let dk_dk0 = a * g2 * (gamma + Rational::ONE + &k0) / g1k0.pow(3u64);
+
+    let d_n_minus_1 = d.clone().pow(n - 1);
+    let d_n = d.pow(n);
+
+    let partial = |x: &Rational| {
+        &dk_dk0 * &k0 / x * (&d_n_minus_1 * &sum - &d_n) + &k * &d_n_minus_1 + &prod / x
+    };
+
+    Ok(partial(&xp[i]) / partial(&xp[j]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // tricrypto2 parameters
+    const ANN: U256 = U256::from_limbs([1_707_629, 0, 0, 0]);
+    const GAMMA: U256 = U256::from_limbs([11_809_167_828_997, 0, 0, 0]);
+
+    fn xp(amounts: &[u64]) -> Vec<U256> {
+        amounts
+            .iter()
+            .map(|amount| U256::from(*amount) * PRECISION)
+            .collect()
+    }
+
+    #[test]
+    fn test_newton_d_balanced() {
+        let d = newton_d(ANN, GAMMA, &xp(&[1_000_000, 1_000_000, 1_000_000])).unwrap();
+        let expected = U256::from(3_000_000) * PRECISION;
+
+        assert!(d.abs_diff(expected) < PRECISION);
+    }
+
+    #[test]
+    fn test_marginal_price() {
+        let balanced = xp(&[1_000_000, 1_000_000, 1_000_000]);
+        let d = newton_d(ANN, GAMMA, &balanced).unwrap();
+        assert_eq!(marginal_price(&balanced, d, ANN, GAMMA, 0, 1).unwrap(), Rational::ONE);
+
+        // the pool holds more of coin 0, so coin 0 is worth less than coin 1
+        let skewed = xp(&[1_100_000, 950_000, 1_000_000]);
+        let d = newton_d(ANN, GAMMA, &skewed).unwrap();
+        let price = marginal_price(&skewed, d, ANN, GAMMA, 0, 1).unwrap();
+        assert!(price < Rational::ONE);
+        assert_eq!(
+            price.clone() * marginal_price(&skewed, d, ANN, GAMMA, 1, 0).unwrap(),
+            Rational::ONE
+        );
+    }
+}
@@ -0,0 +1,211 @@
+pub mod crypto_swap;
+pub mod stable_swap;
+
+use std::sync::Arc;
+
+use alloy_primitives::{keccak256, Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use brontes_types::{
+    constants::{ETH_ADDRESS, WETH_ADDRESS},
+    traits::TracingProvider,
+};
+use futures::future::try_join_all;
+
+use super::{get_decimals, make_call_request};
+use crate::errors::{AmmError, EventLogError};
+
+sol!(
+    interface ICurvePool {
+        function coins(uint256 i) external view returns (address);
+        function balances(uint256 i) external view returns (uint256);
+    }
+);
+
+sol!(
+    interface ICurveLegacyPool {
+        function coins(int128 i) external view returns (address);
+        function balances(int128 i) external view returns (uint256);
+    }
+);
+
+/// The most coins that any curve pool is deployed with
+const MAX_COINS: usize = 8;
+
+/// The coins of a curve pool along with their decimals and the balances the
+/// pool holds of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurveCoins {
+    pub coins:    Vec<Address>,
+    pub decimals: Vec<u8>,
+    pub balances: Vec<U256>,
+}
+
+impl CurveCoins {
+    /// Loads the coins of the pool. The number of coins isn't exposed, so we
+    /// query until the index is out of range. The oldest pools index their
+    /// coins with an `int128`.
+    pub async fn load<M: TracingProvider>(
+        address: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let mut legacy = false;
+        let mut coins = Vec::new();
+
+        for i in 0..MAX_COINS {
+            let coin = if legacy {
+                make_call_request(
+                    ICurveLegacyPool::coinsCall::new((i as i128,)),
+                    &middleware,
+                    address,
+                    Some(block_number),
+                )
+                .await
+                .map(|res| res._0)
+            } else {
+                make_call_request(
+                    ICurvePool::coinsCall::new((U256::from(i),)),
+                    &middleware,
+                    address,
+                    Some(block_number),
+                )
+                .await
+                .map(|res| res._0)
+            };
+
+            match coin {
+                Ok(coin) => coins.push(native_to_weth(coin)),
+                Err(_) if i == 0 && !legacy => {
+                    legacy = true;
+                    let coin = make_call_request(
+                        ICurveLegacyPool::coinsCall::new((0,)),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await?;
+                    coins.push(native_to_weth(coin._0));
+                }
+                Err(_) => break,
+            }
+        }
+
+        if coins.len() < 2 {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        let balances = try_join_all((0..coins.len()).map(|i| {
+            let middleware = middleware.clone();
+            async move {
+                if legacy {
+                    make_call_request(
+                        ICurveLegacyPool::balancesCall::new((i as i128,)),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await
+                    .map(|res| res._0)
+                } else {
+                    make_call_request(
+                        ICurvePool::balancesCall::new((U256::from(i),)),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await
+                    .map(|res| res._0)
+                }
+            }
+        }))
+        .await?;
+
+        let decimals = try_join_all(
+            coins
+                .iter()
+                .map(|coin| get_decimals(*coin, block_number, middleware.clone())),
+        )
+        .await?;
+
+        Ok(Self { coins, decimals, balances })
+    }
+
+    pub fn index_of(&self, token: Address) -> Option<usize> {
+        self.coins.iter().position(|coin| *coin == token)
+    }
+}
+
+/// Curve uses 0xEeee..EEeE for native ETH, which is priced as WETH
+pub fn native_to_weth(coin: Address) -> Address {
+    if coin == ETH_ADDRESS {
+        WETH_ADDRESS
+    } else {
+        coin
+    }
+}
+
+/// The event signatures of curve pools depend on the amount of coins in the
+/// pool, so they are built for the pool instead of being declared with `sol!`.
+pub(crate) fn event_signature(name: &str, params: &str) -> B256 {
+    keccak256(format!("{name}({params})"))
+}
+
+/// The event signature of a curve log
+pub(crate) fn log_topic(log: &Log) -> Result<B256, EventLogError> {
+    log.topics()
+        .first()
+        .copied()
+        .ok_or(EventLogError::InvalidEventSignature)
+}
+
+/// All non indexed curve event fields are static, so the log data can be read
+/// as a list of words.
+pub(crate) struct LogWords(Vec<U256>);
+
+impl LogWords {
+    pub(crate) fn new(log: &Log) -> Self {
+        Self(
+            log.data
+                .data
+                .chunks_exact(32)
+                .map(U256::from_be_slice)
+                .collect(),
+        )
+    }
+
+    pub(crate) fn get(&self, i: usize) -> Result<U256, EventLogError> {
+        self.0.get(i).copied().ok_or(EventLogError::InvalidLogData)
+    }
+
+    /// `n` words starting at `start`, for the fixed size arrays of the
+    /// liquidity events
+    pub(crate) fn slice(&self, start: usize, n: usize) -> Result<&[U256], EventLogError> {
+        self.0
+            .get(start..start + n)
+            .ok_or(EventLogError::InvalidLogData)
+    }
+
+    /// A coin index. Older pools log these as an `int128`, so a negative index
+    /// is out of range as well.
+    pub(crate) fn index(&self, i: usize, n_coins: usize) -> Result<usize, EventLogError> {
+        usize::try_from(self.get(i)?)
+            .ok()
+            .filter(|i| *i < n_coins)
+            .ok_or(EventLogError::InvalidLogData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_event_signature() {
+        assert_eq!(
+            event_signature("TokenExchange", "address,int128,uint256,int128,uint256"),
+            B256::new(hex!("8b3e96f2b889fa771c53c981b40daf005f63f637f1869f707052d15a3dd97140"))
+        );
+    }
+}
@@ -0,0 +1,747 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use async_trait::async_trait;
+use brontes_types::{
+    normalized_actions::Action, queries::make_call_request, traits::TracingProvider, Protocol,
+    ToScaledRational,
+};
+use malachite::{
+    num::{
+        arithmetic::traits::{Pow, Reciprocal},
+        basic::traits::One,
+    },
+    Rational,
+};
+
+use super::{event_signature, log_topic, native_to_weth, CurveCoins, LogWords};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    UpdatableProtocol,
+};
+
+sol!(
+    interface ICurveStableSwap {
+        function A() external view returns (uint256);
+        function A_precise() external view returns (uint256);
+        function fee() external view returns (uint256);
+        function admin_fee() external view returns (uint256);
+        function base_pool() external view returns (address);
+        function get_virtual_price() external view returns (uint256);
+    }
+);
+
+const PRECISION: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+const FEE_DENOMINATOR: U256 = U256::from_limbs([10_000_000_000, 0, 0, 0]);
+
+/// A curve StableSwap pool. Base pools, plain pools and metapools all share
+/// the same invariant, a metapool trades its coin against the lp token of its
+/// base pool, which is the last coin of the pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurveStablePool {
+    pub address:     Address,
+    pub protocol:    Protocol,
+    pub coins:       CurveCoins,
+    /// multiplier that normalizes a balance to 18 decimals, scaled by 1e18. For
+    /// the base pool lp token of a metapool this is the virtual price
+    pub rates:       Vec<U256>,
+    /// `A * A_PRECISION`
+    pub amp:         U256,
+    pub a_precision: U256,
+    pub fee:         U256,
+    pub admin_fee:   U256,
+    pub base_pool:   Option<Box<CurveStablePool>>,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveStablePool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Curve actions don't carry the balances the pool ended up at, so the
+    /// state is always synced from the pool events
+    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
+        Err(AmmError::SyncFromActionNotSupported(self.protocol))
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let topic = log_topic(&log)?;
+        let n = self.coins.coins.len();
+        let words = LogWords::new(&log);
+
+        if topic == event_signature("TokenExchange", "address,int128,uint256,int128,uint256") {
+            let (i, j) = (words.index(0, n)?, words.index(2, n)?);
+            self.apply_exchange(i, words.get(1)?, j, words.get(3)?)?;
+        } else if topic
+            == event_signature("TokenExchangeUnderlying", "address,int128,uint256,int128,uint256")
+        {
+            // the metapool coin followed by the coins of the base pool
+            let n_underlying = self
+                .base_pool
+                .as_ref()
+                .map(|base_pool| base_pool.coins.coins.len() + 1)
+                .unwrap_or(n);
+            let (i, j) = (words.index(0, n_underlying)?, words.index(2, n_underlying)?);
+            self.apply_underlying_exchange(i, words.get(1)?, j, words.get(3)?)?;
+        } else if topic
+            == event_signature(
+                "AddLiquidity",
+                &format!("address,uint256[{n}],uint256[{n}],uint256,uint256"),
+            )
+        {
+            let (amounts, fees) = (words.slice(0, n)?, words.slice(n, n)?);
+            self.coins.balances = self
+                .coins
+                .balances
+                .iter()
+                .zip(amounts.iter().zip(fees))
+                .map(|(balance, (amount, fee))| {
+                    Ok(balance
+                        .checked_add(*amount)
+                        .ok_or(ArithmeticError::CurveOverflow)?
+                        .saturating_sub(self.admin_fee_of(*fee)?))
+                })
+                .collect::<Result<_, ArithmeticError>>()?;
+        } else if topic
+            == event_signature(
+                "RemoveLiquidity",
+                &format!("address,uint256[{n}],uint256[{n}],uint256"),
+            )
+        {
+            let amounts = words.slice(0, n)?;
+            for (balance, amount) in self.coins.balances.iter_mut().zip(amounts) {
+                *balance = balance.saturating_sub(*amount);
+            }
+        } else if topic
+            == event_signature(
+                "RemoveLiquidityImbalance",
+                &format!("address,uint256[{n}],uint256[{n}],uint256,uint256"),
+            )
+        {
+            let (amounts, fees) = (words.slice(0, n)?, words.slice(n, n)?);
+            self.coins.balances = self
+                .coins
+                .balances
+                .iter()
+                .zip(amounts.iter().zip(fees))
+                .map(|(balance, (amount, fee))| {
+                    Ok(balance
+                        .saturating_sub(*amount)
+                        .saturating_sub(self.admin_fee_of(*fee)?))
+                })
+                .collect::<Result<_, ArithmeticError>>()?;
+        } else if topic
+            == event_signature("RemoveLiquidityOne", "address,int128,uint256,uint256,uint256")
+        {
+            let i = words.index(0, n)?;
+            self.coins.balances[i] = self.coins.balances[i].saturating_sub(words.get(2)?);
+        } else if is_legacy_remove_liquidity_one(topic) {
+            Err(EventLogError::MissingCoinIndex)?
+        } else {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        Ok(())
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.coins.coins.clone()
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = if self.coins.coins[0] == base_token {
+            self.coins.coins[1]
+        } else {
+            self.coins.coins[0]
+        };
+
+        self.calculate_price_pair(base_token, quote)
+    }
+}
+
+impl CurveStablePool {
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let mut pool = Self::load_pool(address, protocol, block_number, middleware.clone()).await?;
+
+        if is_meta_pool(protocol) {
+            let base_address = make_call_request(
+                ICurveStableSwap::base_poolCall::new(()),
+                &middleware,
+                address,
+                Some(block_number),
+            )
+            .await?
+            ._0;
+
+            let (mut base_pool, virtual_price) = futures::try_join!(
+                Self::load_pool(base_address, protocol, block_number, middleware.clone()),
+                async {
+                    Ok::<_, AmmError>(
+                        make_call_request(
+                            ICurveStableSwap::get_virtual_priceCall::new(()),
+                            &middleware,
+                            base_address,
+                            Some(block_number),
+                        )
+                        .await?
+                        ._0,
+                    )
+                },
+            )?;
+
+            base_pool.protocol = match base_pool.coins.coins.len() {
+                2 => Protocol::CurveBasePool2,
+                3 => Protocol::CurveBasePool3,
+                _ => Protocol::CurveBasePool4,
+            };
+            *pool.rates.last_mut().unwrap() = virtual_price;
+            pool.base_pool = Some(Box::new(base_pool));
+        }
+
+        Ok(pool)
+    }
+
+    async fn load_pool<M: 'static + TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<Self, AmmError> {
+        let (coins, fee, admin_fee) = futures::try_join!(
+            CurveCoins::load(address, block_number, middleware.clone()),
+            async {
+                Ok::<_, AmmError>(
+                    make_call_request(
+                        ICurveStableSwap::feeCall::new(()),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await?
+                    ._0,
+                )
+            },
+            async {
+                Ok::<_, AmmError>(
+                    make_call_request(
+                        ICurveStableSwap::admin_feeCall::new(()),
+                        &middleware,
+                        address,
+                        Some(block_number),
+                    )
+                    .await?
+                    ._0,
+                )
+            },
+        )?;
+
+        // pools deployed before `A_PRECISION` was added only expose `A`
+        let (amp, a_precision) = match make_call_request(
+            ICurveStableSwap::A_preciseCall::new(()),
+            &middleware,
+            address,
+            Some(block_number),
+        )
+        .await
+        {
+            Ok(amp) => (amp._0, U256::from(100)),
+            Err(_) => (
+                make_call_request(
+                    ICurveStableSwap::ACall::new(()),
+                    &middleware,
+                    address,
+                    Some(block_number),
+                )
+                .await?
+                ._0,
+                U256::from(1),
+            ),
+        };
+
+        let rates = coins
+            .decimals
+            .iter()
+            .map(|decimals| U256::from(10).pow(U256::from(36 - *decimals as u64)))
+            .collect();
+
+        Ok(Self {
+            address,
+            protocol,
+            coins,
+            rates,
+            amp,
+            a_precision,
+            fee,
+            admin_fee,
+            base_pool: None,
+        })
+    }
+
+    /// The balances normalized to 18 decimals
+    pub fn xp(&self) -> Vec<U256> {
+        self.coins
+            .balances
+            .iter()
+            .zip(&self.rates)
+            .map(|(balance, rate)| balance * rate / PRECISION)
+            .collect()
+    }
+
+    /// Price of the base token in the quote token. For metapools either
+    /// token can be a coin of the base pool, in which case the price is
+    /// routed through the base pool lp token.
+    pub fn calculate_price_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let (i, j) = (self.coins.index_of(base), self.coins.index_of(quote));
+        if let (Some(i), Some(j)) = (i, j) {
+            return self.spot_price(i, j)
+        }
+
+        let base_pool = self
+            .base_pool
+            .as_ref()
+            .ok_or(ArithmeticError::CurveTokenNotInPool(if i.is_none() { base } else { quote }))?;
+        let lp = self.coins.coins.len() - 1;
+        let virtual_price = self.rates[lp];
+
+        match (i, j) {
+            (Some(i), None) => {
+                let j = base_pool.index_of(quote)?;
+                Ok(self.spot_price(i, lp)? * base_pool.lp_price(j, virtual_price)?)
+            }
+            (None, Some(j)) => {
+                let i = base_pool.index_of(base)?;
+                Ok(base_pool.lp_price(i, virtual_price)?.reciprocal() * self.spot_price(lp, j)?)
+            }
+            _ => base_pool.calculate_price_pair(base, quote),
+        }
+    }
+
+    /// The balances of the base and quote token. A base pool coin held by a
+    /// metapool is the share of the base pool that the metapool owns.
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        (self.balance_of(base), self.balance_of(quote))
+    }
+
+    fn balance_of(&self, token: Address) -> Rational {
+        if let Some(i) = self.coins.index_of(token) {
+            return self.coins.balances[i].to_scaled_rational(self.coins.decimals[i])
+        }
+
+        let Some(base_pool) = self.base_pool.as_ref() else { return Rational::default() };
+        let Ok(i) = base_pool.index_of(token) else { return Rational::default() };
+        let Ok(d) = get_d(&base_pool.xp(), base_pool.amp, base_pool.a_precision) else {
+            return Rational::default()
+        };
+        if d.is_zero() {
+            return Rational::default()
+        }
+
+        // lp supply = D / virtual price
+        let virtual_price = *self.rates.last().unwrap();
+        let lp_balance = *self.coins.balances.last().unwrap();
+        let share = lp_balance.to_scaled_rational(0) * virtual_price.to_scaled_rational(18)
+            / d.to_scaled_rational(0);
+
+        base_pool.coins.balances[i].to_scaled_rational(base_pool.coins.decimals[i]) * share
+    }
+
+    fn index_of(&self, token: Address) -> Result<usize, ArithmeticError> {
+        self.coins
+            .index_of(token)
+            .ok_or(ArithmeticError::CurveTokenNotInPool(token))
+    }
+
+    /// The value of a whole token in whole normalized units
+    fn weight(&self, i: usize) -> Rational {
+        (self.rates[i] * U256::from(10).pow(U256::from(self.coins.decimals[i])))
+            .to_scaled_rational(36)
+    }
+
+    /// The marginal price of coin `i` in coin `j`, excluding fees
+    fn spot_price(&self, i: usize, j: usize) -> Result<Rational, ArithmeticError> {
+        let xp = self.xp();
+        let d = get_d(&xp, self.amp, self.a_precision)?;
+        let price = marginal_price(&xp, d, self.amp, self.a_precision, i, j)?;
+
+        Ok(price * self.weight(i) / self.weight(j))
+    }
+
+    /// The price of the lp token of this pool in coin `i`. A whole lp token is
+    /// worth the virtual price in units of `D`, which is divided by the
+    /// change in `D` from adding a whole coin.
+    fn lp_price(&self, i: usize, virtual_price: U256) -> Result<Rational, ArithmeticError> {
+        let xp = self.xp();
+        let d = get_d(&xp, self.amp, self.a_precision)?;
+        let d_per_coin = d_derivative(&xp, d, self.amp, self.a_precision, i)? * self.weight(i);
+
+        Ok(virtual_price.to_scaled_rational(18) / d_per_coin)
+    }
+
+    /// Pools deployed before the coin index was added to `RemoveLiquidityOne`
+    /// don't log which coin was withdrawn, so it is taken from the burn that
+    /// the log was classified into.
+    pub fn sync_from_log_with_action(&mut self, log: Log, action: &Action) -> Result<(), AmmError> {
+        if !is_legacy_remove_liquidity_one(log_topic(&log)?) {
+            return self.sync_from_log(log)
+        }
+
+        let Action::Burn(burn) = action else { return Err(EventLogError::MissingCoinIndex.into()) };
+        let [token] = burn.token.as_slice() else {
+            return Err(EventLogError::MissingCoinIndex.into())
+        };
+        let coin = native_to_weth(token.address);
+        let i = self.index_of(coin)?;
+
+        // token_amount, coin_amount and the token_supply for some pools
+        let coin_amount = LogWords::new(&log).get(1)?;
+        self.coins.balances[i] = self.coins.balances[i].saturating_sub(coin_amount);
+
+        Ok(())
+    }
+
+    /// The base pool of a metapool is a pool of its own, so the logs of its
+    /// updates are forwarded to the copy of it that the metapool prices its
+    /// underlying coins with.
+    pub fn sync_base_pool(&mut self, log: Log, action: &Action) -> Result<(), AmmError> {
+        let Some(base_pool) = self.base_pool.as_mut() else { return Ok(()) };
+        base_pool.sync_from_log_with_action(log, action)
+    }
+
+    pub fn base_pool_address(&self) -> Option<Address> {
+        self.base_pool.as_ref().map(|base_pool| base_pool.address)
+    }
+
+    fn admin_fee_of(&self, fee: U256) -> Result<U256, ArithmeticError> {
+        Ok(fee
+            .checked_mul(self.admin_fee)
+            .ok_or(ArithmeticError::CurveOverflow)?
+            / FEE_DENOMINATOR)
+    }
+
+    fn apply_exchange(
+        &mut self,
+        i: usize,
+        dx: U256,
+        j: usize,
+        dy: U256,
+    ) -> Result<(), ArithmeticError> {
+        // the logged amount out is after fees, the admin share of the fee is
+        // also removed from the pool balance
+        let dy_fee = dy
+            .checked_mul(self.fee)
+            .ok_or(ArithmeticError::CurveOverflow)?
+            .checked_div(FEE_DENOMINATOR.saturating_sub(self.fee))
+            .ok_or(ArithmeticError::CurveDivZero)?;
+        let dy_admin_fee = self.admin_fee_of(dy_fee)?;
+
+        self.coins.balances[i] = self.coins.balances[i]
+            .checked_add(dx)
+            .ok_or(ArithmeticError::CurveOverflow)?;
+        self.coins.balances[j] = self.coins.balances[j]
+            .saturating_sub(dy)
+            .saturating_sub(dy_admin_fee);
+
+        Ok(())
+    }
+
+    /// Index 0 is the metapool coin and the base pool coins follow it. Trading
+    /// into or out of a base pool coin changes the lp balance of the metapool
+    /// by the amount worth of lp tokens.
+    fn apply_underlying_exchange(
+        &mut self,
+        i: usize,
+        dx: U256,
+        j: usize,
+        dy: U256,
+    ) -> Result<(), ArithmeticError> {
+        let lp = self.coins.balances.len() - 1;
+        let virtual_price = self.rates[lp];
+        let Some(base_rates) = self.base_pool.as_ref().map(|pool| pool.rates.clone()) else {
+            return Ok(())
+        };
+        if virtual_price.is_zero() {
+            return Err(ArithmeticError::CurveDivZero)
+        }
+
+        let to_lp = |k: usize, amount: U256| {
+            let rate = base_rates.get(k - 1).copied().unwrap_or_default();
+            Ok::<_, ArithmeticError>(
+                amount
+                    .checked_mul(rate)
+                    .ok_or(ArithmeticError::CurveOverflow)?
+                    / virtual_price,
+            )
+        };
+
+        match (i, j) {
+            (0, j) if j != 0 => {
+                let lp_out = to_lp(j, dy)?;
+                self.apply_exchange(0, dx, lp, lp_out)
+            }
+            (i, 0) if i != 0 => {
+                let lp_in = to_lp(i, dx)?;
+                self.apply_exchange(lp, lp_in, 0, dy)
+            }
+            // a trade between two base pool coins only touches the base pool
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `RemoveLiquidityOne` of the pools that don't log the coin index
+fn is_legacy_remove_liquidity_one(topic: B256) -> bool {
+    topic == event_signature("RemoveLiquidityOne", "address,uint256,uint256")
+        || topic == event_signature("RemoveLiquidityOne", "address,uint256,uint256,uint256")
+}
+
+fn is_meta_pool(protocol: Protocol) -> bool {
+    matches!(
+        protocol,
+        Protocol::CurveV1MetaPool | Protocol::CurveV2MetaPool | Protocol::CurvecrvUSDMetaPool
+    )
+}
+
+/// Solves the StableSwap invariant for `D` the same way the pool contract does
+pub fn get_d(xp: &[U256], amp: U256, a_precision: U256) -> Result<U256, ArithmeticError> {
+    let n = U256::from(xp.len());
+    let s = xp.iter().fold(U256::ZERO, |acc, x| acc + x);
+    if s.is_zero() {
+        return Ok(U256::ZERO)
+    }
+
+    let ann = amp * n;
+    let mut d = s;
+
+    for _ in 0..255 {
+        let mut d_p = d;
+        for x in xp {
+            if x.is_zero() {
+                return Err(ArithmeticError::CurveDivZero)
+            }
+            d_p = d_p * d / (*x * n);
+        }
+
+        let d_prev = d;
+        d = (ann * s / a_precision + d_p * n) * d
+            / ((ann - a_precision) * d / a_precision + (n + U256::from(1)) * d_p);
+
+        if d.abs_diff(d_prev) <= U256::from(1) {
+            return Ok(d)
+        }
+    }
+
+    Err(ArithmeticError::CurveNoConvergence)
+}
+
+/// `A * n^n` and `D^(n+1) / (n^n * prod(x))` of the invariant
+/// `A n^n sum(x) + D = A n^n D + D^(n+1) / (n^n prod(x))`
+fn invariant_terms(
+    xp: &[Rational],
+    d: &Rational,
+    amp: U256,
+    a_precision: U256,
+) -> (Rational, Rational) {
+    let n = xp.len() as u64;
+    let ann = (amp * U256::from(n)).to_scaled_rational(0) / a_precision.to_scaled_rational(0);
+    let prod = xp.iter().fold(Rational::ONE, |acc, x| acc * x);
+    let k = d.clone().pow(n + 1) / (Rational::from(n).pow(n) * prod);
+
+    (ann, k)
+}
+
+/// The amount of coin `j` received for an infinitesimal amount of coin `i`, in
+/// normalized units. This is the ratio of the partial derivatives of the
+/// invariant, `(Ann + K / x_i) / (Ann + K / x_j)`.
+pub(crate) fn marginal_price(
+    xp: &[U256],
+    d: U256,
+    amp: U256,
+    a_precision: U256,
+    i: usize,
+    j: usize,
+) -> Result<Rational, ArithmeticError> {
+    if xp[i].is_zero() || xp[j].is_zero() {
+        return Err(ArithmeticError::CurveDivZero)
+    }
+
+    let xp = xp
+        .iter()
+        .map(|x| x.to_scaled_rational(0))
+        .collect::<Vec<_>>();
+    let (ann, k) = invariant_terms(&xp, &d.to_scaled_rational(0), amp, a_precision);
+
+    Ok(&xp[j] * (&ann * &xp[i] + &k) / (&xp[i] * (ann * &xp[j] + k)))
+}
+
+/// The change in `D` for an infinitesimal amount of coin `i`, in normalized
+/// units.
+fn d_derivative(
+    xp: &[U256],
+    d: U256,
+    amp: U256,
+    a_precision: U256,
+    i: usize,
+) -> Result<Rational, ArithmeticError> {
+    if xp[i].is_zero() {
+        return Err(ArithmeticError::CurveDivZero)
+    }
+
+    let n = xp.len() as u64;
+    let d = d.to_scaled_rational(0);
+    let xp = xp
+        .iter()
+        .map(|x| x.to_scaled_rational(0))
+        .collect::<Vec<_>>();
+    let (ann, k) = invariant_terms(&xp, &d, amp, a_precision);
+
+    Ok((&ann + &k / &xp[i]) / (ann - Rational::ONE + Rational::from(n + 1) * k / d))
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{db::token_info::TokenInfoWithAddress, normalized_actions::NormalizedBurn};
+
+    use super::*;
+
+    fn xp(amounts: &[u64]) -> Vec<U256> {
+        amounts
+            .iter()
+            .map(|amount| U256::from(*amount) * PRECISION)
+            .collect()
+    }
+
+    fn pool(address: Address, balances: &[u64]) -> CurveStablePool {
+        CurveStablePool {
+            address,
+            coins: CurveCoins {
+                coins:    (1..=balances.len() as u8)
+                    .map(Address::with_last_byte)
+                    .collect(),
+                decimals: vec![18; balances.len()],
+                balances: xp(balances),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn log(name: &str, params: &str, words: &[u64]) -> Log {
+        let data = words
+            .iter()
+            .flat_map(|word| U256::from(*word).to_be_bytes::<32>())
+            .collect::<Vec<_>>();
+
+        Log::new_unchecked(Address::ZERO, vec![event_signature(name, params)], data.into())
+    }
+
+    #[test]
+    fn test_sync_from_malformed_log() {
+        let mut pool = pool(Address::ZERO, &[100, 100, 100]);
+        let balances = pool.coins.balances.clone();
+        let exchange = "address,int128,uint256,int128,uint256";
+
+        // missing words
+        assert!(pool
+            .sync_from_log(log("TokenExchange", exchange, &[0, 1]))
+            .is_err());
+        // coin index out of range
+        assert!(pool
+            .sync_from_log(log("TokenExchange", exchange, &[0, 1, 5, 1]))
+            .is_err());
+        // anonymous log
+        let anonymous = log("TokenExchange", exchange, &[0, 1, 2, 1]);
+        let anonymous = Log::new_unchecked(Address::ZERO, vec![], anonymous.data.data);
+        assert!(pool.sync_from_log(anonymous).is_err());
+
+        assert_eq!(pool.coins.balances, balances);
+    }
+
+    #[test]
+    fn test_sync_legacy_remove_liquidity_one() {
+        let mut pool = pool(Address::ZERO, &[100, 100, 100]);
+        let remove_one = log("RemoveLiquidityOne", "address,uint256,uint256", &[10, 40]);
+
+        // the coin can't be recovered from the log alone
+        assert!(pool.sync_from_log(remove_one.clone()).is_err());
+
+        let burn = Action::Burn(NormalizedBurn {
+            token: vec![TokenInfoWithAddress {
+                address: Address::with_last_byte(2),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        pool.sync_from_log_with_action(remove_one, &burn).unwrap();
+
+        assert_eq!(
+            pool.coins.balances,
+            vec![
+                U256::from(100) * PRECISION,
+                U256::from(100) * PRECISION - U256::from(40),
+                U256::from(100) * PRECISION
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sync_meta_pool_base_pool() {
+        let base_address = Address::with_last_byte(0xba);
+        let mut meta_pool = pool(Address::ZERO, &[100, 100]);
+        meta_pool.base_pool = Some(Box::new(pool(base_address, &[100, 100, 100])));
+        assert_eq!(meta_pool.base_pool_address(), Some(base_address));
+
+        let remove =
+            log("RemoveLiquidity", "address,uint256[3],uint256[3],uint256", &[1, 2, 3, 0, 0, 0, 0]);
+        meta_pool
+            .sync_base_pool(remove, &Action::Burn(NormalizedBurn::default()))
+            .unwrap();
+
+        let base_balances = &meta_pool.base_pool.as_ref().unwrap().coins.balances;
+        assert_eq!(base_balances[0], U256::from(100) * PRECISION - U256::from(1));
+        assert_eq!(base_balances[2], U256::from(100) * PRECISION - U256::from(3));
+        assert_eq!(meta_pool.coins.balances, xp(&[100, 100]));
+    }
+
+    #[test]
+    fn test_get_d() {
+        let amp = U256::from(2000 * 100);
+        let a_precision = U256::from(100);
+
+        let balanced = xp(&[1_000_000, 1_000_000, 1_000_000]);
+        assert_eq!(get_d(&balanced, amp, a_precision).unwrap(), U256::from(3_000_000) * PRECISION);
+
+        // with a high amplification the invariant is close to the sum of balances
+        let skewed = xp(&[1_500_000, 1_000_000, 500_000]);
+        let d = get_d(&skewed, amp, a_precision).unwrap();
+        assert!(d < U256::from(3_000_000) * PRECISION);
+        assert!(d > U256::from(2_990_000) * PRECISION);
+    }
+
+    #[test]
+    fn test_marginal_price() {
+        let amp = U256::from(2000 * 100);
+        let a_precision = U256::from(100);
+
+        let balanced = xp(&[1_000_000, 1_000_000, 1_000_000]);
+        let d = get_d(&balanced, amp, a_precision).unwrap();
+        assert_eq!(marginal_price(&balanced, d, amp, a_precision, 0, 2).unwrap(), Rational::ONE);
+        assert_eq!(d_derivative(&balanced, d, amp, a_precision, 1).unwrap(), Rational::ONE);
+
+        // the pool holds more of coin 0, so coin 0 is worth less than coin 2
+        let skewed = xp(&[1_500_000, 1_000_000, 500_000]);
+        let d = get_d(&skewed, amp, a_precision).unwrap();
+        let price = marginal_price(&skewed, d, amp, a_precision, 0, 2).unwrap();
+        assert!(price < Rational::ONE);
+        assert!(price > Rational::from_signeds(99, 100));
+    }
+}
@@ -2,6 +2,7 @@ use std::time::SystemTimeError;
 
 use alloy_primitives::{Address, U256};
 use alloy_sol_types::Error as AlloyError;
+use brontes_types::Protocol;
 use thiserror::Error;
 use tokio::task::JoinError;
 
@@ -55,6 +56,10 @@ pub enum AmmError {
     AlloyError(#[from] AlloyError),
     #[error("")]
     UnsupportedProtocol,
+    #[error("pool {0:?} can't be priced")]
+    UnsupportedPool(Address),
+    #[error("{0} pools can't be synced from actions")]
+    SyncFromActionNotSupported(Protocol),
 }
 
 #[derive(Error, Debug)]
@@ -73,6 +78,18 @@ pub enum ArithmeticError {
     UniswapV3MathError(#[from] UniswapV3MathError),
     #[error("v2 div by zero")]
     UniV2DivZero,
+    #[error("curve div by zero")]
+    CurveDivZero,
+    #[error("curve invariant did not converge")]
+    CurveNoConvergence,
+    #[error("token {0:?} is not in the curve pool")]
+    CurveTokenNotInPool(Address),
+    #[error("curve balance overflow")]
+    CurveOverflow,
+    #[error("balancer div by zero")]
+    BalancerDivZero,
+    #[error("token {0:?} is not in the balancer pool")]
+    BalancerTokenNotInPool(Address),
 }
 
 #[derive(Error, Debug)]
@@ -81,6 +98,10 @@ pub enum EventLogError {
     InvalidEventSignature,
     #[error("Log Block number not found")]
     LogBlockNumberNotFound,
+    #[error("Log data doesn't match the event")]
+    InvalidLogData,
+    #[error("Log doesn't include the coin index")]
+    MissingCoinIndex,
 }
 
 #[derive(Error, Debug)]
@@ -12,7 +12,7 @@ use tokio::task::JoinError;
 
 use crate::{
     errors::AmmError,
-    protocols::LoadState,
+    protocols::{uniswap_v4::V4PoolKeyLookup, LoadState},
     types::{PairWithFirstPoolHop, PoolState},
     Protocol,
 };
@@ -63,6 +63,7 @@ type BlockNumber = u64;
 /// state for a given block.
 pub struct LazyExchangeLoader<T: TracingProvider> {
     provider:          Arc<T>,
+    v4_pool_keys:      V4PoolKeyLookup,
     pool_load_futures: MultiBlockPoolFutures,
     /// addresses currently being processed. to the blocks of the address we are
     /// fetching state for
@@ -74,8 +75,9 @@ pub struct LazyExchangeLoader<T: TracingProvider> {
 }
 
 impl<T: TracingProvider> LazyExchangeLoader<T> {
-    pub fn new(provider: Arc<T>, ex: BrontesTaskExecutor) -> Self {
+    pub fn new(provider: Arc<T>, v4_pool_keys: V4PoolKeyLookup, ex: BrontesTaskExecutor) -> Self {
         Self {
+            v4_pool_keys,
             state_tracking: LoadingStateTracker::default(),
             pool_buf: FastHashMap::default(),
             pool_load_futures: MultiBlockPoolFutures::new(),
@@ -165,7 +167,14 @@ impl<T: TracingProvider> LazyExchangeLoader<T> {
         let provider = self.provider.clone();
         self.add_state_trackers(block_number, id, address, pair);
 
-        let fut = ex_type.try_load_state(address, provider, block_number, pool_pair, pair);
+        let fut = ex_type.try_load_state(
+            address,
+            provider,
+            block_number,
+            pool_pair,
+            pair,
+            self.v4_pool_keys.clone(),
+        );
         self.pool_load_futures.add_future(
             block_number,
             Box::pin(self.ex.handle().spawn(async move {
@@ -1,26 +1,45 @@
+pub mod balancer_v2;
+pub mod curve;
 pub mod errors;
 pub mod lazy;
 pub mod uniswap_v2;
 pub mod uniswap_v3;
+pub mod uniswap_v4;
 
 use std::{future::Future, sync::Arc};
 
-use alloy_primitives::{Address, Log};
+use alloy_primitives::{Address, Log, U256};
+use alloy_sol_macro::sol;
 use async_trait::async_trait;
-use brontes_types::{normalized_actions::Action, pair::Pair, traits::TracingProvider};
+use brontes_types::{
+    constants::WETH_ADDRESS, normalized_actions::Action, pair::Pair, traits::TracingProvider,
+};
 pub use brontes_types::{queries::make_call_request, Protocol};
-use malachite::Rational;
+use malachite::{
+    num::{arithmetic::traits::Pow, conversion::traits::RoundingFrom},
+    rounding_modes::RoundingMode,
+    Natural, Rational,
+};
 use tracing::{debug, warn};
 
 use crate::{
+    balancer_v2::BalancerV2Pool,
+    curve::{crypto_swap::CurveCryptoPool, stable_swap::CurveStablePool},
     lazy::{PoolFetchError, PoolFetchSuccess},
     protocols::errors::{AmmError, ArithmeticError},
     types::PairWithFirstPoolHop,
     uniswap_v2::UniswapV2Pool,
     uniswap_v3::UniswapV3Pool,
+    uniswap_v4::{UniswapV4Pool, V4PoolKeyLookup},
     LoadResult, PoolState,
 };
 
+sol!(
+    interface IERC20Decimals {
+        function decimals() external view returns (uint8);
+    }
+);
+
 #[async_trait]
 pub trait UpdatableProtocol {
     fn address(&self) -> Address;
@@ -39,6 +58,7 @@ pub trait LoadState {
         block_number: u64,
         pool_pair: Pair,
         full_pair: PairWithFirstPoolHop,
+        v4_pool_keys: V4PoolKeyLookup,
     ) -> impl Future<Output = Result<PoolFetchSuccess, PoolFetchError>> + Send;
 }
 
@@ -52,6 +72,18 @@ impl LoadState for Protocol {
                 | Self::SushiSwapV3
                 | Self::PancakeSwapV2
                 | Self::PancakeSwapV3
+                | Self::UniswapV4
+                | Self::CurveBasePool2
+                | Self::CurveBasePool3
+                | Self::CurveBasePool4
+                | Self::CurveV1MetaPool
+                | Self::CurveV2MetaPool
+                | Self::CurveV2PlainPool
+                | Self::CurvecrvUSDMetaPool
+                | Self::CurvecrvUSDPlainPool
+                | Self::CurveCryptoSwapPool
+                | Self::CurveTriCryptoPool
+                | Self::BalancerV2
         )
     }
 
@@ -62,6 +94,7 @@ impl LoadState for Protocol {
         block_number: u64,
         pool_pair: Pair,
         fp: PairWithFirstPoolHop,
+        v4_pool_keys: V4PoolKeyLookup,
     ) -> Result<PoolFetchSuccess, PoolFetchError> {
         match self {
             Self::UniswapV2 | Self::SushiSwapV2 | Self::PancakeSwapV2 => {
@@ -120,6 +153,142 @@ impl LoadState for Protocol {
                     res,
                 ))
             }
+            Self::UniswapV4 => {
+                let (pool, res) = if let Ok(pool) = UniswapV4Pool::new_from_address(
+                    address,
+                    block_number - 1,
+                    provider.clone(),
+                    v4_pool_keys.clone(),
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        UniswapV4Pool::new_from_address(
+                            address,
+                            block_number,
+                            provider,
+                            v4_pool_keys,
+                        )
+                        .await
+                        .map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, Protocol::UniswapV4, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::UniswapV4(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::CurveBasePool2
+            | Self::CurveBasePool3
+            | Self::CurveBasePool4
+            | Self::CurveV1MetaPool
+            | Self::CurveV2MetaPool
+            | Self::CurveV2PlainPool
+            | Self::CurvecrvUSDMetaPool
+            | Self::CurvecrvUSDPlainPool => {
+                let (pool, res) = if let Ok(pool) = CurveStablePool::new_from_address(
+                    address,
+                    self,
+                    block_number - 1,
+                    provider.clone(),
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveStablePool::new_from_address(address, self, block_number, provider)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveStable(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::CurveCryptoSwapPool | Self::CurveTriCryptoPool => {
+                let (pool, res) = if let Ok(pool) = CurveCryptoPool::new_from_address(
+                    address,
+                    self,
+                    block_number - 1,
+                    provider.clone(),
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveCryptoPool::new_from_address(address, self, block_number, provider)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveCrypto(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::BalancerV2 => {
+                let (pool, res) = if let Ok(pool) =
+                    BalancerV2Pool::new_from_address(address, block_number - 1, provider.clone())
+                        .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        BalancerV2Pool::new_from_address(address, block_number, provider)
+                            .await
+                            .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, self, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::BalancerV2(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
             rest => {
                 warn!(protocol=?rest, "no state updater is build for");
                 Err((address, self, block_number, pool_pair, fp, AmmError::UnsupportedProtocol))
@@ -127,3 +296,45 @@ impl LoadState for Protocol {
         }
     }
 }
+
+pub(crate) async fn get_decimals<M: TracingProvider>(
+    token: Address,
+    block_number: u64,
+    middleware: Arc<M>,
+) -> Result<u8, AmmError> {
+    if token == WETH_ADDRESS {
+        return Ok(18)
+    }
+
+    let res = make_call_request(
+        IERC20Decimals::decimalsCall::new(()),
+        &middleware,
+        token,
+        Some(block_number),
+    )
+    .await?;
+
+    Ok(res._0)
+}
+
+/// Converts a normalized action amount back to the raw token amount
+pub(crate) fn to_raw_amount(amount: &Rational, decimals: u8) -> U256 {
+    let raw = amount * Rational::from(10u8).pow(decimals as u64);
+    let (raw, _) = Natural::rounding_from(raw, RoundingMode::Floor);
+
+    U256::checked_from_limbs_slice(&raw.to_limbs_asc()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::ToScaledRational;
+
+    use super::*;
+
+    #[test]
+    fn test_to_raw_amount() {
+        let amount = U256::from(123_456_789u64);
+        assert_eq!(to_raw_amount(&amount.to_scaled_rational(6), 6), amount);
+        assert_eq!(to_raw_amount(&amount.to_scaled_rational(18), 18), amount);
+    }
+}
@@ -3,12 +3,16 @@ pub mod factory;
 
 use std::sync::Arc;
 
-use alloy_primitives::{Address, FixedBytes, Log, B256};
+use alloy_primitives::{Address, FixedBytes, Log, B256, U256};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
 use alloy_sol_macro::sol;
 use alloy_sol_types::SolEvent;
 use async_trait::async_trait;
-use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToScaledRational};
+use brontes_types::{
+    normalized_actions::{Action, NormalizedSwapWithFee},
+    traits::TracingProvider,
+    ToScaledRational,
+};
 use malachite::{
     num::{arithmetic::traits::Pow, basic::traits::Zero},
     Natural, Rational,
@@ -16,6 +20,7 @@ use malachite::{
 use serde::{Deserialize, Serialize};
 
 use self::batch_request::get_v2_pool_data;
+use super::to_raw_amount;
 use crate::{
     errors::{AmmError, ArithmeticError, EventLogError},
     UpdatableProtocol,
@@ -68,8 +73,29 @@ impl UpdatableProtocol for UniswapV2Pool {
         self.address
     }
 
-    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
-        todo!("syncing from actions is currently not supported for v2")
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        match action {
+            Action::Swap(swap) | Action::SwapWithFee(NormalizedSwapWithFee { swap, .. }) => {
+                let amount_in = to_raw_amount(&swap.amount_in, swap.token_in.decimals);
+                let amount_out = to_raw_amount(&swap.amount_out, swap.token_out.decimals);
+
+                self.apply_delta(swap.token_in.address, amount_in, true);
+                self.apply_delta(swap.token_out.address, amount_out, false);
+            }
+            Action::Mint(mint) => {
+                for (token, amount) in mint.token.iter().zip(&mint.amount) {
+                    self.apply_delta(token.address, to_raw_amount(amount, token.decimals), true);
+                }
+            }
+            Action::Burn(burn) => {
+                for (token, amount) in burn.token.iter().zip(&burn.amount) {
+                    self.apply_delta(token.address, to_raw_amount(amount, token.decimals), false);
+                }
+            }
+            _ => return Err(AmmError::SyncError(self.address)),
+        }
+
+        Ok(())
     }
 
     fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
@@ -122,6 +148,23 @@ impl UniswapV2Pool {
         }
     }
 
+    /// Moves `amount` of `token` in to or out of the reserves. The whole
+    /// amount in stays in the pool, so the reserves match the `Sync` log
+    /// unless a token takes a fee on transfer.
+    fn apply_delta(&mut self, token: Address, amount: U256, into_pool: bool) {
+        let reserve = if token == self.token_a {
+            &mut self.reserve_0
+        } else if token == self.token_b {
+            &mut self.reserve_1
+        } else {
+            return
+        };
+        let amount = amount.saturating_to::<u128>();
+
+        *reserve =
+            if into_pool { reserve.saturating_add(amount) } else { reserve.saturating_sub(amount) };
+    }
+
     pub async fn populate_data<M: TracingProvider>(
         &mut self,
         block: Option<u64>,
@@ -233,3 +276,36 @@ impl UniswapV2Pool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{db::token_info::TokenInfoWithAddress, normalized_actions::NormalizedSwap};
+
+    use super::*;
+
+    #[test]
+    fn test_sync_from_swap_action() {
+        let (usdc, weth) = (TokenInfoWithAddress::usdc(), TokenInfoWithAddress::weth());
+        let mut pool = UniswapV2Pool {
+            token_a: usdc.address,
+            token_a_decimals: 6,
+            token_b: weth.address,
+            token_b_decimals: 18,
+            reserve_0: 3_000_000_000_000,
+            reserve_1: 1_000_000_000_000_000_000_000,
+            ..Default::default()
+        };
+
+        let swap = NormalizedSwap {
+            token_in: usdc,
+            amount_in: U256::from(3_000_000_000u64).to_scaled_rational(6),
+            token_out: weth,
+            amount_out: U256::from(990_000_000_000_000_000u64).to_scaled_rational(18),
+            ..Default::default()
+        };
+        pool.sync_from_action(Action::Swap(swap)).unwrap();
+
+        assert_eq!(pool.reserve_0, 3_003_000_000_000);
+        assert_eq!(pool.reserve_1, 999_010_000_000_000_000_000);
+    }
+}
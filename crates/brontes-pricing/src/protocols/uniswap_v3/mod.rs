@@ -7,13 +7,15 @@ use alloy_sol_macro::sol;
 use alloy_sol_types::{SolCall, SolEvent};
 use async_trait::async_trait;
 use brontes_types::{
-    normalized_actions::Action, traits::TracingProvider, FastHashMap, ToScaledRational,
+    normalized_actions::{Action, NormalizedSwapWithFee},
+    traits::TracingProvider,
+    FastHashMap, ToScaledRational,
 };
 use malachite::Rational;
 use serde::{Deserialize, Serialize};
 
 use self::batch_request::get_v3_pool_data_batch_request;
-use super::make_call_request;
+use super::{make_call_request, to_raw_amount};
 #[cfg(feature = "uni-v3-ticks")]
 use crate::uniswap_v3::batch_request::get_uniswap_v3_tick_data_batch_request;
 #[cfg(feature = "uni-v3-ticks")]
@@ -162,8 +164,27 @@ impl UpdatableProtocol for UniswapV3Pool {
         self.address
     }
 
-    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
-        todo!("syncing from actions is currently not supported for v3")
+    /// Actions don't carry the tick range of a position or the price after a
+    /// swap. Swaps move the price with the in range liquidity, so a swap that
+    /// crosses an initialized tick will drift from the log state. Mints and
+    /// burns change the liquidity of the position's ticks, so they can't be
+    /// synced from the action and are synced from their logs instead.
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        match action {
+            Action::Swap(swap) | Action::SwapWithFee(NormalizedSwapWithFee { swap, .. }) => {
+                let amount_in = to_raw_amount(&swap.amount_in, swap.token_in.decimals);
+                let amount_out = to_raw_amount(&swap.amount_out, swap.token_out.decimals);
+                let zero_for_one = swap.token_in.address == self.token_a;
+
+                self.sync_from_swap_amounts(zero_for_one, amount_in, amount_out)?;
+            }
+            // the principal of a position is taken out of the reserves on the burn and
+            // fees aren't tracked as reserves, same as for the logs
+            Action::Collect(_) => {}
+            _ => return Err(AmmError::SyncError(self.address)),
+        }
+
+        Ok(())
     }
 
     fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
@@ -478,6 +499,48 @@ impl UniswapV3Pool {
         Ok(())
     }
 
+    /// Moves the price by the amount out of the swap with the current
+    /// liquidity. The amount out is used as it doesn't include the swap fee.
+    pub fn sync_from_swap_amounts(
+        &mut self,
+        zero_for_one: bool,
+        amount_in: U256,
+        amount_out: U256,
+    ) -> Result<(), AmmError> {
+        if self.liquidity == 0 || self.sqrt_price.is_zero() {
+            return Err(AmmError::SyncError(self.address))
+        }
+        let liquidity = U256::from(self.liquidity) << 96;
+
+        let sqrt_price = if zero_for_one {
+            // amount1 = L * (sqrt_p - sqrt_p_next)
+            self.sqrt_price
+                .checked_sub((amount_out << 96) / U256::from(self.liquidity))
+        } else {
+            // amount0 = L / sqrt_p_next - L / sqrt_p
+            (liquidity / self.sqrt_price)
+                .checked_sub(amount_out)
+                .filter(|denominator| !denominator.is_zero())
+                .map(|denominator| liquidity / denominator)
+        }
+        .filter(|price| *price >= MIN_SQRT_RATIO && *price < MAX_SQRT_RATIO)
+        .ok_or(AmmError::SyncError(self.address))?;
+        let tick = uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(sqrt_price)?;
+
+        if zero_for_one {
+            self.reserve_0 += amount_in;
+            self.reserve_1 = self.reserve_1.saturating_sub(amount_out);
+        } else {
+            self.reserve_0 = self.reserve_0.saturating_sub(amount_out);
+            self.reserve_1 += amount_in;
+        }
+
+        self.sqrt_price = sqrt_price;
+        self.tick = tick;
+
+        Ok(())
+    }
+
     pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
         if self.token_a == base {
             (
@@ -514,3 +577,36 @@ pub struct Tick {
     pub seconds_outside: u32,
     pub initialized: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_from_swap_amounts() {
+        let q96 = U256::from(1) << 96;
+        let mut pool = UniswapV3Pool {
+            liquidity: 1_000_000_000_000_000_000,
+            sqrt_price: q96,
+            reserve_0: U256::from(1_000_000_000_000_000_000u64),
+            reserve_1: U256::from(1_000_000_000_000_000_000u64),
+            ..Default::default()
+        };
+
+        // token1 out moves the price down by the amount over the liquidity
+        let (amount_in, amount_out) =
+            (U256::from(1_003_000_000_000_000u64), U256::from(1_000_000_000_000_000u64));
+        pool.sync_from_swap_amounts(true, amount_in, amount_out)
+            .unwrap();
+
+        assert_eq!(pool.sqrt_price, q96 - q96 / U256::from(1000));
+        assert!(pool.tick < 0);
+        assert_eq!(pool.reserve_0, U256::from(1_001_003_000_000_000_000u64));
+        assert_eq!(pool.reserve_1, U256::from(999_000_000_000_000_000u64));
+
+        // the opposite swap brings the price back up
+        pool.sync_from_swap_amounts(false, amount_out, amount_in)
+            .unwrap();
+        assert!(pool.sqrt_price > q96 - q96 / U256::from(1000));
+    }
+}
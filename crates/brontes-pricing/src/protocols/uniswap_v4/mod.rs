@@ -0,0 +1,247 @@
+use std::sync::Arc;
+
+use alloy_primitives::{keccak256, Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+use brontes_types::{
+    db::{
+        traits::LibmdbxReader,
+        uniswap_v4::{UniswapV4PoolKey, POOL_MANAGER_ADDRESS},
+    },
+    normalized_actions::Action,
+    traits::TracingProvider,
+};
+use malachite::Rational;
+use tracing::debug;
+
+use super::get_decimals;
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    uniswap_v3::UniswapV3Pool,
+    Protocol, UpdatableProtocol,
+};
+
+sol!(
+    interface IUniswapV4PoolManager {
+        event Initialize(
+            bytes32 indexed id,
+            address indexed currency0,
+            address indexed currency1,
+            uint24 fee,
+            int24 tickSpacing,
+            address hooks,
+            uint160 sqrtPriceX96,
+            int24 tick
+        );
+        event ModifyLiquidity(
+            bytes32 indexed id,
+            address indexed sender,
+            int24 tickLower,
+            int24 tickUpper,
+            int256 liquidityDelta,
+            bytes32 salt
+        );
+        event Swap(
+            bytes32 indexed id,
+            address indexed sender,
+            int128 amount0,
+            int128 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick,
+            uint24 fee
+        );
+        event Donate(
+            bytes32 indexed id,
+            address indexed sender,
+            uint256 amount0,
+            uint256 amount1
+        );
+    }
+);
+
+/// Storage slot of the `_pools` mapping in the PoolManager
+const POOLS_SLOT: U256 = U256::from_limbs([6, 0, 0, 0]);
+/// Offset of `liquidity` in `Pool.State`, after slot0 and both fee growths
+const LIQUIDITY_OFFSET: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+/// Looks up the key of a V4 pool by its address. The keys are stored in
+/// libmdbx by the classifier, which the pricing engine can't read directly.
+pub type V4PoolKeyLookup = Arc<dyn Fn(Address) -> Option<UniswapV4PoolKey> + Send + Sync>;
+
+pub fn db_pool_key_lookup<DB: LibmdbxReader>(db: &'static DB) -> V4PoolKeyLookup {
+    Arc::new(move |pool| {
+        db.try_fetch_v4_pool_key(pool)
+            .map_err(|e| debug!(?pool, err=%e, "failed to read v4 pool key"))
+            .ok()
+            .flatten()
+    })
+}
+
+/// A Uniswap V4 pool. The concentrated liquidity math is the same as V3, so
+/// the price and tick state is kept in a [`UniswapV3Pool`] with the synthetic
+/// pool address and the PoolManager events are applied to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UniswapV4Pool {
+    pub pool_id: B256,
+    pub inner:   UniswapV3Pool,
+}
+
+#[async_trait]
+impl UpdatableProtocol for UniswapV4Pool {
+    fn address(&self) -> Address {
+        self.inner.address
+    }
+
+    /// The V4 actions don't carry the price & liquidity the pool ended up at,
+    /// so the state is always synced from the PoolManager events
+    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
+        Err(AmmError::SyncFromActionNotSupported(Protocol::UniswapV4))
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let Some(&event_signature) = log.topics().first() else {
+            return Err(EventLogError::InvalidEventSignature.into())
+        };
+
+        // the PoolManager emits the events for all pools
+        if log.topics().get(1) != Some(&self.pool_id) {
+            return Ok(())
+        }
+
+        if event_signature == IUniswapV4PoolManager::Swap::SIGNATURE_HASH {
+            self.sync_from_swap_log(log)?;
+        } else if event_signature == IUniswapV4PoolManager::ModifyLiquidity::SIGNATURE_HASH {
+            self.sync_from_modify_liquidity_log(log)?;
+        } else if event_signature != IUniswapV4PoolManager::Donate::SIGNATURE_HASH {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        Ok(())
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.inner.token_a, self.inner.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        self.inner.calculate_price(base_token)
+    }
+}
+
+impl UniswapV4Pool {
+    pub async fn new_from_address<M: 'static + TracingProvider>(
+        pool_address: Address,
+        block_number: u64,
+        middleware: Arc<M>,
+        pool_keys: V4PoolKeyLookup,
+    ) -> Result<Self, AmmError> {
+        let key = pool_keys(pool_address).ok_or(AmmError::NoStateError(pool_address))?;
+        if key.has_hooks() {
+            return Err(AmmError::UnsupportedPool(pool_address))
+        }
+        let [token_a, token_b] = key.tokens();
+
+        let mut pool = UniswapV4Pool {
+            pool_id: key.pool_id,
+            inner:   UniswapV3Pool {
+                address: pool_address,
+                token_a,
+                token_b,
+                fee: key.fee,
+                tick_spacing: key.tick_spacing,
+                ..Default::default()
+            },
+        };
+
+        pool.inner.token_a_decimals =
+            get_decimals(token_a, block_number, middleware.clone()).await?;
+        pool.inner.token_b_decimals =
+            get_decimals(token_b, block_number, middleware.clone()).await?;
+        pool.populate_data(block_number, middleware).await?;
+
+        if pool.inner.sqrt_price.is_zero() {
+            return Err(AmmError::NoStateError(pool_address))
+        }
+
+        Ok(pool)
+    }
+
+    async fn populate_data<M: TracingProvider>(
+        &mut self,
+        block_number: u64,
+        middleware: Arc<M>,
+    ) -> Result<(), AmmError> {
+        let mut buf = [0u8; 64];
+        buf[0..32].copy_from_slice(self.pool_id.as_slice());
+        buf[32..64].copy_from_slice(&POOLS_SLOT.to_be_bytes::<32>());
+        let state_slot = U256::from_be_bytes(keccak256(buf).0);
+
+        let slot0 = middleware
+            .get_storage(Some(block_number), POOL_MANAGER_ADDRESS, state_slot.into())
+            .await?
+            .unwrap_or_default();
+        let liquidity = middleware
+            .get_storage(
+                Some(block_number),
+                POOL_MANAGER_ADDRESS,
+                (state_slot + LIQUIDITY_OFFSET).into(),
+            )
+            .await?
+            .unwrap_or_default();
+
+        // slot0 is packed as | lpFee | protocolFee | tick | sqrtPriceX96 |
+        let sqrt_price_mask = (U256::from(1) << 160) - U256::from(1);
+        self.inner.sqrt_price = slot0 & sqrt_price_mask;
+        let tick_mask = U256::from(0xffffff);
+        self.inner.tick = (((slot0 >> 160) & tick_mask).to::<u32>() << 8) as i32 >> 8;
+        self.inner.liquidity = liquidity.to::<u128>();
+        self.update_virtual_reserves();
+
+        Ok(())
+    }
+
+    pub fn sync_from_swap_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let swap_event = IUniswapV4PoolManager::Swap::decode_log_data(&log, false)?;
+
+        self.inner.sqrt_price = swap_event.sqrtPriceX96;
+        self.inner.liquidity = swap_event.liquidity;
+        self.inner.tick = swap_event.tick;
+        self.update_virtual_reserves();
+
+        Ok(())
+    }
+
+    pub fn sync_from_modify_liquidity_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let modify_event = IUniswapV4PoolManager::ModifyLiquidity::decode_log_data(&log, false)?;
+        let liquidity_delta = modify_event.liquidityDelta.as_i128();
+
+        #[cfg(feature = "uni-v3-ticks")]
+        self.inner
+            .update_position(modify_event.tickLower, modify_event.tickUpper, liquidity_delta);
+
+        if self.inner.tick >= modify_event.tickLower && self.inner.tick < modify_event.tickUpper {
+            self.inner.liquidity = self.inner.liquidity.saturating_add_signed(liquidity_delta);
+        }
+        self.update_virtual_reserves();
+
+        Ok(())
+    }
+
+    /// The PoolManager holds the balances of all pools, so the reserves are
+    /// derived from the active liquidity instead of token balances.
+    fn update_virtual_reserves(&mut self) {
+        if self.inner.sqrt_price.is_zero() {
+            return
+        }
+        let liquidity = U256::from(self.inner.liquidity);
+
+        self.inner.reserve_0 = (liquidity << 96) / self.inner.sqrt_price;
+        self.inner.reserve_1 = liquidity.saturating_mul(self.inner.sqrt_price >> 32) >> 64;
+    }
+
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        self.inner.get_tvl(base)
+    }
+}
@@ -9,8 +9,13 @@ use brontes_types::{
 use malachite::Rational;
 
 use crate::{
-    errors::ArithmeticError, uniswap_v2::UniswapV2Pool, uniswap_v3::UniswapV3Pool, LoadState,
-    Protocol, UpdatableProtocol,
+    balancer_v2::BalancerV2Pool,
+    curve::{crypto_swap::CurveCryptoPool, stable_swap::CurveStablePool},
+    errors::ArithmeticError,
+    uniswap_v2::UniswapV2Pool,
+    uniswap_v3::UniswapV3Pool,
+    uniswap_v4::UniswapV4Pool,
+    LoadState, Protocol, UpdatableProtocol,
 };
 
 wrap_fixed_bytes!(extra_derives:[],
@@ -51,18 +56,21 @@ impl PairWithFirstPoolHop {
     }
 }
 
+/// Price and liquidity of a pool for an edge of the pricing graph. Pools with
+/// more than two tokens have an edge per pair, so both sides of the edge are
+/// passed.
 pub trait ProtocolState: Debug {
-    fn price(&self, base: Address) -> Result<Rational, ArithmeticError>;
-    fn tvl(&self, base: Address) -> (Rational, Rational);
+    fn price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError>;
+    fn tvl(&self, base: Address, quote: Address) -> (Rational, Rational);
 }
 
 impl ProtocolState for PoolState {
-    fn tvl(&self, base: Address) -> (Rational, Rational) {
-        self.get_tvl(base)
+    fn tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        self.get_tvl(base, quote)
     }
 
-    fn price(&self, base: Address) -> Result<Rational, ArithmeticError> {
-        self.get_price(base)
+    fn price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError> {
+        self.get_price(base, quote)
     }
 }
 
@@ -76,8 +84,8 @@ impl Debug for PoolState {
         f.debug_struct("Pool State")
             .field("addr", &self.address())
             .field("pair", &self.pair())
-            .field("tvl 0", &self.get_tvl(self.pair().0).0)
-            .field("tvl 1", &self.get_tvl(self.pair().0).1)
+            .field("tvl 0", &self.get_tvl(self.pair().0, self.pair().1).0)
+            .field("tvl 1", &self.get_tvl(self.pair().0, self.pair().1).1)
             .field("block", &self.last_update)
             .finish()
     }
@@ -92,6 +100,10 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(v) => Pair(v.token_a, v.token_b),
             PoolVariants::UniswapV3(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::UniswapV4(v) => Pair(v.inner.token_a, v.inner.token_b),
+            PoolVariants::CurveStable(v) => Pair(v.coins.coins[0], v.coins.coins[1]),
+            PoolVariants::CurveCrypto(v) => Pair(v.coins.coins[0], v.coins.coins[1]),
+            PoolVariants::BalancerV2(v) => Pair(v.tokens[0], v.tokens[1]),
         }
     }
 
@@ -99,36 +111,67 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(_) => Protocol::UniswapV2,
             PoolVariants::UniswapV3(_) => Protocol::UniswapV3,
+            PoolVariants::UniswapV4(_) => Protocol::UniswapV4,
+            PoolVariants::CurveStable(v) => v.protocol,
+            PoolVariants::CurveCrypto(v) => v.protocol,
+            PoolVariants::BalancerV2(_) => Protocol::BalancerV2,
         }
     }
 
-    pub fn increment_state(&mut self, state: PoolUpdate) {
+    pub fn increment_state(&mut self, state: PoolUpdate, mode: PoolSyncMode) {
         if !state.is_supported_protocol() {
             tracing::error!(state_transition=?state, "tried to apply a invalid state transition");
             return
         }
         self.last_update = state.block;
-        self.variant.increment_state(state.logs);
+        self.variant.increment_state(state.action, state.logs, mode);
     }
 
-    pub fn address(&self) -> Address {
+    /// The base pool that a curve metapool prices its underlying coins with
+    pub fn base_pool(&self) -> Option<Address> {
         match &self.variant {
-            PoolVariants::UniswapV2(v) => v.address(),
-            PoolVariants::UniswapV3(v) => v.address(),
+            PoolVariants::CurveStable(v) => v.base_pool_address(),
+            _ => None,
+        }
+    }
+
+    /// Applies an update of the base pool to the copy of it held by a
+    /// metapool
+    pub fn sync_base_pool(&mut self, update: &PoolUpdate) {
+        let PoolVariants::CurveStable(pool) = &mut self.variant else { return };
+        for log in &update.logs {
+            let _ = pool.sync_base_pool(log.clone(), &update.action);
         }
     }
 
-    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+    /// Whether the pool can be synced from normalized actions
+    pub fn syncs_from_actions(&self) -> bool {
+        matches!(self.variant, PoolVariants::UniswapV2(_) | PoolVariants::UniswapV3(_))
+    }
+
+    pub fn address(&self) -> Address {
+        self.variant.address()
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.get_tvl(base),
             PoolVariants::UniswapV3(v) => v.get_tvl(base),
+            PoolVariants::UniswapV4(v) => v.get_tvl(base),
+            PoolVariants::CurveStable(v) => v.get_tvl(base, quote),
+            PoolVariants::CurveCrypto(v) => v.get_tvl(base, quote),
+            PoolVariants::BalancerV2(v) => v.get_tvl(base, quote),
         }
     }
 
-    pub fn get_price(&self, base: Address) -> Result<Rational, ArithmeticError> {
+    pub fn get_price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError> {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.calculate_price(base),
             PoolVariants::UniswapV3(v) => v.calculate_price(base),
+            PoolVariants::UniswapV4(v) => v.calculate_price(base),
+            PoolVariants::CurveStable(v) => v.calculate_price_pair(base, quote),
+            PoolVariants::CurveCrypto(v) => v.calculate_price_pair(base, quote),
+            PoolVariants::BalancerV2(v) => v.calculate_price_pair(base, quote),
         }
     }
 }
@@ -137,19 +180,78 @@ impl PoolState {
 pub enum PoolVariants {
     UniswapV2(Box<UniswapV2Pool>),
     UniswapV3(Box<UniswapV3Pool>),
+    UniswapV4(Box<UniswapV4Pool>),
+    CurveStable(Box<CurveStablePool>),
+    CurveCrypto(Box<CurveCryptoPool>),
+    BalancerV2(Box<BalancerV2Pool>),
 }
 
 impl PoolVariants {
-    fn increment_state(&mut self, logs: Vec<Log>) {
+    fn increment_state(&mut self, action: Action, logs: Vec<Log>, mode: PoolSyncMode) {
+        let synced_from_action = match self {
+            PoolVariants::UniswapV2(a) if mode == PoolSyncMode::Actions => {
+                Some(a.sync_from_action(action.clone()))
+            }
+            PoolVariants::UniswapV3(a) if mode == PoolSyncMode::Actions => {
+                Some(a.sync_from_action(action.clone()))
+            }
+            // the vault's swap log isn't part of the pool's `onSwap` frame
+            PoolVariants::BalancerV2(a) if action.is_swap() => {
+                Some(a.sync_from_action(action.clone()))
+            }
+            _ => None,
+        };
+
+        match synced_from_action {
+            Some(Ok(())) => {}
+            Some(Err(e)) => {
+                tracing::debug!(
+                    pool=?self.address(),
+                    err=%e,
+                    "failed to sync from action, syncing from logs"
+                );
+                self.increment_state_from_logs(logs, &action);
+            }
+            None => self.increment_state_from_logs(logs, &action),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            PoolVariants::UniswapV2(v) => v.address(),
+            PoolVariants::UniswapV3(v) => v.address(),
+            PoolVariants::UniswapV4(v) => v.address(),
+            PoolVariants::CurveStable(v) => v.address(),
+            PoolVariants::CurveCrypto(v) => v.address(),
+            PoolVariants::BalancerV2(v) => v.address(),
+        }
+    }
+
+    fn increment_state_from_logs(&mut self, logs: Vec<Log>, action: &Action) {
         for log in logs {
             let _ = match self {
                 PoolVariants::UniswapV3(a) => a.sync_from_log(log),
                 PoolVariants::UniswapV2(a) => a.sync_from_log(log),
+                PoolVariants::UniswapV4(a) => a.sync_from_log(log),
+                PoolVariants::CurveStable(a) => a.sync_from_log_with_action(log, action),
+                PoolVariants::CurveCrypto(a) => a.sync_from_log(log),
+                PoolVariants::BalancerV2(a) => a.sync_from_log(log),
             };
         }
     }
 }
 
+/// Where pool state transitions are read from. Only uniswap v2 and v3 pools
+/// can be synced from normalized actions, all other pools sync from their logs
+/// in either mode. Actions that a pool can't be synced from fall back to the
+/// logs of the update.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PoolSyncMode {
+    #[default]
+    Logs,
+    Actions,
+}
+
 #[derive(Debug, Clone)]
 pub enum DexPriceMsg {
     /// marker for only updating loaded state and not generating prices
@@ -202,6 +304,7 @@ impl PoolUpdate {
             || self.action.is_batch()
             || self.action.is_aggregator()
             || self.action.is_eth_transfer()
+            || self.action.is_oracle_update()
         {
             return None
         }
@@ -250,3 +353,41 @@ impl PoolUpdate {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::U256;
+    use alloy_sol_types::SolEvent;
+    use brontes_types::normalized_actions::NormalizedMint;
+
+    use super::*;
+    use crate::uniswap_v3::IUniswapV3Pool;
+
+    #[test]
+    fn test_unsupported_action_syncs_from_logs() {
+        let address = Address::with_last_byte(1);
+        let pool = UniswapV3Pool { address, ..Default::default() };
+        let mut state = PoolState::new(PoolVariants::UniswapV3(Box::new(pool)), 0);
+
+        let mint = IUniswapV3Pool::Mint {
+            sender:    address,
+            owner:     address,
+            tickLower: -10,
+            tickUpper: 10,
+            amount:    1000,
+            amount0:   U256::from(5),
+            amount1:   U256::from(7),
+        };
+        let update = PoolUpdate {
+            block:  1,
+            tx_idx: 0,
+            logs:   vec![Log { address, data: mint.encode_log_data() }],
+            action: Action::Mint(NormalizedMint { pool: address, ..Default::default() }),
+        };
+        state.increment_state(update, PoolSyncMode::Actions);
+
+        let PoolVariants::UniswapV3(pool) = &state.variant else { unreachable!() };
+        assert_eq!((pool.reserve_0, pool.reserve_1), (U256::from(5), U256::from(7)));
+        assert_eq!(state.last_update, 1);
+    }
+}
@@ -6,7 +6,10 @@ use reth_primitives::BlockHash;
 use strum::IntoEnumIterator;
 
 use super::RelayBlockMetadata;
-use crate::block_metadata::{RelayBid, RelayPayload};
+use crate::{
+    block_metadata::{RelayBid, RelayPayload},
+    db::builder_auction::BuilderAuction,
+};
 
 macro_rules! relays {
     ($([$relay:ident, $min_block:literal, $url:expr]),*) => {
@@ -146,6 +149,31 @@ impl Relays {
         Ok(None)
     }
 
+    /// Collects the bids every relay received for the block
+    pub async fn get_builder_auction(
+        block_number: u64,
+        block_hash: BlockHash,
+    ) -> Option<BuilderAuction> {
+        let bids = futures::future::join_all(Relays::iter().map(|relay| async move {
+            match relay
+                .get_received_bids(None, None, Some(block_number.to_string()), None, None)
+                .await
+            {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::warn!(%relay, "error getting bids - {:?}", e);
+                    vec![]
+                }
+            }
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        BuilderAuction::from_relay_bids(block_hash, bids)
+    }
+
     async fn get_winning_bid(
         self,
         block_number: u64,
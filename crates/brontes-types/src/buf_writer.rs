@@ -1,4 +1,5 @@
 use std::{
+    path::Path,
     pin::{pin, Pin},
     task::{Context, Poll},
 };
@@ -8,7 +9,61 @@ use bytes::{BufMut, Bytes};
 use futures::{stream::Stream, Future, FutureExt, StreamExt};
 use humansize::{format_size, BINARY};
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
-use tokio::{fs::File, io::AsyncWriteExt};
+use reqwest::{header::RANGE, StatusCode};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+};
+
+/// Downloads `url` to `path`. If an interrupted download of the file is
+/// already at `path`, only the remaining bytes are requested. Servers that
+/// don't support range requests send the whole file again, in which case the
+/// download starts over.
+pub async fn download_with_resume(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    total_download_size: Option<u64>,
+    buffer_cap: usize,
+    multi_bar: &MultiProgress,
+) -> eyre::Result<()> {
+    let mut downloaded = tokio::fs::metadata(path)
+        .await
+        .map(|meta| meta.len())
+        .unwrap_or_default();
+
+    match total_download_size {
+        Some(total) if downloaded == total => return Ok(()),
+        // not the file we are looking for
+        Some(total) if downloaded > total => downloaded = 0,
+        _ => {}
+    }
+
+    let mut request = client.get(url);
+    if downloaded != 0 {
+        request = request.header(RANGE, format!("bytes={downloaded}-"));
+    }
+    let response = request.send().await?;
+
+    let file = match response.status() {
+        StatusCode::PARTIAL_CONTENT => OpenOptions::new().append(true).open(path).await?,
+        status if status.is_success() => {
+            downloaded = 0;
+            File::create(path).await?
+        }
+        status => eyre::bail!("failed to download {url}: {status}"),
+    };
+
+    DownloadBufWriterWithProgress::resume(
+        downloaded,
+        total_download_size,
+        response.bytes_stream(),
+        file,
+        buffer_cap,
+        multi_bar,
+    )
+    .await
+}
 
 pub struct DownloadBufWriterWithProgress<S: Stream<Item = Result<Bytes, reqwest::Error>>> {
     progress_bar:    Option<ProgressBar>,
@@ -38,6 +93,23 @@ impl<S: Stream<Item = Result<Bytes, reqwest::Error>>> DownloadBufWriterWithProgr
         }
     }
 
+    /// Continues a download of which `downloaded` bytes are already in `file`
+    pub fn resume(
+        downloaded: u64,
+        total_download_size: Option<u64>,
+        download_stream: S,
+        file: File,
+        buffer_cap: usize,
+        multi_bar: &MultiProgress,
+    ) -> Self {
+        let this = Self::new(total_download_size, download_stream, file, buffer_cap, multi_bar);
+        this.progress_bar
+            .as_ref()
+            .inspect(|bar| bar.set_position(downloaded));
+
+        this
+    }
+
     fn is_over_buffer(&self) -> bool {
         self.buffer.len() > self.buffer_cap
     }
@@ -159,6 +231,9 @@ impl WriteProgress {
             let fut = Box::pin(async move {
                 let buf_moved = buf;
                 file_handle.write_all(&buf_moved).await.unwrap();
+                // make sure the bytes hit the file before the download is
+                // reported as finished
+                file_handle.flush().await.unwrap();
                 file_handle
             }) as Pin<Box<dyn Future<Output = File> + Send + 'static>>;
             #[allow(clippy::missing_transmute_annotations)]
@@ -178,3 +253,100 @@ impl WriteProgress {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+
+    use super::*;
+
+    /// Serves `body` as a static file that supports range requests. Returns
+    /// the url of the file & the range header of every request
+    async fn serve_file(body: Vec<u8>) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/partition.tar.gz", listener.local_addr().unwrap());
+        let requested_ranges = Arc::new(Mutex::new(Vec::new()));
+        let ranges = requested_ranges.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    if read == 0 {
+                        break
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+
+                let range = String::from_utf8(request)
+                    .unwrap()
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("range")
+                            .then(|| value.trim().to_string())
+                    });
+                let start = range
+                    .as_deref()
+                    .and_then(|range| range.strip_prefix("bytes="))
+                    .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok())
+                    .unwrap_or_default();
+                ranges.lock().unwrap().push(range);
+
+                let status = if start == 0 { "200 OK" } else { "206 Partial Content" };
+                let content = &body[start..];
+                let header = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    content.len()
+                );
+                stream.write_all(header.as_bytes()).await.unwrap();
+                stream.write_all(content).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        (url, requested_ranges)
+    }
+
+    #[test]
+    fn test_download_resumes() {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(async {
+                let body = (0..100_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+                let (url, requested_ranges) = serve_file(body.clone()).await;
+
+                // an interrupted download
+                let path = std::env::temp_dir().join("brontes-download-resume-test.tar.gz");
+                std::fs::write(&path, &body[..40_000]).unwrap();
+
+                let client = reqwest::Client::new();
+                let multi_bar = MultiProgress::with_draw_target(ProgressDrawTarget::hidden());
+                let size = Some(body.len() as u64);
+
+                download_with_resume(&client, &url, &path, size, 1024, &multi_bar)
+                    .await
+                    .unwrap();
+                assert_eq!(std::fs::read(&path).unwrap(), body);
+                assert_eq!(
+                    requested_ranges.lock().unwrap().as_slice(),
+                    &[Some("bytes=40000-".to_string())]
+                );
+
+                // a finished download isn't requested again
+                download_with_resume(&client, &url, &path, size, 1024, &multi_bar)
+                    .await
+                    .unwrap();
+                assert_eq!(requested_ranges.lock().unwrap().len(), 1);
+
+                std::fs::remove_file(&path).unwrap();
+            });
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     db::redefined_types::primitives::AddressRedefined,
     implement_table_value_codecs_with_zc,
+    pair::Pair,
     serde_utils::{addresss, option_addresss, protocol},
     Protocol,
 };
@@ -59,6 +60,20 @@ impl ProtocolInfo {
 
         tokens
     }
+
+    /// The pairs that the pool is priced on. The curve lp token is the base
+    /// pool of a metapool and is priced through the base pool itself. Pools
+    /// that register their own token, such as balancer composable stable
+    /// pools, don't price it.
+    pub fn pricing_pairs(&self, pool: Address) -> Vec<Pair> {
+        Pair::all_pairs(
+            &self
+                .clone()
+                .into_iter()
+                .filter(|token| *token != pool)
+                .collect::<Vec<_>>(),
+        )
+    }
 }
 
 impl IntoIterator for ProtocolInfo {
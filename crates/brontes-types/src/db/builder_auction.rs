@@ -0,0 +1,303 @@
+//! The mev-boost auction of each block.
+//!
+//! Relays publish every bid they received for a slot, but only the delivered
+//! payload makes it into the block metadata. A [`BuilderAuction`] keeps the
+//! full bid history of a block, from which the [`BuilderAuctionSummary`]
+//! attached to the [`MevBlock`](crate::mev::MevBlock) is computed: the margin
+//! of the winning bid over the best competing one & the bid trajectory of
+//! every builder relative to the start of the slot.
+use std::str::FromStr;
+
+use alloy_primitives::B256;
+use itertools::Itertools;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block_metadata::RelayBid, db::redefined_types::primitives::B256Redefined,
+    implement_table_value_codecs_with_zc,
+};
+
+/// Unix timestamp of the beacon chain genesis
+pub const BEACON_GENESIS_TIMESTAMP: u64 = 1606824023;
+pub const SECONDS_PER_SLOT: u64 = 12;
+
+/// Unix timestamp in milliseconds at which the slot starts
+pub fn slot_start_ms(slot: u64) -> u64 {
+    (BEACON_GENESIS_TIMESTAMP + slot * SECONDS_PER_SLOT) * 1000
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct AuctionBid {
+    pub relay:          String,
+    pub builder_pubkey: String,
+    pub block_hash:     B256,
+    pub value:          u128,
+    pub timestamp_ms:   u64,
+}
+
+impl TryFrom<RelayBid> for AuctionBid {
+    type Error = eyre::ErrReport;
+
+    fn try_from(value: RelayBid) -> eyre::Result<Self> {
+        Ok(Self {
+            relay:          value.relay.to_string(),
+            builder_pubkey: value.builder_pubkey,
+            block_hash:     B256::from_str(&value.block_hash)?,
+            value:          value.value,
+            timestamp_ms:   value.timestamp_ms,
+        })
+    }
+}
+
+/// All bids the relays received for a block, ordered by time
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BuilderAuction {
+    pub slot: u64,
+    pub bids: Vec<AuctionBid>,
+}
+
+implement_table_value_codecs_with_zc!(BuilderAuctionRedefined);
+
+impl BuilderAuction {
+    pub fn new(slot: u64, mut bids: Vec<AuctionBid>) -> Self {
+        bids.sort_by(|a, b| {
+            a.timestamp_ms
+                .cmp(&b.timestamp_ms)
+                .then_with(|| a.relay.cmp(&b.relay))
+        });
+        bids.dedup();
+
+        Self { slot, bids }
+    }
+
+    /// Builds the auction of the slot the block was proposed in. Bids for the
+    /// same block number can span several slots if a slot was missed, so only
+    /// the bids of the winning slot are kept, bids with a malformed block
+    /// hash are skipped. Returns `None` if none of the relays saw the winning
+    /// bid.
+    pub fn from_relay_bids(block_hash: B256, bids: Vec<RelayBid>) -> Option<Self> {
+        let slot = bids
+            .iter()
+            .find(|bid| {
+                B256::from_str(&bid.block_hash).is_ok_and(|bid_hash| bid_hash == block_hash)
+            })?
+            .slot;
+
+        let bids = bids
+            .into_iter()
+            .filter(|bid| bid.slot == slot)
+            .filter_map(|bid| {
+                let relay = bid.relay;
+                AuctionBid::try_from(bid)
+                    .inspect_err(|e| tracing::warn!(%relay, %slot, "skipping relay bid - {:?}", e))
+                    .ok()
+            })
+            .collect();
+
+        Some(Self::new(slot, bids))
+    }
+
+    pub fn slot_start_ms(&self) -> u64 {
+        slot_start_ms(self.slot)
+    }
+
+    /// Milliseconds between the start of the slot and the bid. Bids for the
+    /// slot can arrive before it starts, in which case this is negative
+    pub fn ms_into_slot(&self, bid: &AuctionBid) -> i64 {
+        bid.timestamp_ms as i64 - self.slot_start_ms() as i64
+    }
+
+    /// The first bid that carried the proposed block
+    pub fn winning_bid(&self, block_hash: B256) -> Option<&AuctionBid> {
+        self.bids.iter().find(|bid| bid.block_hash == block_hash)
+    }
+
+    /// The best bid of any other builder that was received before the
+    /// winning bid, i.e the bid the winner had to beat
+    pub fn runner_up(&self, winning_bid: &AuctionBid) -> Option<&AuctionBid> {
+        self.bids
+            .iter()
+            .filter(|bid| {
+                bid.builder_pubkey != winning_bid.builder_pubkey
+                    && bid.timestamp_ms <= winning_bid.timestamp_ms
+            })
+            .max_by_key(|bid| bid.value)
+    }
+
+    /// How each builder bid over the course of the slot
+    pub fn trajectories(&self) -> Vec<BuilderBidTrajectory> {
+        self.bids
+            .iter()
+            .into_group_map_by(|bid| bid.builder_pubkey.clone())
+            .into_iter()
+            .map(|(builder_pubkey, bids)| {
+                let first = bids.first().unwrap();
+                let last = bids.last().unwrap();
+                // the earliest of the highest bids
+                let max = bids.iter().rev().max_by_key(|bid| bid.value).unwrap();
+
+                BuilderBidTrajectory {
+                    builder_pubkey,
+                    bid_count: bids.len() as u64,
+                    first_bid_ms: self.ms_into_slot(first),
+                    last_bid_ms: self.ms_into_slot(last),
+                    first_value: first.value,
+                    last_value: last.value,
+                    max_value: max.value,
+                    max_value_ms: self.ms_into_slot(max),
+                }
+            })
+            .sorted_by(|a, b| b.max_value.cmp(&a.max_value))
+            .collect()
+    }
+
+    pub fn summary(&self, block_hash: B256) -> Option<BuilderAuctionSummary> {
+        let winning_bid = self.winning_bid(block_hash)?;
+        let runner_up = self.runner_up(winning_bid);
+        let trajectories = self.trajectories();
+
+        Some(BuilderAuctionSummary {
+            slot:              self.slot,
+            bid_count:         self.bids.len() as u64,
+            builder_count:     trajectories.len() as u64,
+            winning_builder:   winning_bid.builder_pubkey.clone(),
+            winning_relay:     winning_bid.relay.clone(),
+            winning_bid:       winning_bid.value,
+            winning_bid_ms:    self.ms_into_slot(winning_bid),
+            runner_up_builder: runner_up.map(|bid| bid.builder_pubkey.clone()),
+            runner_up_bid:     runner_up.map(|bid| bid.value),
+            winning_margin:    runner_up.map(|bid| winning_bid.value.saturating_sub(bid.value)),
+            builders:          trajectories,
+        })
+    }
+}
+
+/// The bids of a single builder. All times are in milliseconds relative to
+/// the start of the slot
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BuilderBidTrajectory {
+    pub builder_pubkey: String,
+    pub bid_count:      u64,
+    pub first_bid_ms:   i64,
+    pub last_bid_ms:    i64,
+    pub first_value:    u128,
+    pub last_value:     u128,
+    pub max_value:      u128,
+    pub max_value_ms:   i64,
+}
+
+/// The competition for the block, as attached to the
+/// [`MevBlock`](crate::mev::MevBlock)
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BuilderAuctionSummary {
+    pub slot:              u64,
+    pub bid_count:         u64,
+    pub builder_count:     u64,
+    pub winning_builder:   String,
+    pub winning_relay:     String,
+    pub winning_bid:       u128,
+    /// Milliseconds between the start of the slot and the winning bid
+    pub winning_bid_ms:    i64,
+    pub runner_up_builder: Option<String>,
+    pub runner_up_bid:     Option<u128>,
+    /// How much the winning bid paid over the runner-up
+    pub winning_margin:    Option<u128>,
+    /// The bid trajectory of each builder, highest bidder first
+    pub builders:          Vec<BuilderBidTrajectory>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_metadata::Relays;
+
+    fn relay_bid(slot: u64, block_hash: &str, value: u128) -> RelayBid {
+        RelayBid {
+            relay: Relays::UltraSound,
+            slot,
+            parent_hash: String::new(),
+            block_hash: block_hash.to_string(),
+            builder_pubkey: "titan".to_string(),
+            proposer_fee_recipient: String::new(),
+            gas_limit: 0,
+            gas_used: 0,
+            value,
+            block_number: 1,
+            num_tx: 0,
+            timestamp: 0,
+            timestamp_ms: slot_start_ms(slot) + value as u64,
+        }
+    }
+
+    fn bid(builder: &str, block_hash: u8, value: u128, ms_into_slot: u64) -> AuctionBid {
+        AuctionBid {
+            relay: "UltraSound".to_string(),
+            builder_pubkey: builder.to_string(),
+            block_hash: B256::with_last_byte(block_hash),
+            value,
+            timestamp_ms: slot_start_ms(100) + ms_into_slot,
+        }
+    }
+
+    #[test]
+    fn test_auction_summary() {
+        let auction = BuilderAuction::new(
+            100,
+            vec![
+                bid("beaver", 3, 120, 900),
+                bid("titan", 1, 100, 200),
+                bid("beaver", 2, 110, 400),
+                bid("titan", 4, 130, 1000),
+                bid("rsync", 5, 150, 1500),
+            ],
+        );
+
+        let summary = auction.summary(B256::with_last_byte(4)).unwrap();
+        assert_eq!(summary.bid_count, 5);
+        assert_eq!(summary.builder_count, 3);
+        assert_eq!(summary.winning_builder, "titan");
+        assert_eq!(summary.winning_bid_ms, 1000);
+        // the later rsync bid didn't exist when titan won
+        assert_eq!(summary.runner_up_builder.as_deref(), Some("beaver"));
+        assert_eq!(summary.winning_margin, Some(10));
+
+        let beaver = summary
+            .builders
+            .iter()
+            .find(|builder| builder.builder_pubkey == "beaver")
+            .unwrap();
+        assert_eq!(beaver.bid_count, 2);
+        assert_eq!(beaver.first_bid_ms, 400);
+        assert_eq!(beaver.first_value, 110);
+        assert_eq!(beaver.max_value, 120);
+        assert_eq!(beaver.max_value_ms, 900);
+        assert_eq!(summary.builders[0].builder_pubkey, "rsync");
+
+        assert!(auction.summary(B256::with_last_byte(9)).is_none());
+    }
+
+    #[test]
+    fn test_from_relay_bids() {
+        let winning_hash = B256::with_last_byte(1);
+        let bids = vec![
+            relay_bid(100, &winning_hash.to_string(), 3),
+            relay_bid(100, &B256::with_last_byte(2).to_string(), 2),
+            // malformed bids are skipped instead of dropping the auction
+            relay_bid(100, "0xnope", 1),
+            // a missed slot for the same block number
+            relay_bid(99, &B256::with_last_byte(3).to_string(), 4),
+        ];
+
+        let auction = BuilderAuction::from_relay_bids(winning_hash, bids.clone()).unwrap();
+        assert_eq!(auction.slot, 100);
+        assert_eq!(auction.bids.iter().map(|bid| bid.value).collect::<Vec<_>>(), vec![2, 3]);
+
+        assert!(BuilderAuction::from_relay_bids(B256::with_last_byte(9), bids).is_none());
+    }
+}
@@ -6,6 +6,7 @@ use redefined::self_convert_redefined;
 use serde::Deserialize;
 use strum::Display;
 
+use super::CexInstrument;
 use crate::constants::*;
 
 #[derive(
@@ -43,6 +44,10 @@ pub enum CexExchange {
     OptimisticVWAP,
     #[default]
     Unknown,
+    /// Binance USDⓈ-M perpetual futures
+    BinanceFutures,
+    /// Okex perpetual swaps
+    OkexSwap,
 }
 
 self_convert_redefined!(CexExchange);
@@ -77,6 +82,9 @@ impl CexExchange {
             CexExchange::Average => "c.exchange = ''",
             CexExchange::VWAP => "c.exchange = ''",
             CexExchange::OptimisticVWAP => "c.exchange = ''",
+            // the perp venues are only read from cex data files
+            CexExchange::BinanceFutures => "c.exchange = ''",
+            CexExchange::OkexSwap => "c.exchange = ''",
         }
     }
 }
@@ -87,9 +95,11 @@ impl From<&str> for CexExchange {
         let value = val.as_str();
         match value {
             "binance" | "binance-futures" => CexExchange::Binance,
+            "binancefutures" | "binance_futures" => CexExchange::BinanceFutures,
             "bitmex" | "Bitmex" => CexExchange::Bitmex,
             "deribit" | "Deribit" => CexExchange::Deribit,
             "okex" | "Okex" | "okex-swap" => CexExchange::Okex,
+            "okexswap" | "okex_swap" => CexExchange::OkexSwap,
             "coinbase" | "Coinbase" => CexExchange::Coinbase,
             "kraken" | "Kraken" => CexExchange::Kraken,
             "bybit-spot" | "bybitspot" | "BybitSpot" | "Bybit-Spot" | "Bybit_Spot" | "bybit" => {
@@ -205,6 +215,8 @@ impl CexExchange {
             CexExchange::Upbit => {
                 vec![WETH_ADDRESS, WBTC_ADDRESS, LINK_ADDRESS, EURT_ADDRESS, UNI_TOKEN]
             }
+            CexExchange::BinanceFutures => vec![USDT_ADDRESS, USDC_ADDRESS, WBTC_ADDRESS],
+            CexExchange::OkexSwap => vec![USDT_ADDRESS, USDC_ADDRESS],
 
             _ => vec![],
         }
@@ -216,7 +228,6 @@ impl CexExchange {
     /// pairs
     ///
     /// TODO: Account for special fee pairs & stableswap rates
-    /// TODO: Account for futures & spot fee deltas
     pub fn fees(&self) -> (Rational, Rational) {
         match self {
             CexExchange::Binance => (
@@ -270,6 +281,14 @@ impl CexExchange {
                 Rational::from_sci_string("0").unwrap(),
                 Rational::from_sci_string("0.0003").unwrap(),
             ),
+            CexExchange::BinanceFutures => (
+                Rational::from_sci_string("0").unwrap(),
+                Rational::from_sci_string("0.00017").unwrap(),
+            ),
+            CexExchange::OkexSwap => (
+                Rational::from_sci_string("-0.00005").unwrap(),
+                Rational::from_sci_string("0.00015").unwrap(),
+            ),
             CexExchange::Average => {
                 unreachable!("Cannot get fees for cross exchange average quote")
             }
@@ -279,4 +298,13 @@ impl CexExchange {
             }
         }
     }
+
+    /// Whether the exchange is one of the perp venues that are only used to
+    /// hedge with `--perp-hedge`. Bitmex & Deribit are priced as spot venues.
+    pub fn instrument(&self) -> CexInstrument {
+        match self {
+            CexExchange::BinanceFutures | CexExchange::OkexSwap => CexInstrument::Perpetual,
+            _ => CexInstrument::Spot,
+        }
+    }
 }
@@ -0,0 +1,280 @@
+//! Configurable cex fee schedules.
+//!
+//! [`CexExchange::fees`] assumes the best public fee tier of every exchange.
+//! A [`CexFeeSchedule`] overrides these per exchange, per [`PairClass`] and
+//! per searcher [`Fund`], e.g:
+//!
+//! ```toml
+//! [exchanges.binance]
+//! maker = 0.0002
+//! taker = 0.0004
+//!
+//! [exchanges.binance.pair_classes.stable]
+//! maker = 0.0
+//! taker = 0.0001
+//!
+//! [funds.Wintermute.binance]
+//! maker = -0.00005
+//! taker = 0.00015
+//! ```
+//!
+//! The schedule is passed to the cex-dex inspectors in their
+//! [`CexDexTradeConfig`](super::trades::CexDexTradeConfig), fees are then
+//! looked up per transaction with a [`FeeLookup`] for the searcher's fund.
+use std::path::Path;
+
+use alloy_primitives::Address;
+use malachite::Rational;
+use redefined::self_convert_redefined;
+use serde::Deserialize;
+use strum::Display;
+
+use super::CexExchange;
+use crate::{constants::*, db::searcher::Fund, pair::Pair, FastHashMap};
+
+const STABLES: [Address; 7] = [
+    USDT_ADDRESS,
+    USDC_ADDRESS,
+    DAI_ADDRESS,
+    BUSD_ADDRESS,
+    TUSD_ADDRESS,
+    FRAX_ADDRESS,
+    LUSD_ADDRESS,
+];
+
+/// Exchanges usually have their own fee tiers for stable and major pairs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum PairClass {
+    /// Both tokens are stables
+    Stable,
+    /// Both tokens are stables, ETH or BTC
+    Major,
+    Other,
+}
+
+impl PairClass {
+    pub fn of(pair: &Pair) -> Self {
+        let is_stable = |token: &Address| STABLES.contains(token);
+        let is_major =
+            |token: &Address| is_stable(token) || *token == WETH_ADDRESS || *token == WBTC_ADDRESS;
+
+        if is_stable(&pair.0) && is_stable(&pair.1) {
+            PairClass::Stable
+        } else if is_major(&pair.0) && is_major(&pair.1) {
+            PairClass::Major
+        } else {
+            PairClass::Other
+        }
+    }
+}
+
+/// Which part of the fee schedule the fees of a trade were taken from,
+/// ordered from the least to the most specific
+#[derive(
+    Copy,
+    Display,
+    Default,
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    rkyv::Serialize,
+    rkyv::Deserialize,
+    rkyv::Archive,
+)]
+pub enum FeeAssumption {
+    /// The hardcoded best tier fees of [`CexExchange::fees`]
+    #[default]
+    BestTier,
+    Exchange,
+    PairClass,
+    Fund,
+}
+
+self_convert_redefined!(FeeAssumption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawFeeTier")]
+pub struct FeeTier {
+    pub maker: Rational,
+    pub taker: Rational,
+}
+
+impl FeeTier {
+    pub fn new(maker: f64, taker: f64) -> eyre::Result<Self> {
+        RawFeeTier { maker, taker }.try_into()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawFeeTier {
+    maker: f64,
+    taker: f64,
+}
+
+impl TryFrom<RawFeeTier> for FeeTier {
+    type Error = eyre::Report;
+
+    fn try_from(value: RawFeeTier) -> Result<Self, Self::Error> {
+        let to_rational = |fee: f64| {
+            Rational::try_from_float_simplest(fee).map_err(|_| eyre::eyre!("invalid fee {fee}"))
+        };
+
+        Ok(Self { maker: to_rational(value.maker)?, taker: to_rational(value.taker)? })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExchangeFees {
+    #[serde(flatten)]
+    pub default:      Option<FeeTier>,
+    #[serde(default)]
+    pub pair_classes: FastHashMap<PairClass, FeeTier>,
+}
+
+/// The maker & taker fees of a trade and where they were taken from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedFees {
+    pub maker:      Rational,
+    pub taker:      Rational,
+    pub assumption: FeeAssumption,
+}
+
+impl AppliedFees {
+    /// The hardcoded best tier fees of the exchange
+    pub fn best_tier(exchange: CexExchange) -> Self {
+        let (maker, taker) = exchange.fees();
+        Self { maker, taker, assumption: FeeAssumption::BestTier }
+    }
+}
+
+/// Looks up the fees of the trades of a transaction, for the fund of its
+/// searcher. Without a schedule the best tier fees are used.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeLookup<'a> {
+    pub schedule: Option<&'a CexFeeSchedule>,
+    pub fund:     Fund,
+}
+
+impl<'a> FeeLookup<'a> {
+    pub fn new(schedule: Option<&'a CexFeeSchedule>, fund: Fund) -> Self {
+        Self { schedule, fund }
+    }
+
+    pub fn fees(&self, exchange: CexExchange, pair: &Pair) -> AppliedFees {
+        self.schedule.map_or_else(
+            || AppliedFees::best_tier(exchange),
+            |schedule| schedule.fees(exchange, pair, self.fund),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CexFeeSchedule {
+    #[serde(default)]
+    pub exchanges: FastHashMap<CexExchange, ExchangeFees>,
+    #[serde(default)]
+    pub funds:     FastHashMap<Fund, FastHashMap<CexExchange, FeeTier>>,
+}
+
+impl CexFeeSchedule {
+    /// Reads a toml schedule, or json if the file has a `.json` extension
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Ok(serde_json::from_str(&contents)?)
+        } else {
+            Ok(toml::from_str(&contents)?)
+        }
+    }
+
+    /// Sets the default fee tier of an exchange
+    pub fn with_exchange_fees(mut self, exchange: CexExchange, tier: FeeTier) -> Self {
+        self.exchanges.entry(exchange).or_default().default = Some(tier);
+        self
+    }
+
+    /// Looks up the fees of a trade, from the most to the least specific
+    /// entry: the fund's fees, the pair class of the exchange, the exchange
+    /// and lastly the hardcoded best tier fees
+    pub fn fees(&self, exchange: CexExchange, pair: &Pair, fund: Fund) -> AppliedFees {
+        let applied = |tier: &FeeTier, assumption| AppliedFees {
+            maker: tier.maker.clone(),
+            taker: tier.taker.clone(),
+            assumption,
+        };
+
+        if let Some(tier) = self
+            .funds
+            .get(&fund)
+            .and_then(|exchanges| exchanges.get(&exchange))
+        {
+            return applied(tier, FeeAssumption::Fund)
+        }
+
+        if let Some(exchange_fees) = self.exchanges.get(&exchange) {
+            if let Some(tier) = exchange_fees.pair_classes.get(&PairClass::of(pair)) {
+                return applied(tier, FeeAssumption::PairClass)
+            }
+            if let Some(tier) = &exchange_fees.default {
+                return applied(tier, FeeAssumption::Exchange)
+            }
+        }
+
+        AppliedFees::best_tier(exchange)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_lookup_order() {
+        let schedule: CexFeeSchedule = serde_json::from_str(
+            r#"{
+                "exchanges": {
+                    "binance": {
+                        "maker": 0.0002,
+                        "taker": 0.0004,
+                        "pair_classes": { "stable": { "maker": 0.0, "taker": 0.0001 } }
+                    }
+                },
+                "funds": { "Wintermute": { "binance": { "maker": -0.00005, "taker": 0.0001 } } }
+            }"#,
+        )
+        .unwrap();
+
+        let stable = Pair(USDT_ADDRESS, USDC_ADDRESS);
+        let major = Pair(WETH_ADDRESS, USDT_ADDRESS);
+
+        let fees = schedule.fees(CexExchange::Binance, &major, Fund::None);
+        assert_eq!(fees.assumption, FeeAssumption::Exchange);
+        assert_eq!(fees.taker, Rational::try_from_float_simplest(0.0004).unwrap());
+
+        let fees = schedule.fees(CexExchange::Binance, &stable, Fund::None);
+        assert_eq!(fees.assumption, FeeAssumption::PairClass);
+
+        let fees = schedule.fees(CexExchange::Binance, &stable, Fund::Wintermute);
+        assert_eq!(fees.assumption, FeeAssumption::Fund);
+
+        let fees = schedule.fees(CexExchange::Okex, &major, Fund::Wintermute);
+        assert_eq!(fees.assumption, FeeAssumption::BestTier);
+        assert_eq!((fees.maker, fees.taker), CexExchange::Okex.fees());
+
+        let lookup = FeeLookup::new(Some(&schedule), Fund::Wintermute);
+        assert_eq!(lookup.fees(CexExchange::Binance, &stable).assumption, FeeAssumption::Fund);
+        assert_eq!(
+            FeeLookup::default().fees(CexExchange::Binance, &stable),
+            AppliedFees::best_tier(CexExchange::Binance)
+        );
+    }
+}
@@ -1,10 +1,14 @@
 mod best_cex_per_pair;
 mod cex_symbols;
 mod exchanges;
+mod fees;
+mod perps;
 
 pub use best_cex_per_pair::*;
 pub use cex_symbols::*;
 pub use exchanges::*;
+pub use fees::*;
+pub use perps::*;
 
 pub mod quotes;
 pub mod trades;
@@ -0,0 +1,256 @@
+//! Perpetual futures support.
+//!
+//! Perp venues are their own [`CexExchange`]s, e.g.
+//! [`CexExchange::BinanceFutures`], so their trades & quotes are kept apart
+//! from spot in the [`CexTradeMap`](super::trades::CexTradeMap) and the
+//! [`CexPriceMap`](super::quotes::CexPriceMap) while being keyed by the same
+//! `(base, quote)` pairs. They are only read from cex data files, where they
+//! are named `binancefutures` & `okexswap`, as `binance-futures` &
+//! `okex-swap` have always been read as their spot venues.
+//!
+//! A perp trades at a basis to the spot index and a position that is held
+//! pays or receives funding, so a hedge priced on a perp is brought back to
+//! its spot equivalent with the [`PerpTicker`] of the perp at the time of the
+//! trade.
+use malachite::{
+    num::basic::traits::{One, Zero},
+    Rational,
+};
+
+use super::{
+    trades::{optimistic::OptimisticPrice, time_window_vwam::WindowExchangePrice, Direction},
+    CexExchange,
+};
+use crate::{pair::Pair, FastHashMap};
+
+/// Funding is exchanged every 8 hours on all supported perp venues
+pub const FUNDING_INTERVAL_US: u64 = 8 * 60 * 60 * 1_000_000;
+
+#[derive(
+    Copy, Debug, Clone, Default, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum CexInstrument {
+    #[default]
+    Spot,
+    Perpetual,
+}
+
+/// The funding rate, mark & index price of a perp at a point in time
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PerpTicker {
+    pub exchange:     CexExchange,
+    pub timestamp:    u64,
+    /// The funding rate paid by longs to shorts over a funding interval
+    pub funding_rate: Rational,
+    pub mark_price:   Rational,
+    pub index_price:  Rational,
+}
+
+impl PerpTicker {
+    /// The premium of the perp over the spot index
+    pub fn basis(&self) -> Rational {
+        if self.index_price == Rational::ZERO {
+            return Rational::ZERO
+        }
+
+        (&self.mark_price - &self.index_price) / &self.index_price
+    }
+
+    /// The factor that converts the price of a hedge on the perp into its spot
+    /// equivalent, for a hedge that is held for `horizon_us`. With
+    /// [`Direction::Buy`] the hedge sells the base and is short the perp,
+    /// with [`Direction::Sell`] it buys the base and is long.
+    pub fn spot_factor(&self, direction: Direction, horizon_us: u64) -> Rational {
+        let funding =
+            &self.funding_rate * Rational::from(horizon_us) / Rational::from(FUNDING_INTERVAL_US);
+        let premium = Rational::ONE + self.basis();
+
+        match direction {
+            Direction::Buy => (Rational::ONE + funding) / premium,
+            Direction::Sell => (Rational::ONE - funding) * premium,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PerpTickerMap(pub FastHashMap<CexExchange, FastHashMap<Pair, Vec<PerpTicker>>>);
+
+impl PerpTickerMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the map from tickers in any order
+    pub fn from_tickers(tickers: impl IntoIterator<Item = (Pair, PerpTicker)>) -> Self {
+        let mut this = Self::new();
+        for (pair, ticker) in tickers {
+            this.0
+                .entry(ticker.exchange)
+                .or_default()
+                .entry(pair)
+                .or_default()
+                .push(ticker);
+        }
+
+        this.0
+            .values_mut()
+            .flat_map(|pairs| pairs.values_mut())
+            .for_each(|tickers| tickers.sort_unstable_by_key(|ticker| ticker.timestamp));
+
+        this
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(|pairs| pairs.is_empty())
+    }
+
+    /// The most recent ticker of the perp at or before `timestamp`
+    pub fn get_ticker(
+        &self,
+        exchange: &CexExchange,
+        pair: &Pair,
+        timestamp: u64,
+    ) -> Option<(&PerpTicker, Direction)> {
+        let pairs = self.0.get(exchange)?;
+        let (tickers, direction) = pairs
+            .get(pair)
+            .map(|tickers| (tickers, Direction::Sell))
+            .or_else(|| {
+                pairs
+                    .get(&pair.flip())
+                    .map(|tickers| (tickers, Direction::Buy))
+            })?;
+
+        let index = tickers.partition_point(|ticker| ticker.timestamp <= timestamp);
+
+        Some((tickers.get(index.checked_sub(1)?)?, direction))
+    }
+
+    /// The spot factor of trading through `pairs` on the perp of the exchange.
+    /// Without a ticker the perp is assumed to trade at the index.
+    pub fn spot_factor(
+        &self,
+        exchange: &CexExchange,
+        pairs: &[Pair],
+        timestamp: u64,
+        horizon_us: u64,
+    ) -> Rational {
+        pairs
+            .iter()
+            .filter_map(|pair| self.get_ticker(exchange, pair, timestamp))
+            .map(|(ticker, direction)| ticker.spot_factor(direction, horizon_us))
+            .fold(Rational::ONE, |acc, factor| acc * factor)
+    }
+
+    /// Converts a time window price on perps into its spot equivalent. The
+    /// global price is adjusted by the volume weighted factor of the
+    /// exchanges.
+    pub fn adjust_window(
+        &self,
+        mut window: WindowExchangePrice,
+        timestamp: u64,
+        horizon_us: u64,
+    ) -> WindowExchangePrice {
+        let mut weighted = Rational::ZERO;
+        let mut volume = Rational::ZERO;
+
+        for (exchange, path) in &mut window.exchange_price_with_volume_direct {
+            let factor = self.spot_factor(exchange, &window.pairs, timestamp, horizon_us);
+
+            weighted += &factor * &path.volume;
+            volume += &path.volume;
+
+            path.price_maker *= &factor;
+            path.price_taker *= factor;
+        }
+
+        if volume != Rational::ZERO {
+            let factor = weighted / volume;
+            window.global.price_maker *= &factor;
+            window.global.price_taker *= factor;
+        }
+
+        window
+    }
+
+    /// Converts an optimistic price on perps into its spot equivalent, using
+    /// the volume weighted factor of the exchanges the trades were taken from
+    pub fn adjust_optimistic(
+        &self,
+        mut price: OptimisticPrice,
+        timestamp: u64,
+        horizon_us: u64,
+    ) -> OptimisticPrice {
+        let factor = price
+            .pairs
+            .iter()
+            .map(|pair| {
+                let (weighted, volume) = price
+                    .trades_used
+                    .iter()
+                    .filter(|trade| trade.pair == *pair || trade.pair == pair.flip())
+                    .fold((Rational::ZERO, Rational::ZERO), |(weighted, volume), trade| {
+                        let factor = self.spot_factor(
+                            &trade.exchange,
+                            std::slice::from_ref(pair),
+                            timestamp,
+                            horizon_us,
+                        );
+                        (weighted + factor * &trade.volume, volume + &trade.volume)
+                    });
+
+                if volume == Rational::ZERO {
+                    Rational::ONE
+                } else {
+                    weighted / volume
+                }
+            })
+            .fold(Rational::ONE, |acc, factor| acc * factor);
+
+        price.global.price_maker *= &factor;
+        price.global.price_taker *= factor;
+
+        price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+
+    use super::*;
+
+    #[test]
+    fn test_perp_spot_factor() {
+        let base = Address::with_last_byte(1);
+        let quote = Address::with_last_byte(2);
+        let ticker = PerpTicker {
+            exchange:     CexExchange::BinanceFutures,
+            timestamp:    10,
+            funding_rate: Rational::from_signeds(1, 1000),
+            mark_price:   Rational::from(101),
+            index_price:  Rational::from(100),
+        };
+        let tickers = PerpTickerMap::from_tickers([(Pair(base, quote), ticker)]);
+
+        // selling the base is short the perp, we sold at a 1% premium and
+        // receive a full interval of funding
+        let factor = tickers.spot_factor(
+            &CexExchange::BinanceFutures,
+            &[Pair(quote, base)],
+            10,
+            FUNDING_INTERVAL_US,
+        );
+        assert_eq!(factor, Rational::from_signeds(1001, 1010));
+
+        // buying the base is long the perp, paying the premium & the funding
+        let factor = tickers.spot_factor(&CexExchange::BinanceFutures, &[Pair(base, quote)], 10, 0);
+        assert_eq!(factor, Rational::from_signeds(101, 100));
+
+        // no ticker before the timestamp
+        assert_eq!(
+            tickers.spot_factor(&CexExchange::BinanceFutures, &[Pair(base, quote)], 9, 0),
+            Rational::ONE
+        );
+    }
+}
@@ -35,7 +35,7 @@ use tracing::error;
 use super::types::CexQuote;
 use crate::{
     db::{
-        cex::{quotes::CexQuoteRedefined, trades::Direction, CexExchange},
+        cex::{quotes::CexQuoteRedefined, trades::Direction, AppliedFees, CexExchange, FeeLookup},
         redefined_types::malachite::RationalRedefined,
     },
     implement_table_value_codecs_with_zc,
@@ -105,6 +105,7 @@ impl CexPriceMap {
         pair: &Pair,
         timestamp: u64,
         max_time_diff: Option<u64>,
+        fees: FeeLookup,
     ) -> Option<FeeAdjustedQuote> {
         self.most_liquid_ex
             .get(pair)
@@ -112,7 +113,7 @@ impl CexPriceMap {
             .and_then(|exchanges| {
                 for exchange in exchanges {
                     tracing::debug!(?exchange, ?pair);
-                    let res = self.get_quote_at(pair, exchange, timestamp, max_time_diff);
+                    let res = self.get_quote_at(pair, exchange, timestamp, max_time_diff, fees);
                     if res.is_some() {
                         return res
                     }
@@ -127,14 +128,16 @@ impl CexPriceMap {
         exchange: &CexExchange,
         timestamp: u64,
         max_time_diff: Option<u64>,
+        fees: FeeLookup,
     ) -> Option<FeeAdjustedQuote> {
-        self.get_exchange_quote_at_direct(pair, exchange, timestamp, max_time_diff)
+        self.get_exchange_quote_at_direct(pair, exchange, timestamp, max_time_diff, fees)
             .or_else(|| {
                 self.get_exchange_quote_at_via_intermediary(
                     pair,
                     exchange,
                     timestamp,
                     max_time_diff,
+                    fees,
                 )
             })
     }
@@ -145,6 +148,7 @@ impl CexPriceMap {
         exchange: &CexExchange,
         timestamp: u64,
         _max_time_diff: Option<u64>,
+        fees: FeeLookup,
     ) -> Option<FeeAdjustedQuote> {
         if pair.0 == pair.1 {
             return Some(FeeAdjustedQuote::default_one_to_one())
@@ -173,16 +177,16 @@ impl CexPriceMap {
                 let closest_quote = adjusted_quotes.get(index.saturating_sub(1))?;
                 let adjusted_quote = closest_quote.adjust_for_direction(direction);
 
-                let fees = exchange.fees();
+                let fees = fees.fees(*exchange, pair);
 
                 let fee_adjusted_maker = (
-                    &adjusted_quote.price.0 * (Rational::ONE - &fees.0),
-                    &adjusted_quote.price.1 * (Rational::ONE - &fees.0),
+                    &adjusted_quote.price.0 * (Rational::ONE - &fees.maker),
+                    &adjusted_quote.price.1 * (Rational::ONE - &fees.maker),
                 );
 
                 let fee_adjusted_taker = (
-                    &adjusted_quote.price.0 * (Rational::ONE - &fees.1),
-                    &adjusted_quote.price.1 * (Rational::ONE - &fees.1),
+                    &adjusted_quote.price.0 * (Rational::ONE - &fees.taker),
+                    &adjusted_quote.price.1 * (Rational::ONE - &fees.taker),
                 );
 
                 Some(FeeAdjustedQuote {
@@ -202,6 +206,7 @@ impl CexPriceMap {
         exchange: &CexExchange,
         timestamp: u64,
         max_time_diff: Option<u64>,
+        fees: FeeLookup,
     ) -> Option<FeeAdjustedQuote> {
         let intermediaries = self.calculate_intermediary_addresses(exchange, pair);
 
@@ -212,8 +217,20 @@ impl CexPriceMap {
                 let pair1 = Pair(intermediary, pair.1);
 
                 if let (Some(quote1), Some(quote2)) = (
-                    self.get_exchange_quote_at_direct(&pair0, exchange, timestamp, max_time_diff),
-                    self.get_exchange_quote_at_direct(&pair1, exchange, timestamp, max_time_diff),
+                    self.get_exchange_quote_at_direct(
+                        &pair0,
+                        exchange,
+                        timestamp,
+                        max_time_diff,
+                        fees,
+                    ),
+                    self.get_exchange_quote_at_direct(
+                        &pair1,
+                        exchange,
+                        timestamp,
+                        max_time_diff,
+                        fees,
+                    ),
                 ) {
                     let combined_price_maker = (
                         &quote1.price_maker.0 * &quote2.price_maker.0,
@@ -315,16 +332,16 @@ impl CexPriceMap {
                     let volume_weighted_bid = volume_price.0 / &cumulative_bbo.0;
                     let volume_weighted_ask = volume_price.1 / &cumulative_bbo.1;
 
-                    let fees = exchange.fees();
+                    let fees = AppliedFees::best_tier(*exchange);
 
                     let fee_adjusted_maker = (
-                        &volume_weighted_bid * (Rational::ONE - &fees.0),
-                        &volume_weighted_ask * (Rational::ONE - &fees.0),
+                        &volume_weighted_bid * (Rational::ONE - &fees.maker),
+                        &volume_weighted_ask * (Rational::ONE - &fees.maker),
                     );
 
                     let fee_adjusted_taker = (
-                        volume_weighted_bid * (Rational::ONE - &fees.1),
-                        volume_weighted_ask * (Rational::ONE - &fees.1),
+                        volume_weighted_bid * (Rational::ONE - &fees.taker),
+                        volume_weighted_ask * (Rational::ONE - &fees.taker),
                     );
 
                     Some(FeeAdjustedQuote {
@@ -1,7 +1,9 @@
 mod cex_quotes;
 mod download;
+mod order_book;
 mod types;
 
 pub use cex_quotes::*;
 pub use download::*;
+pub use order_book::*;
 pub use types::*;
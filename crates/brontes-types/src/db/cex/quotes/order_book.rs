@@ -0,0 +1,315 @@
+//! L2 order book snapshots, used to check whether a cex could actually absorb
+//! the hedge of a cex-dex arb at the quoted price.
+//!
+//! Books are keyed the same way as the [`CexPriceMap`](super::CexPriceMap),
+//! by the exchange and the `(base, quote)` address pair of the symbol, with
+//! prices denominated in the quote asset.
+use malachite::{
+    num::{
+        arithmetic::traits::Reciprocal,
+        basic::traits::{One, Zero},
+    },
+    Rational,
+};
+
+use crate::{
+    db::cex::{
+        trades::{time_window_vwam::ExchangePath, Direction},
+        AppliedFees, CexExchange, FeeLookup,
+    },
+    pair::Pair,
+    FastHashMap,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BookLevel {
+    pub price:  Rational,
+    pub amount: Rational,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CexOrderBook {
+    pub exchange:  CexExchange,
+    pub timestamp: u64,
+    /// Sorted from the best (highest) bid
+    pub bids:      Vec<BookLevel>,
+    /// Sorted from the best (lowest) ask
+    pub asks:      Vec<BookLevel>,
+}
+
+impl CexOrderBook {
+    /// Walks the book to trade `amount` of the token we give. With
+    /// [`Direction::Sell`] we buy the base with the quote and walk the asks,
+    /// with [`Direction::Buy`] we sell the base for the quote and walk the
+    /// bids. The snapshot only holds the top levels of the book, so an amount
+    /// past its depth gives a partial fill, priced over the matched part.
+    /// Returns `None` if nothing could be matched.
+    pub fn walk(&self, direction: Direction, amount: &Rational) -> Option<BookFill> {
+        if *amount == Rational::ZERO {
+            return None
+        }
+
+        let (levels, top_of_book) = match direction {
+            Direction::Sell => {
+                let best = self.asks.first()?;
+                (&self.asks, best.price.clone().reciprocal())
+            }
+            Direction::Buy => {
+                let best = self.bids.first()?;
+                (&self.bids, best.price.clone())
+            }
+        };
+
+        let mut remaining = amount.clone();
+        let mut received = Rational::ZERO;
+
+        for level in levels {
+            if remaining == Rational::ZERO {
+                break
+            }
+
+            // the price & size of the level in terms of the token we give
+            let (price, size) = match direction {
+                Direction::Sell => (level.price.clone().reciprocal(), &level.amount * &level.price),
+                Direction::Buy => (level.price.clone(), level.amount.clone()),
+            };
+
+            let taken = if remaining < size { remaining.clone() } else { size };
+            received += &taken * &price;
+            remaining -= taken;
+        }
+
+        let filled = amount - &remaining;
+        if filled == Rational::ZERO {
+            return None
+        }
+
+        let price = received / &filled;
+        let slippage = (&top_of_book - &price) / &top_of_book;
+
+        Some(BookFill {
+            exchange: self.exchange,
+            timestamp: self.timestamp,
+            price,
+            top_of_book,
+            amount: amount.clone(),
+            filled,
+            slippage,
+        })
+    }
+}
+
+/// The result of walking an order book
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookFill {
+    pub exchange:    CexExchange,
+    pub timestamp:   u64,
+    /// The average execution price, before fees
+    pub price:       Rational,
+    pub top_of_book: Rational,
+    /// The amount we wanted to trade
+    pub amount:      Rational,
+    /// How much of the amount was matched against the levels in the snapshot
+    pub filled:      Rational,
+    /// The relative price impact compared to trading at the top of the book
+    pub slippage:    Rational,
+}
+
+impl BookFill {
+    /// Whether the snapshot was deep enough to fill the whole amount
+    pub fn is_full(&self) -> bool {
+        self.filled == self.amount
+    }
+
+    /// The execution price adjusted for the fees of the exchange
+    pub fn exchange_path(&self, fees: AppliedFees) -> ExchangePath {
+        ExchangePath {
+            price_maker:      &self.price * (Rational::ONE - fees.maker),
+            price_taker:      &self.price * (Rational::ONE - fees.taker),
+            volume:           self.filled.clone(),
+            final_start_time: self.timestamp,
+            final_end_time:   self.timestamp,
+            was_intermediary: false,
+            fee_assumption:   fees.assumption,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CexOrderBookMap(pub FastHashMap<CexExchange, FastHashMap<Pair, Vec<CexOrderBook>>>);
+
+impl CexOrderBookMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the map from books in any order
+    pub fn from_books(books: impl IntoIterator<Item = (Pair, CexOrderBook)>) -> Self {
+        let mut this = Self::new();
+        for (pair, book) in books {
+            this.0
+                .entry(book.exchange)
+                .or_default()
+                .entry(pair)
+                .or_default()
+                .push(book);
+        }
+
+        this.0
+            .values_mut()
+            .flat_map(|pairs| pairs.values_mut())
+            .for_each(|books| books.sort_unstable_by_key(|book| book.timestamp));
+
+        this
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(|pairs| pairs.is_empty())
+    }
+
+    /// The most recent book of the exchange at or before `timestamp`
+    pub fn get_book(
+        &self,
+        exchange: &CexExchange,
+        pair: &Pair,
+        timestamp: u64,
+    ) -> Option<(&CexOrderBook, Direction)> {
+        let pairs = self.0.get(exchange)?;
+        let (books, direction) = pairs
+            .get(pair)
+            .map(|books| (books, Direction::Sell))
+            .or_else(|| pairs.get(&pair.flip()).map(|books| (books, Direction::Buy)))?;
+
+        let index = books.partition_point(|book| book.timestamp <= timestamp);
+
+        Some((books.get(index.checked_sub(1)?)?, direction))
+    }
+
+    /// Walks the book of every exchange and returns the fill with the best
+    /// price for trading `amount` of `pair.1` for `pair.0`. Full fills are
+    /// preferred over partial ones.
+    pub fn best_fill(
+        &self,
+        exchanges: &[CexExchange],
+        pair: Pair,
+        amount: &Rational,
+        timestamp: u64,
+        fees: FeeLookup,
+    ) -> Option<BookFill> {
+        exchanges
+            .iter()
+            .filter_map(|exchange| {
+                let (book, direction) = self.get_book(exchange, &pair, timestamp)?;
+                book.walk(direction, amount)
+            })
+            .max_by(|a, b| {
+                a.is_full().cmp(&b.is_full()).then_with(|| {
+                    a.exchange_path(fees.fees(a.exchange, &pair))
+                        .price_taker
+                        .cmp(&b.exchange_path(fees.fees(b.exchange, &pair)).price_taker)
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+
+    use super::*;
+
+    fn level(price: u64, amount: u64) -> BookLevel {
+        BookLevel { price: Rational::from(price), amount: Rational::from(amount) }
+    }
+
+    #[test]
+    fn test_walk_book() {
+        let base = Address::with_last_byte(1);
+        let quote = Address::with_last_byte(2);
+        let book = CexOrderBook {
+            exchange:  CexExchange::Binance,
+            timestamp: 10,
+            bids:      vec![level(100, 1), level(90, 1)],
+            asks:      vec![level(110, 1), level(120, 1)],
+        };
+        let books = CexOrderBookMap::from_books([(Pair(base, quote), book)]);
+
+        // selling 2 base walks both bid levels
+        let fill = books
+            .best_fill(
+                &[CexExchange::Binance],
+                Pair(quote, base),
+                &Rational::from(2),
+                10,
+                FeeLookup::default(),
+            )
+            .unwrap();
+        assert_eq!(fill.price, Rational::from(95));
+        assert_eq!(fill.top_of_book, Rational::from(100));
+        assert_eq!(fill.slippage, Rational::from_signeds(1, 20));
+
+        // spending 170 quote buys the first ask and half of the second
+        let fill = books
+            .best_fill(
+                &[CexExchange::Binance],
+                Pair(base, quote),
+                &Rational::from(170),
+                10,
+                FeeLookup::default(),
+            )
+            .unwrap();
+        assert_eq!(fill.price, Rational::from_signeds(3, 340));
+        assert_eq!(fill.filled, Rational::from(170));
+        assert!(fill.is_full());
+
+        // past the depth of the snapshot only the levels in it are filled
+        let fill = books
+            .best_fill(
+                &[CexExchange::Binance],
+                Pair(quote, base),
+                &Rational::from(3),
+                10,
+                FeeLookup::default(),
+            )
+            .unwrap();
+        assert_eq!(fill.price, Rational::from(95));
+        assert_eq!(fill.filled, Rational::from(2));
+        assert!(!fill.is_full());
+
+        // a deep enough book is preferred over a better priced partial fill
+        let deep = CexOrderBook {
+            exchange:  CexExchange::Coinbase,
+            timestamp: 10,
+            bids:      vec![level(80, 3)],
+            asks:      vec![],
+        };
+        let mut books = books;
+        books
+            .0
+            .entry(CexExchange::Coinbase)
+            .or_default()
+            .insert(Pair(base, quote), vec![deep]);
+        let fill = books
+            .best_fill(
+                &[CexExchange::Binance, CexExchange::Coinbase],
+                Pair(quote, base),
+                &Rational::from(3),
+                10,
+                FeeLookup::default(),
+            )
+            .unwrap();
+        assert_eq!(fill.exchange, CexExchange::Coinbase);
+        assert!(fill.is_full());
+
+        // no book before the timestamp
+        assert!(books
+            .best_fill(
+                &[CexExchange::Binance],
+                Pair(quote, base),
+                &Rational::ONE,
+                9,
+                FeeLookup::default(),
+            )
+            .is_none());
+    }
+}
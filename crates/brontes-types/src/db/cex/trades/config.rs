@@ -1,3 +1,8 @@
+use crate::db::{
+    cex::{CexFeeSchedule, FeeLookup},
+    searcher::Fund,
+};
+
 #[derive(Debug, Clone, Copy)]
 pub struct CexDexTradeConfig {
     pub initial_vwap_pre_block_us:         u64,
@@ -19,6 +24,15 @@ pub struct CexDexTradeConfig {
     pub pre_decay_weight_op:               f64,
     pub post_decay_weight_op:              f64,
     pub quote_offset_from_block_us:        u64,
+    /// The cex fees per exchange, pair class & fund. Without a schedule the
+    /// best tier fees of each exchange are used
+    pub cex_fee_schedule:                  Option<&'static CexFeeSchedule>,
+    /// Price the hedge on the perp venues when the spot venues don't have the
+    /// volume to fill the swap
+    pub perp_hedge_on_thin_spot:           bool,
+    /// How long a perp hedge is assumed to be held, which sets the funding
+    /// it pays or receives
+    pub perp_hedge_horizon_us:             u64,
 }
 
 impl Default for CexDexTradeConfig {
@@ -43,6 +57,9 @@ impl Default for CexDexTradeConfig {
             pre_decay_weight_op:               -0.0000003,
             post_decay_weight_op:              -0.00000012,
             quote_offset_from_block_us:        0,
+            cex_fee_schedule:                  None,
+            perp_hedge_on_thin_spot:           false,
+            perp_hedge_horizon_us:             3_600_000_000,
         }
     }
 }
@@ -52,4 +69,9 @@ impl CexDexTradeConfig {
         self.use_block_time_weights_optimistic = true;
         self.use_block_time_weights_vwap = true;
     }
+
+    /// Looks up the fees of the trades hedging a transaction of `fund`
+    pub fn fee_lookup(&self, fund: Fund) -> FeeLookup<'static> {
+        FeeLookup::new(self.cex_fee_schedule, fund)
+    }
 }
@@ -16,7 +16,7 @@ pub use time_window_vwam::*;
 use utils::SortedTrades;
 
 use super::CexExchange;
-use crate::{normalized_actions::NormalizedSwap, pair::Pair, FastHashMap};
+use crate::{db::searcher::Fund, normalized_actions::NormalizedSwap, pair::Pair, FastHashMap};
 
 impl CexTradeMap {
     /// Calculate the price of a pair with a given volume using both the dynamic
@@ -33,9 +33,11 @@ impl CexTradeMap {
         dex_swap: &NormalizedSwap,
         tx_hash: FixedBytes<32>,
         config: CexDexTradeConfig,
+        fund: Fund,
     ) -> (Option<WindowExchangePrice>, Option<OptimisticPrice>) {
         let window = self.calculate_time_window_vwam(
             config,
+            fund,
             exchanges,
             pair,
             volume,
@@ -47,6 +49,7 @@ impl CexTradeMap {
 
         let vwam = self.get_optimistic_vmap(
             config,
+            fund,
             exchanges,
             pair,
             volume,
@@ -63,6 +66,7 @@ impl CexTradeMap {
     pub fn calculate_time_window_vwam(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         exchanges: &[CexExchange],
         pair: Pair,
         volume: &Rational,
@@ -74,6 +78,7 @@ impl CexTradeMap {
         TimeWindowTrades::new_from_cex_trade_map(&self.0, block_timestamp, exchanges, pair)
             .get_price(
                 config,
+                fund,
                 exchanges,
                 pair,
                 volume,
@@ -87,6 +92,7 @@ impl CexTradeMap {
     pub fn get_optimistic_vmap(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         exchanges: &[CexExchange],
         pair: Pair,
         volume: &Rational,
@@ -99,6 +105,7 @@ impl CexTradeMap {
         SortedTrades::new_from_cex_trade_map(&self.0, exchanges, pair, block_timestamp)
             .get_optimistic_price(
                 config,
+                fund,
                 exchanges,
                 block_timestamp,
                 pair,
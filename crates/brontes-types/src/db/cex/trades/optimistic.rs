@@ -18,12 +18,15 @@ const R2: Rational = Rational::TWO;
 use super::{config::CexDexTradeConfig, time_window_vwam::ExchangePath};
 use crate::{
     constants::{USDC_ADDRESS, USDT_ADDRESS},
-    db::cex::{
-        trades::{
-            utils::{log_insufficient_trade_volume, log_missing_trade_data, TimeBasketQueue},
-            CexTrades, Direction, SortedTrades,
+    db::{
+        cex::{
+            trades::{
+                utils::{log_insufficient_trade_volume, log_missing_trade_data, TimeBasketQueue},
+                CexTrades, Direction, SortedTrades,
+            },
+            AppliedFees, CexExchange, FeeAssumption,
         },
-        CexExchange,
+        searcher::Fund,
     },
     display::utils::format_etherscan_url,
     mev::OptimisticTrade,
@@ -57,6 +60,7 @@ impl Mul for OptimisticPrice {
         self.global.final_start_time =
             min(self.global.final_start_time, rhs.global.final_start_time);
         self.global.final_end_time = max(self.global.final_end_time, rhs.global.final_end_time);
+        self.global.fee_assumption = max(self.global.fee_assumption, rhs.global.fee_assumption);
 
         self.trades_used.extend(rhs.trades_used);
 
@@ -100,6 +104,7 @@ impl<'a> SortedTrades<'a> {
     pub(crate) fn get_optimistic_price(
         &mut self,
         config: CexDexTradeConfig,
+        fund: Fund,
         _exchanges: &[CexExchange],
         block_timestamp: u64,
         pair: Pair,
@@ -120,6 +125,7 @@ impl<'a> SortedTrades<'a> {
                     final_start_time: 0,
                     final_end_time:   0,
                     was_intermediary: false,
+                    fee_assumption:   FeeAssumption::default(),
                 },
             })
         }
@@ -127,6 +133,7 @@ impl<'a> SortedTrades<'a> {
         let res = self
             .get_optimistic_direct(
                 config,
+                fund,
                 block_timestamp,
                 pair,
                 volume,
@@ -139,6 +146,7 @@ impl<'a> SortedTrades<'a> {
             .or_else(|| {
                 self.get_optimistic_via_intermediary(
                     config,
+                    fund,
                     block_timestamp,
                     pair,
                     volume,
@@ -159,6 +167,7 @@ impl<'a> SortedTrades<'a> {
     fn get_optimistic_via_intermediary(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         block_timestamp: u64,
         pair: Pair,
         volume: &Rational,
@@ -187,6 +196,7 @@ impl<'a> SortedTrades<'a> {
 
                 let first_leg = self.get_optimistic_direct(
                     config,
+                    fund,
                     block_timestamp,
                     pair0,
                     volume,
@@ -207,6 +217,7 @@ impl<'a> SortedTrades<'a> {
 
                 let second_leg = self.get_optimistic_direct(
                     config,
+                    fund,
                     block_timestamp,
                     pair1,
                     &new_vol,
@@ -227,6 +238,7 @@ impl<'a> SortedTrades<'a> {
     fn get_optimistic_direct(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         block_timestamp: u64,
         pair: Pair,
         volume: &Rational,
@@ -295,9 +307,13 @@ impl<'a> SortedTrades<'a> {
 
         let mut global_start_time = u64::MAX;
         let mut global_end_time = 0;
+        let mut fee_assumption = FeeAssumption::default();
 
+        let fees = config.fee_lookup(fund);
         for trade in trades_used {
-            let (m_fee, t_fee) = trade.exchange.fees();
+            let AppliedFees { maker: m_fee, taker: t_fee, assumption } =
+                fees.fees(trade.exchange, &pair);
+            fee_assumption = max(fee_assumption, assumption);
 
             let weight = if config.use_block_time_weights_vwap {
                 calculate_weight(
@@ -339,12 +355,13 @@ impl<'a> SortedTrades<'a> {
         }
 
         let global = ExchangePath {
-            price_maker:      vxp_maker / &trade_volume_weight,
-            price_taker:      vxp_taker / &trade_volume_weight,
-            volume:           trade_volume,
+            price_maker: vxp_maker / &trade_volume_weight,
+            price_taker: vxp_taker / &trade_volume_weight,
+            volume: trade_volume,
             final_start_time: global_start_time,
-            final_end_time:   global_end_time,
+            final_end_time: global_end_time,
             was_intermediary: was_inter,
+            fee_assumption,
         };
 
         let price = OptimisticPrice { trades_used: optimistic_trades, pairs: vec![pair], global };
@@ -20,7 +20,10 @@ use super::{
 };
 use crate::{
     constants::{USDC_ADDRESS, USDT_ADDRESS},
-    db::cex::CexExchange,
+    db::{
+        cex::{AppliedFees, CexExchange, FeeAssumption},
+        searcher::Fund,
+    },
     display::utils::format_etherscan_url,
     normalized_actions::NormalizedSwap,
     pair::Pair,
@@ -36,6 +39,8 @@ pub struct ExchangePath {
     pub final_start_time: u64,
     pub final_end_time:   u64,
     pub was_intermediary: bool,
+    /// The most specific fee assumption of the trades the price is made of
+    pub fee_assumption:   FeeAssumption,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -65,6 +70,7 @@ impl Mul for WindowExchangePrice {
                     min(first_leg.final_start_time, second_leg.final_start_time);
 
                 first_leg.final_end_time = max(first_leg.final_end_time, second_leg.final_end_time);
+                first_leg.fee_assumption = max(first_leg.fee_assumption, second_leg.fee_assumption);
 
                 Some((exchange, first_leg))
             })
@@ -75,6 +81,7 @@ impl Mul for WindowExchangePrice {
         self.global.final_start_time =
             min(self.global.final_start_time, rhs.global.final_start_time);
         self.global.final_end_time = max(self.global.final_end_time, rhs.global.final_end_time);
+        self.global.fee_assumption = max(self.global.fee_assumption, rhs.global.fee_assumption);
 
         self.global.price_maker *= rhs.global.price_maker;
         self.global.price_taker *= rhs.global.price_taker;
@@ -135,6 +142,7 @@ impl<'a> TimeWindowTrades<'a> {
     pub(crate) fn get_price(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         exchanges: &[CexExchange],
         pair: Pair,
         volume: &Rational,
@@ -149,11 +157,13 @@ impl<'a> TimeWindowTrades<'a> {
 
         let res = self
             .get_vwap_price(
-                config, exchanges, pair, volume, timestamp, bypass_vol, dex_swap, tx_hash, false,
+                config, fund, exchanges, pair, volume, timestamp, bypass_vol, dex_swap, tx_hash,
+                false,
             )
             .or_else(|| {
                 self.get_vwap_price_via_intermediary(
-                    config, exchanges, &pair, volume, timestamp, bypass_vol, dex_swap, tx_hash,
+                    config, fund, exchanges, &pair, volume, timestamp, bypass_vol, dex_swap,
+                    tx_hash,
                 )
             });
 
@@ -167,6 +177,7 @@ impl<'a> TimeWindowTrades<'a> {
     fn get_vwap_price_via_intermediary(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         exchanges: &[CexExchange],
         pair: &Pair,
         volume: &Rational,
@@ -194,6 +205,7 @@ impl<'a> TimeWindowTrades<'a> {
                 tracing::debug!(target: "brontes_types::db::cex::time_window_vwam", ?pair, ?intermediary, ?volume, "trying via intermediary");
                 let first_leg = self.get_vwap_price(
                     config,
+                    fund,
                     exchanges,
                     pair0,
                     volume,
@@ -215,6 +227,7 @@ impl<'a> TimeWindowTrades<'a> {
 
                 let second_leg = self.get_vwap_price(
                     config,
+                    fund,
                     exchanges,
                     pair1,
                     &second_leg_volume,
@@ -249,6 +262,7 @@ impl<'a> TimeWindowTrades<'a> {
     fn get_vwap_price(
         &self,
         config: CexDexTradeConfig,
+        fund: Fund,
         exchanges: &[CexExchange],
         pair: Pair,
         vol: &Rational,
@@ -289,7 +303,8 @@ impl<'a> TimeWindowTrades<'a> {
                 // See explanation of trade representation in the book
                 let adjusted_trade = trade.adjust_for_direction(trade_data.direction);
 
-                let (m_fee, t_fee) = trade.exchange.fees();
+                let AppliedFees { maker: m_fee, taker: t_fee, assumption } =
+                    config.fee_lookup(fund).fees(trade.exchange, &pair);
 
                 let (
                     vxp_maker,
@@ -298,6 +313,7 @@ impl<'a> TimeWindowTrades<'a> {
                     trade_volume_ex,
                     start_time,
                     end_time,
+                    fee_assumption,
                 ) = exchange_vxp.entry(trade.exchange).or_insert((
                     Rational::ZERO,
                     Rational::ZERO,
@@ -305,6 +321,7 @@ impl<'a> TimeWindowTrades<'a> {
                     Rational::ZERO,
                     0u64,
                     0u64,
+                    assumption,
                 ));
 
                 *vxp_maker += (&adjusted_trade.price * (Rational::ONE - m_fee))
@@ -354,9 +371,20 @@ impl<'a> TimeWindowTrades<'a> {
 
         let mut global_start_time = u64::MAX;
         let mut global_end_time = 0;
-
-        for (ex, (vxp_maker, vxp_taker, trade_vol_weight, trade_vol, start_time, end_time)) in
-            exchange_vxp
+        let mut global_fee_assumption = FeeAssumption::default();
+
+        for (
+            ex,
+            (
+                vxp_maker,
+                vxp_taker,
+                trade_vol_weight,
+                trade_vol,
+                start_time,
+                end_time,
+                fee_assumption,
+            ),
+        ) in exchange_vxp
         {
             if trade_vol == Rational::ZERO {
                 continue
@@ -368,16 +396,18 @@ impl<'a> TimeWindowTrades<'a> {
             global_taker += &taker_price * &trade_vol;
 
             let exchange_price = ExchangePath {
-                volume:           trade_vol.clone(),
-                price_maker:      maker_price,
-                price_taker:      taker_price,
-                final_end_time:   end_time,
+                volume: trade_vol.clone(),
+                price_maker: maker_price,
+                price_taker: taker_price,
+                final_end_time: end_time,
                 final_start_time: start_time,
                 was_intermediary: inter,
+                fee_assumption,
             };
 
             global_start_time = min(global_start_time, start_time);
             global_end_time = max(global_end_time, end_time);
+            global_fee_assumption = max(global_fee_assumption, fee_assumption);
 
             per_exchange_price.insert(ex, exchange_price);
         }
@@ -407,6 +437,7 @@ impl<'a> TimeWindowTrades<'a> {
             final_start_time: global_start_time,
             final_end_time:   global_end_time,
             was_intermediary: inter,
+            fee_assumption:   global_fee_assumption,
         };
 
         let window_exchange_prices = WindowExchangePrice {
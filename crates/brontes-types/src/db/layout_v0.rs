@@ -0,0 +1,370 @@
+//! Layouts the `MevBlocks`, `SearcherEOAs` and `SearcherContracts` tables
+//! stored their values in before they were versioned. They are only kept to
+//! read dbs written with them and are converted into the current layout by
+//! `brontes db migrate`, filling the fields added since with their defaults.
+use alloy_primitives::Address;
+use malachite::Rational;
+use redefined::{self_convert_redefined, Redefined};
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::Serialize;
+
+use crate::{
+    db::{
+        cex::{CexExchange, FeeAssumption},
+        mev_block::MevBlockWithClassified,
+        redefined_types::{malachite::RationalRedefined, primitives::*},
+        searcher::{Fund, SearcherInfo, TollByType},
+    },
+    implement_table_value_codecs_with_zc,
+    mev::*,
+    normalized_actions::*,
+    GasDetails,
+};
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct MevBlockWithClassifiedV0 {
+    pub block: MevBlockV0,
+    pub mev:   Vec<BundleV0>,
+}
+
+implement_table_value_codecs_with_zc!(MevBlockWithClassifiedV0Redefined);
+
+impl From<MevBlockWithClassifiedV0> for MevBlockWithClassified {
+    fn from(value: MevBlockWithClassifiedV0) -> Self {
+        Self { block: value.block.into(), mev: value.mev.into_iter().map(Into::into).collect() }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct MevBlockV0 {
+    pub block_hash:                  B256,
+    pub block_number:                u64,
+    #[redefined(same_fields)]
+    pub mev_count:                   MevCountV0,
+    pub eth_price:                   f64,
+    pub total_gas_used:              u128,
+    pub total_priority_fee:          u128,
+    pub total_bribe:                 u128,
+    pub total_mev_bribe:             u128,
+    pub total_mev_priority_fee_paid: u128,
+    pub builder_address:             Address,
+    pub builder_name:                Option<String>,
+    pub builder_eth_profit:          f64,
+    pub builder_profit_usd:          f64,
+    pub builder_mev_profit_usd:      f64,
+    pub builder_searcher_bribes:     u128,
+    pub builder_searcher_bribes_usd: f64,
+    pub builder_sponsorship_amount:  u128,
+    pub ultrasound_bid_adjusted:     bool,
+    pub proposer_fee_recipient:      Option<Address>,
+    pub proposer_mev_reward:         Option<u128>,
+    pub proposer_profit_usd:         Option<f64>,
+    pub total_mev_profit_usd:        f64,
+    pub possible_mev:                PossibleMevCollection,
+}
+
+impl From<MevBlockV0> for MevBlock {
+    fn from(value: MevBlockV0) -> Self {
+        Self {
+            block_hash:                  value.block_hash,
+            block_number:                value.block_number,
+            mev_count:                   value.mev_count.into(),
+            eth_price:                   value.eth_price,
+            total_gas_used:              value.total_gas_used,
+            total_priority_fee:          value.total_priority_fee,
+            total_bribe:                 value.total_bribe,
+            total_mev_bribe:             value.total_mev_bribe,
+            total_mev_priority_fee_paid: value.total_mev_priority_fee_paid,
+            builder_address:             value.builder_address,
+            builder_name:                value.builder_name,
+            builder_eth_profit:          value.builder_eth_profit,
+            builder_profit_usd:          value.builder_profit_usd,
+            builder_mev_profit_usd:      value.builder_mev_profit_usd,
+            builder_searcher_bribes:     value.builder_searcher_bribes,
+            builder_searcher_bribes_usd: value.builder_searcher_bribes_usd,
+            builder_sponsorship_amount:  value.builder_sponsorship_amount,
+            ultrasound_bid_adjusted:     value.ultrasound_bid_adjusted,
+            proposer_fee_recipient:      value.proposer_fee_recipient,
+            proposer_mev_reward:         value.proposer_mev_reward,
+            proposer_profit_usd:         value.proposer_profit_usd,
+            total_mev_profit_usd:        value.total_mev_profit_usd,
+            possible_mev:                value.possible_mev,
+            builder_auction:             None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Clone, Default, rDeserialize, rSerialize, Archive)]
+pub struct MevCountV0 {
+    pub bundle_count:         u64,
+    pub sandwich_count:       Option<u64>,
+    pub cex_dex_trade_count:  Option<u64>,
+    pub cex_dex_quote_count:  Option<u64>,
+    pub cex_dex_rfq_count:    Option<u64>,
+    pub jit_cex_dex_count:    Option<u64>,
+    pub jit_count:            Option<u64>,
+    pub jit_sandwich_count:   Option<u64>,
+    pub atomic_backrun_count: Option<u64>,
+    pub liquidation_count:    Option<u64>,
+    pub searcher_tx_count:    Option<u64>,
+}
+
+self_convert_redefined!(MevCountV0);
+
+impl From<MevCountV0> for MevCount {
+    fn from(value: MevCountV0) -> Self {
+        Self {
+            bundle_count:         value.bundle_count,
+            sandwich_count:       value.sandwich_count,
+            cex_dex_trade_count:  value.cex_dex_trade_count,
+            cex_dex_quote_count:  value.cex_dex_quote_count,
+            cex_dex_rfq_count:    value.cex_dex_rfq_count,
+            jit_cex_dex_count:    value.jit_cex_dex_count,
+            jit_count:            value.jit_count,
+            jit_sandwich_count:   value.jit_sandwich_count,
+            atomic_backrun_count: value.atomic_backrun_count,
+            liquidation_count:    value.liquidation_count,
+            searcher_tx_count:    value.searcher_tx_count,
+            gen_frontrun_count:   None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BundleV0 {
+    pub header: BundleHeader,
+    pub data:   BundleDataV0,
+}
+
+impl From<BundleV0> for Bundle {
+    fn from(value: BundleV0) -> Self {
+        Self { header: value.header, data: value.data.into() }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub enum BundleDataV0 {
+    Sandwich(SandwichV0),
+    AtomicArb(AtomicArb),
+    JitSandwich(JitLiquiditySandwich),
+    Jit(JitLiquidity),
+    CexDexQuote(CexDexQuoteV0),
+    CexDex(CexDexV0),
+    Liquidation(LiquidationV0),
+    Unknown(SearcherTx),
+}
+
+impl From<BundleDataV0> for BundleData {
+    fn from(value: BundleDataV0) -> Self {
+        match value {
+            BundleDataV0::Sandwich(s) => BundleData::Sandwich(s.into()),
+            BundleDataV0::AtomicArb(a) => BundleData::AtomicArb(a),
+            BundleDataV0::JitSandwich(j) => BundleData::JitSandwich(j),
+            BundleDataV0::Jit(j) => BundleData::Jit(j),
+            BundleDataV0::CexDexQuote(c) => BundleData::CexDexQuote(c.into()),
+            BundleDataV0::CexDex(c) => BundleData::CexDex(c.into()),
+            BundleDataV0::Liquidation(l) => BundleData::Liquidation(l.into()),
+            BundleDataV0::Unknown(s) => BundleData::Unknown(s),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SandwichV0 {
+    pub block_number:             u64,
+    pub frontrun_tx_hash:         Vec<B256>,
+    pub frontrun_swaps:           Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub frontrun_gas_details:     Vec<GasDetails>,
+    pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+    pub victim_swaps:             Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub backrun_tx_hash:          B256,
+    pub backrun_swaps:            Vec<NormalizedSwap>,
+    #[redefined(same_fields)]
+    pub backrun_gas_details:      GasDetails,
+}
+
+impl From<SandwichV0> for Sandwich {
+    fn from(value: SandwichV0) -> Self {
+        Self {
+            block_number:             value.block_number,
+            frontrun_tx_hash:         value.frontrun_tx_hash,
+            frontrun_swaps:           value.frontrun_swaps,
+            frontrun_gas_details:     value.frontrun_gas_details,
+            victim_swaps_tx_hashes:   value.victim_swaps_tx_hashes,
+            victim_swaps:             value.victim_swaps,
+            victim_swaps_gas_details: value.victim_swaps_gas_details,
+            backrun_tx_hash:          value.backrun_tx_hash,
+            backrun_swaps:            value.backrun_swaps,
+            backrun_gas_details:      value.backrun_gas_details,
+            is_multi_block:           false,
+            victim_loss:              vec![],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct CexDexV0 {
+    pub tx_hash: B256,
+    pub block_timestamp: u64,
+    pub block_number: u64,
+    #[redefined(same_fields)]
+    pub header_pnl_methodology: CexMethodology,
+    pub swaps: Vec<NormalizedSwap>,
+    pub global_vmap_details: Vec<ArbDetails>,
+    pub global_vmap_pnl_maker: Rational,
+    pub global_vmap_pnl_taker: Rational,
+    pub optimal_route_details: Vec<ArbDetails>,
+    pub optimal_route_pnl_maker: Rational,
+    pub optimal_route_pnl_taker: Rational,
+    pub optimistic_route_details: Vec<ArbDetails>,
+    pub optimistic_trade_details: Vec<Vec<OptimisticTrade>>,
+    pub optimistic_route_pnl_maker: Rational,
+    pub optimistic_route_pnl_taker: Rational,
+    pub per_exchange_details: Vec<Vec<ArbDetails>>,
+    #[redefined(field((CexExchange, same)))]
+    pub per_exchange_pnl: Vec<(CexExchange, (Rational, Rational))>,
+    #[redefined(same_fields)]
+    pub gas_details: GasDetails,
+}
+
+impl From<CexDexV0> for CexDex {
+    fn from(value: CexDexV0) -> Self {
+        Self {
+            tx_hash: value.tx_hash,
+            block_timestamp: value.block_timestamp,
+            block_number: value.block_number,
+            header_pnl_methodology: value.header_pnl_methodology,
+            // the best tier fees were the only fees before they were configurable
+            fee_assumption: FeeAssumption::BestTier,
+            swaps: value.swaps,
+            global_vmap_details: value.global_vmap_details,
+            global_vmap_pnl_maker: value.global_vmap_pnl_maker,
+            global_vmap_pnl_taker: value.global_vmap_pnl_taker,
+            optimal_route_details: value.optimal_route_details,
+            optimal_route_pnl_maker: value.optimal_route_pnl_maker,
+            optimal_route_pnl_taker: value.optimal_route_pnl_taker,
+            optimistic_route_details: value.optimistic_route_details,
+            optimistic_trade_details: value.optimistic_trade_details,
+            optimistic_route_pnl_maker: value.optimistic_route_pnl_maker,
+            optimistic_route_pnl_taker: value.optimistic_route_pnl_taker,
+            per_exchange_details: value.per_exchange_details,
+            per_exchange_pnl: value.per_exchange_pnl,
+            gas_details: value.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct CexDexQuoteV0 {
+    pub tx_hash:           B256,
+    pub block_timestamp:   u64,
+    pub block_number:      u64,
+    pub swaps:             Vec<NormalizedSwap>,
+    pub instant_mid_price: Vec<f64>,
+    pub t2_mid_price:      Vec<f64>,
+    pub t12_mid_price:     Vec<f64>,
+    pub t30_mid_price:     Vec<f64>,
+    pub t60_mid_price:     Vec<f64>,
+    pub t300_mid_price:    Vec<f64>,
+    #[redefined(same_fields)]
+    pub exchange:          CexExchange,
+    pub pnl:               f64,
+    #[redefined(same_fields)]
+    pub gas_details:       GasDetails,
+}
+
+impl From<CexDexQuoteV0> for CexDexQuote {
+    fn from(value: CexDexQuoteV0) -> Self {
+        Self {
+            tx_hash:           value.tx_hash,
+            block_timestamp:   value.block_timestamp,
+            block_number:      value.block_number,
+            swaps:             value.swaps,
+            instant_mid_price: value.instant_mid_price,
+            t2_mid_price:      value.t2_mid_price,
+            t12_mid_price:     value.t12_mid_price,
+            t30_mid_price:     value.t30_mid_price,
+            t60_mid_price:     value.t60_mid_price,
+            t300_mid_price:    value.t300_mid_price,
+            exchange:          value.exchange,
+            fee_assumption:    FeeAssumption::BestTier,
+            pnl:               value.pnl,
+            gas_details:       value.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct LiquidationV0 {
+    pub liquidation_tx_hash: B256,
+    pub block_number:        u64,
+    pub trigger:             B256,
+    pub liquidation_swaps:   Vec<NormalizedSwap>,
+    pub liquidations:        Vec<NormalizedLiquidation>,
+    #[redefined(same_fields)]
+    pub gas_details:         GasDetails,
+}
+
+impl From<LiquidationV0> for Liquidation {
+    fn from(value: LiquidationV0) -> Self {
+        Self {
+            liquidation_tx_hash: value.liquidation_tx_hash,
+            block_number:        value.block_number,
+            trigger:             value.trigger,
+            trigger_block:       None,
+            trigger_tx_index:    None,
+            blocks_to_liquidate: None,
+            liquidation_swaps:   value.liquidation_swaps,
+            liquidations:        value.liquidations,
+            gas_details:         value.gas_details,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SearcherInfoV0 {
+    pub name:              Option<String>,
+    #[redefined(same_fields)]
+    pub fund:              Fund,
+    #[redefined(same_fields)]
+    pub mev_count:         MevCountV0,
+    #[redefined(same_fields)]
+    pub pnl:               TollByType,
+    #[redefined(same_fields)]
+    pub gas_bids:          TollByType,
+    pub builder:           Option<Address>,
+    #[redefined(same_fields)]
+    pub config_labels:     Vec<MevType>,
+    pub sibling_searchers: Vec<Address>,
+}
+
+implement_table_value_codecs_with_zc!(SearcherInfoV0Redefined);
+
+impl From<SearcherInfoV0> for SearcherInfo {
+    fn from(value: SearcherInfoV0) -> Self {
+        Self {
+            name:              value.name,
+            fund:              value.fund,
+            mev_count:         value.mev_count.into(),
+            pnl:               value.pnl,
+            gas_bids:          value.gas_bids,
+            builder:           value.builder,
+            config_labels:     value.config_labels,
+            sibling_searchers: value.sibling_searchers,
+        }
+    }
+}
@@ -9,7 +9,12 @@ use serde_with::serde_as;
 
 use super::{
     builder::BuilderInfo,
-    cex::{quotes::CexPriceMap, trades::CexTradeMap},
+    builder_auction::BuilderAuction,
+    cex::{
+        quotes::{CexOrderBookMap, CexPriceMap},
+        trades::CexTradeMap,
+        PerpTickerMap,
+    },
     dex::DexQuotes,
     traits::LibmdbxReader,
 };
@@ -80,11 +85,20 @@ impl BlockMetadataInner {
 pub struct Metadata {
     #[deref]
     #[as_ref]
-    pub block_metadata: BlockMetadata,
-    pub cex_quotes:     CexPriceMap,
-    pub dex_quotes:     Option<DexQuotes>,
-    pub builder_info:   Option<BuilderInfo>,
-    pub cex_trades:     Option<CexTradeMap>,
+    pub block_metadata:  BlockMetadata,
+    pub cex_quotes:      CexPriceMap,
+    pub dex_quotes:      Option<DexQuotes>,
+    pub builder_info:    Option<BuilderInfo>,
+    pub cex_trades:      Option<CexTradeMap>,
+    /// Order book snapshots around the block, if a source for them is
+    /// configured
+    pub cex_books:       Option<CexOrderBookMap>,
+    /// Funding rates & basis of the perps around the block, if a source for
+    /// them is configured
+    pub perp_tickers:    Option<PerpTickerMap>,
+    /// The bids the relays received for the block, if they were fetched with
+    /// the relay metadata
+    pub builder_auction: Option<BuilderAuction>,
 }
 
 impl Metadata {
@@ -189,6 +203,19 @@ impl BlockMetadata {
         self.block_timestamp * 1_000_000
     }
 
+    /// The block info table entry of the block
+    pub fn to_block_info(&self) -> BlockMetadataInner {
+        BlockMetadataInner {
+            block_hash:             self.block_hash,
+            block_timestamp:        self.block_timestamp,
+            relay_timestamp:        self.relay_timestamp,
+            p2p_timestamp:          self.p2p_timestamp,
+            proposer_fee_recipient: self.proposer_fee_recipient,
+            proposer_mev_reward:    self.proposer_mev_reward,
+            private_flow:           self.private_flow.iter().copied().collect(),
+        }
+    }
+
     pub fn into_metadata(
         self,
         cex_quotes: CexPriceMap,
@@ -196,6 +223,15 @@ impl BlockMetadata {
         builder_info: Option<BuilderInfo>,
         cex_trades: Option<CexTradeMap>,
     ) -> Metadata {
-        Metadata { block_metadata: self, cex_quotes, dex_quotes, builder_info, cex_trades }
+        Metadata {
+            block_metadata: self,
+            cex_quotes,
+            dex_quotes,
+            builder_info,
+            cex_trades,
+            cex_books: None,
+            perp_tickers: None,
+            builder_auction: None,
+        }
     }
 }
@@ -8,6 +8,7 @@ pub mod address_to_protocol_info;
 pub mod block_analysis;
 pub mod block_times;
 pub mod builder;
+pub mod builder_auction;
 pub mod cex;
 
 pub mod clickhouse;
@@ -15,15 +16,19 @@ pub mod clickhouse_serde;
 pub mod codecs;
 pub mod dex;
 pub mod initialized_state;
+pub mod layout_v0;
 pub mod metadata;
 pub mod mev_block;
 pub mod normalized_actions;
 pub mod pool_creation_block;
 pub mod redefined_types;
 pub mod searcher;
+pub mod searcher_inventory;
+pub mod table_version;
 pub mod token_info;
 pub mod traces;
 pub mod traits;
+pub mod uniswap_v4;
 
 /// This table is used to add run id inserts for each clickhouse table in order
 /// for us to not have to clear runs multiple times
@@ -0,0 +1,332 @@
+//! The running cex inventory of cex-dex searchers.
+//!
+//! Every [`CexDex`] bundle is marked out on its own, which hides how a
+//! searcher's inventory builds up across blocks. The [`InventoryTracker`]
+//! folds the cex-dex bundles of a block range into a [`SearcherInventory`]
+//! per searcher eoa & contract, with the net position of every token it
+//! traded.
+//!
+//! Each dex swap implies a hedge on a cex in the opposite direction. The part
+//! of the hedge that is matched by the trades on the cex tape, as found by the
+//! optimistic methodology, is counted as hedged. Whatever position is left is
+//! either inventory the searcher carries or hedges the tape can't explain.
+use alloy_primitives::Address;
+use malachite::{num::basic::traits::Zero, Rational};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::{
+        redefined_types::primitives::AddressRedefined,
+        searcher::{Fund, SearcherInfo},
+    },
+    implement_table_value_codecs_with_zc,
+    mev::{Bundle, BundleData, CexDex},
+    FastHashMap, ToFloatNearest,
+};
+
+/// The share of a token's dex flow that may be left unhedged before it is
+/// flagged
+pub const DEFAULT_UNEXPLAINED_TOLERANCE: f64 = 0.1;
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct TokenInventory {
+    pub token:      Address,
+    /// Amount of the token bought on dexes
+    pub dex_bought: f64,
+    /// Amount of the token sold on dexes
+    pub dex_sold:   f64,
+    /// Amount of the token sold on cexes, as matched on the trade tape
+    pub cex_sold:   f64,
+    /// Amount of the token bought on cexes, as matched on the trade tape
+    pub cex_bought: f64,
+}
+
+impl TokenInventory {
+    pub fn new(token: Address) -> Self {
+        Self { token, ..Default::default() }
+    }
+
+    /// The net amount of the token bought on dexes
+    pub fn dex_net(&self) -> f64 {
+        self.dex_bought - self.dex_sold
+    }
+
+    /// The net amount of the token bought on cexes
+    pub fn cex_net(&self) -> f64 {
+        self.cex_bought - self.cex_sold
+    }
+
+    /// The position of the searcher if it only hedged on the trades we
+    /// found on the tape
+    pub fn net_position(&self) -> f64 {
+        self.dex_net() + self.cex_net()
+    }
+
+    /// The share of the dex flow that isn't explained by the cex tape
+    pub fn unexplained_ratio(&self) -> f64 {
+        let dex_volume = self.dex_bought + self.dex_sold;
+        if dex_volume == 0.0 {
+            return 0.0
+        }
+
+        self.net_position().abs() / dex_volume
+    }
+
+    pub fn is_unexplained(&self, tolerance: f64) -> bool {
+        self.unexplained_ratio() > tolerance
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct SearcherInventory {
+    #[redefined(same_fields)]
+    pub fund:               Fund,
+    pub is_contract:        bool,
+    pub first_block:        u64,
+    pub last_block:         u64,
+    pub bundle_count:       u64,
+    pub positions:          Vec<TokenInventory>,
+    /// The tokens whose dex flow the cex tape didn't explain, as of the last
+    /// [`SearcherInventory::flag_unexplained`]
+    pub unexplained_tokens: Vec<Address>,
+}
+
+implement_table_value_codecs_with_zc!(SearcherInventoryRedefined);
+
+impl SearcherInventory {
+    pub fn new(fund: Fund, is_contract: bool, block_number: u64) -> Self {
+        Self { fund, is_contract, first_block: block_number, ..Default::default() }
+    }
+
+    pub fn position(&self, token: Address) -> Option<&TokenInventory> {
+        self.positions
+            .iter()
+            .find(|position| position.token == token)
+    }
+
+    fn position_mut(&mut self, token: Address) -> &mut TokenInventory {
+        if let Some(i) = self.positions.iter().position(|p| p.token == token) {
+            return &mut self.positions[i]
+        }
+
+        self.positions.push(TokenInventory::new(token));
+        self.positions.last_mut().unwrap()
+    }
+
+    /// The positions whose dex flow isn't explained by the cex tape
+    pub fn unexplained_positions(&self, tolerance: f64) -> impl Iterator<Item = &TokenInventory> {
+        self.positions
+            .iter()
+            .filter(move |position| position.is_unexplained(tolerance))
+    }
+
+    /// Stores the tokens whose dex flow isn't explained by the cex tape, so
+    /// the flag is kept with the inventory
+    pub fn flag_unexplained(&mut self, tolerance: f64) {
+        self.unexplained_tokens = self
+            .unexplained_positions(tolerance)
+            .map(|position| position.token)
+            .collect();
+    }
+
+    /// Whether any of the positions was flagged as unexplained
+    pub fn is_flagged(&self) -> bool {
+        !self.unexplained_tokens.is_empty()
+    }
+
+    /// Adds the swaps of a cex-dex arb & the hedges the tape can account for.
+    /// A leg is covered by the tape in proportion to the volume of the
+    /// optimistic trades of its output token.
+    pub fn apply_cex_dex(&mut self, block_number: u64, cex_dex: &CexDex) {
+        for (i, swap) in cex_dex.swaps.iter().enumerate() {
+            let token_in = swap.token_in.address;
+            let token_out = swap.token_out.address;
+
+            let tape_volume = cex_dex
+                .optimistic_trade_details
+                .get(i)
+                .into_iter()
+                .flatten()
+                .filter(|trade| trade.pair.0 == token_out || trade.pair.1 == token_out)
+                .fold(Rational::ZERO, |volume, trade| volume + &trade.volume);
+
+            let coverage = if swap.amount_out == Rational::ZERO {
+                0.0
+            } else {
+                (tape_volume / &swap.amount_out).to_float().min(1.0)
+            };
+
+            let amount_in = swap.amount_in.clone().to_float();
+            let amount_out = swap.amount_out.clone().to_float();
+
+            let position = self.position_mut(token_in);
+            position.dex_sold += amount_in;
+            position.cex_bought += amount_in * coverage;
+
+            let position = self.position_mut(token_out);
+            position.dex_bought += amount_out;
+            position.cex_sold += amount_out * coverage;
+        }
+
+        self.bundle_count += 1;
+        self.last_block = self.last_block.max(block_number);
+    }
+}
+
+/// Builds the inventories of all searchers from their cex-dex bundles, in
+/// block order
+#[derive(Debug, Default)]
+pub struct InventoryTracker {
+    inventories: FastHashMap<Address, SearcherInventory>,
+    /// The last block of each stored inventory we continue from
+    resumed_at:  FastHashMap<Address, u64>,
+}
+
+impl InventoryTracker {
+    /// Continues from previously stored inventories
+    pub fn new(inventories: impl IntoIterator<Item = (Address, SearcherInventory)>) -> Self {
+        let inventories: FastHashMap<_, _> = inventories.into_iter().collect();
+        let resumed_at = inventories
+            .iter()
+            .map(|(address, inventory)| (*address, inventory.last_block))
+            .collect();
+
+        Self { inventories, resumed_at }
+    }
+
+    /// Applies a bundle to the inventory of its eoa & contract. Bundles that
+    /// aren't cex-dex arbs, or that a stored inventory already includes, are
+    /// skipped.
+    pub fn on_bundle(
+        &mut self,
+        bundle: &Bundle,
+        eoa_info: Option<&SearcherInfo>,
+        contract_info: Option<&SearcherInfo>,
+    ) {
+        let BundleData::CexDex(cex_dex) = &bundle.data else { return };
+        let header = &bundle.header;
+
+        let searchers = std::iter::once((header.eoa, false, eoa_info)).chain(
+            header
+                .mev_contract
+                .map(|contract| (contract, true, contract_info)),
+        );
+
+        for (address, is_contract, info) in searchers {
+            if self
+                .resumed_at
+                .get(&address)
+                .is_some_and(|last_block| header.block_number <= *last_block)
+            {
+                continue
+            }
+
+            let fund = info.map_or(header.fund, |info| info.fund);
+            let inventory = self
+                .inventories
+                .entry(address)
+                .or_insert_with(|| SearcherInventory::new(fund, is_contract, header.block_number));
+            inventory.fund = fund;
+            inventory.apply_cex_dex(header.block_number, cex_dex);
+        }
+    }
+
+    pub fn inventories(&self) -> &FastHashMap<Address, SearcherInventory> {
+        &self.inventories
+    }
+
+    pub fn into_inventories(self) -> FastHashMap<Address, SearcherInventory> {
+        self.inventories
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::token_info::TokenInfoWithAddress,
+        mev::{BundleHeader, MevType, OptimisticTrade},
+        normalized_actions::NormalizedSwap,
+        pair::Pair,
+    };
+
+    fn token(byte: u8) -> TokenInfoWithAddress {
+        TokenInfoWithAddress { address: Address::with_last_byte(byte), ..Default::default() }
+    }
+
+    #[test]
+    fn test_unexplained_hedges() {
+        let weth = token(1);
+        let usdt = token(2);
+        let eoa = Address::with_last_byte(10);
+
+        let cex_dex = |block_number, tape_volume: u64| Bundle {
+            header: BundleHeader {
+                block_number,
+                eoa,
+                mev_type: MevType::CexDexTrades,
+                ..Default::default()
+            },
+            data:   BundleData::CexDex(CexDex {
+                block_number,
+                swaps: vec![NormalizedSwap {
+                    token_in: usdt.clone(),
+                    token_out: weth.clone(),
+                    amount_in: Rational::from(2000),
+                    amount_out: Rational::from(1),
+                    ..Default::default()
+                }],
+                optimistic_trade_details: vec![vec![OptimisticTrade {
+                    pair: Pair(weth.address, usdt.address),
+                    volume: Rational::from(tape_volume),
+                    ..Default::default()
+                }]],
+                ..Default::default()
+            }),
+        };
+
+        let mut tracker = InventoryTracker::default();
+        tracker.on_bundle(&cex_dex(1, 1), None, None);
+
+        // continuing from the stored inventory skips the blocks it includes
+        let mut tracker = InventoryTracker::new(tracker.into_inventories());
+        tracker.on_bundle(&cex_dex(1, 1), None, None);
+
+        let inventory = &tracker.inventories()[&eoa];
+        assert_eq!(inventory.bundle_count, 1);
+        assert_eq!(inventory.position(weth.address).unwrap().net_position(), 0.0);
+        assert_eq!(
+            inventory
+                .unexplained_positions(DEFAULT_UNEXPLAINED_TOLERANCE)
+                .count(),
+            0
+        );
+
+        let mut inventory = tracker.inventories()[&eoa].clone();
+        inventory.flag_unexplained(DEFAULT_UNEXPLAINED_TOLERANCE);
+        assert!(!inventory.is_flagged());
+
+        // no trades on the tape to hedge the second arb
+        tracker.on_bundle(&cex_dex(2, 0), None, None);
+
+        let inventory = &tracker.inventories()[&eoa];
+        let weth_position = inventory.position(weth.address).unwrap();
+        assert_eq!(weth_position.net_position(), 1.0);
+        assert_eq!(weth_position.unexplained_ratio(), 0.5);
+        assert_eq!(
+            inventory
+                .unexplained_positions(DEFAULT_UNEXPLAINED_TOLERANCE)
+                .count(),
+            2
+        );
+
+        let mut inventory = inventory.clone();
+        inventory.flag_unexplained(DEFAULT_UNEXPLAINED_TOLERANCE);
+        assert!(inventory.is_flagged());
+        assert_eq!(inventory.unexplained_tokens, vec![usdt.address, weth.address]);
+    }
+}
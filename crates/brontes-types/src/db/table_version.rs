@@ -0,0 +1,27 @@
+use redefined::self_convert_redefined;
+use serde::{Deserialize, Serialize};
+
+use crate::implement_table_value_codecs_with_zc;
+
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    rkyv::Serialize,
+    rkyv::Deserialize,
+    rkyv::Archive,
+)]
+#[repr(transparent)]
+/// Version of the layout a libmdbx table stores its values in
+pub struct TableVersion(pub u16);
+
+self_convert_redefined!(TableVersion);
+implement_table_value_codecs_with_zc!(TableVersion);
@@ -2,17 +2,25 @@ use alloy_primitives::Address;
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo, cex::trades::CexTradeMap, dex::DexQuotes, metadata::Metadata,
-        mev_block::MevBlockWithClassified, searcher::SearcherInfo,
+        address_metadata::AddressMetadata,
+        address_to_protocol_info::ProtocolInfo,
+        builder::BuilderInfo,
+        builder_auction::BuilderAuction,
+        cex::trades::CexTradeMap,
+        dex::DexQuotes,
+        metadata::{BlockMetadataInner, Metadata},
+        mev_block::MevBlockWithClassified,
+        searcher::SearcherInfo,
+        searcher_inventory::SearcherInventory,
         token_info::TokenInfoWithAddress,
+        uniswap_v4::UniswapV4PoolKey,
     },
     pair::Pair,
     structured_trace::TxTrace,
     FastHashMap, Protocol,
 };
 pub type AllSearcherInfo = (Vec<(Address, SearcherInfo)>, Vec<(Address, SearcherInfo)>);
-pub type ProtocolCreatedRange = FastHashMap<u64, Vec<(Address, Protocol, Pair)>>;
+pub type ProtocolCreatedRange = FastHashMap<u64, Vec<(Address, Protocol, Vec<Pair>)>>;
 
 #[auto_impl::auto_impl(&, Box)]
 pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
@@ -96,8 +104,16 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
 
     fn fetch_all_builder_info(&self) -> eyre::Result<Vec<(Address, BuilderInfo)>>;
 
+    fn fetch_all_searcher_inventories(&self) -> eyre::Result<Vec<(Address, SearcherInventory)>>;
+
+    fn try_fetch_builder_auction(&self, block_num: u64) -> eyre::Result<Option<BuilderAuction>>;
+
+    fn try_fetch_v4_pool_key(&self, pool: Address) -> eyre::Result<Option<UniswapV4PoolKey>>;
+
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata>;
 
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>>;
+
     fn get_cex_trades(&self, block: u64) -> eyre::Result<CexTradeMap>;
 
     fn try_fetch_address_metadata(&self, address: Address)
@@ -113,6 +129,8 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         self.try_fetch_token_info(address).map(|info| info.decimals)
     }
 
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>>;
+
     fn try_fetch_mev_blocks(
         &self,
         start_block: Option<u64>,
@@ -127,7 +145,7 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
     fn protocols_created_before(
         &self,
         start_block: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>>;
+    ) -> eyre::Result<FastHashMap<(Address, Protocol), Vec<Pair>>>;
 
     fn protocols_created_range(
         &self,
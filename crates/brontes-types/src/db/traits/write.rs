@@ -4,7 +4,9 @@ use futures::Future;
 use crate::{
     db::{
         address_metadata::AddressMetadata, block_analysis::BlockAnalysis, builder::BuilderInfo,
-        dex::DexQuotes, searcher::SearcherInfo,
+        builder_auction::BuilderAuction, dex::DexQuotes, metadata::BlockMetadataInner,
+        searcher::SearcherInfo, searcher_inventory::SearcherInventory,
+        uniswap_v4::UniswapV4PoolKey,
     },
     mev::{Bundle, MevBlock},
     normalized_actions::Action,
@@ -43,6 +45,20 @@ pub trait DBWriter: Send + Unpin + 'static {
         self.inner().write_token_info(address, decimals, symbol)
     }
 
+    fn write_block_info(
+        &self,
+        block_number: u64,
+        block_info: BlockMetadataInner,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_block_info(block_number, block_info)
+    }
+
+    /// Removes everything the processing of a block wrote, so that it can be
+    /// processed again after a reorg
+    fn rollback_block(&self, block_number: u64) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().rollback_block(block_number)
+    }
+
     fn save_mev_blocks(
         &self,
         block_number: u64,
@@ -81,6 +97,29 @@ pub trait DBWriter: Send + Unpin + 'static {
             .write_searcher_contract_info(searcher_contract, searcher_info)
     }
 
+    fn write_searcher_inventory(
+        &self,
+        searcher: Address,
+        inventory: SearcherInventory,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_searcher_inventory(searcher, inventory)
+    }
+
+    fn write_builder_auction(
+        &self,
+        block_number: u64,
+        auction: BuilderAuction,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_builder_auction(block_number, auction)
+    }
+
+    fn write_v4_pool_key(
+        &self,
+        key: UniswapV4PoolKey,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().write_v4_pool_key(key)
+    }
+
     fn write_builder_info(
         &self,
         builder_address: Address,
@@ -0,0 +1,97 @@
+//! Uniswap V4 pools.
+//!
+//! All V4 pools live inside of the PoolManager and are identified by the
+//! PoolId, the hash of their `PoolKey`. The rest of brontes keys pools by
+//! address, so every pool gets an address derived from the PoolManager and its
+//! PoolId. The address can't be reversed, so the full [`UniswapV4PoolKey`] is
+//! stored in libmdbx under it when the classifier first sees the pool.
+use alloy_primitives::{hex, keccak256, Address, B256};
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::WETH_ADDRESS,
+    db::redefined_types::primitives::{AddressRedefined, B256Redefined},
+    implement_table_value_codecs_with_zc,
+};
+
+pub const POOL_MANAGER_ADDRESS: Address =
+    Address::new(hex!("000000000004444c5dc75cB358380D2e3dE08A90"));
+
+/// The `PoolKey` of a V4 pool along with its PoolId. Currencies are stored as
+/// they are in the key, with the zero address for native ETH.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct UniswapV4PoolKey {
+    pub pool_id:      B256,
+    pub currency0:    Address,
+    pub currency1:    Address,
+    pub fee:          u32,
+    pub tick_spacing: i32,
+    pub hooks:        Address,
+}
+
+impl UniswapV4PoolKey {
+    /// The address the pool is keyed by everywhere outside of the PoolManager
+    pub fn pool_address(&self) -> Address {
+        v4_pool_address(self.pool_id)
+    }
+
+    /// Hooks are called around every swap & liquidity change and can take
+    /// fees or change the amounts and the pool state in ways that aren't
+    /// visible in the PoolManager calls. Pools with hooks are therefore not
+    /// classified or priced.
+    pub fn has_hooks(&self) -> bool {
+        !self.hooks.is_zero()
+    }
+
+    /// The tokens of the pool, with native ETH priced as WETH
+    pub fn tokens(&self) -> [Address; 2] {
+        [native_to_weth(self.currency0), native_to_weth(self.currency1)]
+    }
+}
+
+/// Derives the address of a pool from its PoolId in the same way `CREATE2`
+/// derives contract addresses, so that the address of a V4 pool can't be
+/// chosen to collide with a deployed contract by grinding its PoolId.
+pub fn v4_pool_address(pool_id: B256) -> Address {
+    let mut buf = [0u8; 52];
+    buf[0..20].copy_from_slice(POOL_MANAGER_ADDRESS.as_slice());
+    buf[20..52].copy_from_slice(pool_id.as_slice());
+
+    Address::from_slice(&keccak256(buf)[12..])
+}
+
+/// V4 uses the zero address for native ETH, which is priced as WETH
+pub fn native_to_weth(currency: Address) -> Address {
+    if currency.is_zero() {
+        WETH_ADDRESS
+    } else {
+        currency
+    }
+}
+
+implement_table_value_codecs_with_zc!(UniswapV4PoolKeyRedefined);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_address_isnt_the_pool_id() {
+        let pool_id =
+            B256::new(hex!("21c67e77068de97969ba93d4aab21826d33ca12bb9f565d8496e8fda8a82ca27"));
+        let key = UniswapV4PoolKey { pool_id, ..Default::default() };
+
+        assert_eq!(key.pool_address(), v4_pool_address(pool_id));
+        assert_ne!(key.pool_address(), Address::from_slice(&pool_id[0..20]));
+        assert_ne!(
+            key.pool_address(),
+            v4_pool_address(B256::new(hex!(
+                "21c67e77068de97969ba93d4aab21826d33ca12b000000000000000000000000"
+            )))
+        );
+        assert_eq!(key.tokens(), [WETH_ADDRESS, WETH_ADDRESS]);
+    }
+}
@@ -45,6 +45,10 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
         }
     }
 
+    if sandwich_data.is_multi_block {
+        writeln!(f, "   - {}", "Multi Block".bright_magenta().bold())?;
+    }
+
     writeln!(f, "\n{}:", "Attacks".bright_yellow().underline())?;
     for (i, ((tx_hash, swaps), gas_details)) in sandwich_data
         .frontrun_tx_hash
@@ -507,6 +511,20 @@ pub fn display_liquidation(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Resu
         "Trigger".bright_blue(),
         format_etherscan_url(&liquidation_data.trigger)
     )?;
+    if let (Some(block), Some(tx_index), Some(delay)) = (
+        liquidation_data.trigger_block,
+        liquidation_data.trigger_tx_index,
+        liquidation_data.blocks_to_liquidate,
+    ) {
+        writeln!(
+            f,
+            " - {}: block {} tx {} ({} blocks to liquidate)",
+            "Trigger Position".bright_blue(),
+            block,
+            tx_index,
+            delay
+        )?;
+    }
 
     // Swaps Section
     writeln!(f, "\n{}\n", "Liquidation Swaps".bright_yellow().underline())?;
@@ -1014,6 +1032,76 @@ pub fn display_searcher_tx(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Resu
     Ok(())
 }
 
+pub fn display_generalized_frontrun(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let ascii_header = indoc! {r#"
+
+         ______                     _                       
+        |  ___|                   | |                      
+        | |_    _ __   ___   _ __  | |_  _ __  _   _  _ __  
+        |  _|  | '__| / _ \ | '_ \ | __|| '__|| | | || '_ \ 
+        | |    | |   | (_) || | | || |_ | |   | |_| || | | |
+        \_|    |_|    \___/ |_| |_| \__||_|    \__,_||_| |_|
+
+    "#};
+
+    let frontrun_data = match &bundle.data {
+        BundleData::GeneralizedFrontrun(data) => data,
+        _ => panic!("Wrong bundle type"),
+    };
+
+    for line in ascii_header.lines() {
+        writeln!(f, "{}", line)?;
+    }
+
+    // Frontrun details
+    writeln!(f, "\n{}: \n", "Frontrun Transaction".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Tx Index: {}", bundle.header.tx_index.to_string().bold())?;
+    writeln!(f, "   - EOA: {}", bundle.header.eoa)?;
+
+    match bundle.header.mev_contract {
+        Some(contract) => {
+            writeln!(f, "   - Mev Contract: {}", formate_etherscan_address_url(&contract))?;
+        }
+        None => {
+            writeln!(f, "   - Mev Contract: None")?;
+        }
+    }
+
+    writeln!(f, "   - Etherscan: {}", format_etherscan_url(&frontrun_data.frontrun_tx_hash))?;
+
+    // Victim details
+    writeln!(f, "\n{}: \n", "Copied Transaction".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Etherscan: {}", format_etherscan_url(&frontrun_data.victim_tx_hash))?;
+    if frontrun_data.victim_reverted {
+        writeln!(f, "   - Outcome: {}", "Reverted".red())?;
+    } else {
+        writeln!(
+            f,
+            "   - Victim Profit (USD): {}",
+            format_profit(frontrun_data.victim_profit_usd)
+        )?;
+    }
+
+    writeln!(f, "\n{}: \n", "PnL".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Frontrun Profit (USD): {}", format_profit(bundle.header.profit_usd))?;
+    writeln!(f, "   - Bribe (USD): {}", (format_bribe(bundle.header.bribe_usd)).to_string().red())?;
+
+    bundle
+        .header
+        .balance_deltas
+        .iter()
+        .for_each(|tx_delta| writeln!(f, "{}", tx_delta).expect("Failed to write balance deltas"));
+
+    // Gas Details
+    writeln!(f, "\n{}: \n", "Gas Details".underline().bright_yellow())?;
+
+    frontrun_data
+        .frontrun_gas_details
+        .pretty_print_with_spaces(f, 8)?;
+
+    Ok(())
+}
+
 // Helper function to format profit values
 fn format_profit(value: f64) -> ColoredString {
     if value < 0.0 {
@@ -16,7 +16,10 @@ use serde_with::serde_as;
 
 use super::MevType;
 use crate::{
-    db::redefined_types::primitives::{AddressRedefined, B256Redefined},
+    db::{
+        builder_auction::{BuilderAuctionSummary, BuilderAuctionSummaryRedefined},
+        redefined_types::primitives::{AddressRedefined, B256Redefined},
+    },
     display::utils::formate_etherscan_address_url,
     ToFloatNearest, ToScaledRational,
 };
@@ -58,6 +61,9 @@ pub struct MevBlock {
     pub proposer_profit_usd:         Option<f64>,
     pub total_mev_profit_usd:        f64,
     pub possible_mev:                PossibleMevCollection,
+    /// The mev-boost auction the block was won in, if the relays published
+    /// their bids
+    pub builder_auction:             Option<BuilderAuctionSummary>,
 }
 
 impl fmt::Display for MevBlock {
@@ -199,6 +205,7 @@ pub struct MevCount {
     pub atomic_backrun_count: Option<u64>,
     pub liquidation_count:    Option<u64>,
     pub searcher_tx_count:    Option<u64>,
+    pub gen_frontrun_count:   Option<u64>,
 }
 
 impl MevCount {
@@ -231,6 +238,9 @@ impl MevCount {
             MevType::JitCexDex => {
                 self.jit_cex_dex_count = Some(self.jit_cex_dex_count.unwrap_or_default().add(1))
             }
+            MevType::GeneralizedFrontrun => {
+                self.gen_frontrun_count = Some(self.gen_frontrun_count.unwrap_or_default().add(1))
+            }
             _ => {}
         }
     }
@@ -268,6 +278,9 @@ impl fmt::Display for MevCount {
         if let Some(count) = self.searcher_tx_count {
             writeln!(f, "    - Searcher TXs: {}", count.to_string().bold())?;
         }
+        if let Some(count) = self.gen_frontrun_count {
+            writeln!(f, "    - Generalized Frontrun: {}", count.to_string().bold())?;
+        }
 
         Ok(())
     }
@@ -498,6 +511,60 @@ impl Serialize for MevBlock {
             &possible_high_priority_fee,
         )?;
 
+        let auction = self.builder_auction.iter();
+        ser_struct.serialize_field(
+            "builder_auction.slot",
+            &auction.clone().map(|a| a.slot).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.bid_count",
+            &auction.clone().map(|a| a.bid_count).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.builder_count",
+            &auction.clone().map(|a| a.builder_count).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.winning_builder",
+            &auction
+                .clone()
+                .map(|a| &a.winning_builder)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.winning_relay",
+            &auction
+                .clone()
+                .map(|a| &a.winning_relay)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.winning_bid",
+            &auction.clone().map(|a| a.winning_bid).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.winning_bid_ms",
+            &auction
+                .clone()
+                .map(|a| a.winning_bid_ms)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.runner_up_builder",
+            &auction
+                .clone()
+                .map(|a| &a.runner_up_builder)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.runner_up_bid",
+            &auction.clone().map(|a| a.runner_up_bid).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "builder_auction.winning_margin",
+            &auction.map(|a| a.winning_margin).collect::<Vec<_>>(),
+        )?;
+
         ser_struct.end()
     }
 }
@@ -543,5 +610,15 @@ impl DbRow for MevBlock {
         "possible_mev.triggers.is_private",
         "possible_mev.triggers.coinbase_transfer",
         "possible_mev.triggers.high_priority_fee",
+        "builder_auction.slot",
+        "builder_auction.bid_count",
+        "builder_auction.builder_count",
+        "builder_auction.winning_builder",
+        "builder_auction.winning_relay",
+        "builder_auction.winning_bid",
+        "builder_auction.winning_bid_ms",
+        "builder_auction.runner_up_builder",
+        "builder_auction.runner_up_bid",
+        "builder_auction.winning_margin",
     ];
 }
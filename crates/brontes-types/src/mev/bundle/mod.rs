@@ -68,6 +68,7 @@ impl fmt::Display for Bundle {
             MevType::Liquidation => display_liquidation(self, f)?,
             MevType::JitSandwich => display_jit_liquidity_sandwich(self, f)?,
             MevType::SearcherTx => display_searcher_tx(self, f)?,
+            MevType::GeneralizedFrontrun => display_generalized_frontrun(self, f)?,
             MevType::Unknown => (),
         }
 
@@ -104,6 +105,7 @@ pub enum MevType {
     SearcherTx,
     #[default]
     Unknown,
+    GeneralizedFrontrun,
 }
 
 impl MevType {
@@ -115,6 +117,7 @@ impl MevType {
             | MevType::AtomicArb
             | MevType::Liquidation
             | MevType::SearcherTx
+            | MevType::GeneralizedFrontrun
             | MevType::Unknown => false,
             MevType::CexDexRfq
             | MevType::CexDexTrades
@@ -135,6 +138,7 @@ impl MevType {
             MevType::JitSandwich => "jit-sandwich",
             MevType::SearcherTx => "searcher-tx",
             MevType::Liquidation => "liquidation",
+            MevType::GeneralizedFrontrun => "generalized-frontrun",
             MevType::Unknown => "header",
         }
     }
@@ -154,6 +158,7 @@ impl From<String> for MevType {
             "JitSandwich" => MevType::JitSandwich,
             "AtomicArb" => MevType::AtomicArb,
             "SearcherTx" => MevType::SearcherTx,
+            "GeneralizedFrontrun" => MevType::GeneralizedFrontrun,
             _ => MevType::Unknown,
         }
     }
@@ -17,7 +17,7 @@ use strum::Display;
 use super::{Mev, MevType};
 use crate::{
     db::{
-        cex::CexExchange,
+        cex::{CexExchange, FeeAssumption},
         redefined_types::{malachite::RationalRedefined, primitives::*},
     },
     normalized_actions::*,
@@ -69,6 +69,8 @@ pub struct CexDex {
     pub block_number: u64,
     #[redefined(same_fields)]
     pub header_pnl_methodology: CexMethodology,
+    #[redefined(same_fields)]
+    pub fee_assumption: FeeAssumption,
     pub swaps: Vec<NormalizedSwap>,
     pub global_vmap_details: Vec<ArbDetails>,
     pub global_vmap_pnl_maker: Rational,
@@ -134,6 +136,8 @@ pub enum CexMethodology {
     Optimistic,
     #[default]
     None,
+    /// Hedges priced by walking the order book of each exchange
+    OrderBookDepth,
 }
 
 self_convert_redefined!(CexMethodology);
@@ -142,13 +146,14 @@ impl Serialize for CexDex {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("CexDex", 68)?;
+        let mut ser_struct = serializer.serialize_struct("CexDex", 69)?;
 
         ser_struct.serialize_field("tx_hash", &format!("{:?}", self.tx_hash))?;
         ser_struct.serialize_field("block_timestamp", &self.block_timestamp)?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
         ser_struct
             .serialize_field("header_pnl_methodology", &self.header_pnl_methodology.to_string())?;
+        ser_struct.serialize_field("fee_assumption", &self.fee_assumption.to_string())?;
 
         let swaps: ClickhouseVecNormalizedSwap = self
             .swaps
@@ -627,6 +632,7 @@ impl DbRow for CexDex {
         "block_timestamp",
         "block_number",
         "header_pnl_methodology",
+        "fee_assumption",
         "swaps.trace_idx",
         "swaps.from",
         "swaps.recipient",
@@ -13,7 +13,10 @@ use serde_with::serde_as;
 
 use super::{Mev, MevType};
 use crate::{
-    db::{cex::CexExchange, redefined_types::primitives::*},
+    db::{
+        cex::{CexExchange, FeeAssumption},
+        redefined_types::primitives::*,
+    },
     normalized_actions::*,
     GasDetails, Protocol,
 };
@@ -34,6 +37,8 @@ pub struct CexDexQuote {
     pub t300_mid_price:    Vec<f64>,
     #[redefined(same_fields)]
     pub exchange:          CexExchange,
+    #[redefined(same_fields)]
+    pub fee_assumption:    FeeAssumption,
     pub pnl:               f64,
     #[redefined(same_fields)]
     pub gas_details:       GasDetails,
@@ -70,7 +75,7 @@ impl Serialize for CexDexQuote {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("CexDexQuote", 19)?;
+        let mut ser_struct = serializer.serialize_struct("CexDexQuote", 20)?;
         ser_struct.serialize_field("tx_hash", &format!("{:?}", self.tx_hash))?;
         ser_struct.serialize_field("block_timestamp", &self.block_timestamp)?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
@@ -95,6 +100,7 @@ impl Serialize for CexDexQuote {
         ser_struct.serialize_field("t60_mid_price", &self.t60_mid_price)?;
         ser_struct.serialize_field("t300_mid_price", &self.t300_mid_price)?;
         ser_struct.serialize_field("exchange", &self.exchange.to_string())?;
+        ser_struct.serialize_field("fee_assumption", &self.fee_assumption.to_string())?;
         ser_struct.serialize_field(
             "gas_details",
             &(
@@ -129,6 +135,7 @@ impl DbRow for CexDexQuote {
         "t60_mid_price",
         "t300_mid_price",
         "exchange",
+        "fee_assumption",
         "gas_details",
     ];
 }
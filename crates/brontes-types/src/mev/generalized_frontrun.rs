@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+
+use ::serde::ser::Serializer;
+use ahash::{HashSet, HashSetExt};
+use clickhouse::DbRow;
+use redefined::Redefined;
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    db::redefined_types::primitives::*,
+    mev::{Mev, MevType},
+    normalized_actions::*,
+    Protocol,
+};
+#[allow(unused_imports)]
+use crate::{display::utils::display_sandwich, normalized_actions::NormalizedTransfer, GasDetails};
+
+/// A transaction that copied the call tree of a later transaction in the same
+/// block and captured the opportunity before it. The victim either reverted
+/// or only realized a fraction of the copier's profit.
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct GeneralizedFrontrun {
+    pub block_number:         u64,
+    pub frontrun_tx_hash:     B256,
+    pub frontrun_transfers:   Vec<NormalizedTransfer>,
+    #[redefined(same_fields)]
+    pub frontrun_gas_details: GasDetails,
+    pub victim_tx_hash:       B256,
+    pub victim_reverted:      bool,
+    pub victim_profit_usd:    f64,
+    #[redefined(same_fields)]
+    pub victim_gas_details:   GasDetails,
+}
+
+impl Mev for GeneralizedFrontrun {
+    fn mev_type(&self) -> MevType {
+        MevType::GeneralizedFrontrun
+    }
+
+    // the victim isn't part of the searchers bundle, so we only claim the copy
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        vec![self.frontrun_tx_hash]
+    }
+
+    fn total_gas_paid(&self) -> u128 {
+        self.frontrun_gas_details.gas_paid()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.frontrun_gas_details.priority_fee_paid(base_fee)
+    }
+
+    fn bribe(&self) -> u128 {
+        self.frontrun_gas_details.coinbase_transfer.unwrap_or(0)
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        HashSet::new()
+    }
+}
+
+impl Serialize for GeneralizedFrontrun {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("GeneralizedFrontrun", 13)?;
+
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("frontrun_tx_hash", &format!("{:?}", self.frontrun_tx_hash))?;
+
+        let frontrun_transfers: ClickhouseVecNormalizedTransfer = self
+            .frontrun_transfers
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+        ser_struct
+            .serialize_field("frontrun_transfers.trace_idx", &frontrun_transfers.trace_index)?;
+        ser_struct.serialize_field("frontrun_transfers.from", &frontrun_transfers.from)?;
+        ser_struct.serialize_field("frontrun_transfers.to", &frontrun_transfers.to)?;
+        ser_struct.serialize_field("frontrun_transfers.token", &frontrun_transfers.token)?;
+        ser_struct.serialize_field("frontrun_transfers.amount", &frontrun_transfers.amount)?;
+        ser_struct.serialize_field("frontrun_transfers.fee", &frontrun_transfers.fee)?;
+
+        let frontrun_gas_details = (
+            self.frontrun_gas_details.coinbase_transfer,
+            self.frontrun_gas_details.priority_fee,
+            self.frontrun_gas_details.gas_used,
+            self.frontrun_gas_details.effective_gas_price,
+        );
+        ser_struct.serialize_field("frontrun_gas_details", &(frontrun_gas_details))?;
+
+        ser_struct.serialize_field("victim_tx_hash", &format!("{:?}", self.victim_tx_hash))?;
+        ser_struct.serialize_field("victim_reverted", &self.victim_reverted)?;
+        ser_struct.serialize_field("victim_profit_usd", &self.victim_profit_usd)?;
+
+        let victim_gas_details = (
+            self.victim_gas_details.coinbase_transfer,
+            self.victim_gas_details.priority_fee,
+            self.victim_gas_details.gas_used,
+            self.victim_gas_details.effective_gas_price,
+        );
+        ser_struct.serialize_field("victim_gas_details", &(victim_gas_details))?;
+
+        ser_struct.end()
+    }
+}
+
+impl DbRow for GeneralizedFrontrun {
+    const COLUMN_NAMES: &'static [&'static str] = &[
+        "block_number",
+        "frontrun_tx_hash",
+        "frontrun_transfers.trace_idx",
+        "frontrun_transfers.from",
+        "frontrun_transfers.to",
+        "frontrun_transfers.token",
+        "frontrun_transfers.amount",
+        "frontrun_transfers.fee",
+        "frontrun_gas_details",
+        "victim_tx_hash",
+        "victim_reverted",
+        "victim_profit_usd",
+        "victim_gas_details",
+    ];
+}
@@ -22,7 +22,13 @@ use crate::{display::utils::display_sandwich, normalized_actions::*, GasDetails}
 pub struct Liquidation {
     pub liquidation_tx_hash: B256,
     pub block_number:        u64,
+    /// The oracle update that made the position liquidatable
     pub trigger:             B256,
+    pub trigger_block:       Option<u64>,
+    pub trigger_tx_index:    Option<u64>,
+    /// Blocks between the oracle update and the liquidation, 0 if they landed
+    /// in the same block
+    pub blocks_to_liquidate: Option<u64>,
     pub liquidation_swaps:   Vec<NormalizedSwap>,
     pub liquidations:        Vec<NormalizedLiquidation>,
     #[redefined(same_fields)]
@@ -70,12 +76,16 @@ impl Serialize for Liquidation {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Liquidation", 34)?;
+        let mut ser_struct = serializer.serialize_struct("Liquidation", 38)?;
 
         // frontrun
         ser_struct
             .serialize_field("liquidation_tx_hash", &format!("{:?}", self.liquidation_tx_hash))?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("trigger", &format!("{:?}", self.trigger))?;
+        ser_struct.serialize_field("trigger_block", &self.trigger_block)?;
+        ser_struct.serialize_field("trigger_tx_index", &self.trigger_tx_index)?;
+        ser_struct.serialize_field("blocks_to_liquidate", &self.blocks_to_liquidate)?;
 
         let liquidation_swaps: ClickhouseVecNormalizedSwap = self
             .liquidation_swaps
@@ -131,6 +141,10 @@ impl DbRow for Liquidation {
     const COLUMN_NAMES: &'static [&'static str] = &[
         "liquidation_tx_hash",
         "block_number",
+        "trigger",
+        "trigger_block",
+        "trigger_tx_index",
+        "blocks_to_liquidate",
         "liquidation_swaps.trace_idx",
         "liquidation_swaps.from",
         "liquidation_swaps.recipient",
@@ -16,6 +16,8 @@ pub mod block;
 pub use block::*;
 pub mod searcher_tx;
 pub use searcher_tx::*;
+pub mod generalized_frontrun;
+pub use generalized_frontrun::*;
 
 pub mod cex_dex_quotes;
 pub use cex_dex_quotes::*;
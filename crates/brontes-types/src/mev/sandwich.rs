@@ -12,9 +12,12 @@ use serde_with::serde_as;
 
 use super::{Mev, MevType};
 use crate::{
-    db::{redefined_types::primitives::*, token_info::TokenInfoWithAddress},
+    db::{
+        redefined_types::{malachite::*, primitives::*},
+        token_info::{TokenInfoWithAddress, TokenInfoWithAddressRedefined},
+    },
     normalized_actions::*,
-    ClickhouseVecGasDetails, Protocol,
+    ClickhouseVecGasDetails, Protocol, ToFloatNearest,
 };
 #[allow(unused_imports)]
 use crate::{
@@ -88,17 +91,47 @@ pub struct Sandwich {
     /// Gas details for each backrunning transaction.
     #[redefined(same_fields)]
     pub backrun_gas_details:      GasDetails,
+    /// True if the frontrun(s) and the backrun landed in different blocks.
+    /// `block_number` is always the block of the backrun.
+    #[serde(default)]
+    pub is_multi_block:           bool,
+    /// Loss incurred by each victim transaction. Victims whose tokens we don't
+    /// have a price for are omitted.
+    #[serde(default)]
+    pub victim_loss:              Vec<VictimLossAmount>,
 }
 
 /// calcuation for the loss per user
-#[derive(Debug, Deserialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct VictimLossAmount {
-    pub tx_hash:           B256,
-    pub vicitim_eoa:       Address,
-    pub token:             TokenInfoWithAddress,
-    pub token_amount_lost: Rational,
+    pub tx_hash:                   B256,
+    pub vicitim_eoa:               Address,
+    /// The token the victim received less of
+    pub token:                     TokenInfoWithAddress,
+    /// Estimated from the victim's swap deltas priced at the pre-frontrun dex
+    /// price
+    pub token_amount_lost:         Rational,
     /// is zero if we don't have a price for the given token
-    pub amount_lost_usd:   Rational,
+    pub amount_lost_usd:           Rational,
+    /// Exact loss from re-executing the victim transaction against the
+    /// pre-frontrun state. `None` if the simulation wasn't run or failed.
+    #[serde(default)]
+    pub simulated_amount_lost:     Option<Rational>,
+    /// is `None` if the simulation wasn't run or if we don't have a price for
+    /// the given token
+    #[serde(default)]
+    pub simulated_amount_lost_usd: Option<Rational>,
+}
+
+impl VictimLossAmount {
+    /// Difference between the heuristic estimate and the simulated loss.
+    /// Positive when the heuristic overestimates the loss.
+    pub fn heuristic_error(&self) -> Option<Rational> {
+        self.simulated_amount_lost
+            .as_ref()
+            .map(|simulated| &self.token_amount_lost - simulated)
+    }
 }
 
 impl Mev for Sandwich {
@@ -166,7 +199,7 @@ impl Serialize for Sandwich {
     where
         S: Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("Sandwich", 35)?;
+        let mut ser_struct = serializer.serialize_struct("Sandwich", 43)?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
 
         // frontrun
@@ -282,6 +315,66 @@ impl Serialize for Sandwich {
             &vec![self.backrun_gas_details.effective_gas_price],
         )?;
 
+        ser_struct.serialize_field("is_multi_block", &self.is_multi_block)?;
+
+        // victim loss
+        ser_struct.serialize_field(
+            "victim_loss.tx_hash",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| format!("{:?}", loss.tx_hash))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_loss.victim_eoa",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| format!("{:?}", loss.vicitim_eoa))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_loss.token",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| loss.token.clickhouse_fmt())
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_loss.amount_lost",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| loss.token_amount_lost.clone().to_float())
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_loss.amount_lost_usd",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| loss.amount_lost_usd.clone().to_float())
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_loss.simulated_amount_lost",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| loss.simulated_amount_lost.clone().map(|l| l.to_float()))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "victim_loss.simulated_amount_lost_usd",
+            &self
+                .victim_loss
+                .iter()
+                .map(|loss| loss.simulated_amount_lost_usd.clone().map(|l| l.to_float()))
+                .collect::<Vec<_>>(),
+        )?;
+
         ser_struct.end()
     }
 }
@@ -333,5 +426,13 @@ impl DbRow for Sandwich {
         "backrun_gas_details.priority_fee",
         "backrun_gas_details.gas_used",
         "backrun_gas_details.effective_gas_price",
+        "is_multi_block",
+        "victim_loss.tx_hash",
+        "victim_loss.victim_eoa",
+        "victim_loss.token",
+        "victim_loss.amount_lost",
+        "victim_loss.amount_lost_usd",
+        "victim_loss.simulated_amount_lost",
+        "victim_loss.simulated_amount_lost_usd",
     ];
 }
@@ -0,0 +1,41 @@
+use std::fmt::{self, Debug};
+
+use clickhouse::Row;
+use colored::Colorize;
+use reth_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::Protocol;
+
+/// A price update pushed to an on-chain oracle, e.g a Chainlink OCR
+/// `transmit` or a Maker OSM `poke`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+pub struct NormalizedOracleUpdate {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    pub from:        Address,
+    /// The contract that stores the new price, this is what the lending
+    /// protocols read from
+    pub oracle:      Address,
+    /// The raw, unscaled answer if the update emits one
+    pub answer:      Option<U256>,
+}
+
+impl fmt::Display for NormalizedOracleUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.protocol.to_string().bold();
+        let oracle_address = format!("{}", self.oracle).cyan();
+        let from_address = format!("{}", self.from).cyan();
+        let answer = self
+            .answer
+            .map(|answer| answer.to_string())
+            .unwrap_or_else(|| "-".to_string())
+            .green();
+
+        write!(
+            f,
+            "Protocol {} - Oracle: {}, From: {}, Answer: {}",
+            protocol, oracle_address, from_address, answer
+        )
+    }
+}
@@ -7,7 +7,10 @@ use reth_db::table::{Decode, Encode};
 use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
-use crate::{constants::USD_STABLES_BY_ADDRESS, db::redefined_types::primitives::AddressRedefined};
+use crate::{
+    constants::{ETH_ADDRESS, USD_STABLES_BY_ADDRESS, WETH_ADDRESS},
+    db::redefined_types::primitives::AddressRedefined,
+};
 
 #[derive(
     Debug,
@@ -60,6 +63,23 @@ impl Pair {
         self.1 == addr
     }
 
+    /// Every pair that can be traded between a set of pool tokens. Pools with
+    /// more than two tokens are added to the pricing graph as one edge per
+    /// pair. Native eth is priced as weth.
+    pub fn all_pairs(tokens: &[Address]) -> Vec<Self> {
+        let tokens = tokens
+            .iter()
+            .map(|token| if *token == ETH_ADDRESS { WETH_ADDRESS } else { *token })
+            .collect::<Vec<_>>();
+
+        tokens
+            .iter()
+            .enumerate()
+            .flat_map(|(i, t0)| tokens[i + 1..].iter().map(|t1| Pair(*t0, *t1)))
+            .filter(|pair| pair.0 != pair.1)
+            .collect()
+    }
+
     pub fn map_key(addr1: Address, addr2: Address) -> Self {
         if addr1 <= addr2 {
             Pair(addr1, addr2)
@@ -103,10 +103,22 @@ utils!(
         Dodo,
         #[default]
         Unknown,
+        ChainlinkOCR,
+        ChainlinkOCR2,
+        MakerOSM,
+        CompoundV3,
+        MorphoBlue,
+        Spark,
+        UniswapV4,
     }
 );
 
 impl Protocol {
+    /// Price feeds that liquidations read from
+    pub const fn is_oracle(&self) -> bool {
+        matches!(self, Protocol::ChainlinkOCR | Protocol::ChainlinkOCR2 | Protocol::MakerOSM)
+    }
+
     pub fn into_clickhouse_protocol(&self) -> (&str, &str) {
         match self {
             Protocol::UniswapV2 => ("Uniswap", "V2"),
@@ -146,6 +158,13 @@ impl Protocol {
             Protocol::ClipperExchange => ("ClipperExchange", ""),
             Protocol::PropellerLabsSolver => ("Propeller Labs Solver", ""),
             Protocol::Dodo => ("Dodo", "V1/V2"),
+            Protocol::ChainlinkOCR => ("Chainlink", "OCR"),
+            Protocol::ChainlinkOCR2 => ("Chainlink", "OCR2"),
+            Protocol::MakerOSM => ("Maker", "OSM"),
+            Protocol::CompoundV3 => ("Compound", "V3"),
+            Protocol::MorphoBlue => ("Morpho", "Blue"),
+            Protocol::Spark => ("Spark", ""),
+            Protocol::UniswapV4 => ("Uniswap", "V4"),
             Protocol::Unknown => ("Unknown", "Unknown"),
         }
     }
@@ -155,6 +174,7 @@ impl Protocol {
             "uniswapv2" => Protocol::UniswapV2,
             "sushiswapv2" => Protocol::SushiSwapV2,
             "uniswapv3" => Protocol::UniswapV3,
+            "uniswapv4" => Protocol::UniswapV4,
             "sushiswapv3" => Protocol::SushiSwapV3,
             "curve.fibase2" => Protocol::CurveBasePool2,
             "curve.fibase3" => Protocol::CurveBasePool3,
@@ -230,6 +250,13 @@ impl fmt::Display for Protocol {
                 Protocol::ClipperExchange => "Clipper",
                 Protocol::PropellerLabsSolver => "Propeller Labs",
                 Protocol::Dodo => "Dodo",
+                Protocol::ChainlinkOCR => "Chainlink OCR",
+                Protocol::ChainlinkOCR2 => "Chainlink OCR2",
+                Protocol::MakerOSM => "Maker OSM",
+                Protocol::CompoundV3 => "Compound V3",
+                Protocol::MorphoBlue => "Morpho Blue",
+                Protocol::Spark => "Spark",
+                Protocol::UniswapV4 => "Uni V4",
                 Protocol::Unknown => "Unknown",
             }
         )
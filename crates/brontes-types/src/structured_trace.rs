@@ -345,9 +345,9 @@ impl Serialize for TxTrace {
         let reward_action = ClickhouseRewardAction::from(self);
         ser_struct.serialize_field("trace_reward_actions.trace_idx", &reward_action.trace_idx)?;
         ser_struct.serialize_field("trace_reward_actions.author", &reward_action.author)?;
-        ser_struct.serialize_field("trace_reward_actions.value", &reward_action.value)?;
         ser_struct
             .serialize_field("trace_reward_actions.reward_type", &reward_action.reward_type)?;
+        ser_struct.serialize_field("trace_reward_actions.value", &reward_action.value)?;
 
         let call_output = ClickhouseCallOutput::from(self);
         ser_struct.serialize_field("trace_call_outputs.trace_idx", &call_output.trace_idx)?;
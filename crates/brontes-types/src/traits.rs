@@ -1,4 +1,4 @@
-use alloy_primitives::TxHash;
+use alloy_primitives::{Log as PrimitiveLog, TxHash};
 use alloy_rpc_types::AnyReceiptEnvelope;
 use reth_primitives::{
     Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, B256,
@@ -9,6 +9,15 @@ use reth_rpc_types::{
 
 use crate::structured_trace::TxTrace;
 
+/// The outcome of re-executing a transaction through
+/// [`TracingProvider::simulate_transactions`].
+#[derive(Debug, Clone)]
+pub struct SimulatedTransaction {
+    pub tx_hash: TxHash,
+    pub success: bool,
+    pub logs:    Vec<PrimitiveLog>,
+}
+
 #[async_trait::async_trait]
 #[auto_impl::auto_impl(Box)]
 pub trait TracingProvider: Send + Sync + 'static {
@@ -43,6 +52,27 @@ pub trait TracingProvider: Send + Sync + 'static {
         block_id: BlockId,
     ) -> eyre::Result<Option<Vec<TxTrace>>>;
 
+    /// Whether the provider can re-execute transactions through
+    /// [`TracingProvider::simulate_transactions`].
+    fn supports_simulation(&self) -> bool {
+        false
+    }
+
+    /// Executes all transactions of the block that come before `tx_index` and
+    /// then only the given `tx_hashes` in block order, skipping everything
+    /// else. This allows for simulating transactions as if the transactions
+    /// ordered around them never landed.
+    ///
+    /// Errors if the provider doesn't support simulation.
+    async fn simulate_transactions(
+        &self,
+        _block_number: u64,
+        _tx_index: usize,
+        _tx_hashes: Vec<TxHash>,
+    ) -> eyre::Result<Vec<SimulatedTransaction>> {
+        Err(eyre::eyre!("the tracing provider doesn't support transaction simulation"))
+    }
+
     async fn block_receipts(
         &self,
         number: BlockNumberOrTag,
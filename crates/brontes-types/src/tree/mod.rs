@@ -35,6 +35,8 @@ pub struct BlockTree<V: NormalizedAction> {
     pub header:               Header,
     pub priority_fee_std_dev: f64,
     pub avg_priority_fee:     f64,
+    /// Transactions whose top level call reverted, these have no root
+    pub reverted_txs:         Vec<RevertedTx>,
 }
 
 impl<V: NormalizedAction> BlockTree<V> {
@@ -44,6 +46,7 @@ impl<V: NormalizedAction> BlockTree<V> {
             header,
             priority_fee_std_dev: 0.0,
             avg_priority_fee: 0.0,
+            reverted_txs: vec![],
         }
     }
 
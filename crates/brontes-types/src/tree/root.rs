@@ -1,6 +1,6 @@
 use std::{fmt, fmt::Display};
 
-use alloy_primitives::TxHash;
+use alloy_primitives::{Bytes, TxHash};
 use clickhouse::Row;
 use colored::Colorize;
 use itertools::Itertools;
@@ -59,6 +59,10 @@ pub struct Root<V: NormalizedAction> {
     /// all msg.value transfers that aren't classified as
     /// eth transfers
     pub total_msg_value_transfers: Vec<NormalizedEthTransfer>,
+    /// Every call this transaction made into a contract, starting with the top
+    /// level call, taken from the traces. Used to compare call trees across
+    /// transactions.
+    pub touched_calls: Vec<TouchedCall>,
     pub data_store: NodeData<V>,
 }
 
@@ -83,6 +87,11 @@ impl<V: NormalizedAction> Root<V> {
         )
     }
 
+    /// The contracts called by the transaction, see [`Root::touched_calls`]
+    pub fn touched_contracts(&self) -> impl Iterator<Item = Address> + '_ {
+        self.touched_calls.iter().map(|call| call.to)
+    }
+
     pub fn tx_must_contain_action(&self, f: impl Fn(&V) -> bool) -> bool {
         self.data_store.0.iter().flatten().flatten().any(f)
     }
@@ -325,6 +334,27 @@ impl<V: NormalizedAction> Root<V> {
     }
 }
 
+/// A transaction whose top level call failed. These aren't built into a
+/// [`Root`], only enough is kept to compare them against the rest of the block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevertedTx {
+    pub tx_hash:       B256,
+    pub position:      usize,
+    pub from:          Address,
+    pub to:            Address,
+    pub gas_details:   GasDetails,
+    /// Calls made before the revert, see [`Root::touched_calls`]
+    pub touched_calls: Vec<TouchedCall>,
+}
+
+/// A call into a contract along with its input, which starts with the
+/// function selector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TouchedCall {
+    pub to:    Address,
+    pub input: Bytes,
+}
+
 #[derive(
     Debug,
     Clone,
@@ -1,7 +1,10 @@
 use alloy_primitives::{Address, TxHash};
 
 use crate::{
-    db::{address_metadata::ContractType, searcher::SearcherInfo},
+    db::{
+        address_metadata::ContractType,
+        searcher::{Fund, SearcherInfo},
+    },
     mev::MevType,
     normalized_actions::NormalizedEthTransfer,
     FastHashSet, GasDetails,
@@ -68,6 +71,12 @@ impl TxInfo {
         &self.total_eth_value
     }
 
+    /// Orders txes across blocks. Tx indexes restart at zero every block, so
+    /// they alone can't be compared when searching over multiple blocks.
+    pub fn block_position(&self) -> (u64, u64) {
+        (self.block_number, self.tx_index)
+    }
+
     pub fn split_to_storage_info(self) -> (TxHash, GasDetails) {
         (self.tx_hash, self.gas_details)
     }
@@ -138,6 +147,16 @@ impl TxInfo {
                 .map_or(false, |info| info.is_labelled_searcher_of_type(mev_type))
     }
 
+    /// The fund of the searcher contract, or of the eoa if the contract isn't
+    /// a known searcher
+    pub fn fund(&self) -> Fund {
+        self.searcher_contract_info
+            .as_ref()
+            .or(self.searcher_eoa_info.as_ref())
+            .map(|info| info.fund)
+            .unwrap_or_default()
+    }
+
     pub fn is_private(&self) -> bool {
         self.is_private
     }
@@ -1,13 +1,18 @@
 use std::cmp::min;
 
 use alloy_rpc_types::AnyReceiptEnvelope;
-use brontes_types::{structured_trace::TxTrace, traits::TracingProvider};
+use brontes_types::{
+    structured_trace::TxTrace,
+    traits::{SimulatedTransaction, TracingProvider},
+};
 use eyre::eyre;
 use reth_primitives::{
-    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
-    B256, U256,
+    revm::env::tx_env_with_recovered, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode,
+    Bytes, Header, StorageValue, TxHash, B256, U256,
+};
+use reth_provider::{
+    BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, TransactionVariant,
 };
-use reth_provider::{BlockIdReader, BlockNumReader, HeaderProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc::eth::{
     error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
@@ -21,7 +26,7 @@ use revm::{
     primitives::{
         db::DatabaseRef, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, TransactTo, TxEnv,
     },
-    Database,
+    Database, DatabaseCommit,
 };
 use revm_primitives::ExecutionResult;
 
@@ -88,6 +93,56 @@ impl TracingProvider for TracingClient {
             .map_err(Into::into)
     }
 
+    fn supports_simulation(&self) -> bool {
+        true
+    }
+
+    async fn simulate_transactions(
+        &self,
+        block_number: u64,
+        tx_index: usize,
+        tx_hashes: Vec<TxHash>,
+    ) -> eyre::Result<Vec<SimulatedTransaction>> {
+        let Some(block) = self
+            .provider_factory
+            .provider()?
+            .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+        else {
+            return Err(eyre!("no block found for number {}", block_number));
+        };
+
+        let (cfg, block_env, _) = self.api.evm_env_at(block_number.into()).await?;
+        let state = self.api.state_at(block.parent_hash.into())?;
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+        let mut results = Vec::with_capacity(tx_hashes.len());
+        for (idx, tx) in block.into_transactions_ecrecovered().enumerate() {
+            let is_simulated = tx_hashes.contains(&tx.hash());
+            if idx >= tx_index && !is_simulated {
+                continue
+            }
+
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                tx_env_with_recovered(&tx),
+            );
+            let (res, _) = self.api.transact(&mut db, env)?;
+
+            if idx >= tx_index {
+                results.push(SimulatedTransaction {
+                    tx_hash: tx.hash(),
+                    success: res.result.is_success(),
+                    logs:    res.result.logs().to_vec(),
+                });
+            }
+
+            db.commit(res.state);
+        }
+
+        Ok(results)
+    }
+
     async fn block_receipts(
         &self,
         number: BlockNumberOrTag,